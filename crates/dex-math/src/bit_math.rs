@@ -1,15 +1,15 @@
-use crate::errors::ErrorCode;
+use crate::error::MathError;
 
 use super::U256Muldiv;
 
 pub const Q64_RESOLUTION: u8 = 64;
 pub const TO_Q64: u128 = 1u128 << Q64_RESOLUTION;
 
-pub fn checked_mul_div(n0: u128, n1: u128, d: u128) -> Result<u128, ErrorCode> {
+pub fn checked_mul_div(n0: u128, n1: u128, d: u128) -> Result<u128, MathError> {
     checked_mul_div_round_up_if(n0, n1, d, false)
 }
 
-pub fn checked_mul_div_round_up(n0: u128, n1: u128, d: u128) -> Result<u128, ErrorCode> {
+pub fn checked_mul_div_round_up(n0: u128, n1: u128, d: u128) -> Result<u128, MathError> {
     checked_mul_div_round_up_if(n0, n1, d, true)
 }
 
@@ -18,18 +18,18 @@ pub fn checked_mul_div_round_up_if(
     n1: u128,
     d: u128,
     round_up: bool,
-) -> Result<u128, ErrorCode> {
+) -> Result<u128, MathError> {
     if d == 0 {
-        return Err(ErrorCode::DivideByZero);
+        return Err(MathError::DivideByZero);
     }
 
-    let p = n0.checked_mul(n1).ok_or(ErrorCode::MulDivOverflow)?;
+    let p = n0.checked_mul(n1).ok_or(MathError::MulDivOverflow)?;
     let n = p / d;
 
     Ok(if round_up && p % d > 0 { n + 1 } else { n })
 }
 
-pub fn checked_mul_shift_right(n0: u128, n1: u128) -> Result<u64, ErrorCode> {
+pub fn checked_mul_shift_right(n0: u128, n1: u128) -> Result<u64, MathError> {
     checked_mul_shift_right_round_up_if(n0, n1, false)
 }
 
@@ -41,32 +41,32 @@ pub fn checked_mul_shift_right_round_up_if(
     n0: u128,
     n1: u128,
     round_up: bool,
-) -> Result<u64, ErrorCode> {
+) -> Result<u64, MathError> {
     if n0 == 0 || n1 == 0 {
         return Ok(0);
     }
 
     let p = n0
         .checked_mul(n1)
-        .ok_or(ErrorCode::MultiplicationShiftRightOverflow)?;
+        .ok_or(MathError::MultiplicationShiftRightOverflow)?;
 
     let result = (p >> Q64_RESOLUTION) as u64;
 
     let should_round = round_up && (p & Q64_MASK > 0);
     if should_round && result == u64::MAX {
-        return Err(ErrorCode::MultiplicationOverflow);
+        return Err(MathError::MultiplicationOverflow);
     }
 
     Ok(if should_round { result + 1 } else { result })
 }
 
-pub fn div_round_up(n: u128, d: u128) -> Result<u128, ErrorCode> {
+pub fn div_round_up(n: u128, d: u128) -> Result<u128, MathError> {
     div_round_up_if(n, d, true)
 }
 
-pub fn div_round_up_if(n: u128, d: u128, round_up: bool) -> Result<u128, ErrorCode> {
+pub fn div_round_up_if(n: u128, d: u128, round_up: bool) -> Result<u128, MathError> {
     if d == 0 {
-        return Err(ErrorCode::DivideByZero);
+        return Err(MathError::DivideByZero);
     }
 
     let q = n / d;
@@ -78,7 +78,7 @@ pub fn div_round_up_if_u256(
     n: U256Muldiv,
     d: U256Muldiv,
     round_up: bool,
-) -> Result<u128, ErrorCode> {
+) -> Result<u128, MathError> {
     let (quotient, remainder) = n.div(d, round_up);
 
     let result = if round_up && !remainder.is_zero() {
@@ -87,17 +87,71 @@ pub fn div_round_up_if_u256(
         quotient
     };
 
-    Ok(result.try_into_u128()?)
+    result.try_into_u128()
+}
+
+/// Find the next set bit of a bitmap from `bit`, e.g. the offset of the next initialized tick in
+/// the initialized bitmap of a tick array.
+///
+/// Searching down includes `bit` itself, searching up starts above it. Bits out of range are
+/// clamped, so a search down from below 0 or up from 127 finds nothing.
+///
+/// # Returns
+/// - `Some(i32)`: The next set bit in the search direction
+/// - `None`: No bit is set in the search direction
+pub fn tick_bitmap_search(bitmap: u128, bit: i32, search_down: bool) -> Option<i32> {
+    let masked = if search_down {
+        if bit < 0 {
+            return None;
+        }
+        if bit >= 127 {
+            bitmap
+        } else {
+            bitmap & ((1u128 << (bit + 1)) - 1)
+        }
+    } else {
+        if bit >= 127 {
+            return None;
+        }
+        if bit < 0 {
+            bitmap
+        } else {
+            bitmap & !((1u128 << (bit + 1)) - 1)
+        }
+    };
+
+    if masked == 0 {
+        None
+    } else if search_down {
+        Some(127 - masked.leading_zeros() as i32)
+    } else {
+        Some(masked.trailing_zeros() as i32)
+    }
 }
 
 #[cfg(test)]
 mod fuzz_tests {
-    use crate::math::U256;
+    use crate::test_utils::U256;
 
     use super::*;
     use proptest::prelude::*;
 
     proptest! {
+        #[test]
+        fn test_tick_bitmap_search(
+            bitmap in 0..u128::MAX,
+            bit in -2i32..130,
+            search_down in proptest::bool::ANY,
+        ) {
+            let is_set = |bit: &i32| bitmap & (1u128 << bit) != 0;
+            let expected = if search_down {
+                (0..=bit.min(127)).rev().find(is_set)
+            } else {
+                ((bit + 1).max(0)..128).find(is_set)
+            };
+            assert_eq!(tick_bitmap_search(bitmap, bit, search_down), expected);
+        }
+
         #[test]
         fn test_div_round_up_if(
             n in 0..u128::MAX,
@@ -205,7 +259,7 @@ mod test_bit_math {
     const MAX_FLOOR: (u128, u128, u128) = (11053036065049294753459639, 61572651155449, 2);
 
     mod test_mul_div {
-        use crate::math::checked_mul_div;
+        use crate::checked_mul_div;
 
         use super::MAX_FLOOR;
 
@@ -253,7 +307,7 @@ mod test_bit_math {
         }
     }
     mod test_mul_div_round_up {
-        use crate::math::checked_mul_div_round_up;
+        use crate::checked_mul_div_round_up;
 
         use super::MAX_FLOOR;
 
@@ -293,7 +347,7 @@ mod test_bit_math {
     }
 
     mod test_div_round_up {
-        use crate::math::div_round_up;
+        use crate::div_round_up;
 
         #[test]
         fn test_mul_div_ok() {
@@ -314,7 +368,7 @@ mod test_bit_math {
     }
 
     mod test_mult_shift_right_round_up {
-        use crate::math::checked_mul_shift_right_round_up_if;
+        use crate::checked_mul_shift_right_round_up_if;
 
         #[test]
         fn test_mul_shift_right_ok() {
@@ -0,0 +1,30 @@
+use core::num::TryFromIntError;
+
+/// Errors of the swap math. Variants are named after the matching error codes of the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    DivideByZero,
+    MulDivOverflow,
+    MultiplicationOverflow,
+    MultiplicationShiftRightOverflow,
+    MathOverflow,
+    NumberCastError,
+    NumberDownCastError,
+    TokenMaxExceeded,
+    TokenMinSubceeded,
+    SqrtPriceOutOfBounds,
+    InvalidSqrtPriceLimitDirection,
+    ZeroTradableAmount,
+    LiquidityOverflow,
+    LiquidityUnderflow,
+    LiquidityTooHigh,
+    AmountRemainingOverflow,
+    AmountCalcOverflow,
+    OverflowOrConversion,
+}
+
+impl From<TryFromIntError> for MathError {
+    fn from(_: TryFromIntError) -> Self {
+        MathError::NumberCastError
+    }
+}
@@ -0,0 +1,29 @@
+//! Swap math of the dex program without Anchor or Solana dependencies.
+//!
+//! The crate is `no_std`, so the same quote logic can be compiled into SDKs, e.g. for the
+//! `wasm32-unknown-unknown` target, and into on-chain programs. `yarn check:wasm` checks that the
+//! crate still builds for that target.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+pub mod bit_math;
+pub mod error;
+pub mod liquidity_math;
+pub mod quote;
+pub mod swap;
+pub mod swap_math;
+pub mod tick_math;
+pub mod token_math;
+pub mod u256_math;
+
+#[cfg(test)]
+mod test_utils;
+
+pub use bit_math::*;
+pub use error::*;
+pub use liquidity_math::*;
+pub use quote::*;
+pub use swap::*;
+pub use swap_math::*;
+pub use tick_math::*;
+pub use token_math::*;
+pub use u256_math::*;
@@ -0,0 +1,139 @@
+use crate::error::MathError;
+
+use super::{increasing_price_order, mul_u256, U256Muldiv, Q64_RESOLUTION};
+
+// Adds a signed liquidity delta to a given integer liquidity amount.
+// Errors on overflow or underflow.
+pub fn add_liquidity_delta(liquidity: u128, delta: i128) -> Result<u128, MathError> {
+    if delta == 0 {
+        return Ok(liquidity);
+    }
+    if delta > 0 {
+        liquidity
+            .checked_add(delta as u128)
+            .ok_or(MathError::LiquidityOverflow)
+    } else {
+        liquidity
+            .checked_sub(delta.unsigned_abs())
+            .ok_or(MathError::LiquidityUnderflow)
+    }
+}
+
+// Converts an unsigned liquidity amount to a signed liquidity delta
+pub fn convert_to_liquidity_delta(
+    liquidity_amount: u128,
+    positive: bool,
+) -> Result<i128, MathError> {
+    if liquidity_amount > i128::MAX as u128 {
+        // The liquidity_amount is converted to a liquidity_delta that is represented as an i128
+        // By doing this conversion we lose the most significant bit in the u128
+        // Here we enforce a max value of i128::MAX on the u128 to prevent loss of data.
+        return Err(MathError::LiquidityTooHigh);
+    }
+    Ok(if positive {
+        liquidity_amount as i128
+    } else {
+        -(liquidity_amount as i128)
+    })
+}
+
+// Calculates the liquidity a given amount of token_a provides between two prices, rounded down.
+// L = amount_a * sqrt_price_lower * sqrt_price_upper / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_a: u64,
+) -> Result<u128, MathError> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(MathError::DivideByZero);
+    }
+
+    // Both sqrt prices are below 2^96, so their Q64.64 product fits in 128 bits
+    let price_product = mul_u256(sqrt_price_lower, sqrt_price_upper)
+        .shift_word_right()
+        .try_into_u128()?;
+
+    let numerator = mul_u256(amount_a as u128, price_product);
+    let (quotient, _) = numerator.div(U256Muldiv::new(0, sqrt_price_diff), false);
+    quotient.try_into_u128()
+}
+
+// Calculates the liquidity a given amount of token_b provides between two prices, rounded down.
+// L = amount_b / (sqrt_price_upper - sqrt_price_lower)
+pub fn get_liquidity_from_amount_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    amount_b: u64,
+) -> Result<u128, MathError> {
+    let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
+    let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
+    if sqrt_price_diff == 0 {
+        return Err(MathError::DivideByZero);
+    }
+
+    Ok(((amount_b as u128) << Q64_RESOLUTION) / sqrt_price_diff)
+}
+
+#[cfg(test)]
+mod liquidity_math_tests {
+    use super::add_liquidity_delta;
+    use super::MathError;
+
+    #[test]
+    fn test_valid_add_liquidity_delta() {
+        assert_eq!(add_liquidity_delta(100, 100).unwrap(), 200);
+        assert_eq!(add_liquidity_delta(100, 0).unwrap(), 100);
+        assert_eq!(add_liquidity_delta(100, -100).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_add_liquidity_delta_overflow() {
+        let result = add_liquidity_delta(u128::MAX, 1);
+        assert_eq!(result.unwrap_err(), MathError::LiquidityOverflow);
+    }
+
+    #[test]
+    fn test_invalid_add_liquidity_delta_underflow() {
+        let result = add_liquidity_delta(u128::MIN, -1);
+        assert_eq!(result.unwrap_err(), MathError::LiquidityUnderflow);
+    }
+
+    #[test]
+    fn test_get_liquidity_from_amounts_round_trip() {
+        use super::{get_liquidity_from_amount_a, get_liquidity_from_amount_b};
+        use crate::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index};
+
+        let sqrt_price_lower = sqrt_price_from_tick_index(-1000);
+        let sqrt_price_upper = sqrt_price_from_tick_index(2000);
+
+        let liquidity =
+            get_liquidity_from_amount_a(sqrt_price_lower, sqrt_price_upper, 1_000_000).unwrap();
+        let amount_a =
+            get_amount_delta_a(sqrt_price_lower, sqrt_price_upper, liquidity, true).unwrap();
+        assert!(amount_a <= 1_000_000);
+        assert!(amount_a >= 999_999);
+
+        let liquidity =
+            get_liquidity_from_amount_b(sqrt_price_upper, sqrt_price_lower, 1_000_000).unwrap();
+        let amount_b =
+            get_amount_delta_b(sqrt_price_lower, sqrt_price_upper, liquidity, true).unwrap();
+        assert!(amount_b <= 1_000_000);
+        assert!(amount_b >= 999_999);
+    }
+
+    #[test]
+    fn test_get_liquidity_from_amounts_equal_prices() {
+        use super::{get_liquidity_from_amount_a, get_liquidity_from_amount_b};
+
+        assert_eq!(
+            get_liquidity_from_amount_a(1 << 64, 1 << 64, 100).unwrap_err(),
+            MathError::DivideByZero
+        );
+        assert_eq!(
+            get_liquidity_from_amount_b(1 << 64, 1 << 64, 100).unwrap_err(),
+            MathError::DivideByZero
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use core::convert::TryInto;
+
+use crate::error::MathError;
+use crate::*;
+
+// Thin API over the pool math taking and returning plain integers, so quotes can be computed
+// off-chain (e.g. in a browser) without any account types. The swap and liquidity instructions
+// run the same functions.
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapStepQuoteParams {
+    pub amount_remaining: u64,
+    pub fee_rate: u16,
+    pub protocol_fee_rate: u16,
+    pub liquidity: u128,
+    pub sqrt_price_current: u128,
+    pub sqrt_price_target: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct SwapStepQuote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub next_sqrt_price: u128,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+}
+
+pub fn sqrt_price_from_tick(tick_index: i32) -> u128 {
+    sqrt_price_from_tick_index(tick_index)
+}
+
+pub fn tick_from_sqrt_price(sqrt_price: u128) -> i32 {
+    tick_index_from_sqrt_price(&sqrt_price)
+}
+
+/// Split a swap fee into the part paid to liquidity providers and the protocol fee.
+///
+/// # Returns
+/// - `(u64, u64)`: The liquidity provider fee and the protocol fee
+pub fn split_fee(fee_amount: u64, protocol_fee_rate: u16) -> Result<(u64, u64), MathError> {
+    let protocol_fee: u64 = (fee_amount as u128)
+        .checked_mul(protocol_fee_rate as u128)
+        .ok_or(MathError::OverflowOrConversion)?
+        .checked_div(PROTOCOL_FEE_RATE_MUL_VALUE)
+        .ok_or(MathError::OverflowOrConversion)?
+        .try_into()
+        .map_err(|_| MathError::OverflowOrConversion)?;
+    let lp_fee = fee_amount
+        .checked_sub(protocol_fee)
+        .ok_or(MathError::OverflowOrConversion)?;
+    Ok((lp_fee, protocol_fee))
+}
+
+/// Get the token amounts held by `liquidity` in the tick range at the current price.
+/// Deposits round up, withdrawals round down.
+pub fn token_amounts_from_liquidity(
+    liquidity: u128,
+    current_tick_index: i32,
+    sqrt_price: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    round_up: bool,
+) -> Result<(u64, u64), MathError> {
+    let lower_price = sqrt_price_from_tick_index(tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(tick_upper_index);
+
+    if current_tick_index < tick_lower_index {
+        // current tick below position
+        Ok((
+            get_amount_delta_a(lower_price, upper_price, liquidity, round_up)?,
+            0,
+        ))
+    } else if current_tick_index < tick_upper_index {
+        // current tick inside position
+        Ok((
+            get_amount_delta_a(sqrt_price, upper_price, liquidity, round_up)?,
+            get_amount_delta_b(lower_price, sqrt_price, liquidity, round_up)?,
+        ))
+    } else {
+        // current tick above position
+        Ok((
+            0,
+            get_amount_delta_b(lower_price, upper_price, liquidity, round_up)?,
+        ))
+    }
+}
+
+/// Quote a single swap step within constant liquidity, up to `sqrt_price_target`.
+pub fn quote_swap_step(params: SwapStepQuoteParams) -> Result<SwapStepQuote, MathError> {
+    let swap_computation = compute_swap(
+        params.amount_remaining,
+        params.fee_rate,
+        params.liquidity,
+        params.sqrt_price_current,
+        params.sqrt_price_target,
+        params.amount_specified_is_input,
+        params.a_to_b,
+    )?;
+    let (lp_fee, protocol_fee) = split_fee(swap_computation.fee_amount, params.protocol_fee_rate)?;
+
+    Ok(SwapStepQuote {
+        amount_in: swap_computation.amount_in,
+        amount_out: swap_computation.amount_out,
+        next_sqrt_price: swap_computation.next_price,
+        lp_fee,
+        protocol_fee,
+    })
+}
+
+#[cfg(test)]
+mod quote_tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_conversions() {
+        assert_eq!(sqrt_price_from_tick(0), 1 << 64);
+        assert_eq!(tick_from_sqrt_price(1 << 64), 0);
+        assert_eq!(tick_from_sqrt_price(sqrt_price_from_tick(-640)), -640);
+    }
+
+    #[test]
+    fn test_split_fee() {
+        assert_eq!(split_fee(300, 300), Ok((291, 9)));
+        assert_eq!(split_fee(300, 0), Ok((300, 0)));
+        assert_eq!(
+            split_fee(u64::MAX, 2_500),
+            Ok((u64::MAX - u64::MAX / 4, u64::MAX / 4))
+        );
+    }
+
+    #[test]
+    fn test_token_amounts_from_liquidity() {
+        let sqrt_price = sqrt_price_from_tick(0);
+        let (amount_a, amount_b) =
+            token_amounts_from_liquidity(1_000_000, 0, sqrt_price, -64, 64, true).unwrap();
+        assert!(amount_a > 0 && amount_b > 0);
+
+        let (amount_a, amount_b) =
+            token_amounts_from_liquidity(1_000_000, 0, sqrt_price, 64, 128, false).unwrap();
+        assert!(amount_a > 0);
+        assert_eq!(amount_b, 0);
+
+        let (amount_a, amount_b) =
+            token_amounts_from_liquidity(1_000_000, 0, sqrt_price, -128, -64, false).unwrap();
+        assert_eq!(amount_a, 0);
+        assert!(amount_b > 0);
+    }
+
+    #[test]
+    fn test_known_swap_step_quote() {
+        // 100_000 token A into 1e9 liquidity at price 1 with a 0.3% fee, 3% of it to the protocol
+        let quote = quote_swap_step(SwapStepQuoteParams {
+            amount_remaining: 100_000,
+            fee_rate: 3_000,
+            protocol_fee_rate: 300,
+            liquidity: 1_000_000_000,
+            sqrt_price_current: 1 << 64,
+            sqrt_price_target: MIN_SQRT_PRICE_X64,
+            amount_specified_is_input: true,
+            a_to_b: true,
+        })
+        .unwrap();
+        assert_eq!(
+            quote,
+            SwapStepQuote {
+                amount_in: 99_700,
+                amount_out: 99_690,
+                next_sqrt_price: 18444905116669419675,
+                lp_fee: 291,
+                protocol_fee: 9,
+            }
+        );
+    }
+}
@@ -0,0 +1,487 @@
+use core::convert::Infallible;
+
+use crate::bit_math::Q64_RESOLUTION;
+use crate::error::MathError;
+use crate::liquidity_math::add_liquidity_delta;
+use crate::quote::split_fee;
+use crate::swap_math::compute_swap;
+use crate::tick_math::*;
+
+/// The state of a pool a swap starts from.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "solana",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct PoolState {
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub liquidity: u128,
+    pub fee_rate: u16,
+    pub protocol_fee_rate: u16,
+    pub fee_growth_global_a: u128,
+    pub fee_growth_global_b: u128,
+}
+
+/// An initialized tick of a pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "solana",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct TickState {
+    pub index: i32,
+    pub liquidity_net: i128,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "solana",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct SwapParams {
+    pub amount: u64,
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+    // Basis points of the LP fee of each step that are routed to the referral of the swap
+    pub referral_fee_rate: u16,
+    // The swap stops after crossing this many initialized ticks, 0 for no limit
+    pub max_tick_crossings: u8,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "solana",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct SwapResult {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub next_liquidity: u128,
+    pub next_tick_index: i32,
+    pub next_sqrt_price: u128,
+    // Fee growth of the input token after the swap
+    pub next_fee_growth_global: u128,
+    // Protocol fee charged by the swap in input token units
+    pub protocol_fee: u64,
+    // Part of the LP fee routed to the referral of the swap
+    pub referral_fee: u64,
+    // Total fee charged by the swap in input token units, protocol fee included
+    pub fee: u64,
+    pub tick_crossed_count: u32,
+}
+
+/// The ticks a swap moves through.
+pub trait TickSource {
+    type Error;
+
+    /// The index of the next tick the swap moves to from `tick_index`. The search is inclusive of
+    /// `tick_index` for a to b swaps. The tick does not have to be initialized, e.g. when the
+    /// source ends before the next initialized tick.
+    fn next_tick_index(&mut self, tick_index: i32, a_to_b: bool) -> Result<i32, Self::Error>;
+
+    /// Cross the tick last returned by `next_tick_index`, once the price of the swap reached it.
+    /// `fee_growth_global_input` is the fee growth of the input token at the crossing.
+    ///
+    /// # Returns
+    /// - `Some(i128)`: The liquidity net of the tick, if the tick is initialized
+    /// - `None`: The tick is not initialized
+    fn cross_tick(
+        &mut self,
+        tick_index: i32,
+        a_to_b: bool,
+        fee_growth_global_input: u128,
+    ) -> Result<Option<i128>, Self::Error>;
+}
+
+/// Errors of `swap_with_tick_source`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapError<E> {
+    Math(MathError),
+    TickSource(E),
+}
+
+impl<E> From<MathError> for SwapError<E> {
+    fn from(error: MathError) -> Self {
+        SwapError::Math(error)
+    }
+}
+
+/// Simulate a swap with the same math as the swap instructions of the program.
+///
+/// `ticks` are the initialized ticks of the pool in ascending order of their index. Unlike the
+/// program, which stops at the end of the tick-arrays passed to the swap, the simulation can move
+/// the price up to the min or max tick.
+pub fn simulate_swap(
+    state: &PoolState,
+    ticks: &[TickState],
+    params: SwapParams,
+) -> Result<SwapResult, MathError> {
+    swap_with_tick_source(state, &mut InitializedTicks(ticks), params).map_err(
+        |error| match error {
+            SwapError::Math(error) => error,
+            SwapError::TickSource(never) => match never {},
+        },
+    )
+}
+
+/// Run a swap over the ticks of `tick_source`. This is the swap loop of the swap instructions of
+/// the program and of `simulate_swap`.
+///
+/// The swap is charged `state.fee_rate`, which may differ from the fee rate of the pool, e.g. when
+/// it is reduced by an LP fee rebate.
+pub fn swap_with_tick_source<T: TickSource>(
+    state: &PoolState,
+    tick_source: &mut T,
+    params: SwapParams,
+) -> Result<SwapResult, SwapError<T::Error>> {
+    let SwapParams {
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        referral_fee_rate,
+        max_tick_crossings,
+    } = params;
+
+    if !(MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_limit) {
+        return Err(MathError::SqrtPriceOutOfBounds.into());
+    }
+
+    if a_to_b && sqrt_price_limit > state.sqrt_price
+        || !a_to_b && sqrt_price_limit < state.sqrt_price
+    {
+        return Err(MathError::InvalidSqrtPriceLimitDirection.into());
+    }
+
+    if amount == 0 {
+        return Err(MathError::ZeroTradableAmount.into());
+    }
+
+    let mut amount_remaining: u64 = amount;
+    let mut amount_calculated: u64 = 0;
+    let mut curr_sqrt_price = state.sqrt_price;
+    let mut curr_tick_index = state.tick_current_index;
+    let mut curr_liquidity = state.liquidity;
+    let mut fee: u64 = 0;
+    let mut curr_protocol_fee: u64 = 0;
+    let mut curr_referral_fee: u64 = 0;
+    let mut tick_crossed_count: u32 = 0;
+    let mut curr_fee_growth_global_input = if a_to_b {
+        state.fee_growth_global_a
+    } else {
+        state.fee_growth_global_b
+    };
+
+    while amount_remaining > 0 && sqrt_price_limit != curr_sqrt_price {
+        let next_tick_index = tick_source
+            .next_tick_index(curr_tick_index, a_to_b)
+            .map_err(SwapError::TickSource)?;
+
+        let next_tick_sqrt_price = sqrt_price_from_tick_index(next_tick_index);
+        let sqrt_price_target = if a_to_b {
+            sqrt_price_limit.max(next_tick_sqrt_price)
+        } else {
+            sqrt_price_limit.min(next_tick_sqrt_price)
+        };
+
+        let swap_computation = compute_swap(
+            amount_remaining,
+            state.fee_rate,
+            curr_liquidity,
+            curr_sqrt_price,
+            sqrt_price_target,
+            amount_specified_is_input,
+            a_to_b,
+        )?;
+
+        if amount_specified_is_input {
+            amount_remaining = amount_remaining
+                .checked_sub(swap_computation.amount_in)
+                .and_then(|amount| amount.checked_sub(swap_computation.fee_amount))
+                .ok_or(MathError::AmountRemainingOverflow)?;
+            amount_calculated = amount_calculated
+                .checked_add(swap_computation.amount_out)
+                .ok_or(MathError::AmountCalcOverflow)?;
+        } else {
+            amount_remaining = amount_remaining
+                .checked_sub(swap_computation.amount_out)
+                .ok_or(MathError::AmountRemainingOverflow)?;
+            amount_calculated = amount_calculated
+                .checked_add(swap_computation.amount_in)
+                .and_then(|amount| amount.checked_add(swap_computation.fee_amount))
+                .ok_or(MathError::AmountCalcOverflow)?;
+        }
+
+        let (protocol_fee, referral_fee, lp_fee) = split_step_fee(
+            swap_computation.fee_amount,
+            state.protocol_fee_rate,
+            referral_fee_rate,
+        )?;
+        curr_protocol_fee = curr_protocol_fee.wrapping_add(protocol_fee);
+        curr_referral_fee = curr_referral_fee
+            .checked_add(referral_fee)
+            .ok_or(MathError::AmountCalcOverflow)?;
+        if let Some(fee_growth) = ((lp_fee as u128) << Q64_RESOLUTION).checked_div(curr_liquidity) {
+            curr_fee_growth_global_input = curr_fee_growth_global_input
+                .checked_add(fee_growth)
+                .ok_or(MathError::OverflowOrConversion)?;
+        }
+
+        if swap_computation.next_price == next_tick_sqrt_price {
+            let liquidity_net = tick_source
+                .cross_tick(next_tick_index, a_to_b, curr_fee_growth_global_input)
+                .map_err(SwapError::TickSource)?;
+            if let Some(liquidity_net) = liquidity_net {
+                // Use -liquidity_net if going left, +liquidity_net going right
+                let signed_liquidity_net = if a_to_b {
+                    liquidity_net.checked_neg().ok_or(MathError::MathOverflow)?
+                } else {
+                    liquidity_net
+                };
+                curr_liquidity = add_liquidity_delta(curr_liquidity, signed_liquidity_net)?;
+                tick_crossed_count += 1;
+            }
+
+            // The search is inclusive of the current index in an a_to_b trade.
+            // We therefore have to shift the index by 1 to advance to the next init tick to the left.
+            curr_tick_index = if a_to_b {
+                next_tick_index - 1
+            } else {
+                next_tick_index
+            };
+        } else if swap_computation.next_price != curr_sqrt_price {
+            curr_tick_index = tick_index_from_sqrt_price(&swap_computation.next_price);
+        }
+
+        curr_sqrt_price = swap_computation.next_price;
+        fee = fee
+            .checked_add(swap_computation.fee_amount)
+            .ok_or(MathError::AmountCalcOverflow)?;
+
+        if max_tick_crossings > 0 && tick_crossed_count >= max_tick_crossings as u32 {
+            break;
+        }
+    }
+
+    let amount_specified = amount
+        .checked_sub(amount_remaining)
+        .ok_or(MathError::OverflowOrConversion)?;
+    let (amount_a, amount_b) = if a_to_b == amount_specified_is_input {
+        (amount_specified, amount_calculated)
+    } else {
+        (amount_calculated, amount_specified)
+    };
+
+    Ok(SwapResult {
+        amount_a,
+        amount_b,
+        next_liquidity: curr_liquidity,
+        next_tick_index: curr_tick_index,
+        next_sqrt_price: curr_sqrt_price,
+        next_fee_growth_global: curr_fee_growth_global_input,
+        protocol_fee: curr_protocol_fee,
+        referral_fee: curr_referral_fee,
+        fee,
+        tick_crossed_count,
+    })
+}
+
+// Split the fee of a swap step into the protocol fee, the referral fee and the LP fee. The referral
+// fee is a share of the LP fee, so it uses the same basis point split as the protocol fee.
+fn split_step_fee(
+    fee_amount: u64,
+    protocol_fee_rate: u16,
+    referral_fee_rate: u16,
+) -> Result<(u64, u64, u64), MathError> {
+    let (lp_fee, protocol_fee) = split_fee(fee_amount, protocol_fee_rate)?;
+    let (lp_fee, referral_fee) = split_fee(lp_fee, referral_fee_rate)?;
+    Ok((protocol_fee, referral_fee, lp_fee))
+}
+
+// The initialized ticks of a pool in ascending order of their index
+struct InitializedTicks<'a>(&'a [TickState]);
+
+impl TickSource for InitializedTicks<'_> {
+    type Error = Infallible;
+
+    // The search is inclusive of tick_index for a to b swaps
+    fn next_tick_index(&mut self, tick_index: i32, a_to_b: bool) -> Result<i32, Infallible> {
+        let ticks = self.0;
+        let position = ticks.partition_point(|tick| tick.index <= tick_index);
+        Ok(if a_to_b {
+            match position.checked_sub(1) {
+                Some(position) => ticks[position].index,
+                None => MIN_TICK_INDEX,
+            }
+        } else {
+            match ticks.get(position) {
+                Some(tick) => tick.index,
+                None => MAX_TICK_INDEX,
+            }
+        })
+    }
+
+    fn cross_tick(
+        &mut self,
+        tick_index: i32,
+        _a_to_b: bool,
+        _fee_growth_global_input: u128,
+    ) -> Result<Option<i128>, Infallible> {
+        Ok(self
+            .0
+            .binary_search_by_key(&tick_index, |tick| tick.index)
+            .ok()
+            .map(|position| self.0[position].liquidity_net))
+    }
+}
+
+#[cfg(test)]
+mod simulate_swap_tests {
+    use super::*;
+
+    fn state() -> PoolState {
+        PoolState {
+            sqrt_price: 1 << 64,
+            tick_current_index: 0,
+            liquidity: 1_000_000_000,
+            fee_rate: 3000,
+            protocol_fee_rate: 300,
+            ..Default::default()
+        }
+    }
+
+    fn params(amount: u64, a_to_b: bool) -> SwapParams {
+        SwapParams {
+            amount,
+            sqrt_price_limit: if a_to_b {
+                MIN_SQRT_PRICE_X64
+            } else {
+                MAX_SQRT_PRICE_X64
+            },
+            amount_specified_is_input: true,
+            a_to_b,
+            ..Default::default()
+        }
+    }
+
+    fn ticks() -> [TickState; 2] {
+        [
+            TickState {
+                index: -640,
+                liquidity_net: 500_000_000,
+            },
+            TickState {
+                index: 640,
+                liquidity_net: -500_000_000,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_swap_within_tick() {
+        let result = simulate_swap(&state(), &[], params(1_000, true)).unwrap();
+        assert_eq!(result.amount_a, 1_000);
+        assert_eq!(result.fee, 3);
+        assert_eq!(result.amount_b, 996);
+        assert_eq!(result.next_liquidity, 1_000_000_000);
+        assert_eq!(result.tick_crossed_count, 0);
+        assert!(result.next_sqrt_price < 1 << 64);
+    }
+
+    #[test]
+    fn test_swap_crosses_initialized_tick() {
+        let ticks = ticks();
+
+        let result = simulate_swap(&state(), &ticks, params(50_000_000, true)).unwrap();
+        assert_eq!(result.tick_crossed_count, 1);
+        assert_eq!(result.next_liquidity, 500_000_000);
+        assert!(result.next_tick_index < -640);
+        assert_eq!(result.amount_a, 50_000_000);
+
+        let result = simulate_swap(&state(), &ticks, params(50_000_000, false)).unwrap();
+        assert_eq!(result.tick_crossed_count, 1);
+        assert_eq!(result.next_liquidity, 500_000_000);
+        assert!(result.next_tick_index >= 640);
+        assert_eq!(result.amount_b, 50_000_000);
+    }
+
+    #[test]
+    fn test_swap_stops_at_price_limit() {
+        let limit = sqrt_price_from_tick_index(-64);
+        let mut params = params(u64::MAX / 2, true);
+        params.sqrt_price_limit = limit;
+
+        let result = simulate_swap(&state(), &[], params).unwrap();
+        assert_eq!(result.next_sqrt_price, limit);
+        assert!(result.amount_a < u64::MAX / 2);
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        assert_eq!(
+            simulate_swap(&state(), &[], params(0, true)),
+            Err(MathError::ZeroTradableAmount)
+        );
+
+        let mut params = params(1_000, true);
+        params.sqrt_price_limit = MAX_SQRT_PRICE_X64;
+        assert_eq!(
+            simulate_swap(&state(), &[], params),
+            Err(MathError::InvalidSqrtPriceLimitDirection)
+        );
+    }
+
+    #[test]
+    fn test_referral_fee_taken_from_lp_fee() {
+        let result = simulate_swap(&state(), &[], params(1_000_000, true)).unwrap();
+        assert_eq!(result.referral_fee, 0);
+
+        let mut referral_params = params(1_000_000, true);
+        referral_params.referral_fee_rate = 2500;
+        let referral_result = simulate_swap(&state(), &[], referral_params).unwrap();
+
+        // The trade and the protocol fee are unchanged, only the LP fee is split
+        assert_eq!(referral_result.amount_a, result.amount_a);
+        assert_eq!(referral_result.amount_b, result.amount_b);
+        assert_eq!(referral_result.fee, 3_000);
+        assert_eq!(referral_result.protocol_fee, result.protocol_fee);
+        assert_eq!(referral_result.protocol_fee, 90);
+        assert_eq!(referral_result.referral_fee, 727);
+        assert!(referral_result.next_fee_growth_global < result.next_fee_growth_global);
+    }
+
+    #[test]
+    fn test_fee_rate_of_state_is_charged() {
+        let mut rebated_state = state();
+        rebated_state.fee_rate = 1000;
+        let result = simulate_swap(&rebated_state, &[], params(1_000_000, true)).unwrap();
+        assert_eq!(result.fee, 1_000);
+    }
+
+    #[test]
+    fn test_max_tick_crossings_stops_swap() {
+        let ticks = [
+            TickState {
+                index: -1280,
+                liquidity_net: 250_000_000,
+            },
+            TickState {
+                index: -640,
+                liquidity_net: 250_000_000,
+            },
+        ];
+
+        let result = simulate_swap(&state(), &ticks, params(500_000_000, true)).unwrap();
+        assert_eq!(result.tick_crossed_count, 2);
+
+        let mut limited_params = params(500_000_000, true);
+        limited_params.max_tick_crossings = 1;
+        let result = simulate_swap(&state(), &ticks, limited_params).unwrap();
+        assert_eq!(result.tick_crossed_count, 1);
+        assert_eq!(result.next_tick_index, -641);
+        assert_eq!(result.next_liquidity, 750_000_000);
+        assert!(result.amount_a < 500_000_000);
+    }
+}
@@ -1,7 +1,7 @@
-use std::convert::TryInto;
+use core::convert::TryInto;
 
-use crate::errors::ErrorCode;
-use crate::math::*;
+use crate::error::MathError;
+use crate::*;
 
 #[derive(PartialEq, Debug)]
 pub struct SwapStepComputation {
@@ -19,10 +19,10 @@ pub fn compute_swap(
     sqrt_price_target: u128,
     amount_specified_is_input: bool,
     a_to_b: bool,
-) -> Result<SwapStepComputation, ErrorCode> {
-    let fee_amount;
-
-    let mut amount_fixed_delta = get_amount_fixed_delta(
+) -> Result<SwapStepComputation, MathError> {
+    // At extreme prices with large liquidity the amount needed to reach the target can exceed
+    // u64::MAX. That amount can never be covered, so the step ends before the target instead.
+    let initial_amount_fixed_delta = try_get_amount_fixed_delta(
         sqrt_price_current,
         sqrt_price_target,
         liquidity,
@@ -40,7 +40,7 @@ pub fn compute_swap(
         .try_into()?;
     }
 
-    let next_sqrt_price = if amount_calc >= amount_fixed_delta {
+    let next_sqrt_price = if initial_amount_fixed_delta.lte(amount_calc) {
         sqrt_price_target
     } else {
         get_next_sqrt_price(
@@ -63,15 +63,17 @@ pub fn compute_swap(
     )?;
 
     // If the swap is not at the max, we need to readjust the amount of the fixed token we are using
-    if !is_max_swap {
-        amount_fixed_delta = get_amount_fixed_delta(
+    let amount_fixed_delta = if !is_max_swap || initial_amount_fixed_delta.exceeds_max() {
+        get_amount_fixed_delta(
             sqrt_price_current,
             next_sqrt_price,
             liquidity,
             amount_specified_is_input,
             a_to_b,
-        )?;
-    }
+        )?
+    } else {
+        initial_amount_fixed_delta.into_result()?
+    };
 
     let (amount_in, mut amount_out) = if amount_specified_is_input {
         (amount_fixed_delta, amount_unfixed_delta)
@@ -84,16 +86,19 @@ pub fn compute_swap(
         amount_out = amount_remaining;
     }
 
-    if amount_specified_is_input && !is_max_swap {
-        fee_amount = amount_remaining - amount_in;
+    // Both branches charge at least one unit of fee for a step that trades with a nonzero fee
+    // rate: amount_calc is rounded down below amount_remaining, and the fee of the full step is
+    // rounded up. Tiny swaps therefore cannot avoid the fee through rounding.
+    let fee_amount = if amount_specified_is_input && !is_max_swap {
+        amount_remaining - amount_in
     } else {
-        fee_amount = checked_mul_div_round_up(
+        checked_mul_div_round_up(
             amount_in as u128,
             fee_rate as u128,
             FEE_RATE_MUL_VALUE - fee_rate as u128,
         )?
-        .try_into()?;
-    }
+        .try_into()?
+    };
 
     Ok(SwapStepComputation {
         amount_in,
@@ -103,13 +108,37 @@ pub fn compute_swap(
     })
 }
 
+fn try_get_amount_fixed_delta(
+    sqrt_price_current: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Result<AmountDeltaU64, MathError> {
+    if a_to_b == amount_specified_is_input {
+        try_get_amount_delta_a(
+            sqrt_price_current,
+            sqrt_price_target,
+            liquidity,
+            amount_specified_is_input,
+        )
+    } else {
+        try_get_amount_delta_b(
+            sqrt_price_current,
+            sqrt_price_target,
+            liquidity,
+            amount_specified_is_input,
+        )
+    }
+}
+
 fn get_amount_fixed_delta(
     sqrt_price_current: u128,
     sqrt_price_target: u128,
     liquidity: u128,
     amount_specified_is_input: bool,
     a_to_b: bool,
-) -> Result<u64, ErrorCode> {
+) -> Result<u64, MathError> {
     if a_to_b == amount_specified_is_input {
         get_amount_delta_a(
             sqrt_price_current,
@@ -133,7 +162,7 @@ fn get_amount_unfixed_delta(
     liquidity: u128,
     amount_specified_is_input: bool,
     a_to_b: bool,
-) -> Result<u64, ErrorCode> {
+) -> Result<u64, MathError> {
     if a_to_b == amount_specified_is_input {
         get_amount_delta_b(
             sqrt_price_current,
@@ -189,6 +218,11 @@ mod fuzz_tests {
             // Amount_in can not exceed maximum amount
             assert!(amount_in <= u64::MAX - fee_amount);
 
+            // Any step that trades charges at least one unit of fee
+            if amount_in > 0 || amount_out > 0 {
+                assert!(fee_amount > 0);
+            }
+
             // Amounts calculated are less than amount specified
             let amount_used = if amount_specified_is_input {
                 amount_in + fee_amount
@@ -555,10 +589,10 @@ mod unit_tests {
 
     mod test_compute_swap {
         const TWO_PCT: u16 = 20000;
-        use std::convert::TryInto;
+        use core::convert::TryInto;
 
         use super::*;
-        use crate::math::bit_math::Q64_RESOLUTION;
+        use crate::bit_math::Q64_RESOLUTION;
 
         #[test]
         fn swap_a_to_b_input() {
@@ -569,7 +603,7 @@ mod unit_tests {
             let price_limit = 4;
 
             // Calculate fee given fee percentage
-            let fee_amount = div_round_up((amount * u128::from(TWO_PCT)).into(), 1_000_000)
+            let fee_amount = div_round_up(amount * u128::from(TWO_PCT), 1_000_000)
                 .ok()
                 .unwrap();
 
@@ -592,8 +626,8 @@ mod unit_tests {
             let amount_out = init_b - div_round_up(init_liq * init_liq, new_a).ok().unwrap();
             test_swap(
                 100,
-                TWO_PCT,                      // 2 % fee
-                init_liq.try_into().unwrap(), // sqrt(ab)
+                TWO_PCT,  // 2 % fee
+                init_liq, // sqrt(ab)
                 // Current
                 // b = 1296 * 9 => 11664
                 // a = 1296 / 9 => 144
@@ -918,6 +952,91 @@ mod unit_tests {
         }
     }
 
+    mod test_compute_swap_extreme_prices {
+        use super::*;
+
+        // The amount needed to move between the price bounds with this liquidity far exceeds
+        // u64::MAX, so each step must end before the target instead of overflowing.
+        const LIQUIDITY: u128 = u64::MAX as u128;
+
+        #[test]
+        fn swap_a_to_b_input_from_max_sqrt_price() {
+            // At the max price one unit of A is worth ~2^64 units of B, so the output of any
+            // tradable input cannot be represented and must surface as an error.
+            let swap_computation = compute_swap(
+                1_000_000_000,
+                3000,
+                LIQUIDITY,
+                MAX_SQRT_PRICE_X64,
+                MIN_SQRT_PRICE_X64,
+                true,
+                true,
+            );
+            assert_eq!(swap_computation, Err(MathError::MathOverflow));
+        }
+
+        #[test]
+        fn swap_a_to_b_output_from_max_sqrt_price() {
+            let amount = 1_000_000_000;
+            let swap_computation = compute_swap(
+                amount,
+                3000,
+                LIQUIDITY,
+                MAX_SQRT_PRICE_X64,
+                MIN_SQRT_PRICE_X64,
+                false,
+                true,
+            )
+            .unwrap();
+
+            assert!(swap_computation.next_price < MAX_SQRT_PRICE_X64);
+            assert!(swap_computation.next_price > MIN_SQRT_PRICE_X64);
+            assert_eq!(swap_computation.amount_out, amount);
+        }
+
+        #[test]
+        fn swap_b_to_a_input_to_max_sqrt_price() {
+            let amount = u64::MAX;
+            let sqrt_price_current = MAX_SQRT_PRICE_X64 / 2;
+            let swap_computation = compute_swap(
+                amount,
+                3000,
+                LIQUIDITY,
+                sqrt_price_current,
+                MAX_SQRT_PRICE_X64,
+                true,
+                false,
+            )
+            .unwrap();
+
+            assert!(swap_computation.next_price > sqrt_price_current);
+            assert!(swap_computation.next_price < MAX_SQRT_PRICE_X64);
+            assert_eq!(
+                swap_computation.amount_in + swap_computation.fee_amount,
+                amount
+            );
+        }
+
+        #[test]
+        fn amount_delta_max_liquidity_does_not_panic() {
+            let delta_a =
+                try_get_amount_delta_a(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, u128::MAX, true);
+            assert!(delta_a.unwrap().exceeds_max());
+
+            let delta_b =
+                try_get_amount_delta_b(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, u128::MAX, true);
+            assert_eq!(
+                delta_b.unwrap(),
+                AmountDeltaU64::ExceedsMax(MathError::MathOverflow)
+            );
+            assert_eq!(
+                get_amount_delta_b(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, u128::MAX, false),
+                Err(MathError::MathOverflow)
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn test_swap(
         amount_remaining: u64,
         fee_rate: u16,
@@ -940,3 +1059,69 @@ mod unit_tests {
         assert_eq!(swap_computation.ok().unwrap(), expected);
     }
 }
+
+#[cfg(test)]
+mod minimum_fee_tests {
+    use super::*;
+
+    fn swap_step(
+        amount: u64,
+        sqrt_price_target: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+    ) -> SwapStepComputation {
+        compute_swap(
+            amount,
+            3000,
+            1_000_000_000,
+            1 << 64,
+            sqrt_price_target,
+            amount_specified_is_input,
+            a_to_b,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_exact_in_below_rounding_threshold() {
+        // 1 * 0.3% rounds to zero, the whole input is charged as fee
+        let step = swap_step(1, MIN_SQRT_PRICE_X64, true, true);
+        assert_eq!(
+            (step.amount_in, step.amount_out, step.fee_amount),
+            (0, 0, 1)
+        );
+
+        // 333 * 0.3% is just below one unit
+        let step = swap_step(333, MAX_SQRT_PRICE_X64, true, false);
+        assert_eq!(
+            (step.amount_in, step.amount_out, step.fee_amount),
+            (332, 331, 1)
+        );
+    }
+
+    #[test]
+    fn test_exact_out_below_rounding_threshold() {
+        let step = swap_step(1, MAX_SQRT_PRICE_X64, false, false);
+        assert_eq!(
+            (step.amount_in, step.amount_out, step.fee_amount),
+            (2, 1, 1)
+        );
+
+        let step = swap_step(1, MIN_SQRT_PRICE_X64, false, true);
+        assert_eq!(
+            (step.amount_in, step.amount_out, step.fee_amount),
+            (2, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_step_reaching_target_below_rounding_threshold() {
+        // The step reaches the target after one unit of input, the fee is rounded up
+        let step = swap_step(10, (1 << 64) - 1, true, true);
+        assert_eq!(step.next_price, (1 << 64) - 1);
+        assert_eq!(
+            (step.amount_in, step.amount_out, step.fee_amount),
+            (1, 0, 1)
+        );
+    }
+}
@@ -0,0 +1,20 @@
+#![allow(clippy::assign_op_pattern)]
+
+use crate::error::MathError;
+use core::convert::TryInto;
+use uint::construct_uint;
+
+construct_uint! {
+    // Reference U256 of [u64; 4] to check the math against
+    pub struct U256(4);
+}
+
+impl U256 {
+    pub fn try_into_u64(self) -> Result<u64, MathError> {
+        self.try_into().map_err(|_| MathError::NumberCastError)
+    }
+
+    pub fn try_into_u128(self) -> Result<u128, MathError> {
+        self.try_into().map_err(|_| MathError::NumberCastError)
+    }
+}
@@ -1,5 +1,8 @@
-use crate::math::u256_math::*;
-use std::convert::TryInto;
+use crate::u256_math::*;
+use core::convert::TryInto;
+
+pub const MAX_TICK_INDEX: i32 = 443636;
+pub const MIN_TICK_INDEX: i32 = -443636;
 
 // Max/Min sqrt_price derived from max/min tick-index
 pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
@@ -56,7 +59,7 @@ pub fn tick_index_from_sqrt_price(sqrt_price_x64: &u128) -> i32 {
 
     while bit > 0 && precision < BIT_PRECISION {
         r *= r;
-        let is_r_more_than_two = r >> 127 as u32;
+        let is_r_more_than_two = r >> 127;
         r >>= 63 + is_r_more_than_two;
         log2p_fraction_x64 += bit * is_r_more_than_two as i128;
         bit >>= 1;
@@ -77,7 +80,7 @@ pub fn tick_index_from_sqrt_price(sqrt_price_x64: &u128) -> i32 {
         .try_into()
         .unwrap();
 
-    let result_tick = if tick_low == tick_high {
+    if tick_low == tick_high {
         tick_low
     } else {
         // If our estimation for tick_high returns a lower sqrt_price than the input
@@ -90,9 +93,7 @@ pub fn tick_index_from_sqrt_price(sqrt_price_x64: &u128) -> i32 {
         } else {
             tick_low
         }
-    };
-
-    result_tick
+    }
 }
 
 fn mul_shift_96(n0: u128, n1: u128) -> u128 {
@@ -236,10 +237,7 @@ fn get_sqrt_price_negative_tick(tick: i32) -> u128 {
 mod fuzz_tests {
 
     use super::*;
-    use crate::{
-        math::U256,
-        state::{MAX_TICK_INDEX, MIN_TICK_INDEX},
-    };
+    use crate::test_utils::U256;
     use proptest::prelude::*;
 
     fn within_price_approximation(lower: u128, upper: u128) -> bool {
@@ -255,15 +253,11 @@ mod fuzz_tests {
         let ratio_x96 = x.div_mod(y).0.as_u128();
 
         // Find absolute error in ratio in x96
-        let error = if sqrt_10001_x96 > ratio_x96 {
-            sqrt_10001_x96 - ratio_x96
-        } else {
-            ratio_x96 - sqrt_10001_x96
-        };
+        let error = sqrt_10001_x96.abs_diff(ratio_x96);
 
         // Calculate number of error bits
         let error_bits = 128 - error.leading_zeros();
-        return precision - error_bits >= 32;
+        precision - error_bits >= 32
     }
 
     proptest! {
@@ -338,7 +332,6 @@ mod fuzz_tests {
 #[cfg(test)]
 mod test_tick_index_from_sqrt_price {
     use super::*;
-    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
 
     #[test]
     fn test_sqrt_price_from_tick_index_at_max() {
@@ -360,7 +353,7 @@ mod test_tick_index_from_sqrt_price {
         let tick_from_max = tick_index_from_sqrt_price(&sqrt_price_x64_max);
 
         // We don't care about accuracy over the limit. We just care about it's equality properties.
-        assert_eq!(tick_from_max_add_one >= tick_from_max, true);
+        assert!(tick_from_max_add_one >= tick_from_max);
     }
 
     #[test]
@@ -385,7 +378,7 @@ mod test_tick_index_from_sqrt_price {
         let tick_from_min = tick_index_from_sqrt_price(&sqrt_price_x64_min);
 
         // We don't care about accuracy over the limit. We just care about it's equality properties.
-        assert_eq!(tick_from_min_sub_one < tick_from_min, true);
+        assert!(tick_from_min_sub_one < tick_from_min);
     }
 
     #[test]
@@ -413,7 +406,6 @@ mod test_tick_index_from_sqrt_price {
 #[cfg(test)]
 mod sqrt_price_from_tick_index_tests {
     use super::*;
-    use crate::state::{MAX_TICK_INDEX, MIN_TICK_INDEX};
 
     #[test]
     #[should_panic(expected = "NumberDownCastError")]
@@ -421,20 +413,14 @@ mod sqrt_price_from_tick_index_tests {
     fn test_tick_exceed_max() {
         let sqrt_price_from_max_tick_add_one = sqrt_price_from_tick_index(MAX_TICK_INDEX + 1);
         let sqrt_price_from_max_tick = sqrt_price_from_tick_index(MAX_TICK_INDEX);
-        assert_eq!(
-            sqrt_price_from_max_tick_add_one > sqrt_price_from_max_tick,
-            true
-        );
+        assert!(sqrt_price_from_max_tick_add_one > sqrt_price_from_max_tick);
     }
 
     #[test]
     fn test_tick_below_min() {
         let sqrt_price_from_min_tick_sub_one = sqrt_price_from_tick_index(MIN_TICK_INDEX - 1);
         let sqrt_price_from_min_tick = sqrt_price_from_tick_index(MIN_TICK_INDEX);
-        assert_eq!(
-            sqrt_price_from_min_tick_sub_one < sqrt_price_from_min_tick,
-            true
-        );
+        assert!(sqrt_price_from_min_tick_sub_one < sqrt_price_from_min_tick);
     }
 
     #[test]
@@ -593,3 +579,42 @@ mod sqrt_price_from_tick_index_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod tick_math_tests {
+    use super::*;
+
+    #[test]
+    fn test_sqrt_price_bounds() {
+        assert_eq!(
+            sqrt_price_from_tick_index(MAX_TICK_INDEX),
+            MAX_SQRT_PRICE_X64
+        );
+        assert_eq!(
+            sqrt_price_from_tick_index(MIN_TICK_INDEX),
+            MIN_SQRT_PRICE_X64
+        );
+        assert_eq!(sqrt_price_from_tick_index(0), 1 << 64);
+    }
+
+    #[test]
+    fn test_tick_index_round_trip() {
+        for tick in [
+            MIN_TICK_INDEX,
+            -100_000,
+            -64,
+            -1,
+            0,
+            1,
+            64,
+            100_000,
+            MAX_TICK_INDEX,
+        ] {
+            let sqrt_price = sqrt_price_from_tick_index(tick);
+            assert_eq!(tick_index_from_sqrt_price(&sqrt_price), tick);
+            if tick < MAX_TICK_INDEX {
+                assert_eq!(tick_index_from_sqrt_price(&(sqrt_price + 1)), tick);
+            }
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use crate::errors::ErrorCode;
-use crate::math::Q64_RESOLUTION;
+use crate::error::MathError;
+use crate::Q64_RESOLUTION;
 
 use super::{
-    checked_mul_shift_right_round_up_if, div_round_up_if, div_round_up_if_u256, mul_u256,
-    U256Muldiv, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
+    div_round_up_if, div_round_up_if_u256, mul_u256, U256Muldiv, MAX_SQRT_PRICE_X64,
+    MIN_SQRT_PRICE_X64,
 };
 
 // Fee rate is represented as hundredths of a basis point.
@@ -20,10 +20,43 @@ pub const FEE_RATE_MUL_VALUE: u128 = 1_000_000;
 // Max protocol fee rate supported is 25% of the fee rate.
 pub const MAX_PROTOCOL_FEE_RATE: u16 = 2_500;
 
+// Referral fee rate is represented as a basis point of the LP fee.
+// Max referral fee rate supported is 50% of the LP fee.
+pub const MAX_REFERRAL_FEE_RATE: u16 = 5_000;
+
 // Assuming that PROTOCOL_FEE_RATE is represented as a basis point
 // We want PROTOCOL_FEE_RATE_MUL_VALUE = 1/PROTOCOL_FEE_UNIT, so 1e4
 pub const PROTOCOL_FEE_RATE_MUL_VALUE: u128 = 10_000;
 
+#[derive(Debug, PartialEq)]
+pub enum AmountDeltaU64 {
+    Valid(u64),
+    ExceedsMax(MathError),
+}
+
+impl AmountDeltaU64 {
+    pub fn lte(&self, other: u64) -> bool {
+        match self {
+            AmountDeltaU64::Valid(value) => *value <= other,
+            AmountDeltaU64::ExceedsMax(_) => false,
+        }
+    }
+
+    pub fn exceeds_max(&self) -> bool {
+        match self {
+            AmountDeltaU64::Valid(_) => false,
+            AmountDeltaU64::ExceedsMax(_) => true,
+        }
+    }
+
+    pub fn into_result(self) -> Result<u64, MathError> {
+        match self {
+            AmountDeltaU64::Valid(value) => Ok(value),
+            AmountDeltaU64::ExceedsMax(error) => Err(error),
+        }
+    }
+}
+
 //
 // Get change in token_a corresponding to a change in price
 //
@@ -43,30 +76,53 @@ pub fn get_amount_delta_a(
     sqrt_price_1: u128,
     liquidity: u128,
     round_up: bool,
-) -> Result<u64, ErrorCode> {
+) -> Result<u64, MathError> {
+    try_get_amount_delta_a(sqrt_price_0, sqrt_price_1, liquidity, round_up)?.into_result()
+}
+
+// Same as get_amount_delta_a, but a result that does not fit in u64 is reported as
+// ExceedsMax instead of an error so that callers can fall back to a bounded computation.
+pub fn try_get_amount_delta_a(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<AmountDeltaU64, MathError> {
     let (sqrt_price_lower, sqrt_price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
 
     let sqrt_price_diff = sqrt_price_upper - sqrt_price_lower;
 
-    let numerator = mul_u256(liquidity, sqrt_price_diff)
-        .checked_shift_word_left()
-        .ok_or(ErrorCode::MultiplicationOverflow)?;
+    // liquidity * sqrt_price_diff fits in 224 bits. Shifting it by a word can only overflow
+    // when the final amount is far above u64::MAX, since the denominator is at most 2^256.
+    let numerator = match mul_u256(liquidity, sqrt_price_diff).checked_shift_word_left() {
+        Some(numerator) => numerator,
+        None => {
+            return Ok(AmountDeltaU64::ExceedsMax(
+                MathError::MultiplicationOverflow,
+            ))
+        }
+    };
 
     let denominator = mul_u256(sqrt_price_upper, sqrt_price_lower);
 
     let (quotient, remainder) = numerator.div(denominator, round_up);
 
     let result = if round_up && !remainder.is_zero() {
-        quotient.add(U256Muldiv::new(0, 1)).try_into_u128()?
+        quotient.add(U256Muldiv::new(0, 1)).try_into_u128()
     } else {
-        quotient.try_into_u128()?
+        quotient.try_into_u128()
+    };
+
+    let result = match result {
+        Ok(result) => result,
+        Err(error) => return Ok(AmountDeltaU64::ExceedsMax(error)),
     };
 
     if result > u64::MAX as u128 {
-        return Err(ErrorCode::TokenMaxExceeded);
+        return Ok(AmountDeltaU64::ExceedsMax(MathError::TokenMaxExceeded));
     }
 
-    return Ok(result as u64);
+    Ok(AmountDeltaU64::Valid(result as u64))
 }
 
 //
@@ -83,12 +139,38 @@ pub fn get_amount_delta_b(
     sqrt_price_1: u128,
     liquidity: u128,
     round_up: bool,
-) -> Result<u64, ErrorCode> {
+) -> Result<u64, MathError> {
+    try_get_amount_delta_b(sqrt_price_0, sqrt_price_1, liquidity, round_up)?.into_result()
+}
+
+// Same as get_amount_delta_b, but a result that does not fit in u64 is reported as
+// ExceedsMax instead of an error so that callers can fall back to a bounded computation.
+pub fn try_get_amount_delta_b(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<AmountDeltaU64, MathError> {
     let (price_lower, price_upper) = increasing_price_order(sqrt_price_0, sqrt_price_1);
 
-    // liquidity * (price_upper - price_lower) must be less than 2^128
-    // for the token amount to be less than 2^64
-    checked_mul_shift_right_round_up_if(liquidity, price_upper - price_lower, round_up)
+    // liquidity * (price_upper - price_lower) can exceed 2^128 at extreme prices with large
+    // liquidity, so the product is kept in 256 bits before dropping the fractional word.
+    let product = mul_u256(liquidity, price_upper - price_lower);
+    let should_round = round_up && product.get_word(0) > 0;
+
+    let result = match product.shift_word_right().try_into_u128() {
+        Ok(result) if result <= u64::MAX as u128 => result as u64,
+        _ => return Ok(AmountDeltaU64::ExceedsMax(MathError::MathOverflow)),
+    };
+
+    if !should_round {
+        return Ok(AmountDeltaU64::Valid(result));
+    }
+
+    match result.checked_add(1) {
+        Some(result) => Ok(AmountDeltaU64::Valid(result)),
+        None => Ok(AmountDeltaU64::ExceedsMax(MathError::MathOverflow)),
+    }
 }
 
 pub fn increasing_price_order(sqrt_price_0: u128, sqrt_price_1: u128) -> (u128, u128) {
@@ -121,7 +203,7 @@ pub fn get_next_sqrt_price_from_a_round_up(
     liquidity: u128,
     amount: u64,
     amount_specified_is_input: bool,
-) -> Result<u128, ErrorCode> {
+) -> Result<u128, MathError> {
     if amount == 0 {
         return Ok(sqrt_price);
     }
@@ -129,12 +211,12 @@ pub fn get_next_sqrt_price_from_a_round_up(
 
     let numerator = mul_u256(liquidity, sqrt_price)
         .checked_shift_word_left()
-        .ok_or(ErrorCode::MultiplicationOverflow)?;
+        .ok_or(MathError::MultiplicationOverflow)?;
 
     // In this scenario the denominator will end up being < 0
     let liquidity_shift_left = U256Muldiv::new(0, liquidity).shift_word_left();
     if !amount_specified_is_input && liquidity_shift_left.lte(product) {
-        return Err(ErrorCode::DivideByZero);
+        return Err(MathError::DivideByZero);
     }
 
     let denominator = if amount_specified_is_input {
@@ -145,9 +227,9 @@ pub fn get_next_sqrt_price_from_a_round_up(
 
     let price = div_round_up_if_u256(numerator, denominator, true)?;
     if price < MIN_SQRT_PRICE_X64 {
-        return Err(ErrorCode::TokenMinSubceeded);
+        return Err(MathError::TokenMinSubceeded);
     } else if price > MAX_SQRT_PRICE_X64 {
-        return Err(ErrorCode::TokenMaxExceeded);
+        return Err(MathError::TokenMaxExceeded);
     }
 
     Ok(price)
@@ -163,7 +245,7 @@ pub fn get_next_sqrt_price_from_b_round_down(
     liquidity: u128,
     amount: u64,
     amount_specified_is_input: bool,
-) -> Result<u128, ErrorCode> {
+) -> Result<u128, MathError> {
     // We always want square root price to be rounded down, which means
     // Case 3. If we are fixing input (adding B), we are increasing price, we want delta to be floor(delta)
     // sqrt_price + floor(delta) < sqrt_price + delta
@@ -182,12 +264,12 @@ pub fn get_next_sqrt_price_from_b_round_down(
         // We are adding token b to supply, causing price to increase
         sqrt_price
             .checked_add(delta)
-            .ok_or(ErrorCode::SqrtPriceOutOfBounds)
+            .ok_or(MathError::SqrtPriceOutOfBounds)
     } else {
         // We are removing token b from supply,. causing price to decrease
         sqrt_price
             .checked_sub(delta)
-            .ok_or(ErrorCode::SqrtPriceOutOfBounds)
+            .ok_or(MathError::SqrtPriceOutOfBounds)
     }
 }
 
@@ -197,7 +279,7 @@ pub fn get_next_sqrt_price(
     amount: u64,
     amount_specified_is_input: bool,
     a_to_b: bool,
-) -> Result<u128, ErrorCode> {
+) -> Result<u128, MathError> {
     if amount_specified_is_input == a_to_b {
         // We are fixing A
         // Case 1. amount_specified_is_input = true, a_to_b = true
@@ -266,7 +348,8 @@ pub fn get_next_sqrt_price(
 #[cfg(test)]
 mod fuzz_tests {
     use super::*;
-    use crate::math::{bit_math::*, tick_math::*, U256};
+    use crate::test_utils::U256;
+    use crate::{bit_math::*, tick_math::*};
     use proptest::prelude::*;
 
     // Cases where the math overflows or errors
@@ -488,7 +571,7 @@ mod test_get_amount_delta {
     #[test]
     fn test_get_amount_delta_a_overflow() {
         assert!(get_amount_delta_a(1 << 64, 2 << 64, u128::MAX, true).is_err());
-        assert!(get_amount_delta_a(1 << 64, 2 << 64, (u64::MAX as u128) << 1 + 1, true).is_err());
+        assert!(get_amount_delta_a(1 << 64, 2 << 64, (u64::MAX as u128) << (1 + 1), true).is_err());
         assert!(get_amount_delta_a(1 << 64, 2 << 64, (u64::MAX as u128) << 1, true).is_ok());
         assert!(get_amount_delta_a(1 << 64, 2 << 64, u64::MAX as u128, true).is_ok());
     }
@@ -1,8 +1,8 @@
-use std::cmp::Ordering;
-use std::fmt::{self, Display, Formatter, Result as FmtResult};
-use std::str::from_utf8;
+use core::cmp::Ordering;
+use core::fmt::{self, Display, Formatter, Result as FmtResult};
+use core::str::from_utf8;
 
-use crate::errors::ErrorCode;
+use crate::error::MathError;
 
 const NUM_WORDS: usize = 4;
 
@@ -94,7 +94,7 @@ impl U256Muldiv {
                 | result.items[i - 1] >> (U64_RESOLUTION - shift_amount);
         }
 
-        result.items[0] = result.items[0] << shift_amount;
+        result.items[0] <<= shift_amount;
 
         result
     }
@@ -133,11 +133,12 @@ impl U256Muldiv {
                 | result.items[i + 1] << (U64_RESOLUTION - shift_amount);
         }
 
-        result.items[3] = result.items[3] >> shift_amount;
+        result.items[3] >>= shift_amount;
 
         result
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn eq(&self, other: U256Muldiv) -> bool {
         for i in 0..self.items.len() {
             if self.items[i] != other.items[i] {
@@ -196,9 +197,9 @@ impl U256Muldiv {
         true
     }
 
-    pub fn try_into_u128(&self) -> Result<u128, ErrorCode> {
+    pub fn try_into_u128(&self) -> Result<u128, MathError> {
         if self.num_words() > 2 {
-            return Err(ErrorCode::NumberDownCastError);
+            return Err(MathError::NumberDownCastError);
         }
 
         Ok((self.items[1] as u128) << U64_RESOLUTION | (self.items[0] as u128))
@@ -383,9 +384,9 @@ impl U256Muldiv {
 
         if return_remainder {
             dividend = dividend.shift_right(s);
-            return (quotient, dividend);
+            (quotient, dividend)
         } else {
-            return (quotient, U256Muldiv::new(0, 0));
+            (quotient, U256Muldiv::new(0, 0))
         }
     }
 }
@@ -579,7 +580,8 @@ fn div_loop(
 mod fuzz_tests {
     use proptest::prelude::*;
 
-    use crate::math::{mul_u256, U256Muldiv, U256};
+    use crate::test_utils::U256;
+    use crate::{mul_u256, U256Muldiv};
 
     fn assert_equality(n0: U256Muldiv, n1: U256) {
         assert_eq!(n0.get_word(0), n1.0[0], "failed: 0");
@@ -895,7 +897,7 @@ mod fuzz_tests {
 
 #[cfg(test)]
 mod test_add {
-    use crate::math::U256Muldiv;
+    use crate::U256Muldiv;
 
     #[test]
     fn test_add_overflow_0() {
@@ -920,7 +922,7 @@ mod test_add {
 
 #[cfg(test)]
 mod test_sub {
-    use crate::math::U256Muldiv;
+    use crate::U256Muldiv;
 
     #[test]
     fn test_sub_underflow_0() {
@@ -945,7 +947,7 @@ mod test_sub {
 
 #[cfg(test)]
 mod test_div {
-    use crate::math::U256;
+    use crate::test_utils::U256;
 
     use super::U256Muldiv;
 
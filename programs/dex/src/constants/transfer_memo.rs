@@ -3,3 +3,5 @@ pub const TRANSFER_MEMO_COLLECT_FEES: &str = "Dex-X CollectFees";
 pub const TRANSFER_MEMO_COLLECT_REWARD: &str = "Dex-X CollectReward";
 pub const TRANSFER_MEMO_DECREASE_LIQUIDITY: &str = "Dex-X Withdraw";
 pub const TRANSFER_MEMO_SWAP: &str = "Dex-X Trade";
+pub const TRANSFER_MEMO_STREAM_PROTOCOL_FEE: &str = "Dex-X StreamProtocolFee";
+pub const TRANSFER_MEMO_REFERRAL_FEE: &str = "Dex-X ReferralFee";
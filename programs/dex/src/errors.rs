@@ -1,6 +1,7 @@
 use std::num::TryFromIntError;
 
 use anchor_lang::prelude::*;
+use dex_math::MathError;
 
 #[error_code]
 #[derive(PartialEq)]
@@ -144,6 +145,166 @@ pub enum ErrorCode {
     OverflowOrConversion, // 0x17a5 (6056)
     #[msg("Index out of bunds")]
     IndexOutOfBounds, // 0x17a5 (6056)
+
+    #[msg("Intermediate value in swap math overflowed")]
+    MathOverflow, // 0x17aa (6058)
+
+    #[msg("Arbitrage cycle does not return enough of the starting token")]
+    ArbitrageNotProfitable, // 0x17ab (6059)
+    #[msg("Pools do not form a closed token cycle")]
+    InvalidArbitrageCycle, // 0x17ac (6060)
+
+    #[msg("Authority key cannot be the default pubkey")]
+    InvalidAuthorityKey, // 0x17ad (6061)
+
+    #[msg("Proposal type is not supported")]
+    InvalidProposalType, // 0x17ae (6062)
+    #[msg("Voting on the proposal has closed")]
+    ProposalVotingClosed, // 0x17af (6063)
+    #[msg("Voting on the proposal has not ended")]
+    ProposalVotingNotEnded, // 0x17b0 (6064)
+    #[msg("Proposal did not reach the required votes")]
+    ProposalNotPassed, // 0x17b1 (6065)
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted, // 0x17b2 (6066)
+
+    #[msg("Pool account has already been migrated")]
+    PoolAlreadyMigrated, // 0x17b3 (6067)
+
+    #[msg("Conversion pool does not trade the fee tokens of the position")]
+    InvalidConversionPool, // 0x17b4 (6068)
+    #[msg("Conversion swap did not consume all of the collected fees")]
+    ConversionNotFullyFilled, // 0x17b5 (6069)
+
+    #[msg("Protocol liquidity position range is outside of the allowed bounds")]
+    ProtocolLiquidityRangeOutOfBounds, // 0x17b6 (6070)
+    #[msg("Protocol liquidity cannot be withdrawn until the timelock expires")]
+    ProtocolLiquidityWithdrawLocked, // 0x17b7 (6071)
+
+    #[msg("Fee tier account has already been migrated")]
+    FeeTierAlreadyMigrated, // 0x17b8 (6072)
+
+    #[msg("The shared deadline of the operation batch has passed")]
+    DeadlineExceeded, // 0x17b9 (6073)
+    #[msg("The deadline context has no remaining operations")]
+    DeadlineContextExhausted, // 0x17ba (6074)
+
+    #[msg("Two-hop route starts and ends with the same token")]
+    CircularRoute, // 0x17bb (6075)
+
+    #[msg("Bundled position accounts do not match the provided bundle indexes")]
+    InvalidBundlePositionAccounts, // 0x17bc (6076)
+
+    #[msg("Fee destination cannot be a vault of the pool")]
+    InvalidFeeDestination, // 0x17bd (6077)
+
+    #[msg("Position account has already been migrated")]
+    PositionAlreadyMigrated, // 0x17be (6078)
+
+    #[msg("Pool tick moved beyond the allowed deviation from the expected tick")]
+    PoolTickMoved, // 0x17bf (6079)
+
+    #[msg("Invalid protocol treasury token account")]
+    InvalidProtocolTreasuryAccount, // 0x17c0 (6080)
+
+    #[msg("Fee tier tick spacing does not match the pool tick spacing")]
+    TickSpacingFeeTierMismatch, // 0x17c1 (6081)
+
+    #[msg("Destination pool must be a different pool of the same token pair")]
+    InvalidMigrationPool, // 0x17c2 (6082)
+    #[msg("Destination pool price deviates from the source pool price beyond the slippage")]
+    MigrationSlippageExceeded, // 0x17c3 (6083)
+
+    #[msg("Invalid swap route")]
+    InvalidSwapRoute, // 0x17c4 (6084)
+    #[msg("Admin control over the pool has been renounced")]
+    PoolAuthorityRenounced, // 0x17c5 (6085)
+    #[msg("Expected up to 8 pairs of tick array and tick array cache accounts")]
+    InvalidTickArrayCacheAccounts, // 0x17c6 (6086)
+    #[msg("Position bundle capacity must be a new multiple of 8 from 256 to 1024")]
+    InvalidPositionBundleCapacity, // 0x17c7 (6087)
+    #[msg("Protocol fee destination balance would overflow")]
+    ProtocolFeeDestinationOverflow, // 0x17c8 (6088)
+    #[msg("Token has a transfer fee, use the v2 swap instructions")]
+    TransferFeeNotSupportedInLegacySwap, // 0x17c9 (6089)
+    #[msg("Instruction cannot be invoked through CPI")]
+    DirectCallRequired, // 0x17ca (6090)
+    #[msg("Fractionalized position must have at least one share")]
+    InvalidFractionShares, // 0x17cb (6091)
+    #[msg("Redeeming a fractionalized position requires holding all of its shares")]
+    IncompleteFractionOwnership, // 0x17cc (6092)
+    #[msg("Not enough deposited position fractions")]
+    InsufficientFractionShares, // 0x17cd (6093)
+    #[msg("Swaps in this direction are disabled for the pool")]
+    SwapDirectionDisabled, // 0x17ce (6094)
+    #[msg("Pool name must be 1 to 32 bytes of UTF-8 without null characters")]
+    InvalidPoolName, // 0x17cf (6095)
+    #[msg("Parameter change cannot be executed before its governance delay elapsed")]
+    ParameterChangeDelayNotElapsed, // 0x17d0 (6096)
+    #[msg("Intermediate amount out of the first hop below minimum threshold")]
+    IntermediateAmountBelowMinimum, // 0x17d1 (6097)
+    #[msg("Feature is disabled for this pools config")]
+    FeatureDisabled, // 0x17d2 (6098)
+    #[msg("Pools config account has already been migrated")]
+    PoolsConfigAlreadyMigrated, // 0x17d3 (6099)
+    #[msg("Tick array does not contain the tick of the position")]
+    TickArrayDoesNotContainPositionTick, // 0x17d4 (6100)
+    #[msg("Fee tier pool count is already zero")]
+    FeeTierPoolCountZero, // 0x17d5 (6101)
+    #[msg("Bundled positions are open beyond the new capacity of the position bundle")]
+    BundledPositionBeyondCapacity, // 0x17d6 (6102)
+    #[msg("Referral fee rate exceeds the maximum of the pools config")]
+    ReferralFeeRateMaxExceeded, // 0x17d7 (6103)
+    #[msg("Referral fee account does not hold the input token of the swap")]
+    InvalidReferralFeeAccount, // 0x17d8 (6104)
+    #[msg("Tick array account is passed more than once outside of trailing padding")]
+    DuplicateTickArrayAccount, // 0x17d9 (6105)
+    #[msg("Bundled position account still exists and cannot be repaired")]
+    BundledPositionNotOrphaned, // 0x17da (6106)
+    #[msg("Protocol fee rate is outside the protocol fee rate bounds of the pools config")]
+    ProtocolFeeRateOutOfBounds, // 0x17db (6107)
+    #[msg("Protocol fee rate bounds must satisfy min <= max <= MAX_PROTOCOL_FEE_RATE")]
+    InvalidProtocolFeeRateBounds, // 0x17dc (6108)
+    #[msg("Vault balances do not match the expected pool reserves")]
+    PoolReserveMismatch, // 0x17dd (6109)
+    #[msg("Reward vault warning threshold exceeds 10_000 basis points")]
+    RewardVaultWarningThresholdMaxExceeded, // 0x17de (6110)
+    #[msg("Tick array account failed to load")]
+    TickArrayLoadFailed, // 0x17df (6111)
+    #[msg("Tick array is not initialized, call initialize_tick_array first")]
+    TickArrayNotInitialized, // 0x17e0 (6112)
+    #[msg("Swap amount is below the minimum swap amount of the pool")]
+    SwapAmountBelowMinimum, // 0x17e1 (6113)
+    #[msg("Swap amount is above the maximum swap amount of the pool")]
+    SwapAmountAboveMaximum, // 0x17e2 (6114)
+    #[msg("Minimum swap amount exceeds the maximum swap amount")]
+    InvalidSwapAmountBounds, // 0x17e3 (6115)
+    #[msg("Position has rewards owed, collect them before transferring the position")]
+    PositionRewardsNotSettled, // 0x17e4 (6116)
+    #[msg("Tick index is not a multiple of the tick spacing")]
+    TickNotAlignedToSpacing, // 0x17e5 (6117)
+    #[msg("Tick index is outside the supported tick range")]
+    TickIndexOutOfBounds, // 0x17e6 (6118)
+    #[msg("Invalid accounts for batch closing positions")]
+    InvalidBatchClosePositionAccounts, // 0x17e7 (6119)
+    #[msg("Reward emissions schedule must have 1 to 8 points in increasing start timestamp order")]
+    InvalidRewardEmissionsSchedule, // 0x17e8 (6120)
+    #[msg("Automatic protocol fee adjustment is not enabled for the pool")]
+    AutoProtocolFeeDisabled, // 0x17e9 (6121)
+    #[msg("Protocol fee rate was adjusted automatically less than a day ago")]
+    AutoProtocolFeeAdjustmentTooEarly, // 0x17ea (6122)
+    #[msg("LP fee rebate exceeds the maximum of 10_000 basis points")]
+    LpFeeRebateBpsMaxExceeded, // 0x17eb (6123)
+    #[msg("Position is not a position of the pool held by the swapper")]
+    InvalidFeeRebatePosition, // 0x17ec (6124)
+    #[msg("Token vault holds tokens before the pool was created")]
+    VaultNotEmpty, // 0x17ed (6125)
+    #[msg("Pool is passed more than once")]
+    DuplicateFeeTierPool, // 0x17ee (6126)
+    #[msg("Pool does not trade the vote mint of the proposal")]
+    InvalidVoteMint, // 0x17ef (6127)
+    #[msg("Protocol fee streaming is enabled and the protocol treasury accounts are missing")]
+    MissingProtocolTreasuryAccount, // 0x17f0 (6128)
 }
 
 impl From<TryFromIntError> for ErrorCode {
@@ -151,3 +312,30 @@ impl From<TryFromIntError> for ErrorCode {
         ErrorCode::NumberCastError
     }
 }
+
+impl From<MathError> for ErrorCode {
+    fn from(error: MathError) -> Self {
+        match error {
+            MathError::DivideByZero => ErrorCode::DivideByZero,
+            MathError::MulDivOverflow => ErrorCode::MulDivOverflow,
+            MathError::MultiplicationOverflow => ErrorCode::MultiplicationOverflow,
+            MathError::MultiplicationShiftRightOverflow => {
+                ErrorCode::MultiplicationShiftRightOverflow
+            }
+            MathError::MathOverflow => ErrorCode::MathOverflow,
+            MathError::NumberCastError => ErrorCode::NumberCastError,
+            MathError::NumberDownCastError => ErrorCode::NumberDownCastError,
+            MathError::TokenMaxExceeded => ErrorCode::TokenMaxExceeded,
+            MathError::TokenMinSubceeded => ErrorCode::TokenMinSubceeded,
+            MathError::SqrtPriceOutOfBounds => ErrorCode::SqrtPriceOutOfBounds,
+            MathError::InvalidSqrtPriceLimitDirection => ErrorCode::InvalidSqrtPriceLimitDirection,
+            MathError::ZeroTradableAmount => ErrorCode::ZeroTradableAmount,
+            MathError::LiquidityOverflow => ErrorCode::LiquidityOverflow,
+            MathError::LiquidityUnderflow => ErrorCode::LiquidityUnderflow,
+            MathError::LiquidityTooHigh => ErrorCode::LiquidityTooHigh,
+            MathError::AmountRemainingOverflow => ErrorCode::AmountRemainingOverflow,
+            MathError::AmountCalcOverflow => ErrorCode::AmountCalcOverflow,
+            MathError::OverflowOrConversion => ErrorCode::OverflowOrConversion,
+        }
+    }
+}
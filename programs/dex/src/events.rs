@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::manager::liquidity_distribution_manager::LiquidityDistributionPoint;
+
 #[event]
 #[cfg_attr(feature = "client", derive(Debug))]
 pub struct ConfigChangeEvent {
@@ -59,6 +61,7 @@ pub struct PoolCreatedEvent {
     pub tick_spacing: u16,
 
     /// The address of the created pool
+    #[index]
     pub pool_state: Pubkey,
 
     /// The initial sqrt price of the pool, as a Q64.64
@@ -151,6 +154,7 @@ pub struct SwapEvent {
     pub amount_1: u64,
 
     /// if true, amount_0 is negtive and amount_1 is positive
+    #[index]
     pub zero_for_one: bool,
 
     /// The sqrt(price) of the pool after the swap, as a Q64.64
@@ -163,4 +167,796 @@ pub struct SwapEvent {
     pub tick: i32,
 
     pub fee: u64,
+
+    /// The number of initialized ticks crossed by the swap
+    pub tick_crossed_count: u32,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LiquidityDistributionEvent {
+    /// The pool whose liquidity curve was queried
+    #[index]
+    pub pool_state: Pubkey,
+
+    /// The direction of the walk. If true, ticks at or below the current tick are reported
+    pub a_to_b: bool,
+
+    /// The tick the cumulative liquidity is measured from
+    pub tick_current_index: i32,
+
+    /// The active liquidity of the pool at the current tick
+    pub liquidity: u128,
+
+    /// Initialized ticks in walk order, with the active liquidity after crossing each one
+    pub points: Vec<LiquidityDistributionPoint>,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ArbitrageExecutedEvent {
+    /// The amount of the starting token paid into the first pool, transfer fee included
+    pub initial_amount: u64,
+
+    /// The amount of the starting token received from the last pool, transfer fee excluded
+    pub final_amount: u64,
+
+    /// (final_amount - initial_amount) / initial_amount, in basis points
+    pub profit_bps: i32,
+
+    /// The pools traded through, in order
+    pub path: Vec<Pubkey>,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ProposalCreatedEvent {
+    /// The proposal account
+    #[index]
+    pub proposal: Pubkey,
+
+    /// The config whose parameters the proposal changes
+    pub pools_config: Pubkey,
+
+    pub proposal_id: u64,
+
+    pub proposal_type: u8,
+
+    pub proposed_value: u64,
+
+    /// Votes are accepted until this timestamp (inclusive)
+    pub voting_end_ts: u64,
+
+    pub quorum_liquidity: u128,
+
+    /// Votes are weighted by the value of positions in this mint
+    pub vote_mint: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct VoteCastEvent {
+    /// The proposal voted on
+    #[index]
+    pub proposal: Pubkey,
+
+    /// The position that voted
+    #[index]
+    pub position: Pubkey,
+
+    pub support: bool,
+
+    /// The liquidity of the position, locked until voting has ended
+    pub liquidity: u128,
+
+    /// The vote weight, the value of the position in the vote mint of the proposal
+    pub weight: u128,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ProposalExecutedEvent {
+    /// The executed proposal
+    #[index]
+    pub proposal: Pubkey,
+
+    pub proposal_type: u8,
+
+    pub proposed_value: u64,
+
+    pub votes_for: u128,
+
+    pub votes_against: u128,
+
+    /// The timelocked parameter change that applies the proposal
+    pub parameter_change: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeesCollectedAndConvertedEvent {
+    /// The position whose fees were collected
+    #[index]
+    pub position: Pubkey,
+
+    /// The fees owed in token_a at the time of collection
+    pub token_a_collected: u64,
+
+    /// The fees owed in token_b at the time of collection
+    pub token_b_collected: u64,
+
+    /// The amount of output token received for the converted fee token, transfer fee excluded
+    pub converted_output_amount: u64,
+
+    /// The pool the fees were converted in
+    pub conversion_pool: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardCollectedAndSwappedEvent {
+    /// The position whose reward was collected
+    #[index]
+    pub position: Pubkey,
+
+    /// The index of the collected reward
+    pub reward_index: u8,
+
+    /// The amount of reward token collected from the reward vault
+    pub reward_amount: u64,
+
+    /// The token delivered to the owner, the reward mint if the swap was skipped
+    pub output_mint: Pubkey,
+
+    /// The amount of output token received, transfer fee excluded
+    pub output_amount: u64,
+
+    /// Output tokens received per reward token, as a Q64.64
+    pub conversion_rate: u128,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct BundleBatchRewardClaimedEvent {
+    /// The position bundle whose positions' reward was collected
+    #[index]
+    pub bundle: Pubkey,
+
+    /// The index of the collected reward
+    pub reward_index: u8,
+
+    /// The total amount of reward token transferred
+    pub total_amount: u64,
+
+    /// The number of bundled positions the reward was collected for
+    pub positions_claimed: u32,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ProtocolLiquidityDeployedEvent {
+    /// The protocol-owned position liquidity was added to
+    #[index]
+    pub position: Pubkey,
+
+    /// The amount of liquidity deployed
+    pub liquidity: u128,
+
+    /// The amount of token_a moved from the treasury
+    pub amount_a: u64,
+
+    /// The amount of token_b moved from the treasury
+    pub amount_b: u64,
+
+    /// The liquidity cannot be withdrawn before this timestamp
+    pub withdraw_unlock_timestamp: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ProtocolLiquidityWithdrawnEvent {
+    /// The protocol-owned position liquidity was removed from
+    #[index]
+    pub position: Pubkey,
+
+    /// The amount of liquidity withdrawn
+    pub liquidity: u128,
+
+    /// The amount of token_a returned to the treasury for the withdrawn liquidity
+    pub amount_a: u64,
+
+    /// The amount of token_b returned to the treasury for the withdrawn liquidity
+    pub amount_b: u64,
+
+    /// The fees in token_a earned by the position and swept to the treasury
+    pub fee_a: u64,
+
+    /// The fees in token_b earned by the position and swept to the treasury
+    pub fee_b: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct TwoHopQuoteEvent {
+    /// The pool of the first hop
+    #[index]
+    pub pool_one: Pubkey,
+
+    /// The pool of the second hop
+    #[index]
+    pub pool_two: Pubkey,
+
+    pub amount_specified_is_input: bool,
+
+    /// The amount of input token paid into pool one, transfer fee included
+    pub leg_one_amount_in: u64,
+
+    /// The amount of intermediate token paid out of pool one, transfer fee included
+    pub leg_one_amount_out: u64,
+
+    /// The amount of intermediate token paid into pool two, transfer fee included
+    pub leg_two_amount_in: u64,
+
+    /// The amount of output token paid out of pool two, transfer fee included
+    pub leg_two_amount_out: u64,
+
+    /// The amount of input token the swap would take from the user
+    pub total_input: u64,
+
+    /// The amount of output token the user would receive, transfer fee excluded
+    pub total_output: u64,
+
+    /// The sqrt(price) of pool one after the swap, as a Q64.64
+    pub sqrt_price_one: u128,
+
+    /// The sqrt(price) of pool two after the swap, as a Q64.64
+    pub sqrt_price_two: u128,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeRateVolumeUpdatedEvent {
+    /// The fee tier whose volume reached the milestone
+    #[index]
+    pub fee_tier: Pubkey,
+
+    /// The tick spacing of the fee tier
+    pub tick_spacing: u16,
+
+    /// The cumulative token_a volume of the pools passed for the fee tier
+    pub total_volume_a: u128,
+
+    /// The cumulative token_b volume of the pools passed for the fee tier
+    pub total_volume_b: u128,
+
+    /// The milestone that was reached
+    pub volume_milestone: u128,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ProtocolFeeStreamedEvent {
+    /// The pool the protocol fee was taken from
+    #[index]
+    pub pool: Pubkey,
+
+    /// The mint of the streamed protocol fee
+    pub mint: Pubkey,
+
+    /// The treasury token account that received the protocol fee
+    pub treasury_token_account: Pubkey,
+
+    /// The amount of protocol fee streamed
+    pub amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct VaultInsolvencyDetectedEvent {
+    /// The pool whose vaults hold less than the fees it owes
+    #[index]
+    pub pool: Pubkey,
+
+    /// The amount of token_a the vault is short of
+    pub shortfall_a: u64,
+
+    /// The amount of token_b the vault is short of
+    pub shortfall_b: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionMigratedEvent {
+    /// The pool the liquidity was withdrawn from
+    #[index]
+    pub from_pool: Pubkey,
+
+    /// The pool the liquidity was deposited into
+    #[index]
+    pub to_pool: Pubkey,
+
+    /// The fee rate of the source pool
+    pub from_fee_rate: u16,
+
+    /// The fee rate of the destination pool
+    pub to_fee_rate: u16,
+
+    /// The liquidity withdrawn from the source position
+    pub liquidity_migrated: u128,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionBundleExpandedEvent {
+    /// The expanded or shrunk position bundle
+    #[index]
+    pub bundle: Pubkey,
+
+    /// The number of positions the bundle could hold before the reallocation
+    pub old_capacity: u16,
+
+    /// The number of positions the bundle can hold after the reallocation
+    pub new_capacity: u16,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionFractionalizedEvent {
+    /// The fractionalized position
+    #[index]
+    pub position: Pubkey,
+
+    /// The Token-2022 mint of the position fractions
+    pub fraction_mint: Pubkey,
+
+    /// The number of fractions minted to the position authority
+    pub total_shares: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolNameRegisteredEvent {
+    /// The pool the name refers to
+    #[index]
+    pub pool: Pubkey,
+
+    /// The UTF-8 name, null-terminated unless it uses all 32 bytes
+    pub name: [u8; 32],
+
+    /// The pools config the name is unique within
+    pub pools_config: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ParameterChangeProposedEvent {
+    /// The parameter change account
+    #[index]
+    pub parameter_change: Pubkey,
+
+    /// The config whose parameters are changed
+    pub pools_config: Pubkey,
+
+    /// The pool of pool-level changes, the default pubkey otherwise
+    #[index]
+    pub pool: Pubkey,
+
+    pub change_type: u8,
+
+    pub new_value: u64,
+
+    /// The change can be executed from this timestamp on
+    pub execute_after: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ParameterChangeExecutedEvent {
+    /// The executed parameter change
+    #[index]
+    pub parameter_change: Pubkey,
+
+    pub pools_config: Pubkey,
+
+    #[index]
+    pub pool: Pubkey,
+
+    pub change_type: u8,
+
+    pub new_value: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ParameterChangeCancelledEvent {
+    /// The cancelled parameter change
+    #[index]
+    pub parameter_change: Pubkey,
+
+    pub pools_config: Pubkey,
+
+    pub change_type: u8,
+
+    pub new_value: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardEmissionPausedEvent {
+    /// The pool of the paused reward
+    #[index]
+    pub pool: Pubkey,
+
+    pub reward_index: u8,
+
+    /// The timestamp the reward stopped accruing at
+    pub paused_at: u64,
+
+    pub reward_authority: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardEmissionResumedEvent {
+    /// The pool of the resumed reward
+    #[index]
+    pub pool: Pubkey,
+
+    pub reward_index: u8,
+
+    /// The timestamp the reward started accruing again at
+    pub resumed_at: u64,
+
+    pub reward_authority: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct OperationsAuthoritySetEvent {
+    /// The config whose operations authority was set
+    #[index]
+    pub pools_config: Pubkey,
+
+    pub old_operations_authority: Pubkey,
+
+    /// The default pubkey if the operations authority was removed
+    pub new_operations_authority: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeTierPoolCountChangedEvent {
+    /// The fee tier whose pool count changed
+    #[index]
+    pub fee_tier: Pubkey,
+
+    pub old_count: u32,
+
+    pub new_count: u32,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct TwoHopSwapEvent {
+    /// The pool of the first hop
+    #[index]
+    pub pool_one: Pubkey,
+
+    /// The pool of the second hop
+    #[index]
+    pub pool_two: Pubkey,
+
+    /// The address that initiated the swap
+    pub sender: Pubkey,
+
+    /// The fee charged by the first hop in its input token units, protocol fee included
+    pub fee_one: u64,
+
+    /// The fee charged by the second hop in its input token units, protocol fee included
+    pub fee_two: u64,
+
+    /// The part of fee_one that goes to the protocol
+    pub protocol_fee_one: u64,
+
+    /// The part of fee_two that goes to the protocol
+    pub protocol_fee_two: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeGrowthDriftDetectedEvent {
+    /// The pool whose vault balance drifted from the amounts it owes by more than the tolerance
+    #[index]
+    pub pool: Pubkey,
+
+    /// The mint of the vault
+    pub token: Pubkey,
+
+    /// The principal and fees owed to the reconciled positions plus the protocol fee owed
+    pub expected_vault_balance: u64,
+
+    /// The balance of the vault
+    pub actual_vault_balance: u64,
+
+    /// The difference between both balances in basis points of the expected balance
+    pub drift_bps: u16,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeGrowthReconciledEvent {
+    /// The pool whose vault balance is within the tolerance of the amounts it owes
+    #[index]
+    pub pool: Pubkey,
+
+    /// The mint of the vault
+    pub token: Pubkey,
+
+    /// The principal and fees owed to the reconciled positions plus the protocol fee owed
+    pub expected_vault_balance: u64,
+
+    /// The balance of the vault
+    pub actual_vault_balance: u64,
+
+    /// The difference between both balances in basis points of the expected balance
+    pub drift_bps: u16,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ReferralFeePaidEvent {
+    /// The pool of the swap
+    #[index]
+    pub pool: Pubkey,
+
+    /// The token account receiving the referral fee
+    #[index]
+    pub referral_fee_account: Pubkey,
+
+    /// The input token of the swap the fee is paid in
+    pub mint: Pubkey,
+
+    /// The referral fee rate of the swap, in basis points of the LP fee
+    pub referral_fee_rate: u16,
+
+    /// The amount of the LP fee paid to the referral account
+    pub amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionBundleRepairedEvent {
+    /// The position bundle whose bitmap was repaired
+    #[index]
+    pub position_bundle: Pubkey,
+
+    /// The bundle indexes of the orphaned positions cleared from the bitmap
+    pub cleared_bundle_indexes: Vec<u16>,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AuthorityRotatedEvent {
+    /// The pools config the authority belongs to, also for authorities of its extension
+    #[index]
+    pub config: Pubkey,
+
+    /// One of the AUTHORITY_TYPE_* constants
+    pub authority_type: u8,
+
+    pub old_authority: Pubkey,
+
+    pub new_authority: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SwapTickCrossingLimitReachedEvent {
+    #[index]
+    pub pool: Pubkey,
+
+    /// The max_tick_crossings the swap was limited to
+    pub crossings_limit: u8,
+
+    /// The part of the specified amount that was swapped
+    pub amount_filled: u64,
+
+    /// The part of the specified amount left unswapped when the swap stopped
+    pub amount_remaining: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolReconciliationEvent {
+    #[index]
+    pub pool: Pubkey,
+
+    /// The principal and fees owed to the reconciled positions plus the protocol fee owed in token_a
+    pub expected_reserve_a: u64,
+
+    /// The principal and fees owed to the reconciled positions plus the protocol fee owed in token_b
+    pub expected_reserve_b: u64,
+
+    /// The balance of vault A, excluding withheld transfer fees
+    pub actual_reserve_a: u64,
+
+    /// The balance of vault B, excluding withheld transfer fees
+    pub actual_reserve_b: u64,
+
+    /// actual_reserve_a - expected_reserve_a
+    pub delta_a: i128,
+
+    /// actual_reserve_b - expected_reserve_b
+    pub delta_b: i128,
+
+    /// The Token-2022 transfer fees withheld in vault A
+    pub withheld_transfer_fee_a: u64,
+
+    /// The Token-2022 transfer fees withheld in vault B
+    pub withheld_transfer_fee_b: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardVaultLowBalanceEvent {
+    #[index]
+    pub pool: Pubkey,
+
+    pub reward_index: u8,
+
+    /// The balance of the reward vault
+    pub vault_balance: u64,
+
+    /// The number of days the vault balance lasts at the current emission rate
+    pub estimated_days_remaining: u32,
+
+    /// The reward_vault_warning_threshold_bps the balance fell below
+    pub threshold_bps: u16,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PoolAndTickArraysInitializedEvent {
+    #[index]
+    pub pool: Pubkey,
+
+    /// The tick-arrays below, containing and above the initial price, in ascending order
+    pub tick_arrays: [Pubkey; 3],
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionTransferredEvent {
+    #[index]
+    pub position: Pubkey,
+
+    /// The owner of the position token account the position was transferred from
+    pub from: Pubkey,
+
+    /// The owner of the position token account the position was transferred to
+    pub to: Pubkey,
+
+    /// The fees of token A paid to the previous owner
+    pub fee_a: u64,
+
+    /// The fees of token B paid to the previous owner
+    pub fee_b: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeTierCreationLockChangedEvent {
+    /// The config whose fee tier creation lock changed
+    #[index]
+    pub pools_config: Pubkey,
+
+    pub fee_tier_creation_locked: bool,
+
+    /// The only authority that can create fee tiers while creation is locked
+    pub fee_tier_authority: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeTierAuthoritySetEvent {
+    /// The config whose fee tier authority was set
+    #[index]
+    pub pools_config: Pubkey,
+
+    pub old_fee_tier_authority: Pubkey,
+
+    /// The default pubkey if the fee tier authority was removed
+    pub new_fee_tier_authority: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionClosedEvent {
+    #[index]
+    pub position: Pubkey,
+
+    #[index]
+    pub pool: Pubkey,
+
+    /// The account the rent of the position was returned to
+    pub receiver: Pubkey,
+
+    /// The fees of token A collected before closing
+    pub fee_a: u64,
+
+    /// The fees of token B collected before closing
+    pub fee_b: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardEmissionsScheduleSetEvent {
+    #[index]
+    pub pool: Pubkey,
+
+    pub reward_index: u8,
+
+    /// The number of points of the new schedule
+    pub num_points: u8,
+
+    /// Whether the reward keeps emitting at the rate of the last point after it starts
+    pub hold_final_rate: bool,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ProtocolFeeRateAutoAdjustedEvent {
+    #[index]
+    pub pool: Pubkey,
+
+    pub old_rate: u16,
+
+    pub new_rate: u16,
+
+    /// The token A volume since the previous adjustment, scaled to a day
+    pub daily_volume: u64,
+
+    /// The daily protocol fee revenue targeted, in token A
+    pub target_revenue: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SwapCountMilestoneEvent {
+    #[index]
+    pub pool: Pubkey,
+
+    /// The total number of swaps counted for the pool
+    pub count: u64,
+
+    /// The power of 10 the swap count reached
+    pub milestone: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionBundleNFTMintedEvent {
+    /// The initialized position bundle
+    #[index]
+    pub position_bundle: Pubkey,
+
+    /// The mint of the token representing ownership of the position bundle
+    pub position_bundle_mint: Pubkey,
+
+    /// The owner the position bundle token was minted to
+    pub owner: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct PositionBundleNFTBurnedEvent {
+    /// The deleted position bundle
+    #[index]
+    pub position_bundle: Pubkey,
+
+    /// The mint of the burned position bundle token
+    pub position_bundle_mint: Pubkey,
+
+    /// The owner the position bundle token was burned from
+    pub owner: Pubkey,
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events,
+    manager::pool_manager::calculate_auto_protocol_fee_rate,
+    state::{Pool, PoolsConfig},
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct AutoAdjustProtocolFee<'info> {
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config)]
+    pub pool: Account<'info, Pool>,
+}
+
+/*
+  Moves the protocol fee rate of the pool towards the rate at which the volume of the last day
+  earns the target revenue. Anyone can invoke it once a day.
+*/
+pub fn handler(ctx: Context<AutoAdjustProtocolFee>) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let pools_config = &ctx.accounts.pools_config;
+    let pool = &mut ctx.accounts.pool;
+
+    let daily_volume = pool.auto_protocol_fee_daily_volume(timestamp)?;
+    let old_rate = pool.protocol_fee_rate;
+    let new_rate = calculate_auto_protocol_fee_rate(
+        old_rate,
+        pool.fee_rate,
+        daily_volume,
+        pool.target_protocol_fee_revenue_daily,
+        pools_config.min_protocol_fee_rate,
+        pools_config.max_protocol_fee_rate,
+    );
+    pool.apply_auto_protocol_fee_rate(new_rate, timestamp)?;
+
+    emit!(events::ProtocolFeeRateAutoAdjustedEvent {
+        pool: pool.key(),
+        old_rate,
+        new_rate,
+        daily_volume,
+        target_revenue: pool.target_protocol_fee_revenue_daily,
+    });
+
+    Ok(())
+}
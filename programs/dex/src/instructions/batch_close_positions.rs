@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::{
+    events,
+    state::*,
+    util::{
+        burn_and_close_user_position_token, transfer_from_vault_to_owner, verify_position_authority,
+    },
+};
+
+// Maximum number of positions that can be closed in a single instruction
+pub const MAX_BATCH_CLOSE_POSITIONS: usize = 8;
+// Number of remaining accounts passed for each position
+pub const BATCH_CLOSE_POSITION_ACCOUNTS: usize = 9;
+
+#[derive(Accounts)]
+pub struct BatchClosePositions<'info> {
+    pub position_authority: Signer<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    // remaining accounts
+    // - for each position: position, position_mint, position_token_account, pool, token_vault_a,
+    //   token_vault_b, token_owner_account_a, token_owner_account_b, receiver
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchClosePositionsResult {
+    pub positions_closed: u8,
+    // Fees are summed over all positions, and only add up to a single amount per token if the
+    // positions share a token pair
+    pub total_fee_a: u64,
+    pub total_fee_b: u64,
+}
+
+/*
+  Collects the fees of positions without liquidity, burns their NFTs and closes them, returning
+  the rent to the receiver of each position. Returns the total fees collected.
+*/
+pub fn handler<'a, 'b, 'info>(
+    ctx: Context<'a, 'b, 'info, 'info, BatchClosePositions<'info>>,
+) -> Result<()> {
+    let num_positions = batch_close_position_count(ctx.remaining_accounts.len())?;
+    let mut result = BatchClosePositionsResult {
+        positions_closed: num_positions as u8,
+        ..Default::default()
+    };
+
+    for accounts in ctx.remaining_accounts.chunks(BATCH_CLOSE_POSITION_ACCOUNTS) {
+        let (fee_a, fee_b) = close_position(
+            &ctx.accounts.position_authority,
+            &ctx.accounts.token_program,
+            accounts,
+        )?;
+        result.total_fee_a = result
+            .total_fee_a
+            .checked_add(fee_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        result.total_fee_b = result
+            .total_fee_b
+            .checked_add(fee_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+    }
+
+    set_return_data(&result.try_to_vec()?);
+
+    Ok(())
+}
+
+fn close_position<'info>(
+    position_authority: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<(u64, u64)> {
+    let mut position = Account::<Position>::try_from(&accounts[0])?;
+    let position_mint = Account::<Mint>::try_from(&accounts[1])?;
+    let position_token_account = Account::<TokenAccount>::try_from(&accounts[2])?;
+    let pool = Account::<Pool>::try_from(&accounts[3])?;
+    let token_vault_a = Account::<TokenAccount>::try_from(&accounts[4])?;
+    let token_vault_b = Account::<TokenAccount>::try_from(&accounts[5])?;
+    let token_owner_account_a = Account::<TokenAccount>::try_from(&accounts[6])?;
+    let token_owner_account_b = Account::<TokenAccount>::try_from(&accounts[7])?;
+    let receiver = UncheckedAccount::try_from(&accounts[8]);
+
+    // Bundled positions are closed through their bundle
+    let (position_address, _) = Pubkey::find_program_address(
+        &[b"position".as_ref(), position.position_mint.as_ref()],
+        &crate::ID,
+    );
+    if position.key() != position_address {
+        return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    }
+    if position.pool != pool.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+    if position_mint.key() != position.position_mint
+        || token_vault_a.key() != pool.token_vault_a
+        || token_vault_b.key() != pool.token_vault_b
+    {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+    if position_token_account.mint != position.position_mint
+        || position_token_account.amount != 1
+        || token_owner_account_a.mint != pool.token_mint_a
+        || token_owner_account_b.mint != pool.token_mint_b
+    {
+        return Err(anchor_lang::error::ErrorCode::ConstraintRaw.into());
+    }
+
+    verify_position_authority(&position_token_account, position_authority)?;
+
+    let (fee_owed_a, fee_owed_b) = settle_position_for_close(&mut position)?;
+
+    transfer_from_vault_to_owner(
+        &pool,
+        &token_vault_a,
+        &token_owner_account_a,
+        token_program,
+        fee_owed_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &pool,
+        &token_vault_b,
+        &token_owner_account_b,
+        token_program,
+        fee_owed_b,
+    )?;
+
+    burn_and_close_user_position_token(
+        position_authority,
+        &receiver,
+        &position_mint,
+        &position_token_account,
+        token_program,
+    )?;
+    position.close(receiver.to_account_info())?;
+
+    emit!(events::PositionClosedEvent {
+        position: position.key(),
+        pool: pool.key(),
+        receiver: receiver.key(),
+        fee_a: fee_owed_a,
+        fee_b: fee_owed_b,
+    });
+
+    Ok((fee_owed_a, fee_owed_b))
+}
+
+// Returns the number of positions passed as remaining accounts
+fn batch_close_position_count(num_accounts: usize) -> Result<usize> {
+    let num_positions = num_accounts / BATCH_CLOSE_POSITION_ACCOUNTS;
+    if num_positions == 0
+        || num_positions > MAX_BATCH_CLOSE_POSITIONS
+        || num_accounts % BATCH_CLOSE_POSITION_ACCOUNTS != 0
+    {
+        return Err(ErrorCode::InvalidBatchClosePositionAccounts.into());
+    }
+    Ok(num_positions)
+}
+
+// Takes the fees owed by the position, which can then be closed if it has no liquidity and no
+// rewards owed
+fn settle_position_for_close(position: &mut Position) -> Result<(u64, u64)> {
+    let fees_owed = (position.fee_owed_a, position.fee_owed_b);
+    position.reset_fees_owed();
+    if !Position::is_position_empty(position) {
+        return Err(ErrorCode::ClosePositionNotEmpty.into());
+    }
+    Ok(fees_owed)
+}
+
+#[cfg(test)]
+mod batch_close_positions_tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_close_position_count() {
+        assert_eq!(batch_close_position_count(9).unwrap(), 1);
+        assert_eq!(batch_close_position_count(72).unwrap(), 8);
+        for num_accounts in [0, 8, 10, 81] {
+            assert_eq!(
+                batch_close_position_count(num_accounts).unwrap_err(),
+                ErrorCode::InvalidBatchClosePositionAccounts.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_position_fees_collected() {
+        let mut position = Position {
+            fee_owed_a: 100,
+            fee_owed_b: 200,
+            ..Default::default()
+        };
+        assert_eq!(
+            settle_position_for_close(&mut position).unwrap(),
+            (100, 200)
+        );
+    }
+
+    #[test]
+    fn test_position_with_liquidity_not_closed() {
+        let mut position = Position {
+            liquidity: 1_000,
+            fee_owed_a: 100,
+            ..Default::default()
+        };
+        assert_eq!(
+            settle_position_for_close(&mut position).unwrap_err(),
+            ErrorCode::ClosePositionNotEmpty.into()
+        );
+
+        let mut position = Position::default();
+        position.update_reward_owed(2, 10);
+        assert_eq!(
+            settle_position_for_close(&mut position).unwrap_err(),
+            ErrorCode::ClosePositionNotEmpty.into()
+        );
+    }
+}
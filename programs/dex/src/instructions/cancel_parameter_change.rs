@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ParameterChangeCancelledEvent, state::*};
+
+#[derive(Accounts)]
+pub struct CancelParameterChange<'info> {
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config, has_one = proposer, close = proposer)]
+    pub parameter_change: Account<'info, ProtocolParameterChange>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    /// CHECK: safe, receives the rent of the parameter change
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<CancelParameterChange>) -> Result<()> {
+    let parameter_change = &ctx.accounts.parameter_change;
+
+    emit!(ParameterChangeCancelledEvent {
+        parameter_change: parameter_change.key(),
+        pools_config: parameter_change.pools_config,
+        change_type: parameter_change.change_type,
+        new_value: parameter_change.new_value,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::SharedDeadlineContext;
+
+#[derive(Accounts)]
+pub struct CloseDeadlineContext<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, has_one = authority, close = receiver)]
+    pub deadline_context: Account<'info, SharedDeadlineContext>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+pub fn handler(_ctx: Context<CloseDeadlineContext>) -> Result<()> {
+    Ok(())
+}
@@ -35,12 +35,22 @@ pub struct CollectFees<'info> {
 }
 
 pub fn handler(ctx: Context<CollectFees>) -> Result<()> {
+    collect_position_fees(ctx.accounts)
+}
+
+pub fn collect_position_fees(accounts: &mut CollectFees) -> Result<()> {
     verify_position_authority(
-        &ctx.accounts.position_token_account,
-        &ctx.accounts.position_authority,
+        &accounts.position_token_account,
+        &accounts.position_authority,
+    )?;
+    // The destinations need not be owned by the position authority, so the fees can be routed to
+    // any account of the pool's mints other than its vaults
+    accounts.pool.verify_fee_destinations(
+        accounts.token_owner_account_a.key(),
+        accounts.token_owner_account_b.key(),
     )?;
 
-    let position = &mut ctx.accounts.position;
+    let position = &mut accounts.position;
 
     // Store the fees owed to use as transfer amounts.
     let fee_owed_a = position.fee_owed_a;
@@ -49,20 +59,51 @@ pub fn handler(ctx: Context<CollectFees>) -> Result<()> {
     position.reset_fees_owed();
 
     transfer_from_vault_to_owner(
-        &ctx.accounts.pool,
-        &ctx.accounts.token_vault_a,
-        &ctx.accounts.token_owner_account_a,
-        &ctx.accounts.token_program,
+        &accounts.pool,
+        &accounts.token_vault_a,
+        &accounts.token_owner_account_a,
+        &accounts.token_program,
         fee_owed_a,
     )?;
 
     transfer_from_vault_to_owner(
-        &ctx.accounts.pool,
-        &ctx.accounts.token_vault_b,
-        &ctx.accounts.token_owner_account_b,
-        &ctx.accounts.token_program,
+        &accounts.pool,
+        &accounts.token_vault_b,
+        &accounts.token_owner_account_b,
+        &accounts.token_program,
         fee_owed_b,
     )?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod fee_destination_tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+
+    fn pool() -> Pool {
+        let mut pool = Pool::default();
+        pool.token_vault_a = Pubkey::new_unique();
+        pool.token_vault_b = Pubkey::new_unique();
+        pool
+    }
+
+    #[test]
+    fn test_third_party_destination() {
+        let pool = pool();
+        let (strategy_account_a, strategy_account_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert!(pool
+            .verify_fee_destinations(strategy_account_a, strategy_account_b)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_vault_destination_rejected() {
+        let pool = pool();
+        let result = pool.verify_fee_destinations(pool.token_vault_a, Pubkey::new_unique());
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidFeeDestination.into());
+        let result = pool.verify_fee_destinations(Pubkey::new_unique(), pool.token_vault_b);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidFeeDestination.into());
+    }
+}
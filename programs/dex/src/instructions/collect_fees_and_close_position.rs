@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::errors::ErrorCode;
+use crate::state::Position;
+use crate::util::burn_and_close_user_position_token;
+
+use super::collect_fees::*;
+
+#[derive(Accounts)]
+pub struct CollectFeesAndClosePosition<'info> {
+    pub collect_fees: CollectFees<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut, address = collect_fees.position.position_mint)]
+    pub position_mint: Account<'info, Mint>,
+}
+
+/*
+  Collects the fees of a position and closes it in the same call, so that a position whose
+  liquidity was withdrawn can be swept without a separate close_position.
+*/
+pub fn handler(ctx: Context<CollectFeesAndClosePosition>) -> Result<()> {
+    collect_position_fees(&mut ctx.accounts.collect_fees)?;
+    close_collected_position(ctx.accounts)
+}
+
+fn close_collected_position(accounts: &mut CollectFeesAndClosePosition) -> Result<()> {
+    let collect_fees = &mut accounts.collect_fees;
+    verify_position_closable(&collect_fees.position)?;
+    // Bundled positions are closed through their bundle
+    let (position_address, _) = Pubkey::find_program_address(
+        &[
+            b"position".as_ref(),
+            collect_fees.position.position_mint.as_ref(),
+        ],
+        &crate::ID,
+    );
+    if collect_fees.position.key() != position_address {
+        return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+    }
+    // collect_fees only reads the position token account, burning the token writes it
+    if !collect_fees
+        .position_token_account
+        .to_account_info()
+        .is_writable
+    {
+        return Err(anchor_lang::error::ErrorCode::ConstraintMut.into());
+    }
+
+    burn_and_close_user_position_token(
+        &collect_fees.position_authority,
+        &accounts.receiver,
+        &accounts.position_mint,
+        &collect_fees.position_token_account,
+        &collect_fees.token_program,
+    )?;
+    collect_fees
+        .position
+        .close(accounts.receiver.to_account_info())
+}
+
+// Fees were just collected, so the position is empty unless it holds liquidity or rewards
+fn verify_position_closable(position: &Position) -> Result<()> {
+    if !Position::is_position_empty(position) {
+        return Err(ErrorCode::ClosePositionNotEmpty.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod closable_tests {
+    use super::*;
+
+    fn collected_position(liquidity: u128, reward_owed: u64) -> Position {
+        let mut position = Position {
+            liquidity,
+            fee_owed_a: 100,
+            fee_owed_b: 200,
+            ..Default::default()
+        };
+        position.update_reward_owed(0, reward_owed);
+        position.reset_fees_owed();
+        position
+    }
+
+    #[test]
+    fn test_drained_position_closable() {
+        assert!(verify_position_closable(&collected_position(0, 0)).is_ok());
+    }
+
+    #[test]
+    fn test_position_with_liquidity_not_closable() {
+        assert_eq!(
+            verify_position_closable(&collected_position(1_000, 0)).unwrap_err(),
+            ErrorCode::ClosePositionNotEmpty.into()
+        );
+    }
+
+    #[test]
+    fn test_position_with_rewards_owed_not_closable() {
+        assert_eq!(
+            verify_position_closable(&collected_position(0, 10)).unwrap_err(),
+            ErrorCode::ClosePositionNotEmpty.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod collect_and_close_tests {
+    use super::*;
+    use crate::state::Pool;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token;
+    use std::collections::BTreeSet;
+
+    // Lay an account out like the runtime serializes it, with the original data length before
+    // the key and the data length before the data, so that closing it can shrink its data in place
+    fn account_info(
+        key: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        lamports: u64,
+        data: Vec<u8>,
+        owner: Pubkey,
+        executable: bool,
+    ) -> AccountInfo<'static> {
+        let key_buffer: &'static mut [u32] = Box::leak(vec![0u32; 9].into_boxed_slice());
+        key_buffer[0] = data.len() as u32;
+        let key = unsafe {
+            let key_ptr = (key_buffer.as_mut_ptr() as *mut u8).add(4) as *mut Pubkey;
+            key_ptr.write(key);
+            &*key_ptr
+        };
+        let data_buffer: &'static mut [u64] =
+            Box::leak(vec![0u64; 1 + (data.len() + 7) / 8].into_boxed_slice());
+        data_buffer[0] = data.len() as u64;
+        let data = unsafe {
+            let data_ptr = (data_buffer.as_mut_ptr() as *mut u8).add(8);
+            std::ptr::copy_nonoverlapping(data.as_ptr(), data_ptr, data.len());
+            std::slice::from_raw_parts_mut(data_ptr, data.len())
+        };
+        AccountInfo::new(
+            key,
+            is_signer,
+            is_writable,
+            Box::leak(Box::new(lamports)),
+            data,
+            Box::leak(Box::new(owner)),
+            executable,
+            0,
+        )
+    }
+
+    fn program_account<T: AccountSerialize>(key: Pubkey, account: &T) -> AccountInfo<'static> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        account_info(key, false, true, 2_000_000, data, crate::ID, false)
+    }
+
+    fn token_account(
+        key: Pubkey,
+        is_writable: bool,
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+    ) -> AccountInfo<'static> {
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        account_info(key, false, is_writable, 1_000_000, data, token::ID, false)
+    }
+
+    struct Fixture {
+        pool: Pool,
+        position: Position,
+        authority: Pubkey,
+        position_key: Pubkey,
+        receiver: Pubkey,
+    }
+
+    fn fixture() -> Fixture {
+        let mut pool = Pool::default();
+        pool.token_mint_a = Pubkey::new_unique();
+        pool.token_mint_b = Pubkey::new_unique();
+        pool.token_vault_a = Pubkey::new_unique();
+        pool.token_vault_b = Pubkey::new_unique();
+        let position_mint = Pubkey::new_unique();
+        let position = Position {
+            pool: Pubkey::new_unique(),
+            position_mint,
+            ..Default::default()
+        };
+        let (position_key, _) = Pubkey::find_program_address(
+            &[b"position".as_ref(), position_mint.as_ref()],
+            &crate::ID,
+        );
+        Fixture {
+            pool,
+            position,
+            authority: Pubkey::new_unique(),
+            position_key,
+            receiver: Pubkey::new_unique(),
+        }
+    }
+
+    fn accounts(
+        fixture: &Fixture,
+        position_token_account_writable: bool,
+    ) -> Vec<AccountInfo<'static>> {
+        let pool = &fixture.pool;
+        let mut mint_data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            supply: 1,
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut mint_data);
+        vec![
+            program_account(fixture.position.pool, pool),
+            account_info(
+                fixture.authority,
+                true,
+                false,
+                0,
+                vec![],
+                Pubkey::default(),
+                false,
+            ),
+            program_account(fixture.position_key, &fixture.position),
+            token_account(
+                Pubkey::new_unique(),
+                position_token_account_writable,
+                fixture.position.position_mint,
+                fixture.authority,
+                1,
+            ),
+            token_account(
+                Pubkey::new_unique(),
+                true,
+                pool.token_mint_a,
+                Pubkey::new_unique(),
+                0,
+            ),
+            token_account(
+                pool.token_vault_a,
+                true,
+                pool.token_mint_a,
+                Pubkey::new_unique(),
+                0,
+            ),
+            token_account(
+                Pubkey::new_unique(),
+                true,
+                pool.token_mint_b,
+                Pubkey::new_unique(),
+                0,
+            ),
+            token_account(
+                pool.token_vault_b,
+                true,
+                pool.token_mint_b,
+                Pubkey::new_unique(),
+                0,
+            ),
+            account_info(token::ID, false, false, 0, vec![], Pubkey::default(), true),
+            account_info(
+                fixture.receiver,
+                false,
+                true,
+                0,
+                vec![],
+                Pubkey::default(),
+                false,
+            ),
+            account_info(
+                fixture.position.position_mint,
+                false,
+                true,
+                1_000_000,
+                mint_data,
+                token::ID,
+                false,
+            ),
+        ]
+    }
+
+    fn try_accounts(
+        infos: Vec<AccountInfo<'static>>,
+    ) -> Result<CollectFeesAndClosePosition<'static>> {
+        let infos: &'static [AccountInfo<'static>] = Box::leak(infos.into_boxed_slice());
+        let mut bumps = CollectFeesAndClosePositionBumps::default();
+        CollectFeesAndClosePosition::try_accounts(
+            &crate::ID,
+            &mut &infos[..],
+            &[],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )
+    }
+
+    #[test]
+    fn test_read_only_position_token_account_rejected() {
+        let fixture = fixture();
+        let mut accounts = try_accounts(accounts(&fixture, false)).unwrap();
+        assert_eq!(
+            close_collected_position(&mut accounts).unwrap_err(),
+            anchor_lang::error::ErrorCode::ConstraintMut.into()
+        );
+    }
+
+    #[test]
+    fn test_collect_and_close() {
+        let fixture = fixture();
+        let mut accounts = try_accounts(accounts(&fixture, true)).unwrap();
+        let position_info = accounts.collect_fees.position.to_account_info();
+        let position_rent = position_info.lamports();
+
+        // The token program is not executed natively, so only the position account is closed here
+        close_collected_position(&mut accounts).unwrap();
+
+        let receiver = &accounts.receiver;
+        assert_eq!(receiver.lamports(), position_rent);
+        assert_eq!(position_info.lamports(), 0);
+        assert_eq!(position_info.owner, &anchor_lang::system_program::ID);
+        assert!(position_info.data_is_empty());
+    }
+}
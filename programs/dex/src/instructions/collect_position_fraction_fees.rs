@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    manager::{
+        liquidity_manager::update_position_fees_and_rewards,
+        position_manager::take_position_fraction_fees,
+    },
+    state::*,
+    util::{to_timestamp_u64, v2::transfer_from_vault_to_owner_v2},
+};
+
+#[derive(Accounts)]
+pub struct CollectPositionFractionFees<'info> {
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub fraction_authority: Signer<'info>,
+
+    #[account(mut,
+      seeds = [b"position_fraction_vault", position_fraction_vault.position_mint.as_ref()],
+      bump,
+    )]
+    pub position_fraction_vault: Box<Account<'info, PositionFractionVault>>,
+
+    #[account(mut, has_one = pool, address = position_fraction_vault.position)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(mut,
+      seeds = [
+        b"position_fraction_share",
+        position_fraction_vault.key().as_ref(),
+        fraction_authority.key().as_ref(),
+      ],
+      bump,
+    )]
+    pub position_fraction_share: Box<Account<'info, PositionFractionShare>>,
+
+    #[account(address = pool.token_mint_a)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = pool.token_mint_b)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == pool.token_mint_a)]
+    pub token_owner_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = pool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == pool.token_mint_b)]
+    pub token_owner_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = pool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(has_one = pool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = pool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+/*
+  Collects the share of the fees of a fractionalized position earned by the deposited fractions of
+  the fraction authority.
+*/
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectPositionFractionFees<'info>>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    update_position_fees_and_rewards(
+        &mut ctx.accounts.pool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        timestamp,
+    )?;
+
+    let (fee_a, fee_b) = take_position_fraction_fees(
+        &mut ctx.accounts.position,
+        &mut ctx.accounts.position_fraction_vault,
+        &mut ctx.accounts.position_fraction_share,
+    )?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        fee_a,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        fee_b,
+        transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+    )
+}
@@ -1,5 +1,6 @@
 use crate::{state::*, util::transfer_from_vault_to_owner};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
 use anchor_spl::token::{self, Token, TokenAccount};
 
 #[derive(Accounts)]
@@ -29,8 +30,21 @@ pub struct CollectProtocolFees<'info> {
 }
 
 pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_direct_call(get_stack_height())?;
+
     let pool = &ctx.accounts.pool;
 
+    pool.verify_fee_destinations(
+        ctx.accounts.token_destination_a.key(),
+        ctx.accounts.token_destination_b.key(),
+    )?;
+    pool.verify_protocol_fee_destination_balances(
+        ctx.accounts.token_destination_a.amount,
+        ctx.accounts.token_destination_b.amount,
+    )?;
+
     transfer_from_vault_to_owner(
         pool,
         &ctx.accounts.token_vault_a,
@@ -47,5 +61,7 @@ pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
         pool.protocol_fee_owed_b,
     )?;
 
+    // The owed fees are only reset once both transfers succeeded. A failed transfer reverts the
+    // whole instruction, so the owed fees are never lost.
     Ok(ctx.accounts.pool.reset_protocol_fees_owed())
 }
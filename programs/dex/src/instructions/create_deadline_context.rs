@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::SharedDeadlineContext, util::to_timestamp_u64};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateDeadlineContext<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [b"deadline_ctx", authority.key().as_ref(),
+               nonce.to_le_bytes().as_ref()],
+      bump,
+      space = SharedDeadlineContext::LEN)]
+    pub deadline_context: Account<'info, SharedDeadlineContext>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateDeadlineContext>,
+    nonce: u64,
+    deadline: u64,
+    remaining_ops: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    ctx.accounts.deadline_context.initialize(
+        ctx.accounts.authority.key(),
+        nonce,
+        deadline,
+        remaining_ops,
+        timestamp,
+    )
+}
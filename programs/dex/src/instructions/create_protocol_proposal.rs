@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{events::ProposalCreatedEvent, state::*, util::to_timestamp_u64};
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateProtocolProposal<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [b"proposal", pools_config.key().as_ref(),
+               proposal_id.to_le_bytes().as_ref()],
+      bump,
+      space = GovernanceProposal::LEN)]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    // Votes are weighted by the value of positions in this mint
+    pub vote_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateProtocolProposal>,
+    proposal_id: u64,
+    proposal_type: u8,
+    proposed_value: u64,
+    voting_end_ts: u64,
+    quorum_liquidity: u128,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let pools_config = ctx.accounts.pools_config.key();
+    let vote_mint = ctx.accounts.vote_mint.key();
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.initialize(
+        pools_config,
+        proposal_id,
+        proposal_type,
+        proposed_value,
+        voting_end_ts,
+        quorum_liquidity,
+        vote_mint,
+        timestamp,
+    )?;
+
+    emit!(ProposalCreatedEvent {
+        proposal: proposal.key(),
+        pools_config,
+        proposal_id,
+        proposal_type,
+        proposed_value,
+        voting_end_ts,
+        quorum_liquidity,
+        vote_mint,
+    });
+
+    Ok(())
+}
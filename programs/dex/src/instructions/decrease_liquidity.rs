@@ -6,7 +6,10 @@ use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
-use crate::util::{to_timestamp_u64, transfer_from_vault_to_owner, verify_position_authority};
+use crate::util::{
+    load_position_tick_array, to_timestamp_u64, transfer_from_vault_to_owner,
+    verify_position_authority,
+};
 
 use super::ModifyLiquidity;
 
@@ -24,19 +27,24 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
+    let pool_key = ctx.accounts.pool.key();
+    let tick_array_lower = load_position_tick_array(&ctx.accounts.tick_array_lower, &pool_key)?;
+    let tick_array_upper = load_position_tick_array(&ctx.accounts.tick_array_upper, &pool_key)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
         return Err(ErrorCode::LiquidityZero.into());
     }
-    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, false).map_err(ErrorCode::from)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.pool,
         &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         liquidity_delta,
         timestamp,
     )?;
@@ -44,8 +52,8 @@ pub fn handler(
     sync_modify_liquidity_values(
         &mut ctx.accounts.pool,
         &mut ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         update,
         timestamp,
     )?;
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::position_manager::settle_withdrawal_with_fees;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::{
+    PositionHistory, PositionHistoryEntry, SharedDeadlineContext, POSITION_HISTORY_COLLECT_FEES,
+    POSITION_HISTORY_DECREASE_LIQUIDITY,
+};
+use crate::util::{
+    consume_deadline_context, load_position_tick_array, record_position_history, to_timestamp_u64,
+    transfer_from_vault_to_owner, verify_position_authority,
+};
+
+use super::increase_liquidity::*;
+
+#[derive(Accounts)]
+pub struct DecreaseLiquidityAndCollect<'info> {
+    pub modify_liquidity: ModifyLiquidity<'info>,
+
+    #[account(
+        mut,
+        constraint = deadline_context.authority == modify_liquidity.position_authority.key()
+    )]
+    pub deadline_context: Option<Box<Account<'info, SharedDeadlineContext>>>,
+
+    #[account(mut, constraint = position_history.position == modify_liquidity.position.key())]
+    pub position_history: Option<Box<Account<'info, PositionHistory>>>,
+}
+
+/*
+  Removes liquidity from an existing pool Position and collects its fees in the same call.
+  The withdrawn tokens and the fees are transferred to the owner in a single transfer per token.
+*/
+pub fn handler(
+    ctx: Context<DecreaseLiquidityAndCollect>,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    let accounts = &mut ctx.accounts.modify_liquidity;
+    verify_position_authority(
+        &accounts.position_token_account,
+        &accounts.position_authority,
+    )?;
+
+    let pool_key = accounts.pool.key();
+    let tick_array_lower = load_position_tick_array(&accounts.tick_array_lower, &pool_key)?;
+    let tick_array_upper = load_position_tick_array(&accounts.tick_array_upper, &pool_key)?;
+
+    let clock = Clock::get()?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, false).map_err(ErrorCode::from)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    consume_deadline_context(&mut ctx.accounts.deadline_context, timestamp)?;
+
+    // Settles fee growth of the position once for both the withdrawal and the collection
+    let update = calculate_modify_liquidity(
+        &accounts.pool,
+        &accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut accounts.pool,
+        &mut accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        accounts.pool.tick_current_index,
+        accounts.pool.sqrt_price,
+        &accounts.position,
+        liquidity_delta,
+    )?;
+
+    let position = &mut accounts.position;
+    let (fee_a, fee_b) = (position.fee_owed_a, position.fee_owed_b);
+    let (amount_a, amount_b) = settle_withdrawal_with_fees(position, delta_a, delta_b)?;
+
+    // token_min_a and token_min_b bound the withdrawn tokens and fees combined
+    if amount_a < token_min_a {
+        return Err(ErrorCode::TokenMinSubceeded.into());
+    } else if amount_b < token_min_b {
+        return Err(ErrorCode::TokenMinSubceeded.into());
+    }
+
+    record_position_history(
+        &mut ctx.accounts.position_history,
+        PositionHistoryEntry::new(
+            POSITION_HISTORY_DECREASE_LIQUIDITY,
+            clock.slot,
+            liquidity_delta,
+            delta_a,
+            delta_b,
+        ),
+    );
+    record_position_history(
+        &mut ctx.accounts.position_history,
+        PositionHistoryEntry::new(POSITION_HISTORY_COLLECT_FEES, clock.slot, 0, fee_a, fee_b),
+    );
+
+    transfer_from_vault_to_owner(
+        &accounts.pool,
+        &accounts.token_vault_a,
+        &accounts.token_owner_account_a,
+        &accounts.token_program,
+        amount_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &accounts.pool,
+        &accounts.token_vault_b,
+        &accounts.token_owner_account_b,
+        &accounts.token_program,
+        amount_b,
+    )?;
+    emit!(events::DecreaseLiquidityEvent {
+        position_nft_mint: accounts.position.position_mint.key(),
+        liquidity: liquidity_delta,
+        amount_0: delta_a,
+        amount_1: delta_b,
+    });
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{events, state::*};
+
+#[derive(Accounts)]
+pub struct DecrementPoolCount<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(mut, has_one = pools_config)]
+    pub fee_tier: Account<'info, FeeTier>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+/// Remove an archived pool from the pool count of its fee tier.
+pub fn handler(ctx: Context<DecrementPoolCount>) -> Result<()> {
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    let old_count = fee_tier.pool_count;
+    fee_tier.decrement_pool_count()?;
+
+    emit!(events::FeeTierPoolCountChangedEvent {
+        fee_tier: fee_tier.key(),
+        old_count,
+        new_count: fee_tier.pool_count,
+    });
+
+    Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 use crate::errors::ErrorCode;
+use crate::events;
 use crate::state::*;
 use crate::util::burn_and_close_position_bundle_token;
 
@@ -43,5 +44,13 @@ pub fn handler(ctx: Context<DeletePositionBundle>) -> Result<()> {
         &ctx.accounts.position_bundle_mint,
         &ctx.accounts.position_bundle_token_account,
         &ctx.accounts.token_program,
-    )
+    )?;
+
+    emit!(events::PositionBundleNFTBurnedEvent {
+        position_bundle: position_bundle.key(),
+        position_bundle_mint: ctx.accounts.position_bundle_mint.key(),
+        owner: ctx.accounts.position_bundle_owner.key(),
+    });
+
+    Ok(())
 }
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::math::convert_to_liquidity_delta;
+use crate::util::{to_timestamp_u64, transfer_from_protocol_treasury_to_vault};
+use crate::{events, state::*};
+
+#[derive(Accounts)]
+pub struct ModifyProtocolLiquidity<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut,
+      seeds = [b"pol_manager", pools_config.key().as_ref()],
+      bump,
+      has_one = pools_config)]
+    pub protocol_liquidity_manager: Account<'info, ProtocolLiquidityManager>,
+
+    #[account(mut, has_one = pools_config)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut, has_one = pool, address = protocol_liquidity_manager.managed_position)]
+    pub position: Account<'info, Position>,
+
+    #[account(mut, address = protocol_liquidity_manager.treasury_token_account_a)]
+    pub treasury_token_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = protocol_liquidity_manager.treasury_token_account_b)]
+    pub treasury_token_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_vault_a.key() == pool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_vault_b.key() == pool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, has_one = pool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, has_one = pool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/*
+  Moves tokens from the protocol treasury into the protocol-owned position.
+*/
+pub fn handler(
+    ctx: Context<ModifyProtocolLiquidity>,
+    liquidity_amount: u128,
+    token_max_a: u64,
+    token_max_b: u64,
+) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_feature_enabled(FEATURE_PROTOCOL_LIQUIDITY)?;
+
+    let clock = Clock::get()?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, true).map_err(ErrorCode::from)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.pool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        update,
+        timestamp,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.pool.tick_current_index,
+        ctx.accounts.pool.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    if delta_a > token_max_a {
+        return Err(ErrorCode::TokenMaxExceeded.into());
+    } else if delta_b > token_max_b {
+        return Err(ErrorCode::TokenMaxExceeded.into());
+    }
+
+    ctx.accounts
+        .protocol_liquidity_manager
+        .record_deploy(timestamp)?;
+
+    transfer_from_protocol_treasury_to_vault(
+        &ctx.accounts.protocol_liquidity_manager,
+        &ctx.accounts.treasury_token_account_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_program,
+        delta_a,
+    )?;
+
+    transfer_from_protocol_treasury_to_vault(
+        &ctx.accounts.protocol_liquidity_manager,
+        &ctx.accounts.treasury_token_account_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_program,
+        delta_b,
+    )?;
+
+    emit!(events::ProtocolLiquidityDeployedEvent {
+        position: ctx.accounts.position.key(),
+        liquidity: liquidity_amount,
+        amount_a: delta_a,
+        amount_b: delta_b,
+        withdraw_unlock_timestamp: ctx
+            .accounts
+            .protocol_liquidity_manager
+            .withdraw_unlock_timestamp,
+    });
+
+    Ok(())
+}
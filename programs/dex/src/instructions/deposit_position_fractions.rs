@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::{
+    manager::liquidity_manager::update_position_fees_and_rewards,
+    state::*,
+    util::{to_timestamp_u64, transfer_position_fractions_to_escrow},
+};
+
+#[derive(Accounts)]
+pub struct DepositPositionFractions<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub fraction_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut,
+      seeds = [b"position_fraction_vault", position_fraction_vault.position_mint.as_ref()],
+      bump,
+    )]
+    pub position_fraction_vault: Box<Account<'info, PositionFractionVault>>,
+
+    #[account(mut, has_one = pool, address = position_fraction_vault.position)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(init_if_needed,
+      payer = funder,
+      space = PositionFractionShare::LEN,
+      seeds = [
+        b"position_fraction_share",
+        position_fraction_vault.key().as_ref(),
+        fraction_authority.key().as_ref(),
+      ],
+      bump,
+    )]
+    pub position_fraction_share: Box<Account<'info, PositionFractionShare>>,
+
+    #[account(address = position_fraction_vault.fraction_mint)]
+    pub fraction_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(mut, constraint = fraction_token_account.mint == position_fraction_vault.fraction_mint)]
+    pub fraction_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(mut,
+      constraint = fraction_escrow.mint == position_fraction_vault.fraction_mint,
+      constraint = fraction_escrow.owner == position_fraction_vault.key(),
+    )]
+    pub fraction_escrow: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(has_one = pool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = pool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token_2022::ID)]
+    pub fraction_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Deposits fractions of a fractionalized position into the escrow of the position, so that they
+  earn a share of the fees of the position.
+*/
+pub fn handler(ctx: Context<DepositPositionFractions>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Fees earned before the deposit must be distributed before the deposited shares earn fees
+    update_position_fees_and_rewards(
+        &mut ctx.accounts.pool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        timestamp,
+    )?;
+
+    let vault = &mut ctx.accounts.position_fraction_vault;
+    let share = &mut ctx.accounts.position_fraction_share;
+    if share.vault == Pubkey::default() {
+        share.vault = vault.key();
+        share.owner = ctx.accounts.fraction_authority.key();
+    }
+
+    vault.harvest_fees(
+        ctx.accounts.position.fee_owed_a,
+        ctx.accounts.position.fee_owed_b,
+    )?;
+    share.deposit(vault, amount)?;
+
+    transfer_position_fractions_to_escrow(
+        &ctx.accounts.fraction_authority,
+        &ctx.accounts.fraction_mint,
+        &ctx.accounts.fraction_token_account,
+        &ctx.accounts.fraction_escrow,
+        &ctx.accounts.fraction_token_program,
+        amount,
+    )
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PoolsConfig;
+
+#[derive(Accounts)]
+pub struct DisableProtocolFeeStreaming<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DisableProtocolFeeStreaming>) -> Result<()> {
+    ctx.accounts.pools_config.disable_protocol_fee_streaming();
+    Ok(())
+}
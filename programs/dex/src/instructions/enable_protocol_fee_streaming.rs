@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PoolsConfig, PoolsConfigExtension, FEATURE_PROTOCOL_FEE_STREAMING};
+
+#[derive(Accounts)]
+pub struct EnableProtocolFeeStreaming<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config)]
+    pub pools_config_extension: Account<'info, PoolsConfigExtension>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<EnableProtocolFeeStreaming>,
+    min_stream_amount: u64,
+    protocol_treasury: Pubkey,
+) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_feature_enabled(FEATURE_PROTOCOL_FEE_STREAMING)?;
+
+    ctx.accounts
+        .pools_config_extension
+        .update_protocol_treasury(protocol_treasury)?;
+    ctx.accounts
+        .pools_config
+        .enable_protocol_fee_streaming(min_stream_amount);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use std::convert::TryFrom;
+
+use crate::{events::ParameterChangeExecutedEvent, state::*, util::to_timestamp_u64};
+
+#[derive(Accounts)]
+pub struct ExecuteParameterChange<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config, has_one = proposer, close = proposer)]
+    pub parameter_change: Account<'info, ProtocolParameterChange>,
+
+    #[account(mut, has_one = pools_config, address = parameter_change.pool)]
+    pub pool: Option<Account<'info, Pool>>,
+
+    /// CHECK: safe, receives the rent of the parameter change
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+/*
+  Applies a proposed parameter change once its governance delay elapsed. Anyone can execute it.
+*/
+pub fn handler(ctx: Context<ExecuteParameterChange>) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let parameter_change = &ctx.accounts.parameter_change;
+    parameter_change.check_executable(timestamp)?;
+
+    if parameter_change.is_pool_change() {
+        let pool = ctx
+            .accounts
+            .pool
+            .as_mut()
+            .ok_or(anchor_lang::error::ErrorCode::AccountNotEnoughKeys)?;
+        match parameter_change.change_type {
+            PARAMETER_CHANGE_FEE_RATE => {
+                pool.update_fee_rate(fee_rate_value(parameter_change.new_value)?)?
+            }
+            _ => {
+                let protocol_fee_rate = fee_rate_value(parameter_change.new_value)?;
+                ctx.accounts
+                    .pools_config
+                    .verify_protocol_fee_rate(protocol_fee_rate)?;
+                pool.update_protocol_fee_rate(protocol_fee_rate)?
+            }
+        }
+    } else {
+        match parameter_change.change_type {
+            PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE => ctx
+                .accounts
+                .pools_config
+                .update_default_protocol_fee_rate(fee_rate_value(parameter_change.new_value)?)?,
+            PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS => {
+                let (min_protocol_fee_rate, max_protocol_fee_rate) =
+                    unpack_protocol_fee_rate_bounds(parameter_change.new_value);
+                ctx.accounts
+                    .pools_config
+                    .update_protocol_fee_rate_bounds(min_protocol_fee_rate, max_protocol_fee_rate)?
+            }
+            _ => return Err(crate::errors::ErrorCode::InvalidProposalType.into()),
+        }
+    }
+
+    emit!(ParameterChangeExecutedEvent {
+        parameter_change: parameter_change.key(),
+        pools_config: parameter_change.pools_config,
+        pool: parameter_change.pool,
+        change_type: parameter_change.change_type,
+        new_value: parameter_change.new_value,
+    });
+
+    Ok(())
+}
+
+// new_value was validated against the maximum rate when the change was proposed
+fn fee_rate_value(new_value: u64) -> Result<u16> {
+    u16::try_from(new_value).or(Err(crate::errors::ErrorCode::FeeRateMaxExceeded.into()))
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
+
+use crate::{
+    events::{ParameterChangeProposedEvent, ProposalExecutedEvent},
+    state::*,
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(mut, has_one = pools_config)]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    // Applies the proposal with execute_parameter_change once the governance delay elapsed
+    #[account(init,
+      payer = funder,
+      seeds = [b"param_change", pools_config.key().as_ref(), proposal.key().as_ref()],
+      bump,
+      space = ProtocolParameterChange::LEN)]
+    pub parameter_change: Account<'info, ProtocolParameterChange>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Queues a passed proposal as a timelocked parameter change. Like changes proposed by the fee
+  authority, it takes effect through execute_parameter_change once the governance delay elapsed.
+*/
+pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_direct_call(get_stack_height())?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.mark_executed(timestamp)?;
+    let change_type = proposal.parameter_change_type()?;
+
+    let pools_config = ctx.accounts.pools_config.key();
+    let parameter_change = &mut ctx.accounts.parameter_change;
+    parameter_change.initialize(
+        pools_config,
+        proposal.proposal_id,
+        change_type,
+        None,
+        proposal.proposed_value,
+        ctx.accounts.funder.key(),
+        timestamp,
+    )?;
+
+    emit!(ProposalExecutedEvent {
+        proposal: proposal.key(),
+        proposal_type: proposal.proposal_type,
+        proposed_value: proposal.proposed_value,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        parameter_change: parameter_change.key(),
+    });
+
+    emit!(ParameterChangeProposedEvent {
+        parameter_change: parameter_change.key(),
+        pools_config,
+        pool: parameter_change.pool,
+        change_type,
+        new_value: parameter_change.new_value,
+        execute_after: parameter_change.execute_after,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::events::PositionBundleExpandedEvent;
+use crate::state::*;
+use crate::util::verify_position_bundle_authority;
+
+#[derive(Accounts)]
+#[instruction(new_capacity: u16)]
+pub struct ExpandPositionBundle<'info> {
+    #[account(mut,
+        realloc = PositionBundle::space(new_capacity),
+        realloc::payer = funder,
+        realloc::zero = true,
+    )]
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+
+    #[account(
+        constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
+        constraint = position_bundle_token_account.amount == 1
+    )]
+    pub position_bundle_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub position_bundle_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExpandPositionBundle>, new_capacity: u16) -> Result<()> {
+    // Allow delegation
+    verify_position_bundle_authority(
+        &ctx.accounts.position_bundle_token_account,
+        &ctx.accounts.position_bundle_authority,
+    )?;
+
+    let position_bundle = &mut ctx.accounts.position_bundle;
+    let old_capacity = position_bundle.capacity();
+    position_bundle.expand(new_capacity)?;
+
+    emit!(PositionBundleExpandedEvent {
+        bundle: position_bundle.key(),
+        old_capacity,
+        new_capacity,
+    });
+
+    Ok(())
+}
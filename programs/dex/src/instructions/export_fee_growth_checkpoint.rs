@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{
+    manager::{
+        position_manager::next_fee_growth_checkpoint_export, tick_manager::next_fee_growths_inside,
+    },
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ExportFeeGrowthCheckpoint<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(has_one = pool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(has_one = pool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = pool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+}
+
+pub fn handler(ctx: Context<ExportFeeGrowthCheckpoint>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let position = &ctx.accounts.position;
+
+    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
+    let tick_lower = tick_array_lower.get_tick(position.tick_lower_index, pool.tick_spacing)?;
+
+    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
+    let tick_upper = tick_array_upper.get_tick(position.tick_upper_index, pool.tick_spacing)?;
+
+    let (fee_growth_inside_a, fee_growth_inside_b) = next_fee_growths_inside(
+        pool.tick_current_index,
+        tick_lower,
+        position.tick_lower_index,
+        tick_upper,
+        position.tick_upper_index,
+        pool.fee_growth_global_a,
+        pool.fee_growth_global_b,
+    );
+
+    let export =
+        next_fee_growth_checkpoint_export(position, fee_growth_inside_a, fee_growth_inside_b);
+    set_return_data(&export.try_to_vec()?);
+
+    Ok(())
+}
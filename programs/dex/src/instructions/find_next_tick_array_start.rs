@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::state::Pool;
+use crate::util::{derive_tick_array, next_tick_array_start_index};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct NextTickArrayStart {
+    pub suggested_tick_array_start: i32,
+    pub tick_array: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct FindNextTickArrayStart<'info> {
+    pub pool: Account<'info, Pool>,
+}
+
+pub fn handler(
+    ctx: Context<FindNextTickArrayStart>,
+    start_tick_index: i32,
+    a_to_b: bool,
+) -> Result<()> {
+    let suggested_tick_array_start =
+        next_tick_array_start_index(start_tick_index, ctx.accounts.pool.tick_spacing, a_to_b)?;
+
+    let next_tick_array = NextTickArrayStart {
+        suggested_tick_array_start,
+        tick_array: derive_tick_array(&ctx.accounts.pool.key(), suggested_tick_array_start),
+    };
+    set_return_data(&next_tick_array.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::{
+    events,
+    manager::liquidity_manager::update_position_fees_and_rewards,
+    state::*,
+    util::{
+        mint_position_fractions, to_timestamp_u64, transfer_from_owner_to_vault,
+        verify_position_authority,
+    },
+};
+
+#[derive(Accounts)]
+pub struct FractionalizePosition<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut,
+      has_one = pool,
+      seeds = [b"position".as_ref(), position_mint.key().as_ref()],
+      bump,
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(address = position.position_mint)]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut,
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+      payer = funder,
+      space = PositionFractionVault::LEN,
+      seeds = [b"position_fraction_vault", position_mint.key().as_ref()],
+      bump,
+    )]
+    pub position_fraction_vault: Box<Account<'info, PositionFractionVault>>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = position_mint,
+      associated_token::authority = position_fraction_vault,
+    )]
+    pub vault_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+        payer = funder,
+        mint::authority = position_fraction_vault,
+        mint::decimals = 0,
+        mint::token_program = fraction_token_program,
+    )]
+    pub fraction_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = fraction_mint,
+      associated_token::authority = position_authority,
+      associated_token::token_program = fraction_token_program,
+    )]
+    pub fraction_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = fraction_mint,
+      associated_token::authority = position_fraction_vault,
+      associated_token::token_program = fraction_token_program,
+    )]
+    pub fraction_escrow: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(has_one = pool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = pool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = token_2022::ID)]
+    pub fraction_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/*
+  Moves the position NFT into a vault and mints `total_shares` fungible Token-2022 fractions of the
+  position to the position authority. Fees earned before the position is fractionalized stay with
+  the position and go to the holder redeeming it.
+*/
+pub fn handler(ctx: Context<FractionalizePosition>, total_shares: u64) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // The fees owed by the position are the baseline of the fees distributed to the fractions, so
+    // the fees earned before fractionalizing must be included in it
+    update_position_fees_and_rewards(
+        &mut ctx.accounts.pool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        timestamp,
+    )?;
+
+    let position = &ctx.accounts.position;
+    let vault = &mut ctx.accounts.position_fraction_vault;
+    vault.initialize(
+        position.key(),
+        position.position_mint,
+        ctx.accounts.fraction_mint.key(),
+        total_shares,
+        ctx.bumps.position_fraction_vault,
+        position.fee_owed_a,
+        position.fee_owed_b,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.vault_position_token_account,
+        &ctx.accounts.token_program,
+        1,
+    )?;
+
+    mint_position_fractions(
+        &ctx.accounts.position_fraction_vault,
+        &ctx.accounts.fraction_mint,
+        &ctx.accounts.fraction_token_account,
+        &ctx.accounts.fraction_token_program,
+        total_shares,
+    )?;
+
+    emit!(events::PositionFractionalizedEvent {
+        position: position.key(),
+        fraction_mint: ctx.accounts.fraction_mint.key(),
+        total_shares,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{
+    errors::ErrorCode,
+    manager::position_manager::{BundleLiquiditySnapshot, PositionLiquiditySummary},
+    state::*,
+};
+
+// Maximum number of bundled positions that can be passed as remaining accounts
+pub const MAX_SNAPSHOT_BUNDLE_POSITIONS: usize = 64;
+
+#[derive(Accounts)]
+pub struct GetBundleLiquiditySnapshot<'info> {
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+
+    pub pool: Box<Account<'info, Pool>>,
+    // remaining accounts
+    // - bundled positions of position_bundle in pool, in the order of bundle_indexes
+}
+
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, GetBundleLiquiditySnapshot<'info>>,
+    bundle_indexes: Vec<u16>,
+) -> Result<()> {
+    let position_bundle = &ctx.accounts.position_bundle;
+    let pool = &ctx.accounts.pool;
+
+    if bundle_indexes.len() != ctx.remaining_accounts.len()
+        || bundle_indexes.len() > MAX_SNAPSHOT_BUNDLE_POSITIONS
+    {
+        return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+    }
+
+    let mut positions = Vec::with_capacity(bundle_indexes.len());
+    for (bundle_index, account_info) in bundle_indexes.iter().zip(ctx.remaining_accounts.iter()) {
+        if account_info.key() != position_bundle.bundled_position_address(*bundle_index) {
+            return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+        }
+
+        let position: Account<Position> = Account::try_from(account_info)?;
+        if position.pool != pool.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+
+        positions.push(PositionLiquiditySummary::new(
+            *bundle_index,
+            &position,
+            pool.tick_current_index,
+        ));
+    }
+
+    let snapshot = BundleLiquiditySnapshot::new(positions, Clock::get()?.slot)?;
+    set_return_data(&snapshot.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{
+    errors::ErrorCode,
+    manager::position_manager::{sort_bundle_positions, BundlePositionSorted},
+    state::*,
+};
+
+// Maximum number of bundled positions that can be passed as remaining accounts
+pub const MAX_SORTED_BUNDLE_POSITIONS: usize = 64;
+
+#[derive(Accounts)]
+pub struct GetBundlePositionsSorted<'info> {
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+
+    pub pool: Box<Account<'info, Pool>>,
+    // remaining accounts
+    // - bundled positions of position_bundle in pool, in the order of bundle_indexes
+}
+
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, GetBundlePositionsSorted<'info>>,
+    bundle_indexes: Vec<u16>,
+) -> Result<()> {
+    let position_bundle_mint = ctx.accounts.position_bundle.position_bundle_mint;
+    let pool = &ctx.accounts.pool;
+
+    if bundle_indexes.len() != ctx.remaining_accounts.len()
+        || bundle_indexes.len() > MAX_SORTED_BUNDLE_POSITIONS
+    {
+        return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+    }
+
+    let mut positions = Vec::with_capacity(bundle_indexes.len());
+    for (bundle_index, account_info) in bundle_indexes.iter().zip(ctx.remaining_accounts.iter()) {
+        let (bundled_position, _) = Pubkey::find_program_address(
+            &[
+                b"bundled_position".as_ref(),
+                position_bundle_mint.as_ref(),
+                bundle_index.to_string().as_bytes(),
+            ],
+            &crate::ID,
+        );
+        if account_info.key() != bundled_position {
+            return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+        }
+
+        let position: Account<Position> = Account::try_from(account_info)?;
+        if position.pool != pool.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+
+        positions.push(BundlePositionSorted::new(
+            *bundle_index,
+            &position,
+            pool.tick_current_index,
+        ));
+    }
+
+    sort_bundle_positions(&mut positions);
+    set_return_data(&positions.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{
+    errors::ErrorCode,
+    events::FeeRateVolumeUpdatedEvent,
+    state::{FeeTier, Pool},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct FeeTierVolume {
+    pub tick_spacing: u16,
+    pub total_volume_a: u128,
+    pub total_volume_b: u128,
+}
+
+#[derive(Accounts)]
+pub struct GetFeeTierVolume<'info> {
+    pub fee_tier: Account<'info, FeeTier>,
+    // remaining accounts
+    // - pools initialized with the fee tier
+}
+
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, GetFeeTierVolume<'info>>,
+    volume_milestone: u128,
+) -> Result<()> {
+    let fee_tier = &ctx.accounts.fee_tier;
+
+    let mut volume = FeeTierVolume {
+        tick_spacing: fee_tier.tick_spacing,
+        ..Default::default()
+    };
+    for (i, account_info) in ctx.remaining_accounts.iter().enumerate() {
+        if ctx.remaining_accounts[..i]
+            .iter()
+            .any(|prev_account_info| prev_account_info.key == account_info.key)
+        {
+            return Err(ErrorCode::DuplicateFeeTierPool.into());
+        }
+        let pool = Account::<Pool>::try_from(account_info)?;
+        if !fee_tier.is_fee_tier_of(&pool) {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+        volume.total_volume_a = volume.total_volume_a.saturating_add(pool.total_volume_a);
+        volume.total_volume_b = volume.total_volume_b.saturating_add(pool.total_volume_b);
+    }
+
+    if volume_milestone != 0
+        && (volume.total_volume_a >= volume_milestone || volume.total_volume_b >= volume_milestone)
+    {
+        emit!(FeeRateVolumeUpdatedEvent {
+            fee_tier: fee_tier.key(),
+            tick_spacing: fee_tier.tick_spacing,
+            total_volume_a: volume.total_volume_a,
+            total_volume_b: volume.total_volume_b,
+            volume_milestone,
+        });
+    }
+    set_return_data(&volume.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use std::cell::Ref;
+
+use crate::{
+    errors::ErrorCode,
+    events::LiquidityDistributionEvent,
+    manager::liquidity_distribution_manager::{
+        calculate_liquidity_distribution, LiquidityDistributionPoint,
+    },
+    state::{Pool, TickArray},
+};
+
+// Number of points emitted per LiquidityDistributionEvent to keep each log within limits
+pub const LIQUIDITY_DISTRIBUTION_CHUNK_SIZE: usize = 32;
+
+#[derive(Accounts)]
+pub struct GetLiquidityDistribution<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(has_one = pool)]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
+
+    #[account(has_one = pool)]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
+
+    #[account(has_one = pool)]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+}
+
+pub fn handler(ctx: Context<GetLiquidityDistribution>, a_to_b: bool) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    let tick_array_0 = ctx.accounts.tick_array_0.load()?;
+    let tick_array_1 = load_optional_tick_array(
+        &ctx.accounts.tick_array_1,
+        &[ctx.accounts.tick_array_0.key()],
+    )?;
+    let tick_array_2 = load_optional_tick_array(
+        &ctx.accounts.tick_array_2,
+        &[
+            ctx.accounts.tick_array_0.key(),
+            ctx.accounts.tick_array_1.key(),
+        ],
+    )?;
+
+    let mut tick_arrays: Vec<&TickArray> = Vec::with_capacity(3);
+    tick_arrays.push(&tick_array_0);
+    if let Some(tick_array) = tick_array_1.as_ref() {
+        tick_arrays.push(tick_array);
+    }
+    if let Some(tick_array) = tick_array_2.as_ref() {
+        tick_arrays.push(tick_array);
+    }
+
+    let points = calculate_liquidity_distribution(
+        pool.liquidity,
+        pool.tick_current_index,
+        pool.tick_spacing,
+        &tick_arrays,
+        a_to_b,
+    )?;
+
+    // Always emit at least one event so an empty range is distinguishable from a failed query
+    let chunks: Vec<&[LiquidityDistributionPoint]> = if points.is_empty() {
+        vec![&points[..]]
+    } else {
+        points.chunks(LIQUIDITY_DISTRIBUTION_CHUNK_SIZE).collect()
+    };
+
+    for chunk in chunks {
+        emit!(LiquidityDistributionEvent {
+            pool_state: pool.key(),
+            a_to_b,
+            tick_current_index: pool.tick_current_index,
+            liquidity: pool.liquidity,
+            points: chunk.to_vec(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Load tick_array_1 or tick_array_2 of the walk.
+///
+/// Repeating a previous tick-array omits the account from the walk, as in swaps. Any other account
+/// must load, so that a wrong account fails with `TickArrayLoadFailed` instead of silently
+/// shortening the reported curve.
+fn load_optional_tick_array<'a, 'info>(
+    tick_array: &'a AccountLoader<'info, TickArray>,
+    previous_tick_arrays: &[Pubkey],
+) -> Result<Option<Ref<'a, TickArray>>> {
+    if previous_tick_arrays.contains(&tick_array.key()) {
+        return Ok(None);
+    }
+    match tick_array.load() {
+        Ok(tick_array) => Ok(Some(tick_array)),
+        Err(_) => Err(error!(ErrorCode::TickArrayLoadFailed)
+            .with_pubkeys((tick_array.key(), tick_array.key()))),
+    }
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::math::tick_bitmap_search;
+use crate::state::{Pool, TickArray, TICK_ARRAY_SIZE};
+
+/// The initialized ticks of a tick array next to a tick. `next_initialized_below` includes the
+/// tick itself, as the first tick an a_to_b swap from it would cross.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct NextInitializedTicks {
+    pub next_initialized_above: Option<i32>,
+    pub next_initialized_below: Option<i32>,
+}
+
+#[derive(Accounts)]
+pub struct GetNextInitializedTickCompact<'info> {
+    pub pool: Account<'info, Pool>,
+
+    #[account(has_one = pool)]
+    pub tick_array: AccountLoader<'info, TickArray>,
+}
+
+pub fn handler(ctx: Context<GetNextInitializedTickCompact>, current_tick: i32) -> Result<()> {
+    let tick_array = ctx.accounts.tick_array.load()?;
+    let next_initialized_ticks =
+        next_initialized_ticks(&tick_array, ctx.accounts.pool.tick_spacing, current_tick)?;
+    set_return_data(&next_initialized_ticks.try_to_vec()?);
+
+    Ok(())
+}
+
+// The tick does not need to be in the array, the search then covers the whole array on the side
+// of the tick
+fn next_initialized_ticks(
+    tick_array: &TickArray,
+    tick_spacing: u16,
+    current_tick: i32,
+) -> Result<NextInitializedTicks> {
+    let bitmap = tick_array.initialized_bitmap();
+    let offset = tick_array
+        .tick_offset(current_tick, tick_spacing)?
+        .clamp(-1, TICK_ARRAY_SIZE as isize) as i32;
+    let tick_index = |offset: i32| tick_array.start_tick_index + offset * tick_spacing as i32;
+
+    Ok(NextInitializedTicks {
+        next_initialized_above: tick_bitmap_search(bitmap, offset, false).map(tick_index),
+        next_initialized_below: tick_bitmap_search(bitmap, offset, true).map(tick_index),
+    })
+}
+
+#[cfg(test)]
+mod next_initialized_ticks_tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+
+    const TS_8: u16 = 8;
+
+    fn tick_array(start_tick_index: i32, initialized_offsets: &[usize]) -> TickArray {
+        let mut tick_array = TickArray::default();
+        tick_array.start_tick_index = start_tick_index;
+        for offset in initialized_offsets {
+            tick_array.ticks[*offset].initialized = true;
+        }
+        tick_array
+    }
+
+    fn next_ticks(above: Option<i32>, below: Option<i32>) -> NextInitializedTicks {
+        NextInitializedTicks {
+            next_initialized_above: above,
+            next_initialized_below: below,
+        }
+    }
+
+    #[test]
+    fn test_ticks_around_current_tick() {
+        let tick_array = tick_array(-704, &[0, 40, 87]);
+        assert_eq!(
+            next_initialized_ticks(&tick_array, TS_8, -400).unwrap(),
+            next_ticks(Some(-384), Some(-704))
+        );
+        // The current tick counts as below, also between two usable ticks
+        assert_eq!(
+            next_initialized_ticks(&tick_array, TS_8, -384).unwrap(),
+            next_ticks(Some(-8), Some(-384))
+        );
+        assert_eq!(
+            next_initialized_ticks(&tick_array, TS_8, -380).unwrap(),
+            next_ticks(Some(-8), Some(-384))
+        );
+        assert_eq!(
+            next_initialized_ticks(&tick_array, TS_8, -8).unwrap(),
+            next_ticks(None, Some(-8))
+        );
+    }
+
+    #[test]
+    fn test_current_tick_outside_array() {
+        let tick_array = tick_array(0, &[10, 50]);
+        assert_eq!(
+            next_initialized_ticks(&tick_array, TS_8, -100_000).unwrap(),
+            next_ticks(Some(80), None)
+        );
+        assert_eq!(
+            next_initialized_ticks(&tick_array, TS_8, 100_000).unwrap(),
+            next_ticks(None, Some(400))
+        );
+    }
+
+    #[test]
+    fn test_empty_array() {
+        let tick_array = tick_array(0, &[]);
+        assert_eq!(
+            next_initialized_ticks(&tick_array, TS_8, 100).unwrap(),
+            next_ticks(None, None)
+        );
+    }
+
+    #[test]
+    fn test_invalid_tick_spacing() {
+        let tick_array = tick_array(0, &[10]);
+        assert_eq!(
+            next_initialized_ticks(&tick_array, 0, 100).unwrap_err(),
+            ErrorCode::InvalidTickSpacing.into()
+        );
+    }
+}
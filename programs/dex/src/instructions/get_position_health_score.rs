@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{manager::position_manager::calculate_position_health_score, state::*};
+
+#[derive(Accounts)]
+pub struct GetPositionHealthScore<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(has_one = pool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(has_one = position)]
+    pub position_history: Option<Box<Account<'info, PositionHistory>>>,
+}
+
+pub fn handler(ctx: Context<GetPositionHealthScore>, daily_volatility_bps: u16) -> Result<()> {
+    // Without a history there are no deposits or fee collections to compare with
+    let history = match &ctx.accounts.position_history {
+        Some(position_history) => position_history.recorded_entries(),
+        None => vec![],
+    };
+    let health_score = calculate_position_health_score(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &history,
+        Clock::get()?.slot,
+        daily_volatility_bps,
+    )?;
+    set_return_data(&health_score.try_to_vec()?);
+
+    Ok(())
+}
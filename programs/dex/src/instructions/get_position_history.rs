@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::state::PositionHistory;
+
+#[derive(Accounts)]
+pub struct GetPositionHistory<'info> {
+    pub position_history: Box<Account<'info, PositionHistory>>,
+}
+
+pub fn handler(ctx: Context<GetPositionHistory>) -> Result<()> {
+    let history = ctx.accounts.position_history.recorded_entries();
+    set_return_data(&history.try_to_vec()?);
+
+    Ok(())
+}
@@ -3,10 +3,14 @@ use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::errors::ErrorCode;
 use crate::manager::liquidity_manager::{
-    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+    calculate_increase_liquidity_from_token_amounts, calculate_liquidity_token_deltas,
+    calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
-use crate::util::{to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority};
+use crate::util::{
+    load_position_tick_array, to_timestamp_u64, transfer_from_owner_to_vault,
+    verify_position_authority,
+};
 use crate::{events, state::*};
 
 #[derive(Accounts)]
@@ -37,10 +41,12 @@ pub struct ModifyLiquidity<'info> {
     #[account(mut, constraint = token_vault_b.key() == pool.token_vault_b)]
     pub token_vault_b: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut, has_one = pool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(mut, has_one = pool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: loaded by load_position_tick_array, which checks the pool of the tick-array
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: loaded by load_position_tick_array, which checks the pool of the tick-array
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
 }
 
 pub fn handler(
@@ -54,19 +60,24 @@ pub fn handler(
         &ctx.accounts.position_authority,
     )?;
 
+    let pool_key = ctx.accounts.pool.key();
+    let tick_array_lower = load_position_tick_array(&ctx.accounts.tick_array_lower, &pool_key)?;
+    let tick_array_upper = load_position_tick_array(&ctx.accounts.tick_array_upper, &pool_key)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
         return Err(ErrorCode::LiquidityZero.into());
     }
-    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, true).map_err(ErrorCode::from)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.pool,
         &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         liquidity_delta,
         timestamp,
     )?;
@@ -74,8 +85,8 @@ pub fn handler(
     sync_modify_liquidity_values(
         &mut ctx.accounts.pool,
         &mut ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         update,
         timestamp,
     )?;
@@ -117,3 +128,23 @@ pub fn handler(
 
     Ok(())
 }
+
+/*
+  Increases the liquidity of a position by the largest liquidity the given token amounts provide at
+  the current price of the pool. Only the required token amounts are transferred.
+*/
+pub fn handler_by_token_amounts(
+    ctx: Context<ModifyLiquidity>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<()> {
+    let (liquidity_amount, _, _) = calculate_increase_liquidity_from_token_amounts(
+        ctx.accounts.pool.tick_current_index,
+        ctx.accounts.pool.sqrt_price,
+        &ctx.accounts.position,
+        token_a_amount,
+        token_b_amount,
+    )?;
+
+    handler(ctx, liquidity_amount, token_a_amount, token_b_amount)
+}
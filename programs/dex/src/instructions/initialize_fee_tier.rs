@@ -17,7 +17,9 @@ pub struct InitializeFeeTier<'info> {
     #[account(mut)]
     pub funder: Signer<'info>,
 
-    #[account(address = config.fee_authority)]
+    // The fee tier authority of the config while fee tier creation is locked, otherwise either the
+    // fee authority or the operations authority
+    #[account(constraint = config.is_fee_tier_creator(fee_authority.key()))]
     pub fee_authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
@@ -1,4 +1,4 @@
-use crate::{events, state::*};
+use crate::{events, state::*, util::verify_vault_empty};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token};
 
@@ -43,7 +43,7 @@ pub struct InitializePool<'info> {
         bump)]
     pub token_vault_b: AccountInfo<'info>,
 
-    #[account(has_one = pools_config, constraint = fee_tier.tick_spacing == tick_spacing)]
+    #[account(mut, has_one = pools_config)]
     pub fee_tier: Box<Account<'info, FeeTier>>,
 
     #[account(address = token::ID)]
@@ -58,12 +58,17 @@ pub fn handler(
     tick_spacing: u16,
     initial_sqrt_price: u128,
 ) -> Result<()> {
+    verify_vault_empty(&ctx.accounts.token_vault_a)?;
+    verify_vault_empty(&ctx.accounts.token_vault_b)?;
+
     let token_mint_a = ctx.accounts.token_mint_a.key();
     let token_mint_b = ctx.accounts.token_mint_b.key();
 
     let pool = &mut ctx.accounts.pool;
     let pools_config = &ctx.accounts.pools_config;
 
+    // The pool's fee rate is taken from the fee tier of its tick spacing
+    ctx.accounts.fee_tier.verify_tick_spacing(tick_spacing)?;
     let default_fee_rate = ctx.accounts.fee_tier.default_fee_rate;
 
     // ignore the bump passed and use one Anchor derived
@@ -80,6 +85,16 @@ pub fn handler(
         token_mint_b,
         ctx.accounts.token_vault_b.key(),
     )?;
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    let old_count = fee_tier.pool_count;
+    fee_tier.increment_pool_count()?;
+    emit!(events::FeeTierPoolCountChangedEvent {
+        fee_tier: fee_tier.key(),
+        old_count,
+        new_count: fee_tier.pool_count,
+    });
+
     emit!(events::PoolCreatedEvent {
         token_mint_0: pool.token_mint_a.key(),
         token_mint_1: pool.token_mint_b.key(),
@@ -0,0 +1,172 @@
+use crate::{
+    events,
+    math::tick_index_from_sqrt_price,
+    state::*,
+    util::{surrounding_tick_array_start_indexes, verify_vault_empty},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token};
+
+#[derive(Accounts)]
+#[instruction(bumps: PoolBumps, tick_spacing: u16, initial_sqrt_price: u128)]
+pub struct InitializePoolWithTickArrays<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    pub token_mint_a: Box<Account<'info, Mint>>,
+    pub token_mint_b: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(init,
+      seeds = [
+        b"pool".as_ref(),
+        pools_config.key().as_ref(),
+        token_mint_a.key().as_ref(),
+        token_mint_b.key().as_ref(),
+        tick_spacing.to_le_bytes().as_ref()
+      ],
+      bump,
+      payer = funder,
+      space = Pool::LEN)]
+    pub pool: Box<Account<'info, Pool>>,
+    /// CHECK:safe with seeds
+    #[account(mut,seeds =[
+            b"pool_vault",
+            pool.key().as_ref(),
+            token_mint_a.key().as_ref(),
+        ],
+        bump)]
+    pub token_vault_a: AccountInfo<'info>,
+    /// CHECK:safe with seeds
+    #[account(mut,seeds =[
+            b"pool_vault",
+            pool.key().as_ref(),
+            token_mint_b.key().as_ref(),
+        ],
+        bump)]
+    pub token_vault_b: AccountInfo<'info>,
+
+    #[account(mut, has_one = pools_config)]
+    pub fee_tier: Box<Account<'info, FeeTier>>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"tick_array",
+        pool.key().as_ref(),
+        tick_array_start_index(initial_sqrt_price, tick_spacing, 0).to_string().as_bytes()
+      ],
+      bump,
+      space = TickArray::LEN)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"tick_array",
+        pool.key().as_ref(),
+        tick_array_start_index(initial_sqrt_price, tick_spacing, 1).to_string().as_bytes()
+      ],
+      bump,
+      space = TickArray::LEN)]
+    pub tick_array_current: AccountLoader<'info, TickArray>,
+
+    #[account(
+      init,
+      payer = funder,
+      seeds = [
+        b"tick_array",
+        pool.key().as_ref(),
+        tick_array_start_index(initial_sqrt_price, tick_spacing, 2).to_string().as_bytes()
+      ],
+      bump,
+      space = TickArray::LEN)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+fn tick_array_start_index(initial_sqrt_price: u128, tick_spacing: u16, index: usize) -> i32 {
+    surrounding_tick_array_start_indexes(
+        tick_index_from_sqrt_price(&initial_sqrt_price),
+        tick_spacing,
+    )[index]
+}
+
+/*
+  Initializes a pool together with the tick-arrays below, containing and above its initial price,
+  so that the pool can be traded in both directions right after liquidity is added.
+*/
+pub fn handler(
+    ctx: Context<InitializePoolWithTickArrays>,
+    _bumps: PoolBumps,
+    tick_spacing: u16,
+    initial_sqrt_price: u128,
+) -> Result<()> {
+    verify_vault_empty(&ctx.accounts.token_vault_a)?;
+    verify_vault_empty(&ctx.accounts.token_vault_b)?;
+
+    let token_mint_a = ctx.accounts.token_mint_a.key();
+    let token_mint_b = ctx.accounts.token_mint_b.key();
+
+    let pool = &mut ctx.accounts.pool;
+    let pools_config = &ctx.accounts.pools_config;
+
+    ctx.accounts.fee_tier.verify_tick_spacing(tick_spacing)?;
+    let default_fee_rate = ctx.accounts.fee_tier.default_fee_rate;
+
+    pool.initialize(
+        pools_config,
+        ctx.bumps.pool,
+        tick_spacing,
+        initial_sqrt_price,
+        default_fee_rate,
+        token_mint_a,
+        ctx.accounts.token_vault_a.key(),
+        token_mint_b,
+        ctx.accounts.token_vault_b.key(),
+    )?;
+
+    let start_tick_indexes =
+        surrounding_tick_array_start_indexes(pool.tick_current_index, tick_spacing);
+    let tick_arrays = [
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_current,
+        &ctx.accounts.tick_array_upper,
+    ];
+    for (tick_array, start_tick_index) in tick_arrays.iter().zip(start_tick_indexes) {
+        tick_array.load_init()?.initialize(pool, start_tick_index)?;
+    }
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    let old_count = fee_tier.pool_count;
+    fee_tier.increment_pool_count()?;
+    emit!(events::FeeTierPoolCountChangedEvent {
+        fee_tier: fee_tier.key(),
+        old_count,
+        new_count: fee_tier.pool_count,
+    });
+
+    emit!(events::PoolCreatedEvent {
+        token_mint_0: pool.token_mint_a.key(),
+        token_mint_1: pool.token_mint_b.key(),
+        tick_spacing: tick_spacing,
+        pool_state: pool.key(),
+        sqrt_price_x64: initial_sqrt_price,
+        tick: pool.tick_current_index,
+        token_vault_0: pool.token_vault_a.key(),
+        token_vault_1: pool.token_vault_b.key(),
+    });
+
+    emit!(events::PoolAndTickArraysInitializedEvent {
+        pool: pool.key(),
+        tick_arrays: tick_arrays.map(|tick_array| tick_array.key()),
+    });
+    Ok(())
+}
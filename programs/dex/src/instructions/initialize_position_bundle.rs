@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
-use crate::{state::*, util::mint_position_bundle_token_and_remove_authority};
+use crate::{events, state::*, util::mint_position_bundle_token_and_remove_authority};
 
 #[derive(Accounts)]
 pub struct InitializePositionBundle<'info> {
@@ -59,5 +59,13 @@ pub fn handler(ctx: Context<InitializePositionBundle>) -> Result<()> {
             position_bundle_mint.key().as_ref(),
             &[bump],
         ],
-    )
+    )?;
+
+    emit!(events::PositionBundleNFTMintedEvent {
+        position_bundle: ctx.accounts.position_bundle.key(),
+        position_bundle_mint: position_bundle_mint.key(),
+        owner: ctx.accounts.position_bundle_owner.key(),
+    });
+
+    Ok(())
 }
@@ -4,7 +4,9 @@ use anchor_spl::metadata::Metadata;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 use crate::constants::nft::pool_nft_update_auth::ID as WPB_NFT_UPDATE_AUTH;
-use crate::{state::*, util::mint_position_bundle_token_with_metadata_and_remove_authority};
+use crate::{
+    events, state::*, util::mint_position_bundle_token_with_metadata_and_remove_authority,
+};
 
 #[derive(Accounts)]
 pub struct InitializePositionBundleWithMetadata<'info> {
@@ -78,5 +80,13 @@ pub fn handler(ctx: Context<InitializePositionBundleWithMetadata>) -> Result<()>
             position_bundle_mint.key().as_ref(),
             &[bump],
         ],
-    )
+    )?;
+
+    emit!(events::PositionBundleNFTMintedEvent {
+        position_bundle: ctx.accounts.position_bundle.key(),
+        position_bundle_mint: position_bundle_mint.key(),
+        owner: ctx.accounts.position_bundle_owner.key(),
+    });
+
+    Ok(())
 }
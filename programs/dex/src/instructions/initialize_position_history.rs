@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Position, PositionHistory};
+
+#[derive(Accounts)]
+pub struct InitializePositionHistory<'info> {
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [b"position_history", position.key().as_ref()],
+      bump,
+      space = PositionHistory::LEN)]
+    pub position_history: Box<Account<'info, PositionHistory>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializePositionHistory>) -> Result<()> {
+    ctx.accounts
+        .position_history
+        .initialize(ctx.accounts.position.key());
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializePositionSwapCount<'info> {
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(init,
+      payer = funder,
+      space = PositionSwapCount::LEN,
+      seeds = [b"position_swap_count", position.key().as_ref()],
+      bump,
+    )]
+    pub position_swap_count: Box<Account<'info, PositionSwapCount>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializePositionSwapCount>) -> Result<()> {
+    let position = &ctx.accounts.position;
+    ctx.accounts.position_swap_count.initialize(
+        position.pool,
+        position.key(),
+        position.position_mint,
+    );
+    Ok(())
+}
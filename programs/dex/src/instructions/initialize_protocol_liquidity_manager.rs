@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeProtocolLiquidityManager<'info> {
+    #[account(mut)]
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(init,
+      payer = funder,
+      seeds = [b"pol_manager", pools_config.key().as_ref()],
+      bump,
+      space = ProtocolLiquidityManager::LEN)]
+    pub protocol_liquidity_manager: Account<'info, ProtocolLiquidityManager>,
+
+    #[account(has_one = pools_config)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(has_one = pool)]
+    pub position: Box<Account<'info, Position>>,
+    // The manager must hold the position token, so only it can modify the position or collect its fees
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1,
+        constraint = position_token_account.owner == protocol_liquidity_manager.key()
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = treasury_token_account_a.mint == pool.token_mint_a,
+        constraint = treasury_token_account_a.owner == protocol_liquidity_manager.key()
+    )]
+    pub treasury_token_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(
+        constraint = treasury_token_account_b.mint == pool.token_mint_b,
+        constraint = treasury_token_account_b.owner == protocol_liquidity_manager.key()
+    )]
+    pub treasury_token_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeProtocolLiquidityManager>,
+    min_tick_lower: i32,
+    max_tick_upper: i32,
+) -> Result<()> {
+    let position = &ctx.accounts.position;
+
+    ctx.accounts.protocol_liquidity_manager.initialize(
+        ctx.accounts.pools_config.key(),
+        ctx.bumps.protocol_liquidity_manager,
+        position.key(),
+        ctx.accounts.treasury_token_account_a.key(),
+        ctx.accounts.treasury_token_account_b.key(),
+        min_tick_lower,
+        max_tick_upper,
+        position.tick_lower_index,
+        position.tick_upper_index,
+    )?;
+
+    ctx.accounts
+        .pools_config
+        .update_protocol_liquidity_position(position.key());
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::state::{pool_name_seed, PoolNameEntry};
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct LookupPoolByName<'info> {
+    #[account(
+      seeds = [b"pool_name", pool_name_entry.pools_config.as_ref(),
+               pool_name_seed(&name).as_ref()],
+      bump)]
+    pub pool_name_entry: Account<'info, PoolNameEntry>,
+}
+
+pub fn handler(ctx: Context<LookupPoolByName>, name: String) -> Result<()> {
+    let pool_name_entry = &ctx.accounts.pool_name_entry;
+    pool_name_entry.verify_name(&name)?;
+
+    set_return_data(&pool_name_entry.pool.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::{errors::ErrorCode, state::FeeTier};
+
+#[derive(Accounts)]
+pub struct MigrateFeeTierPoolCount<'info> {
+    /// CHECK: fee tiers created before the pool count was added cannot be deserialized as
+    /// FeeTier, so the owner, discriminator and size are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub fee_tier: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a fee tier account created before the pool count was added. The pool count is
+/// zero-initialized by the realloc.
+pub fn handler(ctx: Context<MigrateFeeTierPoolCount>) -> Result<()> {
+    let fee_tier_info = ctx.accounts.fee_tier.to_account_info();
+
+    {
+        let data = fee_tier_info.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != FeeTier::discriminator() {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        if data.len() == FeeTier::LEN {
+            return Err(ErrorCode::FeeTierAlreadyMigrated.into());
+        }
+        if data.len() != FeeTier::LEN_WITHOUT_POOL_COUNT {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+    }
+
+    let minimum_balance = Rent::get()?.minimum_balance(FeeTier::LEN);
+    let lamports_shortfall = minimum_balance.saturating_sub(fee_tier_info.lamports());
+    if lamports_shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: fee_tier_info.clone(),
+                },
+            ),
+            lamports_shortfall,
+        )?;
+    }
+
+    fee_tier_info.realloc(FeeTier::LEN, true)?;
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::{errors::ErrorCode, state::Pool};
+
+#[derive(Accounts)]
+pub struct MigratePoolMaxLiquidity<'info> {
+    /// CHECK: pools created before the last fields were added cannot be deserialized as Pool,
+    /// so the owner, discriminator and size are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a pool account created before `max_liquidity_observed`, `authority_renounced`, the swap
+/// volume, the swap count or the reward emission schedules were added, and initialize the
+/// high-water mark to the current liquidity of the pool.
+pub fn handler(ctx: Context<MigratePoolMaxLiquidity>) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+
+    // Pools that already track max_liquidity_observed only need to grow, the new fields are
+    // zero initialized by realloc
+    let liquidity = {
+        let data = pool_info.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != Pool::discriminator() {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        if data.len() == Pool::LEN {
+            return Err(ErrorCode::PoolAlreadyMigrated.into());
+        }
+        if data.len() == Pool::LEN_WITHOUT_AUTHORITY_RENOUNCED
+            || data.len() == Pool::LEN_WITHOUT_VOLUME
+            || data.len() == Pool::LEN_WITHOUT_SWAP_COUNT
+            || data.len() == Pool::LEN_WITHOUT_REWARD_EMISSIONS_SCHEDULES
+        {
+            None
+        } else if data.len() == Pool::LEN_WITHOUT_MAX_LIQUIDITY_OBSERVED {
+            let mut liquidity = [0u8; 16];
+            liquidity.copy_from_slice(&data[Pool::LIQUIDITY_OFFSET..Pool::LIQUIDITY_OFFSET + 16]);
+            Some(liquidity)
+        } else {
+            return Err(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+        }
+    };
+
+    let minimum_balance = Rent::get()?.minimum_balance(Pool::LEN);
+    let lamports_shortfall = minimum_balance.saturating_sub(pool_info.lamports());
+    if lamports_shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: pool_info.clone(),
+                },
+            ),
+            lamports_shortfall,
+        )?;
+    }
+
+    pool_info.realloc(Pool::LEN, true)?;
+
+    if let Some(liquidity) = liquidity {
+        let mut data = pool_info.try_borrow_mut_data()?;
+        data[Pool::MAX_LIQUIDITY_OBSERVED_OFFSET..Pool::MAX_LIQUIDITY_OBSERVED_OFFSET + 16]
+            .copy_from_slice(&liquidity);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::{
+    errors::ErrorCode,
+    math::MAX_PROTOCOL_FEE_RATE,
+    state::{PoolsConfig, FEATURE_FLAGS_ALL},
+};
+
+#[derive(Accounts)]
+pub struct MigratePoolsConfig<'info> {
+    /// CHECK: configs created before the last fields were added cannot be deserialized as
+    /// PoolsConfig, so the owner, discriminator and size are verified in the handler
+    #[account(mut, owner = crate::ID)]
+    pub pools_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a pools config account created before `feature_flags`, `max_referral_fee_rate`, the
+/// protocol fee rate bounds, the fee tier creation lock or the protocol liquidity position were
+/// added. Configs without feature flags
+/// get every feature enabled and configs without bounds allow every protocol fee rate, so that the
+/// config keeps its behavior. Referral fees start disabled, fee tier creation unlocked and no
+/// protocol liquidity position is set.
+pub fn handler(ctx: Context<MigratePoolsConfig>) -> Result<()> {
+    let pools_config_info = ctx.accounts.pools_config.to_account_info();
+
+    let (has_feature_flags, has_protocol_fee_rate_bounds) = {
+        let data = pools_config_info.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != PoolsConfig::discriminator() {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+        if data.len() >= PoolsConfig::LEN {
+            return Err(ErrorCode::PoolsConfigAlreadyMigrated.into());
+        }
+        (
+            data.len() >= PoolsConfig::LEN_WITHOUT_REFERRAL_FEE,
+            data.len() >= PoolsConfig::LEN_WITHOUT_FEE_TIER_AUTHORITY,
+        )
+    };
+
+    let minimum_balance = Rent::get()?.minimum_balance(PoolsConfig::LEN);
+    let lamports_shortfall = minimum_balance.saturating_sub(pools_config_info.lamports());
+    if lamports_shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: pools_config_info.clone(),
+                },
+            ),
+            lamports_shortfall,
+        )?;
+    }
+
+    // The fields added since the config was created are zero initialized by realloc
+    pools_config_info.realloc(PoolsConfig::LEN, true)?;
+
+    let mut data = pools_config_info.try_borrow_mut_data()?;
+    if !has_feature_flags {
+        data[PoolsConfig::FEATURE_FLAGS_OFFSET..PoolsConfig::FEATURE_FLAGS_OFFSET + 8]
+            .copy_from_slice(&FEATURE_FLAGS_ALL.to_le_bytes());
+    }
+    if !has_protocol_fee_rate_bounds {
+        data[PoolsConfig::MAX_PROTOCOL_FEE_RATE_OFFSET
+            ..PoolsConfig::MAX_PROTOCOL_FEE_RATE_OFFSET + 2]
+            .copy_from_slice(&MAX_PROTOCOL_FEE_RATE.to_le_bytes());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::PositionMigratedEvent;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_from_token_amounts, calculate_liquidity_token_deltas,
+    calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::pool_manager::check_position_migration_pools;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::*;
+use crate::util::{
+    mint_position_token_and_remove_authority, record_position_history, to_timestamp_u64,
+    transfer_from_vault_to_owner, verify_position_authority,
+};
+
+#[derive(Accounts)]
+pub struct MigratePositionToPool<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub position_authority: Signer<'info>,
+
+    /// CHECK: safe, the owner of the source position receives the new position
+    #[account(address = source_position_token_account.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub source_pool: Box<Account<'info, Pool>>,
+
+    #[account(mut, constraint = source_position.pool == source_pool.key())]
+    pub source_position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = source_position_token_account.mint == source_position.position_mint,
+        constraint = source_position_token_account.amount == 1
+    )]
+    pub source_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = source_pool.token_vault_a)]
+    pub source_token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = source_pool.token_vault_b)]
+    pub source_token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = source_tick_array_lower.load()?.pool == source_pool.key())]
+    pub source_tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, constraint = source_tick_array_upper.load()?.pool == source_pool.key())]
+    pub source_tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(mut)]
+    pub destination_pool: Box<Account<'info, Pool>>,
+
+    #[account(init,
+      payer = funder,
+      space = Position::LEN,
+      seeds = [b"position".as_ref(), destination_position_mint.key().as_ref()],
+      bump,
+    )]
+    pub destination_position: Box<Account<'info, Position>>,
+
+    #[account(init,
+        payer = funder,
+        mint::authority = destination_pool,
+        mint::decimals = 0,
+    )]
+    pub destination_position_mint: Box<Account<'info, Mint>>,
+
+    #[account(init,
+      payer = funder,
+      associated_token::mint = destination_position_mint,
+      associated_token::authority = owner,
+    )]
+    pub destination_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = destination_pool.token_vault_a)]
+    pub destination_token_vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = destination_pool.token_vault_b)]
+    pub destination_token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = destination_tick_array_lower.load()?.pool == destination_pool.key())]
+    pub destination_tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(mut, constraint = destination_tick_array_upper.load()?.pool == destination_pool.key())]
+    pub destination_tick_array_upper: AccountLoader<'info, TickArray>,
+
+    // Receive the tokens that do not fit into the new position
+    #[account(mut, constraint = token_owner_account_a.mint == source_pool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_b.mint == source_pool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(mut, constraint = source_position_history.position == source_position.key())]
+    pub source_position_history: Option<Box<Account<'info, PositionHistory>>>,
+}
+
+/*
+  Moves all liquidity and pending fees of a position into a new position in another pool of the
+  same token pair. Rewards stay claimable on the source position.
+*/
+pub fn handler(
+    ctx: Context<MigratePositionToPool>,
+    new_tick_lower: i32,
+    new_tick_upper: i32,
+    slippage_bps: u16,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.source_position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    if ctx.accounts.source_pool.key() == ctx.accounts.destination_pool.key() {
+        return Err(ErrorCode::InvalidMigrationPool.into());
+    }
+    check_position_migration_pools(
+        &ctx.accounts.source_pool,
+        &ctx.accounts.destination_pool,
+        slippage_bps,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Withdraw all liquidity of the source position
+    let source_liquidity = ctx.accounts.source_position.liquidity;
+    if source_liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let source_liquidity_delta =
+        convert_to_liquidity_delta(source_liquidity, false).map_err(ErrorCode::from)?;
+
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.source_pool,
+        &ctx.accounts.source_position,
+        &ctx.accounts.source_tick_array_lower,
+        &ctx.accounts.source_tick_array_upper,
+        source_liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.source_pool,
+        &mut ctx.accounts.source_position,
+        &ctx.accounts.source_tick_array_lower,
+        &ctx.accounts.source_tick_array_upper,
+        update,
+        timestamp,
+    )?;
+
+    let (withdrawn_a, withdrawn_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.source_pool.tick_current_index,
+        ctx.accounts.source_pool.sqrt_price,
+        &ctx.accounts.source_position,
+        source_liquidity_delta,
+    )?;
+
+    // Collect the pending fees, which are migrated along with the liquidity
+    let source_position = &mut ctx.accounts.source_position;
+    let (fee_a, fee_b) = (source_position.fee_owed_a, source_position.fee_owed_b);
+    source_position.reset_fees_owed();
+
+    record_position_history(
+        &mut ctx.accounts.source_position_history,
+        PositionHistoryEntry::new(
+            POSITION_HISTORY_DECREASE_LIQUIDITY,
+            clock.slot,
+            source_liquidity_delta,
+            withdrawn_a,
+            withdrawn_b,
+        ),
+    );
+    record_position_history(
+        &mut ctx.accounts.source_position_history,
+        PositionHistoryEntry::new(POSITION_HISTORY_COLLECT_FEES, clock.slot, 0, fee_a, fee_b),
+    );
+
+    let amount_a = withdrawn_a
+        .checked_add(fee_a)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+    let amount_b = withdrawn_b
+        .checked_add(fee_b)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+
+    // Open the destination position
+    ctx.accounts.destination_position.open_position(
+        &ctx.accounts.destination_pool,
+        ctx.accounts.destination_position_mint.key(),
+        new_tick_lower,
+        new_tick_upper,
+    )?;
+
+    mint_position_token_and_remove_authority(
+        &ctx.accounts.destination_pool,
+        &ctx.accounts.destination_position_mint,
+        &ctx.accounts.destination_position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    // Deposit as much of the withdrawn tokens as the new range can hold
+    let destination_liquidity = calculate_liquidity_from_token_amounts(
+        ctx.accounts.destination_pool.tick_current_index,
+        ctx.accounts.destination_pool.sqrt_price,
+        new_tick_lower,
+        new_tick_upper,
+        amount_a,
+        amount_b,
+    )?;
+    if destination_liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let destination_liquidity_delta =
+        convert_to_liquidity_delta(destination_liquidity, true).map_err(ErrorCode::from)?;
+
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.destination_pool,
+        &ctx.accounts.destination_position,
+        &ctx.accounts.destination_tick_array_lower,
+        &ctx.accounts.destination_tick_array_upper,
+        destination_liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.destination_pool,
+        &mut ctx.accounts.destination_position,
+        &ctx.accounts.destination_tick_array_lower,
+        &ctx.accounts.destination_tick_array_upper,
+        update,
+        timestamp,
+    )?;
+
+    let (deposit_a, deposit_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.destination_pool.tick_current_index,
+        ctx.accounts.destination_pool.sqrt_price,
+        &ctx.accounts.destination_position,
+        destination_liquidity_delta,
+    )?;
+
+    if deposit_a > amount_a || deposit_b > amount_b {
+        return Err(ErrorCode::TokenMaxExceeded.into());
+    }
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.source_pool,
+        &ctx.accounts.source_token_vault_a,
+        &ctx.accounts.destination_token_vault_a,
+        &ctx.accounts.token_program,
+        deposit_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.source_pool,
+        &ctx.accounts.source_token_vault_b,
+        &ctx.accounts.destination_token_vault_b,
+        &ctx.accounts.token_program,
+        deposit_b,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.source_pool,
+        &ctx.accounts.source_token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program,
+        amount_a - deposit_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.source_pool,
+        &ctx.accounts.source_token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program,
+        amount_b - deposit_b,
+    )?;
+
+    emit!(PositionMigratedEvent {
+        from_pool: ctx.accounts.source_pool.key(),
+        to_pool: ctx.accounts.destination_pool.key(),
+        from_fee_rate: ctx.accounts.source_pool.fee_rate,
+        to_fee_rate: ctx.accounts.destination_pool.fee_rate,
+        liquidity_migrated: source_liquidity,
+    });
+
+    Ok(())
+}
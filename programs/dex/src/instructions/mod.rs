@@ -1,66 +1,204 @@
+pub mod auto_adjust_protocol_fee;
+pub mod batch_close_positions;
+pub mod cancel_parameter_change;
 pub mod close_bundled_position;
+pub mod close_deadline_context;
 pub mod close_position;
 pub mod collect_fees;
+pub mod collect_fees_and_close_position;
+pub mod collect_position_fraction_fees;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
+pub mod create_deadline_context;
+pub mod create_protocol_proposal;
 pub mod decrease_liquidity;
+pub mod decrease_liquidity_and_collect;
+pub mod decrement_pool_count;
 pub mod delete_position_bundle;
+pub mod deploy_protocol_liquidity;
+pub mod deposit_position_fractions;
+pub mod disable_protocol_fee_streaming;
+pub mod enable_protocol_fee_streaming;
+pub mod execute_parameter_change;
+pub mod execute_proposal;
+pub mod expand_position_bundle;
+pub mod export_fee_growth_checkpoint;
+pub mod find_next_tick_array_start;
+pub mod fractionalize_position;
+pub mod get_bundle_liquidity_snapshot;
+pub mod get_bundle_positions_sorted;
+pub mod get_fee_tier_volume;
+pub mod get_liquidity_distribution;
+pub mod get_next_initialized_tick_compact;
+pub mod get_position_health_score;
+pub mod get_position_history;
 pub mod increase_liquidity;
 pub mod initialize_config;
 pub mod initialize_fee_tier;
 pub mod initialize_pool;
+pub mod initialize_pool_with_tick_arrays;
 pub mod initialize_position_bundle;
 pub mod initialize_position_bundle_with_metadata;
+pub mod initialize_position_history;
+pub mod initialize_position_swap_count;
+pub mod initialize_protocol_liquidity_manager;
 pub mod initialize_reward;
 pub mod initialize_tick_array;
+pub mod lookup_pool_by_name;
+pub mod migrate_fee_tier_pool_count;
+pub mod migrate_pool_max_liquidity;
+pub mod migrate_pools_config;
+pub mod migrate_position_to_pool;
 pub mod open_bundled_position;
 pub mod open_position;
 pub mod open_position_with_metadata;
+pub mod pause_reward_emission;
+pub mod propose_parameter_change;
+pub mod reconcile_fee_growth;
+pub mod reconcile_pool;
+pub mod redeem_position_fractions;
+pub mod register_pool_name;
+pub mod renounce_pool_authority;
+pub mod repair_position_bundle;
+pub mod resume_reward_emission;
+pub mod set_auto_protocol_fee;
 pub mod set_collect_protocol_fees_authority;
 pub mod set_default_fee_rate;
 pub mod set_default_protocol_fee_rate;
+pub mod set_directional_swap_status;
+pub mod set_feature_flags;
 pub mod set_fee_authority;
 pub mod set_fee_rate;
+pub mod set_fee_tier_authority;
+pub mod set_fee_tier_creation_locked;
+pub mod set_lp_fee_rebate_config;
+pub mod set_max_referral_fee_rate;
+pub mod set_max_swap_amount;
+pub mod set_min_swap_amount;
+pub mod set_operations_authority;
 pub mod set_protocol_fee_rate;
+pub mod set_protocol_fee_rate_bounds;
+pub mod set_require_direct_call;
 pub mod set_reward_authority;
 pub mod set_reward_authority_by_super_authority;
 pub mod set_reward_emissions;
+pub mod set_reward_emissions_schedule;
 pub mod set_reward_emissions_super_authority;
+pub mod set_reward_vault_warning_threshold;
 pub mod swap;
+pub mod transfer_position;
 pub mod two_hop_swap;
+pub mod two_hop_swap_with_sol;
+pub mod unregister_pool_name;
 pub mod update_fees_and_rewards;
+pub mod validate_position_bundle;
+pub mod verify_vault_solvency;
+pub mod vote_on_proposal;
+pub mod warm_tick_arrays;
+pub mod withdraw_position_fractions;
+pub mod withdraw_protocol_liquidity;
+pub mod withdraw_vote_position;
 
+pub use auto_adjust_protocol_fee::*;
+pub use batch_close_positions::*;
+pub use cancel_parameter_change::*;
 pub use close_bundled_position::*;
+pub use close_deadline_context::*;
 pub use close_position::*;
 pub use collect_fees::*;
+pub use collect_fees_and_close_position::*;
+pub use collect_position_fraction_fees::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
+pub use create_deadline_context::*;
+pub use create_protocol_proposal::*;
 pub use decrease_liquidity::*;
+pub use decrease_liquidity_and_collect::*;
+pub use decrement_pool_count::*;
 pub use delete_position_bundle::*;
+pub use deploy_protocol_liquidity::*;
+pub use deposit_position_fractions::*;
+pub use disable_protocol_fee_streaming::*;
+pub use enable_protocol_fee_streaming::*;
+pub use execute_parameter_change::*;
+pub use execute_proposal::*;
+pub use expand_position_bundle::*;
+pub use export_fee_growth_checkpoint::*;
+pub use find_next_tick_array_start::*;
+pub use fractionalize_position::*;
+pub use get_bundle_liquidity_snapshot::*;
+pub use get_bundle_positions_sorted::*;
+pub use get_fee_tier_volume::*;
+pub use get_liquidity_distribution::*;
+pub use get_next_initialized_tick_compact::*;
+pub use get_position_health_score::*;
+pub use get_position_history::*;
 pub use increase_liquidity::*;
 pub use initialize_config::*;
 pub use initialize_fee_tier::*;
 pub use initialize_pool::*;
+pub use initialize_pool_with_tick_arrays::*;
 pub use initialize_position_bundle::*;
 pub use initialize_position_bundle_with_metadata::*;
+pub use initialize_position_history::*;
+pub use initialize_position_swap_count::*;
+pub use initialize_protocol_liquidity_manager::*;
 pub use initialize_reward::*;
 pub use initialize_tick_array::*;
+pub use lookup_pool_by_name::*;
+pub use migrate_fee_tier_pool_count::*;
+pub use migrate_pool_max_liquidity::*;
+pub use migrate_pools_config::*;
+pub use migrate_position_to_pool::*;
 pub use open_bundled_position::*;
 pub use open_position::*;
 pub use open_position_with_metadata::*;
+pub use pause_reward_emission::*;
+pub use propose_parameter_change::*;
+pub use reconcile_fee_growth::*;
+pub use reconcile_pool::*;
+pub use redeem_position_fractions::*;
+pub use register_pool_name::*;
+pub use renounce_pool_authority::*;
+pub use repair_position_bundle::*;
+pub use resume_reward_emission::*;
+pub use set_auto_protocol_fee::*;
 pub use set_collect_protocol_fees_authority::*;
 pub use set_default_fee_rate::*;
 pub use set_default_protocol_fee_rate::*;
+pub use set_directional_swap_status::*;
+pub use set_feature_flags::*;
 pub use set_fee_authority::*;
 pub use set_fee_rate::*;
+pub use set_fee_tier_authority::*;
+pub use set_fee_tier_creation_locked::*;
+pub use set_lp_fee_rebate_config::*;
+pub use set_max_referral_fee_rate::*;
+pub use set_max_swap_amount::*;
+pub use set_min_swap_amount::*;
+pub use set_operations_authority::*;
 pub use set_protocol_fee_rate::*;
+pub use set_protocol_fee_rate_bounds::*;
+pub use set_require_direct_call::*;
 pub use set_reward_authority::*;
 pub use set_reward_authority_by_super_authority::*;
 pub use set_reward_emissions::*;
+pub use set_reward_emissions_schedule::*;
 pub use set_reward_emissions_super_authority::*;
+pub use set_reward_vault_warning_threshold::*;
 pub use swap::*;
+pub use transfer_position::*;
 pub use two_hop_swap::*;
+pub use two_hop_swap_with_sol::*;
+pub use unregister_pool_name::*;
 pub use update_fees_and_rewards::*;
+pub use validate_position_bundle::*;
+pub use verify_vault_solvency::*;
+pub use vote_on_proposal::*;
+pub use warm_tick_arrays::*;
+pub use withdraw_position_fractions::*;
+pub use withdraw_protocol_liquidity::*;
+pub use withdraw_vote_position::*;
 pub mod initialize_pools_tokens;
 pub use initialize_pools_tokens::*;
 pub mod v2;
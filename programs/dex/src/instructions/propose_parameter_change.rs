@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ParameterChangeProposedEvent, state::*, util::to_timestamp_u64};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ProposeParameterChange<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(init,
+      payer = fee_authority,
+      seeds = [b"param_change", pools_config.key().as_ref(),
+               nonce.to_le_bytes().as_ref()],
+      bump,
+      space = ProtocolParameterChange::LEN)]
+    pub parameter_change: Account<'info, ProtocolParameterChange>,
+
+    // The target of pool-level changes
+    #[account(has_one = pools_config)]
+    pub pool: Option<Box<Account<'info, Pool>>>,
+
+    #[account(mut, address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeParameterChange>,
+    nonce: u64,
+    change_type: u8,
+    new_value: u64,
+) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_feature_enabled(FEATURE_PARAMETER_CHANGES)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let pools_config = ctx.accounts.pools_config.key();
+    let pool = ctx.accounts.pool.as_ref().map(|pool| pool.key());
+    let parameter_change = &mut ctx.accounts.parameter_change;
+    parameter_change.initialize(
+        pools_config,
+        nonce,
+        change_type,
+        pool,
+        new_value,
+        ctx.accounts.fee_authority.key(),
+        timestamp,
+    )?;
+
+    emit!(ParameterChangeProposedEvent {
+        parameter_change: parameter_change.key(),
+        pools_config,
+        pool: parameter_change.pool,
+        change_type,
+        new_value,
+        execute_after: parameter_change.execute_after,
+    });
+
+    Ok(())
+}
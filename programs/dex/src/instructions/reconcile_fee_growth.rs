@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::ErrorCode,
+    events::{FeeGrowthDriftDetectedEvent, FeeGrowthReconciledEvent},
+    manager::pool_manager::*,
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ReconcileFeeGrowth<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(address = pool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    // remaining accounts
+    // - positions of pool
+}
+
+/*
+  Compares the vault balances of a pool against the principal and fees owed to the given positions
+  plus the protocol fees owed, and reports the drift of each vault. No state is modified.
+*/
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ReconcileFeeGrowth<'info>>,
+    max_drift_bps: u16,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    let mut expected_vault_balance_a = pool.protocol_fee_owed_a;
+    let mut expected_vault_balance_b = pool.protocol_fee_owed_b;
+    for account_info in ctx.remaining_accounts.iter() {
+        let position: Account<Position> = Account::try_from(account_info)?;
+        if position.pool != pool.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+
+        let (claim_a, claim_b) = position_vault_claims(pool, &position)?;
+        expected_vault_balance_a = expected_vault_balance_a
+            .checked_add(claim_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        expected_vault_balance_b = expected_vault_balance_b
+            .checked_add(claim_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+    }
+
+    emit_reconciliation(
+        pool.key(),
+        pool.token_mint_a,
+        expected_vault_balance_a,
+        ctx.accounts.token_vault_a.amount,
+        max_drift_bps,
+    );
+    emit_reconciliation(
+        pool.key(),
+        pool.token_mint_b,
+        expected_vault_balance_b,
+        ctx.accounts.token_vault_b.amount,
+        max_drift_bps,
+    );
+
+    Ok(())
+}
+
+fn emit_reconciliation(
+    pool: Pubkey,
+    token: Pubkey,
+    expected_vault_balance: u64,
+    actual_vault_balance: u64,
+    max_drift_bps: u16,
+) {
+    let drift_bps = calculate_drift_bps(expected_vault_balance, actual_vault_balance);
+    if drift_bps > max_drift_bps {
+        emit!(FeeGrowthDriftDetectedEvent {
+            pool,
+            token,
+            expected_vault_balance,
+            actual_vault_balance,
+            drift_bps,
+        });
+    } else {
+        emit!(FeeGrowthReconciledEvent {
+            pool,
+            token,
+            expected_vault_balance,
+            actual_vault_balance,
+            drift_bps,
+        });
+    }
+}
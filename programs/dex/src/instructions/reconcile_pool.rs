@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::ErrorCode, events::PoolReconciliationEvent, manager::pool_manager::*, state::*,
+    util::get_withheld_transfer_fee,
+};
+
+#[derive(Accounts)]
+pub struct ReconcilePool<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(address = pool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    // remaining accounts
+    // - positions of pool
+}
+
+/*
+  Compares the vault balances of a pool against the principal and fees owed to the given positions
+  plus the protocol fees owed. Transfer fees withheld in Token-2022 vaults are not part of the vault
+  balances and are reported separately. No state is modified.
+*/
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ReconcilePool<'info>>,
+    strict: bool,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    let mut positions = Vec::with_capacity(ctx.remaining_accounts.len());
+    for account_info in ctx.remaining_accounts.iter() {
+        let position: Account<Position> = Account::try_from(account_info)?;
+        if position.pool != pool.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+        positions.push(position.into_inner());
+    }
+
+    let result = calculate_pool_reconciliation(
+        pool,
+        &positions,
+        ctx.accounts.token_vault_a.amount,
+        ctx.accounts.token_vault_b.amount,
+    )?;
+
+    emit!(PoolReconciliationEvent {
+        pool: pool.key(),
+        expected_reserve_a: result.expected_reserve_a,
+        expected_reserve_b: result.expected_reserve_b,
+        actual_reserve_a: result.actual_reserve_a,
+        actual_reserve_b: result.actual_reserve_b,
+        delta_a: result.delta_a(),
+        delta_b: result.delta_b(),
+        withheld_transfer_fee_a: get_withheld_transfer_fee(&ctx.accounts.token_vault_a)?,
+        withheld_transfer_fee_b: get_withheld_transfer_fee(&ctx.accounts.token_vault_b)?,
+    });
+
+    if strict && !result.is_reconciled() {
+        return Err(ErrorCode::PoolReserveMismatch.into());
+    }
+
+    set_return_data(&result.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_2022;
+use anchor_spl::token_interface::{self, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::{
+    state::*,
+    util::{
+        burn_position_fractions, close_position_fraction_escrow,
+        transfer_position_token_from_fraction_vault_and_close,
+    },
+};
+
+#[derive(Accounts)]
+pub struct RedeemPositionFractions<'info> {
+    pub fraction_authority: Signer<'info>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(mut,
+      close = receiver,
+      seeds = [b"position_fraction_vault", position_fraction_vault.position_mint.as_ref()],
+      bump,
+    )]
+    pub position_fraction_vault: Box<Account<'info, PositionFractionVault>>,
+
+    #[account(mut,
+      constraint = vault_position_token_account.mint == position_fraction_vault.position_mint,
+      constraint = vault_position_token_account.owner == position_fraction_vault.key(),
+    )]
+    pub vault_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = position_token_account.mint == position_fraction_vault.position_mint)]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = position_fraction_vault.fraction_mint)]
+    pub fraction_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+
+    #[account(mut,
+      constraint = fraction_token_account.mint == position_fraction_vault.fraction_mint,
+      constraint = fraction_token_account.owner == fraction_authority.key(),
+    )]
+    pub fraction_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(mut,
+      constraint = fraction_escrow.mint == position_fraction_vault.fraction_mint,
+      constraint = fraction_escrow.owner == position_fraction_vault.key(),
+    )]
+    pub fraction_escrow: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    #[account(address = token_2022::ID)]
+    pub fraction_token_program: Interface<'info, TokenInterface>,
+}
+
+/*
+  Burns all fractions of a fractionalized position and returns the position NFT. Fees of the
+  position which were not collected by fraction holders remain in the position.
+*/
+pub fn handler(ctx: Context<RedeemPositionFractions>) -> Result<()> {
+    let total_shares = ctx.accounts.position_fraction_vault.total_shares;
+    // Shares deposited to earn fees are held by the escrow, so holding every share also means
+    // that all fees accrued by fraction holders have been paid out.
+    if ctx.accounts.fraction_token_account.amount != total_shares {
+        return Err(ErrorCode::IncompleteFractionOwnership.into());
+    }
+
+    burn_position_fractions(
+        &ctx.accounts.fraction_authority,
+        &ctx.accounts.fraction_mint,
+        &ctx.accounts.fraction_token_account,
+        &ctx.accounts.fraction_token_program,
+        total_shares,
+    )?;
+
+    transfer_position_token_from_fraction_vault_and_close(
+        &ctx.accounts.position_fraction_vault,
+        &ctx.accounts.receiver,
+        &ctx.accounts.vault_position_token_account,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )?;
+
+    close_position_fraction_escrow(
+        &ctx.accounts.position_fraction_vault,
+        &ctx.accounts.receiver,
+        &ctx.accounts.fraction_escrow,
+        &ctx.accounts.fraction_token_program,
+    )
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events,
+    state::{pool_name_seed, Pool, PoolNameEntry, PoolsConfig, FEATURE_POOL_NAME_REGISTRY},
+    util::to_timestamp_u64,
+};
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct RegisterPoolName<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(has_one = pools_config)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    // init fails if the name is already registered in the pools config
+    #[account(init,
+      payer = funder,
+      seeds = [b"pool_name", pools_config.key().as_ref(),
+               pool_name_seed(&name).as_ref()],
+      bump,
+      space = PoolNameEntry::LEN)]
+    pub pool_name_entry: Box<Account<'info, PoolNameEntry>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterPoolName>, name: String) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_feature_enabled(FEATURE_POOL_NAME_REGISTRY)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let pool_name_entry = &mut ctx.accounts.pool_name_entry;
+    pool_name_entry.initialize(
+        ctx.accounts.pools_config.key(),
+        ctx.accounts.pool.key(),
+        &name,
+        timestamp,
+    )?;
+
+    emit!(events::PoolNameRegisteredEvent {
+        pool: ctx.accounts.pool.key(),
+        name: pool_name_entry.name,
+        pools_config: ctx.accounts.pools_config.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    errors::ErrorCode, events::PositionBundleRepairedEvent,
+    instructions::validate_position_bundle::is_orphaned_bundled_position, state::*,
+    util::verify_position_bundle_authority,
+};
+
+#[derive(Accounts)]
+pub struct RepairPositionBundle<'info> {
+    #[account(mut)]
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+
+    #[account(
+        constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
+        constraint = position_bundle_token_account.amount == 1
+    )]
+    pub position_bundle_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub position_bundle_authority: Signer<'info>,
+    // remaining accounts
+    // - bundled position addresses of bundle_indexes, in the same order
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, RepairPositionBundle<'info>>,
+    bundle_indexes: Vec<u16>,
+) -> Result<()> {
+    // Allow delegation
+    verify_position_bundle_authority(
+        &ctx.accounts.position_bundle_token_account,
+        &ctx.accounts.position_bundle_authority,
+    )?;
+
+    if bundle_indexes.len() != ctx.remaining_accounts.len() {
+        return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+    }
+
+    let position_bundle = &mut ctx.accounts.position_bundle;
+    for (bundle_index, account_info) in bundle_indexes.iter().zip(ctx.remaining_accounts.iter()) {
+        if account_info.key() != position_bundle.bundled_position_address(*bundle_index) {
+            return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+        }
+        if !is_orphaned_bundled_position(account_info) {
+            return Err(ErrorCode::BundledPositionNotOrphaned.into());
+        }
+        position_bundle.close_bundled_position(*bundle_index)?;
+    }
+
+    emit!(PositionBundleRepairedEvent {
+        position_bundle: position_bundle.key(),
+        cleared_bundle_indexes: bundle_indexes,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::events;
+use crate::manager::pool_manager::next_pool_reward_infos;
+use crate::state::Pool;
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct ResumeRewardEmission<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ResumeRewardEmission>, reward_index: u8) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    let next_reward_infos = next_pool_reward_infos(pool, timestamp)?;
+
+    ctx.accounts.pool.update_reward_paused(
+        reward_index as usize,
+        next_reward_infos,
+        timestamp,
+        false,
+    )?;
+
+    emit!(events::RewardEmissionResumedEvent {
+        pool: ctx.accounts.pool.key(),
+        reward_index,
+        resumed_at: timestamp,
+        reward_authority: ctx.accounts.reward_authority.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, PoolsConfig};
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+pub struct SetAutoProtocolFee<'info> {
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetAutoProtocolFee>,
+    enabled: bool,
+    target_protocol_fee_revenue_daily: u64,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    Ok(ctx.accounts.pool.update_auto_protocol_fee(
+        enabled,
+        target_protocol_fee_revenue_daily,
+        timestamp,
+    )?)
+}
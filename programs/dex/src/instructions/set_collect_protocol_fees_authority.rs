@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::PoolsConfig;
+use crate::{
+    events,
+    state::{PoolsConfig, AUTHORITY_TYPE_COLLECT_PROTOCOL_FEES},
+};
 
 #[derive(Accounts)]
 pub struct SetCollectProtocolFeesAuthority<'info> {
@@ -15,10 +18,18 @@ pub struct SetCollectProtocolFeesAuthority<'info> {
 }
 
 pub fn handler(ctx: Context<SetCollectProtocolFeesAuthority>) -> Result<()> {
-    Ok(ctx
-        .accounts
+    let old_authority = ctx.accounts.pools_config.collect_protocol_fees_authority;
+    let new_authority = ctx.accounts.new_collect_protocol_fees_authority.key();
+    ctx.accounts
         .pools_config
-        .update_collect_protocol_fees_authority(
-            ctx.accounts.new_collect_protocol_fees_authority.key(),
-        ))
+        .update_collect_protocol_fees_authority(new_authority);
+
+    emit!(events::AuthorityRotatedEvent {
+        config: ctx.accounts.pools_config.key(),
+        authority_type: AUTHORITY_TYPE_COLLECT_PROTOCOL_FEES,
+        old_authority,
+        new_authority,
+    });
+
+    Ok(())
 }
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, PoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetDirectionalSwapStatus<'info> {
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetDirectionalSwapStatus>,
+    swap_a_to_b_enabled: bool,
+    swap_b_to_a_enabled: bool,
+) -> Result<()> {
+    Ok(ctx
+        .accounts
+        .pool
+        .update_directional_swap_status(swap_a_to_b_enabled, swap_b_to_a_enabled)?)
+}
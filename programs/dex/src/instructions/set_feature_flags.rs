@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PoolsConfig;
+
+#[derive(Accounts)]
+pub struct SetFeatureFlags<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeatureFlags>, feature_flags: u64) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .update_feature_flags(feature_flags);
+    Ok(())
+}
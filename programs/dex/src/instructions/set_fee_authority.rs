@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
 
-use crate::state::PoolsConfig;
+use crate::{
+    events,
+    state::{PoolsConfig, AUTHORITY_TYPE_FEE},
+};
 
 #[derive(Accounts)]
 pub struct SetFeeAuthority<'info> {
@@ -16,8 +20,22 @@ pub struct SetFeeAuthority<'info> {
 
 /// Set the fee authority. Only the current fee authority has permission to invoke this instruction.
 pub fn handler(ctx: Context<SetFeeAuthority>) -> Result<()> {
-    Ok(ctx
-        .accounts
+    ctx.accounts
         .pools_config
-        .update_fee_authority(ctx.accounts.new_fee_authority.key()))
+        .verify_direct_call(get_stack_height())?;
+
+    let old_authority = ctx.accounts.pools_config.fee_authority;
+    let new_authority = ctx.accounts.new_fee_authority.key();
+    ctx.accounts
+        .pools_config
+        .update_fee_authority(new_authority);
+
+    emit!(events::AuthorityRotatedEvent {
+        config: ctx.accounts.pools_config.key(),
+        authority_type: AUTHORITY_TYPE_FEE,
+        old_authority,
+        new_authority,
+    });
+
+    Ok(())
 }
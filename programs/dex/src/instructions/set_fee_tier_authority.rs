@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
+
+use crate::{
+    events,
+    state::{PoolsConfig, AUTHORITY_TYPE_FEE_TIER},
+};
+
+#[derive(Accounts)]
+pub struct SetFeeTierAuthority<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be new authority can be arbitrary
+    pub new_fee_tier_authority: UncheckedAccount<'info>,
+}
+
+/// Set the fee tier authority. Only the fee authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetFeeTierAuthority>) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_direct_call(get_stack_height())?;
+
+    let old_fee_tier_authority = ctx.accounts.pools_config.fee_tier_authority;
+    let new_fee_tier_authority = ctx.accounts.new_fee_tier_authority.key();
+    ctx.accounts
+        .pools_config
+        .update_fee_tier_authority(new_fee_tier_authority);
+
+    emit!(events::FeeTierAuthoritySetEvent {
+        pools_config: ctx.accounts.pools_config.key(),
+        old_fee_tier_authority,
+        new_fee_tier_authority,
+    });
+    emit!(events::AuthorityRotatedEvent {
+        config: ctx.accounts.pools_config.key(),
+        authority_type: AUTHORITY_TYPE_FEE_TIER,
+        old_authority: old_fee_tier_authority,
+        new_authority: new_fee_tier_authority,
+    });
+
+    Ok(())
+}
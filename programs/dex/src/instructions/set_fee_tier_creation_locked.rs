@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
+
+use crate::{events, state::PoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetFeeTierCreationLocked<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+/// Lock or unlock the creation of fee tiers to the fee tier authority. Only the fee authority has
+/// permission to invoke this instruction.
+pub fn handler(
+    ctx: Context<SetFeeTierCreationLocked>,
+    fee_tier_creation_locked: bool,
+) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_direct_call(get_stack_height())?;
+
+    ctx.accounts
+        .pools_config
+        .update_fee_tier_creation_locked(fee_tier_creation_locked);
+
+    emit!(events::FeeTierCreationLockChangedEvent {
+        pools_config: ctx.accounts.pools_config.key(),
+        fee_tier_creation_locked,
+        fee_tier_authority: ctx.accounts.pools_config.fee_tier_authority,
+    });
+
+    Ok(())
+}
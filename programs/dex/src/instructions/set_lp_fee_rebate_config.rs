@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetLpFeeRebateConfig<'info> {
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    #[account(init_if_needed,
+      payer = funder,
+      space = LpFeeRebateConfig::LEN,
+      seeds = [b"lp_fee_rebate_config", pool.key().as_ref()],
+      bump,
+    )]
+    pub lp_fee_rebate_config: Box<Account<'info, LpFeeRebateConfig>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetLpFeeRebateConfig>,
+    swap_count_threshold: u64,
+    rebate_bps: u16,
+) -> Result<()> {
+    ctx.accounts.pool.verify_authority_not_renounced()?;
+    ctx.accounts.lp_fee_rebate_config.update(
+        ctx.accounts.pool.key(),
+        swap_count_threshold,
+        rebate_bps,
+    )?;
+    ctx.accounts.pool.reset_swap_count_since_reset();
+    Ok(())
+}
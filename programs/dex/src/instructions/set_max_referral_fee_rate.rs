@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PoolsConfig;
+
+#[derive(Accounts)]
+pub struct SetMaxReferralFeeRate<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMaxReferralFeeRate>, max_referral_fee_rate: u16) -> Result<()> {
+    Ok(ctx
+        .accounts
+        .pools_config
+        .update_max_referral_fee_rate(max_referral_fee_rate)?)
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Pool, PoolsConfig};
+
+#[derive(Accounts)]
+pub struct SetMinSwapAmount<'info> {
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(mut, has_one = pools_config)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMinSwapAmount>, min_swap_amount: u64) -> Result<()> {
+    Ok(ctx.accounts.pool.update_min_swap_amount(min_swap_amount)?)
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
+
+use crate::{
+    events,
+    state::{PoolsConfig, AUTHORITY_TYPE_OPERATIONS},
+};
+
+#[derive(Accounts)]
+pub struct SetOperationsAuthority<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    /// CHECK: safe, the account that will be new authority can be arbitrary
+    pub new_operations_authority: UncheckedAccount<'info>,
+}
+
+/// Set the operations authority. Only the fee authority has permission to invoke this instruction.
+pub fn handler(ctx: Context<SetOperationsAuthority>) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_direct_call(get_stack_height())?;
+
+    let old_operations_authority = ctx.accounts.pools_config.operations_authority;
+    let new_operations_authority = ctx.accounts.new_operations_authority.key();
+    ctx.accounts
+        .pools_config
+        .update_operations_authority(new_operations_authority);
+
+    emit!(events::OperationsAuthoritySetEvent {
+        pools_config: ctx.accounts.pools_config.key(),
+        old_operations_authority,
+        new_operations_authority,
+    });
+    emit!(events::AuthorityRotatedEvent {
+        config: ctx.accounts.pools_config.key(),
+        authority_type: AUTHORITY_TYPE_OPERATIONS,
+        old_authority: old_operations_authority,
+        new_authority: new_operations_authority,
+    });
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
 
 use crate::state::{Pool, PoolsConfig};
 
@@ -14,6 +15,14 @@ pub struct SetProtocolFeeRate<'info> {
 }
 
 pub fn handler(ctx: Context<SetProtocolFeeRate>, protocol_fee_rate: u16) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_direct_call(get_stack_height())?;
+
+    ctx.accounts
+        .pools_config
+        .verify_protocol_fee_rate(protocol_fee_rate)?;
+
     Ok(ctx
         .accounts
         .pool
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ParameterChangeProposedEvent, state::*, util::to_timestamp_u64};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct SetProtocolFeeRateBounds<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(init,
+      payer = fee_authority,
+      seeds = [b"param_change", pools_config.key().as_ref(),
+               nonce.to_le_bytes().as_ref()],
+      bump,
+      space = ProtocolParameterChange::LEN)]
+    pub parameter_change: Account<'info, ProtocolParameterChange>,
+
+    #[account(mut, address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/*
+  Schedules new protocol fee rate bounds. The bounds are a parameter change, so they only apply
+  once execute_parameter_change is called after MIN_GOVERNANCE_DELAY_SECONDS.
+*/
+pub fn handler(
+    ctx: Context<SetProtocolFeeRateBounds>,
+    nonce: u64,
+    min_protocol_fee_rate: u16,
+    max_protocol_fee_rate: u16,
+) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_feature_enabled(FEATURE_PARAMETER_CHANGES)?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let pools_config = ctx.accounts.pools_config.key();
+    let new_value = pack_protocol_fee_rate_bounds(min_protocol_fee_rate, max_protocol_fee_rate);
+    let parameter_change = &mut ctx.accounts.parameter_change;
+    parameter_change.initialize(
+        pools_config,
+        nonce,
+        PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS,
+        None,
+        new_value,
+        ctx.accounts.fee_authority.key(),
+        timestamp,
+    )?;
+
+    emit!(ParameterChangeProposedEvent {
+        parameter_change: parameter_change.key(),
+        pools_config,
+        pool: parameter_change.pool,
+        change_type: PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS,
+        new_value,
+        execute_after: parameter_change.execute_after,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PoolsConfig;
+
+#[derive(Accounts)]
+pub struct SetRequireDirectCall<'info> {
+    #[account(mut)]
+    pub pools_config: Account<'info, PoolsConfig>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetRequireDirectCall>, require_direct_call: bool) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .update_require_direct_call(require_direct_call);
+    Ok(())
+}
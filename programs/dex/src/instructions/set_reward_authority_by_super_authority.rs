@@ -20,6 +20,8 @@ pub struct SetRewardAuthorityBySuperAuthority<'info> {
 /// Set the pool reward authority at the provided `reward_index`.
 /// Only the current reward emissions super authority has permission to invoke this instruction.
 pub fn handler(ctx: Context<SetRewardAuthorityBySuperAuthority>, reward_index: u8) -> Result<()> {
+    ctx.accounts.pool.verify_authority_not_renounced()?;
+
     Ok(ctx.accounts.pool.update_reward_authority(
         reward_index as usize,
         ctx.accounts.new_reward_authority.key(),
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::events;
+use crate::manager::pool_manager::next_pool_reward_infos;
+use crate::state::*;
+use crate::util::to_timestamp_u64;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardEmissionsSchedule<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+/*
+  Replaces the emissions schedule of a reward. Rewards accrue at the previous rates up to now, and
+  the rate of the reward is set to the rate of the new schedule active now.
+*/
+pub fn handler(
+    ctx: Context<SetRewardEmissionsSchedule>,
+    reward_index: u8,
+    points: Vec<RewardEmissionsSchedulePoint>,
+    hold_final_rate: bool,
+) -> Result<()> {
+    let timestamp = to_timestamp_u64(Clock::get()?.unix_timestamp)?;
+    let pool = &mut ctx.accounts.pool;
+
+    let mut schedule = RewardEmissionsSchedule::default();
+    schedule.update_points(&points, hold_final_rate)?;
+
+    let next_reward_infos = next_pool_reward_infos(pool, timestamp)?;
+    pool.update_emissions_schedule(reward_index as usize, next_reward_infos, timestamp, schedule)?;
+
+    emit!(events::RewardEmissionsScheduleSetEvent {
+        pool: pool.key(),
+        reward_index,
+        num_points: schedule.num_points,
+        hold_final_rate,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::PoolsConfig;
+use crate::{
+    events,
+    state::{PoolsConfig, AUTHORITY_TYPE_REWARD_EMISSIONS_SUPER},
+};
 
 #[derive(Accounts)]
 pub struct SetRewardEmissionsSuperAuthority<'info> {
@@ -15,10 +18,18 @@ pub struct SetRewardEmissionsSuperAuthority<'info> {
 }
 
 pub fn handler(ctx: Context<SetRewardEmissionsSuperAuthority>) -> Result<()> {
-    Ok(ctx
-        .accounts
+    let old_authority = ctx.accounts.pools_config.reward_emissions_super_authority;
+    let new_authority = ctx.accounts.new_reward_emissions_super_authority.key();
+    ctx.accounts
         .pools_config
-        .update_reward_emissions_super_authority(
-            ctx.accounts.new_reward_emissions_super_authority.key(),
-        ))
+        .update_reward_emissions_super_authority(new_authority);
+
+    emit!(events::AuthorityRotatedEvent {
+        config: ctx.accounts.pools_config.key(),
+        authority_type: AUTHORITY_TYPE_REWARD_EMISSIONS_SUPER,
+        old_authority,
+        new_authority,
+    });
+
+    Ok(())
 }
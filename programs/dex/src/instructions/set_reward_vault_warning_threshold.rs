@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardVaultWarningThreshold<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(address = pool.reward_infos[reward_index as usize].authority)]
+    pub reward_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetRewardVaultWarningThreshold>,
+    reward_index: u8,
+    threshold_bps: u16,
+) -> Result<()> {
+    ctx.accounts
+        .pool
+        .update_reward_vault_warning_threshold(reward_index as usize, threshold_bps)
+}
@@ -2,11 +2,10 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::{
-    errors::ErrorCode,
     events,
     manager::swap_manager::*,
     state::{Pool, TickArray},
-    util::{to_timestamp_u64, update_and_swap_pool, SwapTickSequence},
+    util::{to_timestamp_u64, update_and_swap_pool, verify_tick_array_accounts, SwapTickSequence},
 };
 
 #[derive(Accounts)]
@@ -51,11 +50,17 @@ pub fn handler(
     let clock = Clock::get()?;
     // Update the global reward growth which increases as a function of time.
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
-    let mut swap_tick_sequence = SwapTickSequence::new(
-        ctx.accounts.tick_array_0.load_mut()?,
-        ctx.accounts.tick_array_1.load_mut().ok(),
-        ctx.accounts.tick_array_2.load_mut().ok(),
-    );
+    pool.verify_swap_amount(amount)?;
+    verify_tick_array_accounts(&[
+        ctx.accounts.tick_array_0.key(),
+        ctx.accounts.tick_array_1.key(),
+        ctx.accounts.tick_array_2.key(),
+    ])?;
+    let mut swap_tick_sequence = SwapTickSequence::from_accounts(
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+    )?;
 
     let swap_update = swap(
         &pool,
@@ -67,19 +72,12 @@ pub fn handler(
         timestamp,
     )?;
 
-    if amount_specified_is_input {
-        if (a_to_b && other_amount_threshold > swap_update.amount_b)
-            || (!a_to_b && other_amount_threshold > swap_update.amount_a)
-        {
-            return Err(ErrorCode::AmountOutBelowMinimum.into());
-        }
-    } else {
-        if (a_to_b && other_amount_threshold < swap_update.amount_a)
-            || (!a_to_b && other_amount_threshold < swap_update.amount_b)
-        {
-            return Err(ErrorCode::AmountInAboveMaximum.into());
-        }
-    }
+    check_other_amount_threshold(
+        &swap_update,
+        other_amount_threshold,
+        amount_specified_is_input,
+        a_to_b,
+    )?;
 
     update_and_swap_pool(
         pool,
@@ -106,7 +104,8 @@ pub fn handler(
         sqrt_price_x64: pool.sqrt_price,
         liquidity: pool.liquidity,
         tick: pool.tick_current_index,
-        fee: swap_update.fee
+        fee: swap_update.fee,
+        tick_crossed_count: swap_update.tick_crossed_count,
     });
 
     Ok(())
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::{
+    events,
+    manager::liquidity_manager::calculate_fee_and_reward_growths,
+    state::*,
+    util::{
+        record_position_history, to_timestamp_u64, transfer_from_owner_to_vault,
+        transfer_from_vault_to_owner, verify_position_authority,
+    },
+};
+
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(mut,
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = destination_position_token_account.mint == position.position_mint,
+        constraint = destination_position_token_account.key() != position_token_account.key()
+    )]
+    pub destination_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_a.mint == pool.token_mint_a)]
+    pub token_owner_account_a: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = pool.token_vault_a)]
+    pub token_vault_a: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut, constraint = token_owner_account_b.mint == pool.token_mint_b)]
+    pub token_owner_account_b: Box<Account<'info, TokenAccount>>,
+    #[account(mut, address = pool.token_vault_b)]
+    pub token_vault_b: Box<Account<'info, TokenAccount>>,
+
+    #[account(has_one = pool)]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+    #[account(has_one = pool)]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+
+    #[account(mut, has_one = position)]
+    pub position_history: Option<Box<Account<'info, PositionHistory>>>,
+}
+
+/*
+  Transfers a position NFT after paying the fees accrued by the position to the current owner, so
+  that the new owner starts without fees owed. Rewards have to be collected with collect_reward
+  before, since their vaults are not part of this instruction.
+*/
+pub fn handler(ctx: Context<TransferPosition>) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let pool = &mut ctx.accounts.pool;
+    let position = &mut ctx.accounts.position;
+
+    // Fees and rewards of a position without liquidity were settled when the liquidity was removed
+    if position.liquidity > 0 {
+        let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+            pool,
+            position,
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            timestamp,
+        )?;
+        pool.update_rewards(reward_infos, timestamp);
+        position.update(&position_update);
+    }
+
+    let (fee_owed_a, fee_owed_b) = settle_position_for_transfer(position)?;
+    record_position_history(
+        &mut ctx.accounts.position_history,
+        PositionHistoryEntry::new(
+            POSITION_HISTORY_COLLECT_FEES,
+            clock.slot,
+            0,
+            fee_owed_a,
+            fee_owed_b,
+        ),
+    );
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program,
+        fee_owed_a,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program,
+        fee_owed_b,
+    )?;
+
+    transfer_from_owner_to_vault(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.destination_position_token_account,
+        &ctx.accounts.token_program,
+        1,
+    )?;
+
+    emit!(events::PositionTransferredEvent {
+        position: ctx.accounts.position.key(),
+        from: ctx.accounts.position_token_account.owner,
+        to: ctx.accounts.destination_position_token_account.owner,
+        fee_a: fee_owed_a,
+        fee_b: fee_owed_b,
+    });
+
+    Ok(())
+}
+
+// Takes the fees owed by the position for the current owner. Fails if rewards are still owed,
+// since they would pass to the new owner.
+fn settle_position_for_transfer(position: &mut Position) -> Result<(u64, u64)> {
+    if position
+        .reward_infos
+        .iter()
+        .any(|reward_info| reward_info.amount_owed > 0)
+    {
+        return Err(ErrorCode::PositionRewardsNotSettled.into());
+    }
+
+    let fees_owed = (position.fee_owed_a, position.fee_owed_b);
+    position.reset_fees_owed();
+    Ok(fees_owed)
+}
+
+#[cfg(test)]
+mod settle_position_for_transfer_tests {
+    use super::*;
+
+    #[test]
+    fn test_fees_paid_to_previous_owner() {
+        let mut position = Position {
+            liquidity: 1_000,
+            fee_owed_a: 100,
+            fee_owed_b: 200,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            settle_position_for_transfer(&mut position).unwrap(),
+            (100, 200)
+        );
+        // The new owner starts without fees owed
+        assert_eq!((position.fee_owed_a, position.fee_owed_b), (0, 0));
+        assert_eq!(position.liquidity, 1_000);
+    }
+
+    #[test]
+    fn test_rewards_owed_reject_transfer() {
+        let mut position = Position {
+            fee_owed_a: 100,
+            ..Default::default()
+        };
+        position.update_reward_owed(1, 10);
+
+        assert_eq!(
+            settle_position_for_transfer(&mut position).unwrap_err(),
+            ErrorCode::PositionRewardsNotSettled.into()
+        );
+        assert_eq!(position.fee_owed_a, 100);
+    }
+}
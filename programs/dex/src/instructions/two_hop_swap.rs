@@ -6,7 +6,10 @@ use crate::{
     events,
     manager::swap_manager::*,
     state::{Pool, TickArray},
-    util::{to_timestamp_u64, update_and_swap_pool, SwapTickSequence},
+    util::{
+        to_timestamp_u64, update_and_swap_pool, verify_two_hop_tick_array_accounts,
+        SwapTickSequence,
+    },
 };
 
 #[derive(Accounts)]
@@ -70,45 +73,81 @@ pub fn handler(
     a_to_b_two: bool,
     sqrt_price_limit_one: u128,
     sqrt_price_limit_two: u128,
+) -> Result<()> {
+    execute_two_hop_swap(
+        ctx.accounts,
+        amount,
+        other_amount_threshold,
+        amount_specified_is_input,
+        a_to_b_one,
+        a_to_b_two,
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
+        None,
+        0,
+    )
+}
+
+pub fn execute_two_hop_swap(
+    accounts: &mut TwoHopSwap,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
+    intermediate_threshold: Option<u64>,
+    max_tick_crossings: u8,
 ) -> Result<()> {
     let clock = Clock::get()?;
     // Update the global reward growth which increases as a function of time.
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
 
-    let pool_one = &mut ctx.accounts.pool_one;
-    let pool_two = &mut ctx.accounts.pool_two;
+    let pool_one = &mut accounts.pool_one;
+    let pool_two = &mut accounts.pool_two;
 
     // Don't allow swaps on the same pool
     if pool_one.key() == pool_two.key() {
         return Err(ErrorCode::DuplicateTwoHopPool.into());
     }
 
-    let swap_one_output_mint = if a_to_b_one {
-        pool_one.token_mint_b
-    } else {
-        pool_one.token_mint_a
-    };
+    check_two_hop_route(pool_one, pool_two, a_to_b_one, a_to_b_two)?;
 
-    let swap_two_input_mint = if a_to_b_two {
-        pool_two.token_mint_a
+    verify_two_hop_tick_array_accounts(
+        &[
+            accounts.tick_array_one_0.key(),
+            accounts.tick_array_one_1.key(),
+            accounts.tick_array_one_2.key(),
+        ],
+        &[
+            accounts.tick_array_two_0.key(),
+            accounts.tick_array_two_1.key(),
+            accounts.tick_array_two_2.key(),
+        ],
+    )?;
+
+    // The tick crossing limit applies to the hop calculated first, the other hop only swaps the
+    // intermediate amount of the (partial) first calculation
+    let (max_tick_crossings_one, max_tick_crossings_two) = if amount_specified_is_input {
+        (max_tick_crossings, 0)
     } else {
-        pool_two.token_mint_b
+        (0, max_tick_crossings)
     };
-    if swap_one_output_mint != swap_two_input_mint {
-        return Err(ErrorCode::InvalidIntermediaryMint.into());
-    }
 
-    let mut swap_tick_sequence_one = SwapTickSequence::new(
-        ctx.accounts.tick_array_one_0.load_mut()?,
-        ctx.accounts.tick_array_one_1.load_mut().ok(),
-        ctx.accounts.tick_array_one_2.load_mut().ok(),
-    );
+    let mut swap_tick_sequence_one = SwapTickSequence::from_accounts(
+        &accounts.tick_array_one_0,
+        &accounts.tick_array_one_1,
+        &accounts.tick_array_one_2,
+    )?
+    .with_max_tick_crossings(max_tick_crossings_one);
 
-    let mut swap_tick_sequence_two = SwapTickSequence::new(
-        ctx.accounts.tick_array_two_0.load_mut()?,
-        ctx.accounts.tick_array_two_1.load_mut().ok(),
-        ctx.accounts.tick_array_two_2.load_mut().ok(),
-    );
+    let mut swap_tick_sequence_two = SwapTickSequence::from_accounts(
+        &accounts.tick_array_two_0,
+        &accounts.tick_array_two_1,
+        &accounts.tick_array_two_2,
+    )?
+    .with_max_tick_crossings(max_tick_crossings_two);
 
     // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
     // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
@@ -116,6 +155,7 @@ pub fn handler(
         // If the amount specified is input, this means we are doing exact-in
         // and the swap calculations occur from Swap 1 => Swap 2
         // and the swaps occur from Swap 1 => Swap 2
+        pool_one.verify_swap_amount(amount)?;
         let swap_calc_one = swap(
             &pool_one,
             &mut swap_tick_sequence_one,
@@ -126,6 +166,9 @@ pub fn handler(
             timestamp,
         )?;
 
+        // Stop a route whose first hop underdelivers before calculating the second hop
+        check_intermediate_amount(&swap_calc_one, a_to_b_one, intermediate_threshold)?;
+
         // Swap two input is the output of swap one
         let swap_two_input_amount = if a_to_b_one {
             swap_calc_one.amount_b
@@ -133,6 +176,8 @@ pub fn handler(
             swap_calc_one.amount_a
         };
 
+        pool_two.verify_swap_amount(swap_two_input_amount)?;
+
         let swap_calc_two = swap(
             &pool_two,
             &mut swap_tick_sequence_two,
@@ -147,6 +192,7 @@ pub fn handler(
         // If the amount specified is output, this means we need to invert the ordering of the calculations
         // and the swap calculations occur from Swap 2 => Swap 1
         // but the actual swaps occur from Swap 1 => Swap 2 (to ensure that the intermediate token exists in the account)
+        pool_two.verify_swap_amount(amount)?;
         let swap_calc_two = swap(
             &pool_two,
             &mut swap_tick_sequence_two,
@@ -164,6 +210,8 @@ pub fn handler(
             swap_calc_two.amount_b
         };
 
+        pool_one.verify_swap_amount(swap_one_output_amount)?;
+
         let swap_calc_one = swap(
             &pool_one,
             &mut swap_tick_sequence_one,
@@ -176,6 +224,26 @@ pub fn handler(
         (swap_calc_one, swap_calc_two)
     };
 
+    let tick_crossing_limit = if amount_specified_is_input {
+        tick_crossing_limit_reached(
+            &swap_update_one,
+            max_tick_crossings,
+            amount,
+            true,
+            a_to_b_one,
+        )
+        .map(|amounts| (pool_one.key(), amounts))
+    } else {
+        tick_crossing_limit_reached(
+            &swap_update_two,
+            max_tick_crossings,
+            amount,
+            false,
+            a_to_b_two,
+        )
+        .map(|amounts| (pool_two.key(), amounts))
+    };
+
     if amount_specified_is_input {
         // If amount_specified_is_input == true, then we have a variable amount of output
         // The slippage we care about is the output of the second swap.
@@ -204,12 +272,12 @@ pub fn handler(
 
     update_and_swap_pool(
         pool_one,
-        &ctx.accounts.token_authority,
-        &ctx.accounts.token_owner_account_one_a,
-        &ctx.accounts.token_owner_account_one_b,
-        &ctx.accounts.token_vault_one_a,
-        &ctx.accounts.token_vault_one_b,
-        &ctx.accounts.token_program,
+        &accounts.token_authority,
+        &accounts.token_owner_account_one_a,
+        &accounts.token_owner_account_one_b,
+        &accounts.token_vault_one_a,
+        &accounts.token_vault_one_b,
+        &accounts.token_program,
         &swap_update_one,
         a_to_b_one,
         timestamp,
@@ -218,7 +286,7 @@ pub fn handler(
     let amount_b_one = swap_update_one.amount_b;
     emit!(events::SwapEvent {
         pool_state: pool_one.key(),
-        sender: ctx.accounts.token_authority.key(),
+        sender: accounts.token_authority.key(),
         token_account_0: pool_one.token_vault_a,
         token_account_1: pool_one.token_vault_b,
         amount_0: amount_a_one.to_owned(),
@@ -227,17 +295,18 @@ pub fn handler(
         sqrt_price_x64: pool_one.sqrt_price,
         liquidity: pool_one.liquidity,
         tick: pool_one.tick_current_index,
-        fee: swap_update_one.fee
+        fee: swap_update_one.fee,
+        tick_crossed_count: swap_update_one.tick_crossed_count,
     });
 
     update_and_swap_pool(
         pool_two,
-        &ctx.accounts.token_authority,
-        &ctx.accounts.token_owner_account_two_a,
-        &ctx.accounts.token_owner_account_two_b,
-        &ctx.accounts.token_vault_two_a,
-        &ctx.accounts.token_vault_two_b,
-        &ctx.accounts.token_program,
+        &accounts.token_authority,
+        &accounts.token_owner_account_two_a,
+        &accounts.token_owner_account_two_b,
+        &accounts.token_vault_two_a,
+        &accounts.token_vault_two_b,
+        &accounts.token_program,
         &swap_update_two,
         a_to_b_two,
         timestamp,
@@ -246,7 +315,7 @@ pub fn handler(
     let amount_b_two = swap_update_two.amount_b;
     emit!(events::SwapEvent {
         pool_state: pool_two.key(),
-        sender: ctx.accounts.token_authority.key(),
+        sender: accounts.token_authority.key(),
         token_account_0: pool_two.token_vault_a,
         token_account_1: pool_two.token_vault_b,
         amount_0: amount_a_two.to_owned(),
@@ -255,7 +324,25 @@ pub fn handler(
         sqrt_price_x64: pool_two.sqrt_price,
         liquidity: pool_two.liquidity,
         tick: pool_two.tick_current_index,
-        fee: swap_update_two.fee
+        fee: swap_update_two.fee,
+        tick_crossed_count: swap_update_two.tick_crossed_count,
+    });
+    emit!(events::TwoHopSwapEvent {
+        pool_one: pool_one.key(),
+        pool_two: pool_two.key(),
+        sender: accounts.token_authority.key(),
+        fee_one: swap_update_one.fee,
+        fee_two: swap_update_two.fee,
+        protocol_fee_one: swap_update_one.next_protocol_fee,
+        protocol_fee_two: swap_update_two.next_protocol_fee,
     });
+    if let Some((pool, (amount_filled, amount_remaining))) = tick_crossing_limit {
+        emit!(events::SwapTickCrossingLimitReachedEvent {
+            pool,
+            crossings_limit: max_tick_crossings,
+            amount_filled,
+            amount_remaining,
+        });
+    }
     Ok(())
 }
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{spl_token::native_mint, TokenAccount};
+
+use crate::util::{unwrap_sol, wrap_sol};
+
+use super::two_hop_swap::*;
+
+#[derive(Accounts)]
+pub struct TwoHopSwapWithSol<'info> {
+    pub two_hop_swap: TwoHopSwap<'info>,
+
+    // The token authority of the swap, writable to fund the wrapped SOL and receive the unwrapped
+    // SOL. Verified in the handler.
+    #[account(mut)]
+    pub sol_owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// An end of a two-hop route as it was before the instruction
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolRouteAccount {
+    pub key: Pubkey,
+    pub is_native: bool,
+    pub starting_amount: u64,
+}
+
+impl SolRouteAccount {
+    pub fn new(token_account: &TokenAccount, key: Pubkey) -> Self {
+        SolRouteAccount {
+            key,
+            is_native: token_account.mint == native_mint::ID,
+            starting_amount: token_account.amount,
+        }
+    }
+}
+
+// How the SOL ends of a two-hop route are wrapped and unwrapped. The input SOL is wrapped into
+// the input WSOL account before the swap. WSOL accounts that were empty before the instruction are
+// temporary and closed after the swap, so that unused input and the output return to the owner as
+// SOL. WSOL accounts that already held tokens stay open, so WSOL the owner held is not unwrapped
+// with the route, and unused input and the output stay wrapped in them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolRoute {
+    pub wrap_amount: u64,
+    pub unwrap_input: bool,
+    pub unwrap_output: bool,
+}
+
+impl SolRoute {
+    pub fn new(
+        input: SolRouteAccount,
+        output: SolRouteAccount,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+    ) -> Self {
+        let wrap_amount = match (input.is_native, amount_specified_is_input) {
+            (false, _) => 0,
+            (true, true) => amount,
+            // Wrap the maximum input, the unused part is returned if the account is closed
+            (true, false) => other_amount_threshold,
+        };
+        let unwrap_input = input.is_native && input.starting_amount == 0;
+        // A route from SOL back to SOL may use a single WSOL account, which is closed only once
+        let unwrap_output =
+            output.is_native && output.starting_amount == 0 && output.key != input.key;
+        SolRoute {
+            wrap_amount,
+            unwrap_input,
+            unwrap_output,
+        }
+    }
+}
+
+/*
+  Performs a two-hop swap whose input or output mint is native SOL, wrapping the input SOL before
+  the route and unwrapping the output SOL after it in the same instruction. Routes without a native
+  SOL end are swapped as in `two_hop_swap`.
+*/
+pub fn handler(
+    ctx: Context<TwoHopSwapWithSol>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
+    intermediate_threshold: Option<u64>,
+    max_tick_crossings: u8,
+) -> Result<()> {
+    let accounts = &mut ctx.accounts.two_hop_swap;
+    if ctx.accounts.sol_owner.key() != accounts.token_authority.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+    }
+
+    let input_account = if a_to_b_one {
+        accounts.token_owner_account_one_a.clone()
+    } else {
+        accounts.token_owner_account_one_b.clone()
+    };
+    let output_account = if a_to_b_two {
+        accounts.token_owner_account_two_b.clone()
+    } else {
+        accounts.token_owner_account_two_a.clone()
+    };
+
+    let route = SolRoute::new(
+        SolRouteAccount::new(&input_account, input_account.key()),
+        SolRouteAccount::new(&output_account, output_account.key()),
+        amount,
+        other_amount_threshold,
+        amount_specified_is_input,
+    );
+
+    if route.wrap_amount > 0 {
+        wrap_sol(
+            &ctx.accounts.sol_owner,
+            &input_account,
+            &ctx.accounts.system_program,
+            &accounts.token_program,
+            route.wrap_amount,
+        )?;
+    }
+
+    execute_two_hop_swap(
+        accounts,
+        amount,
+        other_amount_threshold,
+        amount_specified_is_input,
+        a_to_b_one,
+        a_to_b_two,
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
+        intermediate_threshold,
+        max_tick_crossings,
+    )?;
+
+    if route.unwrap_input {
+        unwrap_sol(
+            &ctx.accounts.sol_owner,
+            &input_account,
+            &accounts.token_program,
+        )?;
+    }
+    if route.unwrap_output {
+        unwrap_sol(
+            &ctx.accounts.sol_owner,
+            &output_account,
+            &accounts.token_program,
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod sol_route_tests {
+    use super::*;
+
+    fn wsol_account(starting_amount: u64) -> SolRouteAccount {
+        SolRouteAccount {
+            key: Pubkey::new_unique(),
+            is_native: true,
+            starting_amount,
+        }
+    }
+
+    fn token_account() -> SolRouteAccount {
+        SolRouteAccount {
+            key: Pubkey::new_unique(),
+            is_native: false,
+            starting_amount: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_sol_in_token_out() {
+        let route = SolRoute::new(wsol_account(0), token_account(), 1_000_000, 50, true);
+        assert_eq!(
+            route,
+            SolRoute {
+                wrap_amount: 1_000_000,
+                unwrap_input: true,
+                unwrap_output: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sol_in_exact_out_wraps_maximum_input() {
+        let route = SolRoute::new(wsol_account(0), token_account(), 50, 1_000_000, false);
+        assert_eq!(
+            route,
+            SolRoute {
+                wrap_amount: 1_000_000,
+                unwrap_input: true,
+                unwrap_output: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_token_in_sol_out() {
+        let route = SolRoute::new(token_account(), wsol_account(0), 1_000, 700_000, true);
+        assert_eq!(
+            route,
+            SolRoute {
+                wrap_amount: 0,
+                unwrap_input: false,
+                unwrap_output: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_sol_to_sol_with_single_account_closes_once() {
+        let wsol = wsol_account(0);
+        let route = SolRoute::new(wsol, wsol, 1_000_000, 900_000, true);
+        assert_eq!(
+            route,
+            SolRoute {
+                wrap_amount: 1_000_000,
+                unwrap_input: true,
+                unwrap_output: false,
+            }
+        );
+
+        let route = SolRoute::new(wsol_account(0), wsol_account(0), 1_000_000, 900_000, true);
+        assert!(route.unwrap_input && route.unwrap_output);
+    }
+
+    #[test]
+    fn test_held_wsol_not_unwrapped() {
+        // The input is still wrapped into an account that already held WSOL, but it stays open
+        let route = SolRoute::new(wsol_account(5_000), token_account(), 1_000_000, 50, true);
+        assert_eq!(
+            route,
+            SolRoute {
+                wrap_amount: 1_000_000,
+                unwrap_input: false,
+                unwrap_output: false,
+            }
+        );
+
+        let route = SolRoute::new(token_account(), wsol_account(5_000), 1_000, 700_000, true);
+        assert_eq!(route, SolRoute::default());
+
+        let wsol = wsol_account(5_000);
+        let route = SolRoute::new(wsol, wsol, 1_000_000, 900_000, true);
+        assert!(!route.unwrap_input && !route.unwrap_output);
+    }
+
+    #[test]
+    fn test_route_without_sol() {
+        assert_eq!(
+            SolRoute::new(token_account(), token_account(), 1_000, 0, true),
+            SolRoute::default()
+        );
+    }
+}
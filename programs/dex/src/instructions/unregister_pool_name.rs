@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PoolNameEntry, PoolsConfig};
+
+#[derive(Accounts)]
+pub struct UnregisterPoolName<'info> {
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(address = pools_config.fee_authority)]
+    pub fee_authority: Signer<'info>,
+
+    #[account(mut, has_one = pools_config, close = receiver)]
+    pub pool_name_entry: Box<Account<'info, PoolNameEntry>>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+pub fn handler(_ctx: Context<UnregisterPoolName>) -> Result<()> {
+    Ok(())
+}
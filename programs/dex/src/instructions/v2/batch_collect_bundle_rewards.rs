@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::BundleBatchRewardClaimedEvent,
+    state::*,
+    util::{v2::transfer_from_vault_to_owner_v2, verify_position_bundle_authority},
+};
+
+use super::collect_reward::calculate_collect_reward;
+
+// Maximum number of bundled positions whose reward can be collected in a single instruction
+pub const MAX_BATCH_COLLECT_BUNDLE_POSITIONS: usize = 32;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct BatchCollectBundleRewardsV2<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+    #[account(
+        constraint = position_bundle_token_account.mint == position_bundle.position_bundle_mint,
+        constraint = position_bundle_token_account.amount == 1
+    )]
+    pub position_bundle_token_account: Box<Account<'info, token::TokenAccount>>,
+    pub position_bundle_authority: Signer<'info>,
+
+    #[account(mut,
+        constraint = reward_owner_account.mint == pool.reward_infos[reward_index as usize].mint
+    )]
+    pub reward_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.reward_infos[reward_index as usize].mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = reward_mint.to_account_info().owner.clone())]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - bundled positions of position_bundle in pool, in the order of bundle_indexes
+    // - accounts for transfer hook program of reward_mint
+}
+
+/// Collects a reward of all given bundled positions in the pool and transfers the total with a
+/// single transfer. As in `collect_reward_v2`, only the tokens available in the reward vault are
+/// collected, the positions are paid in the order of `bundle_indexes`.
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, BatchCollectBundleRewardsV2<'info>>,
+    reward_index: u8,
+    bundle_indexes: Vec<u16>,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_bundle_authority(
+        &ctx.accounts.position_bundle_token_account,
+        &ctx.accounts.position_bundle_authority,
+    )?;
+
+    let num_positions = bundle_indexes.len();
+    if num_positions == 0
+        || num_positions > MAX_BATCH_COLLECT_BUNDLE_POSITIONS
+        || ctx.remaining_accounts.len() < num_positions
+    {
+        return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+    }
+    // A position passed twice would be paid twice
+    for (i, bundle_index) in bundle_indexes.iter().enumerate() {
+        if bundle_indexes[..i].contains(bundle_index) {
+            return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+        }
+    }
+
+    let (position_accounts, remaining_accounts) = ctx.remaining_accounts.split_at(num_positions);
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookReward],
+    )?;
+
+    let index = reward_index as usize;
+    let position_bundle_mint = ctx.accounts.position_bundle.position_bundle_mint;
+    let pool = &ctx.accounts.pool;
+
+    let mut positions = Vec::with_capacity(num_positions);
+    for (bundle_index, account_info) in bundle_indexes.iter().zip(position_accounts.iter()) {
+        let (bundled_position, _) = Pubkey::find_program_address(
+            &[
+                b"bundled_position".as_ref(),
+                position_bundle_mint.as_ref(),
+                bundle_index.to_string().as_bytes(),
+            ],
+            &crate::ID,
+        );
+        if account_info.key() != bundled_position {
+            return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+        }
+
+        let position: Account<Position> = Account::try_from(account_info)?;
+        if position.pool != pool.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+        positions.push(position);
+    }
+
+    let reward_infos: Vec<PositionRewardInfo> = positions
+        .iter()
+        .map(|position| position.reward_infos[index])
+        .collect();
+    let (updated_amounts_owed, total_amount) =
+        calculate_batch_collect_reward(&reward_infos, ctx.accounts.reward_vault.amount);
+
+    for (position, updated_amount_owed) in positions.iter_mut().zip(updated_amounts_owed) {
+        position.update_reward_owed(index, updated_amount_owed);
+        // Positions loaded from remaining accounts are not persisted by Anchor
+        position.exit(&crate::ID)?;
+    }
+
+    transfer_from_vault_to_owner_v2(
+        pool,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_owner_account,
+        &ctx.accounts.reward_token_program,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_reward,
+        total_amount,
+        transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+    )?;
+
+    emit!(BundleBatchRewardClaimedEvent {
+        bundle: ctx.accounts.position_bundle.key(),
+        reward_index,
+        total_amount,
+        positions_claimed: num_positions as u32,
+    });
+
+    Ok(())
+}
+
+// Pays the positions in order from the vault. Returns the amounts still owed to each position
+// and the total amount to transfer.
+fn calculate_batch_collect_reward(
+    position_rewards: &[PositionRewardInfo],
+    vault_amount: u64,
+) -> (Vec<u64>, u64) {
+    let mut vault_remaining = vault_amount;
+    let mut updated_amounts_owed = Vec::with_capacity(position_rewards.len());
+    for position_reward in position_rewards {
+        let (transfer_amount, updated_amount_owed) =
+            calculate_collect_reward(*position_reward, vault_remaining);
+        vault_remaining -= transfer_amount;
+        updated_amounts_owed.push(updated_amount_owed);
+    }
+    (updated_amounts_owed, vault_amount - vault_remaining)
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::calculate_batch_collect_reward;
+    use crate::state::PositionRewardInfo;
+
+    #[test]
+    fn test_batch_collect_reward_vault_sufficient_tokens() {
+        let (updated_amounts_owed, total_amount) = calculate_batch_collect_reward(
+            &[position_reward(10), position_reward(0), position_reward(5)],
+            100,
+        );
+
+        assert_eq!(updated_amounts_owed, vec![0, 0, 0]);
+        assert_eq!(total_amount, 15);
+    }
+
+    #[test]
+    fn test_batch_collect_reward_vault_insufficient_tokens() {
+        let (updated_amounts_owed, total_amount) = calculate_batch_collect_reward(
+            &[
+                position_reward(10),
+                position_reward(10),
+                position_reward(10),
+            ],
+            15,
+        );
+
+        assert_eq!(updated_amounts_owed, vec![0, 5, 10]);
+        assert_eq!(total_amount, 15);
+    }
+
+    fn position_reward(amount_owed: u64) -> PositionRewardInfo {
+        PositionRewardInfo {
+            amount_owed,
+            ..Default::default()
+        }
+    }
+}
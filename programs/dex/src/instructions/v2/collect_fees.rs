@@ -7,7 +7,11 @@ use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo}
 use crate::{
     constants::transfer_memo,
     state::*,
-    util::{v2::transfer_from_vault_to_owner_v2, verify_position_authority},
+    util::{
+        consume_remaining_deadline_context, exit_remaining_position_history,
+        load_remaining_position_history, record_position_history, to_timestamp_u64,
+        v2::transfer_from_vault_to_owner_v2, verify_position_authority,
+    },
 };
 
 #[derive(Accounts)]
@@ -47,9 +51,11 @@ pub struct CollectFeesV2<'info> {
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - the shared deadline context of the position authority
+    // - the history of the position
 }
 
-pub fn handler<'a, 'b, 'c, 'info>(
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectFeesV2<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
@@ -57,12 +63,34 @@ pub fn handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.position_token_account,
         &ctx.accounts.position_authority,
     )?;
+    // The destinations need not be owned by the position authority, so the fees can be routed to
+    // any account of the pool's mints other than its vaults
+    ctx.accounts.pool.verify_fee_destinations(
+        ctx.accounts.token_owner_account_a.key(),
+        ctx.accounts.token_owner_account_b.key(),
+    )?;
 
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
         &ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::DeadlineContext,
+            AccountsType::PositionHistory,
+        ],
+    )?;
+    consume_remaining_deadline_context(
+        remaining_accounts.deadline_context,
+        &ctx.accounts.position_authority.key(),
+        timestamp,
+    )?;
+    let mut position_history = load_remaining_position_history(
+        remaining_accounts.position_history,
+        &ctx.accounts.position.key(),
     )?;
 
     let position = &mut ctx.accounts.position;
@@ -72,6 +100,16 @@ pub fn handler<'a, 'b, 'c, 'info>(
     let fee_owed_b = position.fee_owed_b;
 
     position.reset_fees_owed();
+    record_position_history(
+        &mut position_history,
+        PositionHistoryEntry::new(
+            POSITION_HISTORY_COLLECT_FEES,
+            clock.slot,
+            0,
+            fee_owed_a,
+            fee_owed_b,
+        ),
+    );
 
     transfer_from_vault_to_owner_v2(
         &ctx.accounts.pool,
@@ -97,5 +135,5 @@ pub fn handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
     )?;
 
-    Ok(())
+    exit_remaining_position_history(&position_history)
 }
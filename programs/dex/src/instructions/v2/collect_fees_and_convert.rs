@@ -0,0 +1,384 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::swap_with_transfer_fee_extension;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::FeesCollectedAndConvertedEvent,
+    manager::swap_manager::swap,
+    math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64},
+    state::*,
+    util::{
+        to_timestamp_u64, update_pool_after_swap, v2::transfer_from_vault_to_owner_v2,
+        verify_position_authority, SwapTickSequence,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(output_token_mint: Pubkey)]
+pub struct CollectFeesAndConvertV2<'info> {
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(address = pool.token_mint_a)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+    #[account(address = pool.token_mint_b)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, constraint = token_owner_account_output.mint == output_token_mint)]
+    pub token_owner_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = pool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = pool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The conversion pool and its vaults are omitted when the fees are converted in `pool` itself
+    #[account(mut)]
+    pub conversion_pool: Option<Box<Account<'info, Pool>>>,
+    #[account(mut)]
+    pub conversion_token_vault_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub conversion_token_vault_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Tick arrays of the conversion pool
+    #[account(mut)]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
+    #[account(mut)]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
+    #[account(mut)]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+
+    #[account(address = token_mint_a.to_account_info().owner.clone())]
+    pub token_program_a: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_b.to_account_info().owner.clone())]
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+}
+
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectFeesAndConvertV2<'info>>,
+    output_token_mint: Pubkey,
+    conversion_pool: Pubkey,
+    min_output_amount: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+    )?;
+
+    let accounts = ctx.accounts;
+    let pool = &mut accounts.pool;
+
+    // The fee token that is not the output token is swapped into the output token
+    let a_to_b = if output_token_mint == pool.token_mint_b {
+        true
+    } else if output_token_mint == pool.token_mint_a {
+        false
+    } else {
+        return Err(ErrorCode::InvalidConversionPool.into());
+    };
+
+    let (
+        output_token_mint,
+        output_token_vault,
+        output_token_program,
+        input_transfer_hook,
+        output_transfer_hook,
+    ) = if a_to_b {
+        (
+            &accounts.token_mint_b,
+            &accounts.token_vault_b,
+            &accounts.token_program_b,
+            &remaining_accounts.transfer_hook_a,
+            &remaining_accounts.transfer_hook_b,
+        )
+    } else {
+        (
+            &accounts.token_mint_a,
+            &accounts.token_vault_a,
+            &accounts.token_program_a,
+            &remaining_accounts.transfer_hook_b,
+            &remaining_accounts.transfer_hook_a,
+        )
+    };
+
+    let position = &mut accounts.position;
+
+    // Store the fees owed to use as transfer amounts.
+    let fee_owed_a = position.fee_owed_a;
+    let fee_owed_b = position.fee_owed_b;
+
+    position.reset_fees_owed();
+
+    let (fee_owed_input, fee_owed_output) = if a_to_b {
+        (fee_owed_a, fee_owed_b)
+    } else {
+        (fee_owed_b, fee_owed_a)
+    };
+
+    let sqrt_price_limit = if a_to_b {
+        MIN_SQRT_PRICE_X64
+    } else {
+        MAX_SQRT_PRICE_X64
+    };
+
+    let converted_output_amount = match accounts.conversion_pool.as_mut() {
+        None => {
+            // Vault-internal path: the input fee token never leaves the vault of the pool, so
+            // only the output token is transferred to the owner.
+            if conversion_pool != pool.key()
+                || accounts.conversion_token_vault_a.is_some()
+                || accounts.conversion_token_vault_b.is_some()
+            {
+                return Err(ErrorCode::InvalidConversionPool.into());
+            }
+
+            let swap_output_amount = if fee_owed_input > 0 {
+                verify_tick_arrays(
+                    [
+                        &accounts.tick_array_0,
+                        &accounts.tick_array_1,
+                        &accounts.tick_array_2,
+                    ],
+                    &pool.key(),
+                )?;
+                let mut swap_tick_sequence = SwapTickSequence::from_accounts(
+                    &accounts.tick_array_0,
+                    &accounts.tick_array_1,
+                    &accounts.tick_array_2,
+                )?;
+
+                pool.verify_swap_amount(fee_owed_input)?;
+                let swap_update = swap(
+                    &pool,
+                    &mut swap_tick_sequence,
+                    fee_owed_input,
+                    sqrt_price_limit,
+                    true,
+                    a_to_b,
+                    timestamp,
+                )?;
+                let (swap_input_amount, swap_output_amount) = if a_to_b {
+                    (swap_update.amount_a, swap_update.amount_b)
+                } else {
+                    (swap_update.amount_b, swap_update.amount_a)
+                };
+                if swap_input_amount != fee_owed_input {
+                    return Err(ErrorCode::ConversionNotFullyFilled.into());
+                }
+
+                update_pool_after_swap(pool, &swap_update, a_to_b, timestamp);
+                swap_output_amount
+            } else {
+                0
+            };
+
+            transfer_from_vault_to_owner_v2(
+                pool,
+                output_token_mint,
+                output_token_vault,
+                &accounts.token_owner_account_output,
+                output_token_program,
+                &accounts.memo_program,
+                output_transfer_hook,
+                fee_owed_output
+                    .checked_add(swap_output_amount)
+                    .ok_or(ErrorCode::TokenMaxExceeded)?,
+                transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+            )?;
+
+            calculate_transfer_fee_excluded_amount(output_token_mint, swap_output_amount)?.amount
+        }
+        Some(conversion) => {
+            let (conversion_token_vault_a, conversion_token_vault_b) = match (
+                accounts.conversion_token_vault_a.as_ref(),
+                accounts.conversion_token_vault_b.as_ref(),
+            ) {
+                (Some(vault_a), Some(vault_b)) => (vault_a, vault_b),
+                _ => return Err(ErrorCode::InvalidConversionPool.into()),
+            };
+            // An adjacent pool trades the same pair with a different tick spacing
+            if conversion.key() != conversion_pool
+                || conversion.key() == pool.key()
+                || conversion.token_mint_a != pool.token_mint_a
+                || conversion.token_mint_b != pool.token_mint_b
+            {
+                return Err(ErrorCode::InvalidConversionPool.into());
+            }
+            if conversion_token_vault_a.key() != conversion.token_vault_a
+                || conversion_token_vault_b.key() != conversion.token_vault_b
+            {
+                return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+            }
+            let (
+                input_token_mint,
+                input_token_vault,
+                input_token_program,
+                conversion_input_vault,
+                conversion_output_vault,
+            ) = if a_to_b {
+                (
+                    &accounts.token_mint_a,
+                    &accounts.token_vault_a,
+                    &accounts.token_program_a,
+                    conversion_token_vault_a,
+                    conversion_token_vault_b,
+                )
+            } else {
+                (
+                    &accounts.token_mint_b,
+                    &accounts.token_vault_b,
+                    &accounts.token_program_b,
+                    conversion_token_vault_b,
+                    conversion_token_vault_a,
+                )
+            };
+
+            transfer_from_vault_to_owner_v2(
+                pool,
+                output_token_mint,
+                output_token_vault,
+                &accounts.token_owner_account_output,
+                output_token_program,
+                &accounts.memo_program,
+                output_transfer_hook,
+                fee_owed_output,
+                transfer_memo::TRANSFER_MEMO_COLLECT_FEES.as_bytes(),
+            )?;
+
+            if fee_owed_input > 0 {
+                verify_tick_arrays(
+                    [
+                        &accounts.tick_array_0,
+                        &accounts.tick_array_1,
+                        &accounts.tick_array_2,
+                    ],
+                    &conversion.key(),
+                )?;
+                let mut swap_tick_sequence = SwapTickSequence::from_accounts(
+                    &accounts.tick_array_0,
+                    &accounts.tick_array_1,
+                    &accounts.tick_array_2,
+                )?;
+
+                conversion.verify_swap_amount(fee_owed_input)?;
+                let swap_update = swap_with_transfer_fee_extension(
+                    &conversion,
+                    &accounts.token_mint_a,
+                    &accounts.token_mint_b,
+                    &mut swap_tick_sequence,
+                    fee_owed_input,
+                    sqrt_price_limit,
+                    true,
+                    a_to_b,
+                    timestamp,
+                )?;
+                let (swap_input_amount, swap_output_amount) = if a_to_b {
+                    (swap_update.amount_a, swap_update.amount_b)
+                } else {
+                    (swap_update.amount_b, swap_update.amount_a)
+                };
+                if swap_input_amount != fee_owed_input {
+                    return Err(ErrorCode::ConversionNotFullyFilled.into());
+                }
+
+                update_pool_after_swap(conversion, &swap_update, a_to_b, timestamp);
+
+                // Transfer from pool to pool
+                transfer_from_vault_to_owner_v2(
+                    pool,
+                    input_token_mint,
+                    input_token_vault,
+                    conversion_input_vault,
+                    input_token_program,
+                    &accounts.memo_program,
+                    input_transfer_hook,
+                    swap_input_amount,
+                    transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+                )?;
+
+                transfer_from_vault_to_owner_v2(
+                    conversion,
+                    output_token_mint,
+                    conversion_output_vault,
+                    &accounts.token_owner_account_output,
+                    output_token_program,
+                    &accounts.memo_program,
+                    output_transfer_hook,
+                    swap_output_amount,
+                    transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+                )?;
+
+                calculate_transfer_fee_excluded_amount(output_token_mint, swap_output_amount)?
+                    .amount
+            } else {
+                0
+            }
+        }
+    };
+
+    if converted_output_amount < min_output_amount {
+        return Err(ErrorCode::AmountOutBelowMinimum.into());
+    }
+
+    emit!(FeesCollectedAndConvertedEvent {
+        position: accounts.position.key(),
+        token_a_collected: fee_owed_a,
+        token_b_collected: fee_owed_b,
+        converted_output_amount,
+        conversion_pool,
+    });
+
+    Ok(())
+}
+
+// The tick arrays are not constrained by has_one since they belong to the conversion pool
+fn verify_tick_arrays(
+    tick_arrays: [&AccountLoader<TickArray>; 3],
+    conversion_pool: &Pubkey,
+) -> Result<()> {
+    if tick_arrays[0].load()?.pool != *conversion_pool {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+    for tick_array in &tick_arrays[1..] {
+        if let Ok(tick_array) = tick_array.load() {
+            if tick_array.pool != *conversion_pool {
+                return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+            }
+        }
+    }
+    Ok(())
+}
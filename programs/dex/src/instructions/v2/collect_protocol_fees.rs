@@ -1,6 +1,7 @@
 use crate::util::{parse_remaining_accounts, AccountsType, RemainingAccountsInfo};
 use crate::{constants::transfer_memo, state::*, util::v2::transfer_from_vault_to_owner_v2};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::get_stack_height;
 use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
@@ -45,8 +46,21 @@ pub fn handler<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFeesV2<'info>>,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
 ) -> Result<()> {
+    ctx.accounts
+        .pools_config
+        .verify_direct_call(get_stack_height())?;
+
     let pool = &ctx.accounts.pool;
 
+    pool.verify_fee_destinations(
+        ctx.accounts.token_destination_a.key(),
+        ctx.accounts.token_destination_b.key(),
+    )?;
+    pool.verify_protocol_fee_destination_balances(
+        ctx.accounts.token_destination_a.amount,
+        ctx.accounts.token_destination_b.amount,
+    )?;
+
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
         &ctx.remaining_accounts,
@@ -78,5 +92,7 @@ pub fn handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_COLLECT_PROTOCOL_FEES.as_bytes(),
     )?;
 
+    // The owed fees are only reset once both transfers succeeded. A failed transfer reverts the
+    // whole instruction, so the owed fees are never lost.
     Ok(ctx.accounts.pool.reset_protocol_fees_owed())
 }
@@ -101,7 +101,10 @@ pub fn handler<'a, 'b, 'c, 'info>(
 }
 
 // TODO: refactor (remove (dup))
-fn calculate_collect_reward(position_reward: PositionRewardInfo, vault_amount: u64) -> (u64, u64) {
+pub(crate) fn calculate_collect_reward(
+    position_reward: PositionRewardInfo,
+    vault_amount: u64,
+) -> (u64, u64) {
     let amount_owed = position_reward.amount_owed;
 
     // Use checked_sub to prevent underflow
@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::swap_with_transfer_fee_extension;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::RewardCollectedAndSwappedEvent,
+    math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64},
+    state::*,
+    util::{
+        to_timestamp_u64, update_pool_after_swap, v2::transfer_from_vault_to_owner_v2,
+        verify_position_authority, SwapTickSequence,
+    },
+};
+
+use super::collect_reward::calculate_collect_reward;
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8, desired_output_mint: Pubkey)]
+pub struct CollectRewardAndSwapV2<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(mut, has_one = pool)]
+    pub position: Box<Account<'info, Position>>,
+    #[account(
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, token::TokenAccount>>,
+
+    #[account(address = pool.reward_infos[reward_index as usize].mint)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, address = pool.reward_infos[reward_index as usize].vault)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Receives the rewards when they are below min_swap_amount
+    #[account(mut, constraint = reward_owner_account.mint == reward_mint.key())]
+    pub reward_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = desired_output_mint)]
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = output_owner_account.mint == desired_output_mint)]
+    pub output_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = conversion_pool.key() != pool.key() @ ErrorCode::InvalidConversionPool)]
+    pub conversion_pool: Box<Account<'info, Pool>>,
+    #[account(mut, address = conversion_pool.token_vault_a)]
+    pub conversion_token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = conversion_pool.token_vault_b)]
+    pub conversion_token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = tick_array_0.load()?.pool == conversion_pool.key())]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
+    #[account(mut, constraint = tick_array_1.load()?.pool == conversion_pool.key())]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
+    #[account(mut, constraint = tick_array_2.load()?.pool == conversion_pool.key())]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+
+    #[account(address = reward_mint.to_account_info().owner.clone())]
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    #[account(address = output_mint.to_account_info().owner.clone())]
+    pub output_token_program: Interface<'info, TokenInterface>,
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - accounts for transfer hook program of reward_mint
+    // - accounts for transfer hook program of output_mint
+}
+
+/// Collects all harvestable tokens for a specified reward and swaps them into
+/// `desired_output_mint` in the conversion pool.
+///
+/// Rewards below `min_swap_amount` are not swapped and are delivered in the reward token.
+/// As in `collect_reward_v2`, only the tokens available in the reward vault are collected.
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, CollectRewardAndSwapV2<'info>>,
+    reward_index: u8,
+    desired_output_mint: Pubkey,
+    min_swap_amount: u64,
+    min_output_amount: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookReward,
+            AccountsType::TransferHookOutput,
+        ],
+    )?;
+
+    let accounts = ctx.accounts;
+    let index = reward_index as usize;
+    let reward_mint = accounts.reward_mint.key();
+
+    // The conversion pool must trade the reward token against the desired output token
+    let conversion = &mut accounts.conversion_pool;
+    let a_to_b = if conversion.token_mint_a == reward_mint
+        && conversion.token_mint_b == desired_output_mint
+    {
+        true
+    } else if conversion.token_mint_b == reward_mint
+        && conversion.token_mint_a == desired_output_mint
+    {
+        false
+    } else {
+        return Err(ErrorCode::InvalidConversionPool.into());
+    };
+
+    let position = &mut accounts.position;
+    let reward_info = match position.reward_infos.get(index) {
+        Some(reward_info) => *reward_info,
+        None => return Err(ErrorCode::InvalidRewardIndex.into()),
+    };
+    let (reward_amount, updated_amount_owed) =
+        calculate_collect_reward(reward_info, accounts.reward_vault.amount);
+    position.update_reward_owed(index, updated_amount_owed);
+
+    if reward_amount == 0 || reward_amount < min_swap_amount {
+        transfer_from_vault_to_owner_v2(
+            &accounts.pool,
+            &accounts.reward_mint,
+            &accounts.reward_vault,
+            &accounts.reward_owner_account,
+            &accounts.reward_token_program,
+            &accounts.memo_program,
+            &remaining_accounts.transfer_hook_reward,
+            reward_amount,
+            transfer_memo::TRANSFER_MEMO_COLLECT_REWARD.as_bytes(),
+        )?;
+
+        let output_amount =
+            calculate_transfer_fee_excluded_amount(&accounts.reward_mint, reward_amount)?.amount;
+        emit!(RewardCollectedAndSwappedEvent {
+            position: position.key(),
+            reward_index,
+            reward_amount,
+            output_mint: reward_mint,
+            output_amount,
+            conversion_rate: conversion_rate_x64(reward_amount, output_amount),
+        });
+        return Ok(());
+    }
+
+    let (token_mint_a, token_mint_b, conversion_input_vault, conversion_output_vault) = if a_to_b {
+        (
+            &accounts.reward_mint,
+            &accounts.output_mint,
+            &accounts.conversion_token_vault_a,
+            &accounts.conversion_token_vault_b,
+        )
+    } else {
+        (
+            &accounts.output_mint,
+            &accounts.reward_mint,
+            &accounts.conversion_token_vault_b,
+            &accounts.conversion_token_vault_a,
+        )
+    };
+    let sqrt_price_limit = if a_to_b {
+        MIN_SQRT_PRICE_X64
+    } else {
+        MAX_SQRT_PRICE_X64
+    };
+
+    conversion.verify_swap_amount(reward_amount)?;
+    let mut swap_tick_sequence = SwapTickSequence::from_accounts(
+        &accounts.tick_array_0,
+        &accounts.tick_array_1,
+        &accounts.tick_array_2,
+    )?;
+    let swap_update = swap_with_transfer_fee_extension(
+        conversion,
+        token_mint_a,
+        token_mint_b,
+        &mut swap_tick_sequence,
+        reward_amount,
+        sqrt_price_limit,
+        true,
+        a_to_b,
+        timestamp,
+    )?;
+    let (swap_input_amount, swap_output_amount) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
+    } else {
+        (swap_update.amount_b, swap_update.amount_a)
+    };
+    if swap_input_amount != reward_amount {
+        return Err(ErrorCode::ConversionNotFullyFilled.into());
+    }
+
+    let output_amount =
+        calculate_transfer_fee_excluded_amount(&accounts.output_mint, swap_output_amount)?.amount;
+    if output_amount < min_output_amount {
+        return Err(ErrorCode::AmountOutBelowMinimum.into());
+    }
+
+    update_pool_after_swap(conversion, &swap_update, a_to_b, timestamp);
+
+    // Transfer from the reward vault to the conversion pool, and from the conversion pool to the
+    // owner
+    transfer_from_vault_to_owner_v2(
+        &accounts.pool,
+        &accounts.reward_mint,
+        &accounts.reward_vault,
+        conversion_input_vault,
+        &accounts.reward_token_program,
+        &accounts.memo_program,
+        &remaining_accounts.transfer_hook_reward,
+        swap_input_amount,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+    transfer_from_vault_to_owner_v2(
+        conversion,
+        &accounts.output_mint,
+        conversion_output_vault,
+        &accounts.output_owner_account,
+        &accounts.output_token_program,
+        &accounts.memo_program,
+        &remaining_accounts.transfer_hook_output,
+        swap_output_amount,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    emit!(RewardCollectedAndSwappedEvent {
+        position: position.key(),
+        reward_index,
+        reward_amount,
+        output_mint: desired_output_mint,
+        output_amount,
+        conversion_rate: conversion_rate_x64(reward_amount, output_amount),
+    });
+
+    Ok(())
+}
+
+// Output tokens received per reward token, as a Q64.64
+fn conversion_rate_x64(reward_amount: u64, output_amount: u64) -> u128 {
+    if reward_amount == 0 {
+        return 0;
+    }
+    ((output_amount as u128) << 64) / reward_amount as u128
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::conversion_rate_x64;
+
+    #[test]
+    fn test_conversion_rate_x64() {
+        assert_eq!(conversion_rate_x64(0, 0), 0);
+        assert_eq!(conversion_rate_x64(100, 100), 1 << 64);
+        assert_eq!(conversion_rate_x64(100, 50), 1 << 63);
+        assert_eq!(conversion_rate_x64(1, u64::MAX), (u64::MAX as u128) << 64);
+    }
+}
@@ -6,12 +6,15 @@ use crate::manager::liquidity_manager::{
     calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
 };
 use crate::math::convert_to_liquidity_delta;
+use crate::state::{PositionHistoryEntry, POSITION_HISTORY_DECREASE_LIQUIDITY};
 use crate::util::{
     calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
     RemainingAccountsInfo,
 };
 use crate::util::{
-    to_timestamp_u64, v2::transfer_from_vault_to_owner_v2, verify_position_authority,
+    consume_remaining_deadline_context, exit_remaining_position_history, load_position_tick_array,
+    load_remaining_position_history, record_position_history, to_timestamp_u64,
+    v2::transfer_from_vault_to_owner_v2, verify_position_authority,
 };
 
 use super::ModifyLiquidityV2;
@@ -19,7 +22,7 @@ use super::ModifyLiquidityV2;
 /*
   Removes liquidity from an existing pool Position.
 */
-pub fn handler<'a, 'b, 'c, 'info>(
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityV2<'info>>,
     liquidity_amount: u128,
     token_min_a: u64,
@@ -31,6 +34,10 @@ pub fn handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.position_authority,
     )?;
 
+    let pool_key = ctx.accounts.pool.key();
+    let tick_array_lower = load_position_tick_array(&ctx.accounts.tick_array_lower, &pool_key)?;
+    let tick_array_upper = load_position_tick_array(&ctx.accounts.tick_array_upper, &pool_key)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -41,17 +48,32 @@ pub fn handler<'a, 'b, 'c, 'info>(
     let remaining_accounts = parse_remaining_accounts(
         &ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::DeadlineContext,
+            AccountsType::PositionHistory,
+        ],
     )?;
 
-    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, false)?;
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, false).map_err(ErrorCode::from)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    consume_remaining_deadline_context(
+        remaining_accounts.deadline_context,
+        &ctx.accounts.position_authority.key(),
+        timestamp,
+    )?;
+    let mut position_history = load_remaining_position_history(
+        remaining_accounts.position_history,
+        &ctx.accounts.position.key(),
+    )?;
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.pool,
         &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         liquidity_delta,
         timestamp,
     )?;
@@ -59,8 +81,8 @@ pub fn handler<'a, 'b, 'c, 'info>(
     sync_modify_liquidity_values(
         &mut ctx.accounts.pool,
         &mut ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         update,
         timestamp,
     )?;
@@ -85,6 +107,17 @@ pub fn handler<'a, 'b, 'c, 'info>(
         return Err(ErrorCode::TokenMinSubceeded.into());
     }
 
+    record_position_history(
+        &mut position_history,
+        PositionHistoryEntry::new(
+            POSITION_HISTORY_DECREASE_LIQUIDITY,
+            clock.slot,
+            liquidity_delta,
+            delta_a,
+            delta_b,
+        ),
+    );
+
     transfer_from_vault_to_owner_v2(
         &ctx.accounts.pool,
         &ctx.accounts.token_mint_a,
@@ -109,5 +142,5 @@ pub fn handler<'a, 'b, 'c, 'info>(
         transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
     )?;
 
-    Ok(())
+    exit_remaining_position_history(&position_history)
 }
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::transfer_memo;
+use crate::errors::ErrorCode;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::manager::position_manager::settle_withdrawal_with_fees;
+use crate::math::convert_to_liquidity_delta;
+use crate::state::{
+    PositionHistoryEntry, POSITION_HISTORY_COLLECT_FEES, POSITION_HISTORY_DECREASE_LIQUIDITY,
+};
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::util::{
+    consume_remaining_deadline_context, exit_remaining_position_history, load_position_tick_array,
+    load_remaining_position_history, record_position_history, to_timestamp_u64,
+    v2::transfer_from_vault_to_owner_v2, verify_position_authority,
+};
+
+use super::ModifyLiquidityV2;
+
+/*
+  Removes liquidity from an existing pool Position and collects its fees in the same call.
+  The withdrawn tokens and the fees are transferred to the owner in a single transfer per token.
+*/
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityV2<'info>>,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let pool_key = ctx.accounts.pool.key();
+    let tick_array_lower = load_position_tick_array(&ctx.accounts.tick_array_lower, &pool_key)?;
+    let tick_array_upper = load_position_tick_array(&ctx.accounts.tick_array_upper, &pool_key)?;
+
+    let clock = Clock::get()?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::DeadlineContext,
+            AccountsType::PositionHistory,
+        ],
+    )?;
+
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, false).map_err(ErrorCode::from)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    consume_remaining_deadline_context(
+        remaining_accounts.deadline_context,
+        &ctx.accounts.position_authority.key(),
+        timestamp,
+    )?;
+    let mut position_history = load_remaining_position_history(
+        remaining_accounts.position_history,
+        &ctx.accounts.position.key(),
+    )?;
+
+    // Settles fee growth of the position once for both the withdrawal and the collection
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.pool,
+        &mut ctx.accounts.position,
+        &tick_array_lower,
+        &tick_array_upper,
+        update,
+        timestamp,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.pool.tick_current_index,
+        ctx.accounts.pool.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    let (fee_a, fee_b) = (position.fee_owed_a, position.fee_owed_b);
+    let (amount_a, amount_b) = settle_withdrawal_with_fees(position, delta_a, delta_b)?;
+
+    let transfer_fee_excluded_amount_a =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_a, amount_a)?;
+    let transfer_fee_excluded_amount_b =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_b, amount_b)?;
+
+    // token_min_a and token_min_b bound the withdrawn tokens and fees combined,
+    // transfer fee excluded
+    if transfer_fee_excluded_amount_a.amount < token_min_a {
+        return Err(ErrorCode::TokenMinSubceeded.into());
+    }
+    if transfer_fee_excluded_amount_b.amount < token_min_b {
+        return Err(ErrorCode::TokenMinSubceeded.into());
+    }
+
+    record_position_history(
+        &mut position_history,
+        PositionHistoryEntry::new(
+            POSITION_HISTORY_DECREASE_LIQUIDITY,
+            clock.slot,
+            liquidity_delta,
+            delta_a,
+            delta_b,
+        ),
+    );
+    record_position_history(
+        &mut position_history,
+        PositionHistoryEntry::new(POSITION_HISTORY_COLLECT_FEES, clock.slot, 0, fee_a, fee_b),
+    );
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.token_owner_account_a,
+        &ctx.accounts.token_program_a,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_a,
+        amount_a,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    transfer_from_vault_to_owner_v2(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.token_owner_account_b,
+        &ctx.accounts.token_program_b,
+        &ctx.accounts.memo_program,
+        &remaining_accounts.transfer_hook_b,
+        amount_b,
+        transfer_memo::TRANSFER_MEMO_DECREASE_LIQUIDITY.as_bytes(),
+    )?;
+
+    exit_remaining_position_history(&position_history)
+}
@@ -0,0 +1,474 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::swap_with_transfer_fee_extension;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
+    transfer_from_owner_to_vault_v2, transfer_from_vault_to_owner_v2, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events::ArbitrageExecutedEvent,
+    manager::swap_manager::PostSwapUpdate,
+    math::{MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64},
+    state::{Pool, TickArray},
+    util::{to_timestamp_u64, update_pool_after_swap, SwapTickSequence},
+};
+
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+#[derive(Accounts)]
+#[instruction(
+    amount: u64,
+    max_slippage_bps: u16,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+)]
+pub struct ExecuteArbitrageCycle<'info> {
+    pub token_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub pool_one: Box<Account<'info, Pool>>,
+    #[account(mut)]
+    pub pool_two: Box<Account<'info, Pool>>,
+    #[account(mut)]
+    pub pool_three: Option<Box<Account<'info, Pool>>>,
+
+    // token_mint_one is both the input of the first pool and the output of the last pool
+    #[account(address = pool_one.input_token_mint(a_to_b_one))]
+    pub token_mint_one: InterfaceAccount<'info, Mint>,
+    #[account(address = pool_one.output_token_mint(a_to_b_one))]
+    pub token_mint_two: InterfaceAccount<'info, Mint>,
+    pub token_mint_three: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = token_mint_one.to_account_info().owner.clone())]
+    pub token_program_one: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_two.to_account_info().owner.clone())]
+    pub token_program_two: Interface<'info, TokenInterface>,
+    pub token_program_three: Option<Interface<'info, TokenInterface>>,
+
+    #[account(mut, constraint = token_owner_account.mint == token_mint_one.key())]
+    pub token_owner_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = pool_one.input_token_vault(a_to_b_one))]
+    pub token_vault_one_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = pool_one.output_token_vault(a_to_b_one))]
+    pub token_vault_one_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = pool_two.input_token_vault(a_to_b_two))]
+    pub token_vault_two_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = pool_two.output_token_vault(a_to_b_two))]
+    pub token_vault_two_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub token_vault_three_input: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub token_vault_three_output: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - tick arrays of pool_one, pool_two and pool_three (1 to 3 each, in swap order)
+    // - accounts for transfer hook program of token_mint_one
+    // - accounts for transfer hook program of token_mint_two
+    // - accounts for transfer hook program of token_mint_three
+}
+
+pub fn handler<'a, 'b, 'info>(
+    ctx: Context<'a, 'b, 'info, 'info, ExecuteArbitrageCycle<'info>>,
+    amount: u64,
+    max_slippage_bps: u16,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    a_to_b_three: bool,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let accounts = ctx.accounts;
+    let pool_one = &mut accounts.pool_one;
+    let pool_two = &mut accounts.pool_two;
+    let token_mint_one = &accounts.token_mint_one;
+    let token_mint_two = &accounts.token_mint_two;
+
+    // The third leg is either fully provided or fully omitted
+    let leg_three = match (
+        accounts.pool_three.as_mut(),
+        accounts.token_mint_three.as_ref(),
+        accounts.token_program_three.as_ref(),
+        accounts.token_vault_three_input.as_ref(),
+        accounts.token_vault_three_output.as_ref(),
+    ) {
+        (Some(pool), Some(mint), Some(program), Some(vault_input), Some(vault_output)) => {
+            if program.key() != *mint.to_account_info().owner
+                || vault_input.key() != pool.input_token_vault(a_to_b_three)
+                || vault_output.key() != pool.output_token_vault(a_to_b_three)
+            {
+                return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+            }
+            Some((pool, mint, program, vault_input, vault_output))
+        }
+        (None, None, None, None, None) => None,
+        _ => return Err(ErrorCode::InvalidArbitrageCycle.into()),
+    };
+
+    // The pools must form a closed loop that starts and ends with token_mint_one
+    if pool_one.key() == pool_two.key()
+        || pool_two.input_token_mint(a_to_b_two) != token_mint_two.key()
+    {
+        return Err(ErrorCode::InvalidArbitrageCycle.into());
+    }
+    match &leg_three {
+        Some((pool_three, token_mint_three, ..)) => {
+            if pool_three.key() == pool_one.key()
+                || pool_three.key() == pool_two.key()
+                || pool_two.output_token_mint(a_to_b_two) != token_mint_three.key()
+                || pool_three.input_token_mint(a_to_b_three) != token_mint_three.key()
+                || pool_three.output_token_mint(a_to_b_three) != token_mint_one.key()
+            {
+                return Err(ErrorCode::InvalidArbitrageCycle.into());
+            }
+        }
+        None => {
+            if pool_two.output_token_mint(a_to_b_two) != token_mint_one.key() {
+                return Err(ErrorCode::InvalidArbitrageCycle.into());
+            }
+        }
+    }
+
+    // Process remaining accounts
+    let mut valid_accounts_type_list = vec![
+        AccountsType::TickArrayOne,
+        AccountsType::TickArrayTwo,
+        AccountsType::TransferHookInput,
+        AccountsType::TransferHookIntermediate,
+    ];
+    if leg_three.is_some() {
+        valid_accounts_type_list.push(AccountsType::TickArrayThree);
+        valid_accounts_type_list.push(AccountsType::TransferHookSecondIntermediate);
+    }
+    let remaining_accounts = parse_remaining_accounts(
+        ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &valid_accounts_type_list,
+    )?;
+
+    let tick_arrays_one = load_tick_arrays(remaining_accounts.tick_array_one, &pool_one.key())?;
+    let tick_arrays_two = load_tick_arrays(remaining_accounts.tick_array_two, &pool_two.key())?;
+    let tick_arrays_three = match &leg_three {
+        Some((pool_three, ..)) => {
+            load_tick_arrays(remaining_accounts.tick_array_three, &pool_three.key())?
+        }
+        None => Vec::new(),
+    };
+
+    // Leg one: token_mint_one => token_mint_two
+    pool_one.verify_swap_amount(amount)?;
+    let swap_update_one = swap_with_transfer_fee_extension(
+        &pool_one,
+        if a_to_b_one {
+            token_mint_one
+        } else {
+            token_mint_two
+        },
+        if a_to_b_one {
+            token_mint_two
+        } else {
+            token_mint_one
+        },
+        &mut new_swap_tick_sequence(&tick_arrays_one)?,
+        amount,
+        no_sqrt_price_limit(a_to_b_one),
+        true,
+        a_to_b_one,
+        timestamp,
+    )?;
+
+    // Leg two: token_mint_two => token_mint_three (or token_mint_one if the cycle has two legs)
+    // We use vault to vault transfer, so transfer fee will be collected once.
+    let token_mint_two_output = match &leg_three {
+        Some((_, token_mint_three, ..)) => *token_mint_three,
+        None => token_mint_one,
+    };
+    let swap_two_input_amount = swap_output_amount(&swap_update_one, a_to_b_one);
+    pool_two.verify_swap_amount(swap_two_input_amount)?;
+    let swap_update_two = swap_with_transfer_fee_extension(
+        &pool_two,
+        if a_to_b_two {
+            token_mint_two
+        } else {
+            token_mint_two_output
+        },
+        if a_to_b_two {
+            token_mint_two_output
+        } else {
+            token_mint_two
+        },
+        &mut new_swap_tick_sequence(&tick_arrays_two)?,
+        swap_two_input_amount,
+        no_sqrt_price_limit(a_to_b_two),
+        true,
+        a_to_b_two,
+        timestamp,
+    )?;
+    check_intermediate_amount(&swap_update_one, a_to_b_one, &swap_update_two, a_to_b_two)?;
+
+    // Leg three: token_mint_three => token_mint_one
+    let swap_update_three = match &leg_three {
+        Some((pool_three, token_mint_three, ..)) => {
+            let swap_three_input_amount = swap_output_amount(&swap_update_two, a_to_b_two);
+            pool_three.verify_swap_amount(swap_three_input_amount)?;
+            let swap_update_three = swap_with_transfer_fee_extension(
+                &pool_three,
+                if a_to_b_three {
+                    *token_mint_three
+                } else {
+                    token_mint_one
+                },
+                if a_to_b_three {
+                    token_mint_one
+                } else {
+                    *token_mint_three
+                },
+                &mut new_swap_tick_sequence(&tick_arrays_three)?,
+                swap_three_input_amount,
+                no_sqrt_price_limit(a_to_b_three),
+                true,
+                a_to_b_three,
+                timestamp,
+            )?;
+            check_intermediate_amount(
+                &swap_update_two,
+                a_to_b_two,
+                &swap_update_three,
+                a_to_b_three,
+            )?;
+            Some(swap_update_three)
+        }
+        None => None,
+    };
+
+    let initial_amount = swap_input_amount(&swap_update_one, a_to_b_one);
+    let final_output_amount = match &swap_update_three {
+        Some(swap_update_three) => swap_output_amount(swap_update_three, a_to_b_three),
+        None => swap_output_amount(&swap_update_two, a_to_b_two),
+    };
+    let final_amount =
+        calculate_transfer_fee_excluded_amount(token_mint_one, final_output_amount)?.amount;
+
+    let profit_bps = calculate_arbitrage_profit_bps(initial_amount, final_amount)?;
+    if final_amount < calculate_arbitrage_minimum_final_amount(initial_amount, max_slippage_bps) {
+        return Err(error!(ErrorCode::ArbitrageNotProfitable)
+            .with_values((profit_bps, -(max_slippage_bps as i32))));
+    }
+
+    let mut path = vec![pool_one.key(), pool_two.key()];
+    let swap_two_output_amount = swap_output_amount(&swap_update_two, a_to_b_two);
+
+    update_pool_after_swap(pool_one, &swap_update_one, a_to_b_one, timestamp);
+    update_pool_after_swap(pool_two, &swap_update_two, a_to_b_two, timestamp);
+
+    transfer_from_owner_to_vault_v2(
+        &accounts.token_authority,
+        token_mint_one,
+        &accounts.token_owner_account,
+        &accounts.token_vault_one_input,
+        &accounts.token_program_one,
+        &accounts.memo_program,
+        &remaining_accounts.transfer_hook_input,
+        initial_amount,
+    )?;
+
+    // Transfer from pool to pool
+    transfer_from_vault_to_owner_v2(
+        pool_one,
+        token_mint_two,
+        &accounts.token_vault_one_output,
+        &accounts.token_vault_two_input,
+        &accounts.token_program_two,
+        &accounts.memo_program,
+        &remaining_accounts.transfer_hook_intermediate,
+        swap_output_amount(&swap_update_one, a_to_b_one),
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    match (leg_three, swap_update_three) {
+        (
+            Some((
+                pool_three,
+                token_mint_three,
+                token_program_three,
+                token_vault_three_input,
+                token_vault_three_output,
+            )),
+            Some(swap_update_three),
+        ) => {
+            path.push(pool_three.key());
+            update_pool_after_swap(pool_three, &swap_update_three, a_to_b_three, timestamp);
+
+            transfer_from_vault_to_owner_v2(
+                pool_two,
+                token_mint_three,
+                &accounts.token_vault_two_output,
+                token_vault_three_input,
+                token_program_three,
+                &accounts.memo_program,
+                &remaining_accounts.transfer_hook_second_intermediate,
+                swap_two_output_amount,
+                transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+            )?;
+
+            transfer_from_vault_to_owner_v2(
+                pool_three,
+                token_mint_one,
+                token_vault_three_output,
+                &accounts.token_owner_account,
+                &accounts.token_program_one,
+                &accounts.memo_program,
+                &remaining_accounts.transfer_hook_input,
+                final_output_amount,
+                transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+            )?;
+        }
+        _ => {
+            transfer_from_vault_to_owner_v2(
+                pool_two,
+                token_mint_one,
+                &accounts.token_vault_two_output,
+                &accounts.token_owner_account,
+                &accounts.token_program_one,
+                &accounts.memo_program,
+                &remaining_accounts.transfer_hook_input,
+                final_output_amount,
+                transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+            )?;
+        }
+    }
+
+    emit!(ArbitrageExecutedEvent {
+        initial_amount,
+        final_amount,
+        profit_bps,
+        path,
+    });
+
+    Ok(())
+}
+
+fn load_tick_arrays<'info>(
+    tick_array_accounts: Option<&'info [AccountInfo<'info>]>,
+    pool: &Pubkey,
+) -> Result<Vec<AccountLoader<'info, TickArray>>> {
+    let tick_array_accounts =
+        tick_array_accounts.ok_or(ErrorCode::RemainingAccountsInsufficient)?;
+    if tick_array_accounts.len() > 3 {
+        return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+    }
+
+    let mut tick_arrays = Vec::with_capacity(tick_array_accounts.len());
+    for account_info in tick_array_accounts.iter() {
+        let tick_array = AccountLoader::<TickArray>::try_from(account_info)?;
+        if tick_array.load()?.pool != *pool {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+        tick_arrays.push(tick_array);
+    }
+    Ok(tick_arrays)
+}
+
+fn new_swap_tick_sequence<'a, 'info>(
+    tick_arrays: &'a [AccountLoader<'info, TickArray>],
+) -> Result<SwapTickSequence<'a>> {
+    let mut tick_arrays = tick_arrays.iter();
+    let ta0 = tick_arrays
+        .next()
+        .ok_or(ErrorCode::RemainingAccountsInsufficient)?
+        .load_mut()?;
+    let ta1 = tick_arrays.next().map(|ta| ta.load_mut()).transpose()?;
+    let ta2 = tick_arrays.next().map(|ta| ta.load_mut()).transpose()?;
+    Ok(SwapTickSequence::new(ta0, ta1, ta2))
+}
+
+pub fn no_sqrt_price_limit(a_to_b: bool) -> u128 {
+    if a_to_b {
+        MIN_SQRT_PRICE_X64
+    } else {
+        MAX_SQRT_PRICE_X64
+    }
+}
+
+pub fn swap_input_amount(swap_update: &PostSwapUpdate, a_to_b: bool) -> u64 {
+    if a_to_b {
+        swap_update.amount_a
+    } else {
+        swap_update.amount_b
+    }
+}
+
+pub fn swap_output_amount(swap_update: &PostSwapUpdate, a_to_b: bool) -> u64 {
+    if a_to_b {
+        swap_update.amount_b
+    } else {
+        swap_update.amount_a
+    }
+}
+
+// All output token of a leg should be consumed by the next leg
+pub fn check_intermediate_amount(
+    swap_update: &PostSwapUpdate,
+    a_to_b: bool,
+    next_swap_update: &PostSwapUpdate,
+    next_a_to_b: bool,
+) -> Result<()> {
+    if swap_output_amount(swap_update, a_to_b) != swap_input_amount(next_swap_update, next_a_to_b) {
+        return Err(ErrorCode::IntermediateTokenAmountMismatch.into());
+    }
+    Ok(())
+}
+
+pub fn calculate_arbitrage_profit_bps(initial_amount: u64, final_amount: u64) -> Result<i32> {
+    if initial_amount == 0 {
+        return Err(ErrorCode::ZeroTradableAmount.into());
+    }
+    let profit = final_amount as i128 - initial_amount as i128;
+    let profit_bps = profit * BPS_DENOMINATOR as i128 / initial_amount as i128;
+    Ok(profit_bps.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+}
+
+pub fn calculate_arbitrage_minimum_final_amount(initial_amount: u64, max_slippage_bps: u16) -> u64 {
+    let max_slippage_bps = (max_slippage_bps as u128).min(BPS_DENOMINATOR);
+    let max_slippage = initial_amount as u128 * max_slippage_bps / BPS_DENOMINATOR;
+    initial_amount - max_slippage as u64
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::{calculate_arbitrage_minimum_final_amount, calculate_arbitrage_profit_bps};
+
+    #[test]
+    fn test_calculate_arbitrage_profit_bps() {
+        assert_eq!(calculate_arbitrage_profit_bps(10_000, 10_100).unwrap(), 100);
+        assert_eq!(calculate_arbitrage_profit_bps(10_000, 9_950).unwrap(), -50);
+        assert_eq!(calculate_arbitrage_profit_bps(10_000, 10_000).unwrap(), 0);
+        assert_eq!(
+            calculate_arbitrage_profit_bps(1, u64::MAX).unwrap(),
+            i32::MAX
+        );
+        assert!(calculate_arbitrage_profit_bps(0, 1).is_err());
+    }
+
+    #[test]
+    fn test_calculate_arbitrage_minimum_final_amount() {
+        assert_eq!(calculate_arbitrage_minimum_final_amount(10_000, 0), 10_000);
+        assert_eq!(calculate_arbitrage_minimum_final_amount(10_000, 50), 9_950);
+        assert_eq!(calculate_arbitrage_minimum_final_amount(10_000, 10_000), 0);
+        assert_eq!(
+            calculate_arbitrage_minimum_final_amount(10_000, u16::MAX),
+            0
+        );
+    }
+}
@@ -14,7 +14,9 @@ use crate::util::{
     RemainingAccountsInfo,
 };
 use crate::util::{
-    to_timestamp_u64, v2::transfer_from_owner_to_vault_v2, verify_position_authority,
+    consume_remaining_deadline_context, exit_remaining_position_history, load_position_tick_array,
+    load_remaining_position_history, record_position_history, to_timestamp_u64,
+    v2::transfer_from_owner_to_vault_v2, verify_position_authority,
 };
 
 #[derive(Accounts)]
@@ -54,16 +56,20 @@ pub struct ModifyLiquidityV2<'info> {
     #[account(mut, constraint = token_vault_b.key() == pool.token_vault_b)]
     pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    #[account(mut, has_one = pool)]
-    pub tick_array_lower: AccountLoader<'info, TickArray>,
-    #[account(mut, has_one = pool)]
-    pub tick_array_upper: AccountLoader<'info, TickArray>,
+    /// CHECK: loaded by load_position_tick_array, which checks the pool of the tick-array
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+    /// CHECK: loaded by load_position_tick_array, which checks the pool of the tick-array
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - the shared deadline context of the position authority
+    // - the history of the position
 }
 
-pub fn handler<'a, 'b, 'c, 'info>(
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityV2<'info>>,
     liquidity_amount: u128,
     token_max_a: u64,
@@ -75,6 +81,10 @@ pub fn handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.position_authority,
     )?;
 
+    let pool_key = ctx.accounts.pool.key();
+    let tick_array_lower = load_position_tick_array(&ctx.accounts.tick_array_lower, &pool_key)?;
+    let tick_array_upper = load_position_tick_array(&ctx.accounts.tick_array_upper, &pool_key)?;
+
     let clock = Clock::get()?;
 
     if liquidity_amount == 0 {
@@ -85,17 +95,32 @@ pub fn handler<'a, 'b, 'c, 'info>(
     let remaining_accounts = parse_remaining_accounts(
         &ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::DeadlineContext,
+            AccountsType::PositionHistory,
+        ],
     )?;
 
-    let liquidity_delta = convert_to_liquidity_delta(liquidity_amount, true)?;
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, true).map_err(ErrorCode::from)?;
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    consume_remaining_deadline_context(
+        remaining_accounts.deadline_context,
+        &ctx.accounts.position_authority.key(),
+        timestamp,
+    )?;
+    let mut position_history = load_remaining_position_history(
+        remaining_accounts.position_history,
+        &ctx.accounts.position.key(),
+    )?;
 
     let update = calculate_modify_liquidity(
         &ctx.accounts.pool,
         &ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         liquidity_delta,
         timestamp,
     )?;
@@ -103,8 +128,8 @@ pub fn handler<'a, 'b, 'c, 'info>(
     sync_modify_liquidity_values(
         &mut ctx.accounts.pool,
         &mut ctx.accounts.position,
-        &ctx.accounts.tick_array_lower,
-        &ctx.accounts.tick_array_upper,
+        &tick_array_lower,
+        &tick_array_upper,
         update,
         timestamp,
     )?;
@@ -129,6 +154,17 @@ pub fn handler<'a, 'b, 'c, 'info>(
         return Err(ErrorCode::TokenMaxExceeded.into());
     }
 
+    record_position_history(
+        &mut position_history,
+        PositionHistoryEntry::new(
+            POSITION_HISTORY_INCREASE_LIQUIDITY,
+            clock.slot,
+            liquidity_delta,
+            delta_a,
+            delta_b,
+        ),
+    );
+
     transfer_from_owner_to_vault_v2(
         &ctx.accounts.position_authority,
         &ctx.accounts.token_mint_a,
@@ -151,5 +187,5 @@ pub fn handler<'a, 'b, 'c, 'info>(
         transfer_fee_included_delta_b.amount,
     )?;
 
-    Ok(())
+    exit_remaining_position_history(&position_history)
 }
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenInterface};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     errors::ErrorCode,
+    events,
     state::*,
-    util::{is_token_badge_initialized, v2::is_supported_token_mint},
+    util::{is_token_badge_initialized, v2::is_supported_token_mint, verify_vault_empty},
 };
 
 #[derive(Accounts)]
@@ -37,23 +38,32 @@ pub struct InitializePoolV2<'info> {
       payer = funder,
       space = Pool::LEN)]
     pub pool: Box<Account<'info, Pool>>,
-    /// CHECK:safe with seeds
-    #[account(mut,seeds =[
+    // Each vault is created by the token program owning its mint
+    #[account(init,
+      payer = funder,
+      seeds =[
             b"pool_vault",
             pool.key().as_ref(),
             token_mint_a.key().as_ref(),
         ],
-        bump)]
-    pub token_vault_a: AccountInfo<'info>,
-    /// CHECK:safe with seeds
-    #[account(mut,seeds =[
+        bump,
+      token::token_program = token_program_a,
+      token::mint = token_mint_a,
+      token::authority = pool)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(init,
+      payer = funder,
+      seeds =[
             b"pool_vault",
             pool.key().as_ref(),
             token_mint_b.key().as_ref(),
         ],
-        bump)]
-    pub token_vault_b: AccountInfo<'info>,
-    #[account(has_one = pools_config, constraint = fee_tier.tick_spacing == tick_spacing)]
+        bump,
+      token::token_program = token_program_b,
+      token::mint = token_mint_b,
+      token::authority = pool)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, has_one = pools_config)]
     pub fee_tier: Account<'info, FeeTier>,
 
     #[account(address = token_mint_a.to_account_info().owner.clone())]
@@ -69,12 +79,17 @@ pub fn handler(
     tick_spacing: u16,
     initial_sqrt_price: u128,
 ) -> Result<()> {
+    verify_vault_empty(&ctx.accounts.token_vault_a.to_account_info())?;
+    verify_vault_empty(&ctx.accounts.token_vault_b.to_account_info())?;
+
     let token_mint_a = ctx.accounts.token_mint_a.key();
     let token_mint_b = ctx.accounts.token_mint_b.key();
 
     let pool = &mut ctx.accounts.pool;
     let pools_config = &ctx.accounts.pools_config;
 
+    // The pool's fee rate is taken from the fee tier of its tick spacing
+    ctx.accounts.fee_tier.verify_tick_spacing(tick_spacing)?;
     let default_fee_rate = ctx.accounts.fee_tier.default_fee_rate;
 
     // ignore the bump passed and use one Anchor derived
@@ -101,7 +116,7 @@ pub fn handler(
         return Err(ErrorCode::UnsupportedTokenMint.into());
     }
 
-    Ok(pool.initialize(
+    pool.initialize(
         pools_config,
         bump,
         tick_spacing,
@@ -111,5 +126,16 @@ pub fn handler(
         ctx.accounts.token_vault_a.key(),
         token_mint_b,
         ctx.accounts.token_vault_b.key(),
-    )?)
+    )?;
+
+    let fee_tier = &mut ctx.accounts.fee_tier;
+    let old_count = fee_tier.pool_count;
+    fee_tier.increment_pool_count()?;
+    emit!(events::FeeTierPoolCountChangedEvent {
+        fee_tier: fee_tier.key(),
+        old_count,
+        new_count: fee_tier.pool_count,
+    });
+
+    Ok(())
 }
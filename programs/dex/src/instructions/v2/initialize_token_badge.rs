@@ -9,7 +9,9 @@ pub struct InitializeTokenBadge<'info> {
     #[account(has_one = pools_config)]
     pub pools_config_extension: Box<Account<'info, PoolsConfigExtension>>,
 
-    #[account(address = pools_config_extension.token_badge_authority)]
+    // Either the token badge authority or an operations authority of the config
+    #[account(constraint = token_badge_authority.key() == pools_config_extension.token_badge_authority
+        || pools_config.is_operations_authority(token_badge_authority.key()))]
     pub token_badge_authority: Signer<'info>,
 
     pub token_mint: InterfaceAccount<'info, Mint>,
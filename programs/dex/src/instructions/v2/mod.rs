@@ -1,13 +1,22 @@
+pub mod batch_collect_bundle_rewards;
 pub mod collect_fees;
+pub mod collect_fees_and_convert;
 pub mod collect_protocol_fees;
 pub mod collect_reward;
+pub mod collect_reward_and_swap;
 pub mod decrease_liquidity;
+pub mod decrease_liquidity_and_collect;
+pub mod execute_arbitrage_cycle;
 pub mod increase_liquidity;
 pub mod initialize_pool;
 pub mod initialize_reward;
+pub mod routed_swap;
 pub mod set_reward_emissions;
+pub mod simulate_two_hop_swap;
 pub mod swap;
+pub mod swap_v3;
 pub mod two_hop_swap;
+pub mod two_hop_swap_v3;
 
 pub mod initialize_config_extension;
 pub mod set_config_extension_authority;
@@ -15,16 +24,25 @@ pub mod set_token_badge_authority;
 pub mod initialize_token_badge;
 pub mod delete_token_badge;
 
+pub use batch_collect_bundle_rewards::*;
 pub use collect_fees::*;
+pub use collect_fees_and_convert::*;
 pub use collect_protocol_fees::*;
 pub use collect_reward::*;
+pub use collect_reward_and_swap::*;
 pub use decrease_liquidity::*;
+pub use decrease_liquidity_and_collect::*;
+pub use execute_arbitrage_cycle::*;
 pub use increase_liquidity::*;
 pub use initialize_pool::*;
 pub use initialize_reward::*;
+pub use routed_swap::*;
 pub use set_reward_emissions::*;
+pub use simulate_two_hop_swap::*;
 pub use swap::*;
+pub use swap_v3::*;
 pub use two_hop_swap::*;
+pub use two_hop_swap_v3::*;
 
 pub use initialize_config_extension::*;
 pub use set_config_extension_authority::*;
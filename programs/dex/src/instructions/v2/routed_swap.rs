@@ -0,0 +1,262 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use std::convert::TryFrom;
+
+use crate::swap_with_transfer_fee_extension;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, transfer_from_owner_to_vault_v2,
+    transfer_from_vault_to_owner_v2,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    manager::swap_manager::{check_route, MAX_ROUTE_HOPS},
+    state::{Pool, TickArray},
+    util::{to_timestamp_u64, update_pool_after_swap, SwapTickSequence},
+};
+
+use super::execute_arbitrage_cycle::{
+    check_intermediate_amount, no_sqrt_price_limit, swap_input_amount, swap_output_amount,
+};
+
+// pool, token_vault_input, token_vault_output, tick_array_0, tick_array_1, tick_array_2
+pub const ROUTE_HOP_ACCOUNTS_LEN: usize = 6;
+// token_mint, token_program
+pub const ROUTE_INTERMEDIATE_ACCOUNTS_LEN: usize = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct RouteHop {
+    pub pool: Pubkey,
+    pub a_to_b: bool,
+    // Zero swaps without a price limit
+    pub sqrt_price_limit: u128,
+}
+
+#[derive(Accounts)]
+pub struct RoutedSwapV2<'info> {
+    pub token_authority: Signer<'info>,
+
+    pub token_mint_input: InterfaceAccount<'info, Mint>,
+    pub token_mint_output: InterfaceAccount<'info, Mint>,
+
+    #[account(address = token_mint_input.to_account_info().owner.clone())]
+    pub token_program_input: Interface<'info, TokenInterface>,
+    #[account(address = token_mint_output.to_account_info().owner.clone())]
+    pub token_program_output: Interface<'info, TokenInterface>,
+
+    #[account(mut, constraint = token_owner_account_input.mint == token_mint_input.key())]
+    pub token_owner_account_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = token_owner_account_output.mint == token_mint_output.key())]
+    pub token_owner_account_output: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub memo_program: Program<'info, Memo>,
+    // remaining accounts
+    // - for each hop: pool, token_vault_input, token_vault_output, tick_array_0, tick_array_1,
+    //   tick_array_2
+    // - for each intermediate token, in swap order: token_mint, token_program
+}
+
+struct RouteHopAccounts<'info> {
+    pool: Box<Account<'info, Pool>>,
+    token_vault_input: Box<InterfaceAccount<'info, TokenAccount>>,
+    token_vault_output: Box<InterfaceAccount<'info, TokenAccount>>,
+    tick_arrays: &'info [AccountInfo<'info>],
+}
+
+/*
+  Swaps an exact input amount through a route of up to MAX_ROUTE_HOPS pools. The output of each
+  hop is transferred vault to vault into the next hop, so transfer fees are collected once per
+  token. Tokens with a transfer hook are not supported.
+*/
+pub fn handler<'a, 'b, 'info>(
+    ctx: Context<'a, 'b, 'info, 'info, RoutedSwapV2<'info>>,
+    route: Vec<RouteHop>,
+    amount: u64,
+    other_amount_threshold: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let num_hops = route.len();
+    if num_hops == 0
+        || num_hops > MAX_ROUTE_HOPS
+        || ctx.remaining_accounts.len()
+            != num_hops * ROUTE_HOP_ACCOUNTS_LEN + (num_hops - 1) * ROUTE_INTERMEDIATE_ACCOUNTS_LEN
+    {
+        return Err(ErrorCode::InvalidSwapRoute.into());
+    }
+    // Each hop swaps against its own copy of the pool, so a pool can only be used once
+    for (i, hop) in route.iter().enumerate() {
+        if route[..i].iter().any(|prev_hop| prev_hop.pool == hop.pool) {
+            return Err(ErrorCode::InvalidSwapRoute.into());
+        }
+    }
+
+    let (hop_accounts, intermediate_accounts) = ctx
+        .remaining_accounts
+        .split_at(num_hops * ROUTE_HOP_ACCOUNTS_LEN);
+
+    let mut hops: Vec<RouteHopAccounts<'info>> = Vec::with_capacity(num_hops);
+    for (hop, accounts) in route
+        .iter()
+        .zip(hop_accounts.chunks(ROUTE_HOP_ACCOUNTS_LEN))
+    {
+        let pool = Box::new(Account::<Pool>::try_from(&accounts[0])?);
+        let token_vault_input = Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?);
+        let token_vault_output =
+            Box::new(InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?);
+        if pool.key() != hop.pool
+            || token_vault_input.key() != pool.input_token_vault(hop.a_to_b)
+            || token_vault_output.key() != pool.output_token_vault(hop.a_to_b)
+        {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+        }
+        hops.push(RouteHopAccounts {
+            pool,
+            token_vault_input,
+            token_vault_output,
+            tick_arrays: &accounts[3..ROUTE_HOP_ACCOUNTS_LEN],
+        });
+    }
+
+    let route_pools: Vec<(&Pool, bool)> = hops
+        .iter()
+        .zip(route.iter())
+        .map(|(accounts, hop)| {
+            let pool: &Pool = &accounts.pool;
+            (pool, hop.a_to_b)
+        })
+        .collect();
+    check_route(&route_pools)?;
+
+    // token_mints[i] and token_programs[i] are the input token of hop i, the last entries are the
+    // output token of the route
+    let mut token_mints = vec![ctx.accounts.token_mint_input.clone()];
+    let mut token_programs = vec![ctx.accounts.token_program_input.clone()];
+    for accounts in intermediate_accounts.chunks(ROUTE_INTERMEDIATE_ACCOUNTS_LEN) {
+        let token_mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+        let token_program = Interface::<TokenInterface>::try_from(&accounts[1])?;
+        if token_program.key() != *token_mint.to_account_info().owner {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+        }
+        token_mints.push(token_mint);
+        token_programs.push(token_program);
+    }
+    token_mints.push(ctx.accounts.token_mint_output.clone());
+    token_programs.push(ctx.accounts.token_program_output.clone());
+
+    for (i, (hop, accounts)) in route.iter().zip(hops.iter()).enumerate() {
+        if token_mints[i].key() != accounts.pool.input_token_mint(hop.a_to_b)
+            || token_mints[i + 1].key() != accounts.pool.output_token_mint(hop.a_to_b)
+        {
+            return Err(anchor_lang::error::ErrorCode::ConstraintAddress.into());
+        }
+    }
+
+    // Simulate each hop, feeding the output of a hop into the next one
+    let mut swap_updates = Vec::with_capacity(num_hops);
+    let mut hop_amount = amount;
+    for (i, (hop, accounts)) in route.iter().zip(hops.iter()).enumerate() {
+        // Each hop is checked like a single swap, with the amount that enters the pool
+        accounts.pool.verify_swap_amount(hop_amount)?;
+
+        let tick_arrays = accounts
+            .tick_arrays
+            .iter()
+            .map(|account_info| AccountLoader::<TickArray>::try_from(account_info))
+            .collect::<Result<Vec<_>>>()?;
+        for tick_array in tick_arrays.iter() {
+            if tick_array.load()?.pool != accounts.pool.key() {
+                return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+            }
+        }
+
+        let (token_mint_a, token_mint_b) = if hop.a_to_b {
+            (&token_mints[i], &token_mints[i + 1])
+        } else {
+            (&token_mints[i + 1], &token_mints[i])
+        };
+        let sqrt_price_limit = if hop.sqrt_price_limit == 0 {
+            no_sqrt_price_limit(hop.a_to_b)
+        } else {
+            hop.sqrt_price_limit
+        };
+
+        let mut swap_tick_sequence =
+            SwapTickSequence::from_accounts(&tick_arrays[0], &tick_arrays[1], &tick_arrays[2])?;
+        let swap_update = swap_with_transfer_fee_extension(
+            &accounts.pool,
+            token_mint_a,
+            token_mint_b,
+            &mut swap_tick_sequence,
+            hop_amount,
+            sqrt_price_limit,
+            true,
+            hop.a_to_b,
+            timestamp,
+        )?;
+
+        if i > 0 {
+            check_intermediate_amount(
+                &swap_updates[i - 1],
+                route[i - 1].a_to_b,
+                &swap_update,
+                hop.a_to_b,
+            )?;
+        }
+        hop_amount = swap_output_amount(&swap_update, hop.a_to_b);
+        swap_updates.push(swap_update);
+    }
+
+    let output_amount = hop_amount;
+    let transfer_fee_excluded_output_amount =
+        calculate_transfer_fee_excluded_amount(&ctx.accounts.token_mint_output, output_amount)?
+            .amount;
+    if transfer_fee_excluded_output_amount < other_amount_threshold {
+        return Err(ErrorCode::AmountOutBelowMinimum.into());
+    }
+
+    for ((hop, accounts), swap_update) in route.iter().zip(hops.iter_mut()).zip(swap_updates.iter())
+    {
+        update_pool_after_swap(&mut accounts.pool, swap_update, hop.a_to_b, timestamp);
+    }
+
+    transfer_from_owner_to_vault_v2(
+        &ctx.accounts.token_authority,
+        &token_mints[0],
+        &ctx.accounts.token_owner_account_input,
+        &hops[0].token_vault_input,
+        &token_programs[0],
+        &ctx.accounts.memo_program,
+        &None,
+        swap_input_amount(&swap_updates[0], route[0].a_to_b),
+    )?;
+
+    // Transfer from pool to pool, and from the last pool to the owner
+    for (i, (hop, accounts)) in route.iter().zip(hops.iter()).enumerate() {
+        let destination = match hops.get(i + 1) {
+            Some(next_accounts) => &next_accounts.token_vault_input,
+            None => &ctx.accounts.token_owner_account_output,
+        };
+        transfer_from_vault_to_owner_v2(
+            &accounts.pool,
+            &token_mints[i + 1],
+            &accounts.token_vault_output,
+            destination,
+            &token_programs[i + 1],
+            &ctx.accounts.memo_program,
+            &None,
+            swap_output_amount(&swap_updates[i], hop.a_to_b),
+            transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+        )?;
+    }
+
+    // Pools loaded from remaining accounts are not persisted by Anchor
+    for accounts in hops.iter() {
+        accounts.pool.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{PoolsConfig, PoolsConfigExtension};
+use crate::{
+    events,
+    state::{PoolsConfig, PoolsConfigExtension, AUTHORITY_TYPE_CONFIG_EXTENSION},
+};
 
 #[derive(Accounts)]
 pub struct SetConfigExtensionAuthority<'info> {
@@ -18,8 +21,21 @@ pub struct SetConfigExtensionAuthority<'info> {
 
 /// Set the config extension authority. Only the current config extension authority has permission to invoke this instruction.
 pub fn handler(ctx: Context<SetConfigExtensionAuthority>) -> Result<()> {
-    Ok(ctx
+    let old_authority = ctx
         .accounts
         .pools_config_extension
-        .update_config_extension_authority(ctx.accounts.new_config_extension_authority.key()))
+        .config_extension_authority;
+    let new_authority = ctx.accounts.new_config_extension_authority.key();
+    ctx.accounts
+        .pools_config_extension
+        .update_config_extension_authority(new_authority)?;
+
+    emit!(events::AuthorityRotatedEvent {
+        config: ctx.accounts.pools_config.key(),
+        authority_type: AUTHORITY_TYPE_CONFIG_EXTENSION,
+        old_authority,
+        new_authority,
+    });
+
+    Ok(())
 }
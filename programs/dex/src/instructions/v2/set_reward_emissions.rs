@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::TokenAccount;
 
 use crate::errors::ErrorCode;
-use crate::manager::pool_manager::next_pool_reward_infos;
+use crate::events;
+use crate::manager::pool_manager::{next_pool_reward_infos, reward_vault_low_balance_days};
 use crate::math::checked_mul_shift_right;
 use crate::state::Pool;
 use crate::util::to_timestamp_u64;
@@ -30,7 +31,8 @@ pub fn handler(
     let pool = &ctx.accounts.pool;
     let reward_vault = &ctx.accounts.reward_vault;
 
-    let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)?;
+    let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)
+        .map_err(ErrorCode::from)?;
     if reward_vault.amount < emissions_per_day {
         return Err(ErrorCode::RewardVaultAmountInsufficient.into());
     }
@@ -39,10 +41,25 @@ pub fn handler(
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
     let next_reward_infos = next_pool_reward_infos(pool, timestamp)?;
 
-    Ok(ctx.accounts.pool.update_emissions(
+    ctx.accounts.pool.update_emissions(
         reward_index as usize,
         next_reward_infos,
         timestamp,
         emissions_per_second_x64,
-    )?)
+    )?;
+
+    let pool = &ctx.accounts.pool;
+    if let Some(estimated_days_remaining) =
+        reward_vault_low_balance_days(pool, reward_index as usize, reward_vault.amount)
+    {
+        emit!(events::RewardVaultLowBalanceEvent {
+            pool: pool.key(),
+            reward_index,
+            vault_balance: reward_vault.amount,
+            estimated_days_remaining,
+            threshold_bps: pool.reward_vault_warning_threshold_bps[reward_index as usize],
+        });
+    }
+
+    Ok(())
 }
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{PoolsConfig, PoolsConfigExtension};
+use crate::{
+    events,
+    state::{PoolsConfig, PoolsConfigExtension, AUTHORITY_TYPE_TOKEN_BADGE},
+};
 
 #[derive(Accounts)]
 pub struct SetTokenBadgeAuthority<'info> {
@@ -18,8 +21,18 @@ pub struct SetTokenBadgeAuthority<'info> {
 
 /// Set the token badge authority. Only the config extension authority has permission to invoke this instruction.
 pub fn handler(ctx: Context<SetTokenBadgeAuthority>) -> Result<()> {
-    Ok(ctx
-        .accounts
+    let old_authority = ctx.accounts.pools_config_extension.token_badge_authority;
+    let new_authority = ctx.accounts.new_token_badge_authority.key();
+    ctx.accounts
         .pools_config_extension
-        .update_token_badge_authority(ctx.accounts.new_token_badge_authority.key()))
+        .update_token_badge_authority(new_authority)?;
+
+    emit!(events::AuthorityRotatedEvent {
+        config: ctx.accounts.pools_config.key(),
+        authority_type: AUTHORITY_TYPE_TOKEN_BADGE,
+        old_authority,
+        new_authority,
+    });
+
+    Ok(())
 }
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::two_hop_swap_with_transfer_fee_extension;
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts,
+    verify_transfer_hook_accounts, AccountsType, RemainingAccountsInfo,
+};
+use crate::{
+    errors::ErrorCode,
+    events::TwoHopQuoteEvent,
+    manager::swap_manager::check_two_hop_route,
+    state::{Pool, TickArray},
+    util::{to_timestamp_u64, SwapTickSequence},
+};
+
+#[derive(Accounts)]
+#[instruction(
+    amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+)]
+pub struct SimulateTwoHopSwap<'info> {
+    pub pool_one: Box<Account<'info, Pool>>,
+    pub pool_two: Box<Account<'info, Pool>>,
+
+    #[account(address = pool_one.input_token_mint(a_to_b_one))]
+    pub token_mint_input: InterfaceAccount<'info, Mint>,
+    #[account(address = pool_one.output_token_mint(a_to_b_one))]
+    pub token_mint_intermediate: InterfaceAccount<'info, Mint>,
+    #[account(address = pool_two.output_token_mint(a_to_b_two))]
+    pub token_mint_output: InterfaceAccount<'info, Mint>,
+
+    #[account(constraint = tick_array_one_0.load()?.pool == pool_one.key())]
+    pub tick_array_one_0: AccountLoader<'info, TickArray>,
+
+    #[account(constraint = tick_array_one_1.load()?.pool == pool_one.key())]
+    pub tick_array_one_1: AccountLoader<'info, TickArray>,
+
+    #[account(constraint = tick_array_one_2.load()?.pool == pool_one.key())]
+    pub tick_array_one_2: AccountLoader<'info, TickArray>,
+
+    #[account(constraint = tick_array_two_0.load()?.pool == pool_two.key())]
+    pub tick_array_two_0: AccountLoader<'info, TickArray>,
+
+    #[account(constraint = tick_array_two_1.load()?.pool == pool_two.key())]
+    pub tick_array_two_1: AccountLoader<'info, TickArray>,
+
+    #[account(constraint = tick_array_two_2.load()?.pool == pool_two.key())]
+    pub tick_array_two_2: AccountLoader<'info, TickArray>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_input
+    // - accounts for transfer hook program of token_mint_intermediate
+    // - accounts for transfer hook program of token_mint_output
+}
+
+/// Quote a two-hop swap without mutating any account. The legs are calculated exactly as in
+/// `two_hop_swap_v2`, and the result is emitted as a `TwoHopQuoteEvent`.
+///
+/// Transfer hooks cannot change the transferred amounts, but they can make the transfers fail.
+/// The transfer hook accounts are resolved as in `two_hop_swap_v2`, so a route that cannot be
+/// executed for missing hook accounts cannot be quoted either.
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SimulateTwoHopSwap<'info>>,
+    amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let pool_one = &ctx.accounts.pool_one;
+    let pool_two = &ctx.accounts.pool_two;
+
+    // Don't allow swaps on the same pool
+    if pool_one.key() == pool_two.key() {
+        return Err(ErrorCode::DuplicateTwoHopPool.into());
+    }
+    check_two_hop_route(pool_one, pool_two, a_to_b_one, a_to_b_two)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookInput,
+            AccountsType::TransferHookIntermediate,
+            AccountsType::TransferHookOutput,
+        ],
+    )?;
+    verify_transfer_hook_accounts(
+        &ctx.accounts.token_mint_input,
+        &remaining_accounts.transfer_hook_input,
+    )?;
+    // The intermediate token is transferred out of pool_one and into pool_two
+    verify_transfer_hook_accounts(
+        &ctx.accounts.token_mint_intermediate,
+        &remaining_accounts.transfer_hook_intermediate,
+    )?;
+    verify_transfer_hook_accounts(
+        &ctx.accounts.token_mint_output,
+        &remaining_accounts.transfer_hook_output,
+    )?;
+
+    let mut swap_tick_sequence_one = SwapTickSequence::new_read_only(
+        ctx.accounts.tick_array_one_0.load()?,
+        ctx.accounts.tick_array_one_1.load().ok(),
+        ctx.accounts.tick_array_one_2.load().ok(),
+    );
+
+    let mut swap_tick_sequence_two = SwapTickSequence::new_read_only(
+        ctx.accounts.tick_array_two_0.load()?,
+        ctx.accounts.tick_array_two_1.load().ok(),
+        ctx.accounts.tick_array_two_2.load().ok(),
+    );
+
+    let (swap_update_one, swap_update_two) = two_hop_swap_with_transfer_fee_extension(
+        pool_one,
+        pool_two,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_mint_intermediate,
+        &ctx.accounts.token_mint_output,
+        &mut swap_tick_sequence_one,
+        &mut swap_tick_sequence_two,
+        amount,
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
+        amount_specified_is_input,
+        a_to_b_one,
+        a_to_b_two,
+        timestamp,
+        None,
+    )?;
+
+    let (leg_one_amount_in, leg_one_amount_out) = if a_to_b_one {
+        (swap_update_one.amount_a, swap_update_one.amount_b)
+    } else {
+        (swap_update_one.amount_b, swap_update_one.amount_a)
+    };
+    let (leg_two_amount_in, leg_two_amount_out) = if a_to_b_two {
+        (swap_update_two.amount_a, swap_update_two.amount_b)
+    } else {
+        (swap_update_two.amount_b, swap_update_two.amount_a)
+    };
+
+    emit!(TwoHopQuoteEvent {
+        pool_one: pool_one.key(),
+        pool_two: pool_two.key(),
+        amount_specified_is_input,
+        leg_one_amount_in,
+        leg_one_amount_out,
+        leg_two_amount_in,
+        leg_two_amount_out,
+        // The same amounts two_hop_swap_v2 checks against other_amount_threshold
+        total_input: leg_one_amount_in,
+        total_output: calculate_transfer_fee_excluded_amount(
+            &ctx.accounts.token_mint_output,
+            leg_two_amount_out,
+        )?
+        .amount,
+        sqrt_price_one: swap_update_one.next_sqrt_price,
+        sqrt_price_two: swap_update_two.next_sqrt_price,
+    });
+
+    Ok(())
+}
@@ -11,7 +11,10 @@ use crate::{
     errors::ErrorCode,
     manager::swap_manager::*,
     state::{Pool, TickArray},
-    util::{to_timestamp_u64, v2::update_and_swap_pool_v2, SwapTickSequence},
+    util::{
+        to_timestamp_u64, v2::update_and_swap_pool_v2, verify_tick_array_accounts,
+        SwapTickSequence, MAX_SWAP_TICK_ARRAYS,
+    },
 };
 
 #[derive(Accounts)]
@@ -54,9 +57,10 @@ pub struct SwapV2<'info> {
     // remaining accounts
     // - accounts for transfer hook program of token_mint_a
     // - accounts for transfer hook program of token_mint_b
+    // - supplemental tick arrays, following tick_array_2 in the swap direction
 }
 
-pub fn handler<'a, 'b, 'c, 'info>(
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, SwapV2<'info>>,
     amount: u64,
     other_amount_threshold: u64,
@@ -69,19 +73,39 @@ pub fn handler<'a, 'b, 'c, 'info>(
     let clock = Clock::get()?;
     // Update the global reward growth which increases as a function of time.
     let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    pool.verify_swap_amount(amount)?;
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
         &ctx.remaining_accounts,
         &remaining_accounts_info,
-        &[AccountsType::TransferHookA, AccountsType::TransferHookB],
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+        ],
     )?;
 
-    let mut swap_tick_sequence = SwapTickSequence::new(
-        ctx.accounts.tick_array_0.load_mut()?,
-        ctx.accounts.tick_array_1.load_mut().ok(),
-        ctx.accounts.tick_array_2.load_mut().ok(),
-    );
+    let supplemental_tick_arrays =
+        load_supplemental_tick_arrays(pool.key(), remaining_accounts.supplemental_tick_arrays)?;
+    verify_tick_array_accounts(&[
+        ctx.accounts.tick_array_0.key(),
+        ctx.accounts.tick_array_1.key(),
+        ctx.accounts.tick_array_2.key(),
+    ])?;
+    let mut swap_tick_sequence = SwapTickSequence::from_accounts(
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+    )?
+    .with_supplemental_arrays(
+        supplemental_tick_arrays
+            .iter()
+            .map(|tick_array| tick_array.load_mut())
+            .collect::<Result<Vec<_>>>()?,
+        pool.tick_spacing,
+        a_to_b,
+    )?;
 
     let swap_update = swap_with_transfer_fee_extension(
         &pool,
@@ -144,6 +168,31 @@ pub fn handler<'a, 'b, 'c, 'info>(
     )
 }
 
+pub fn load_supplemental_tick_arrays<'info>(
+    pool: Pubkey,
+    accounts: Option<&'info [AccountInfo<'info>]>,
+) -> Result<Vec<AccountLoader<'info, TickArray>>> {
+    let accounts = match accounts {
+        Some(accounts) => accounts,
+        None => return Ok(vec![]),
+    };
+    // tick_array_0, tick_array_1 and tick_array_2 are always passed
+    if accounts.len() > MAX_SWAP_TICK_ARRAYS - 3 {
+        return Err(ErrorCode::RemainingAccountsInvalidSlice.into());
+    }
+
+    let tick_arrays = accounts
+        .iter()
+        .map(|account_info| AccountLoader::<TickArray>::try_from(account_info))
+        .collect::<Result<Vec<_>>>()?;
+    for tick_array in tick_arrays.iter() {
+        if tick_array.load()?.pool != pool {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+    }
+    Ok(tick_arrays)
+}
+
 pub fn swap_with_transfer_fee_extension<'info>(
     pool: &Pool,
     token_mint_a: &InterfaceAccount<'info, Mint>,
@@ -154,6 +203,37 @@ pub fn swap_with_transfer_fee_extension<'info>(
     amount_specified_is_input: bool,
     a_to_b: bool,
     timestamp: u64,
+) -> Result<PostSwapUpdate> {
+    swap_with_transfer_fee_extension_and_fee_rate(
+        pool,
+        token_mint_a,
+        token_mint_b,
+        swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        pool.fee_rate,
+        0,
+    )
+}
+
+/// Same as `swap_with_transfer_fee_extension`, but the swap is charged `fee_rate` instead of the
+/// fee rate of the pool, e.g. a fee rate reduced by an LP fee rebate, and `referral_fee_rate`
+/// basis points of the LP fee are split off into `next_referral_fee`.
+pub fn swap_with_transfer_fee_extension_and_fee_rate<'info>(
+    pool: &Pool,
+    token_mint_a: &InterfaceAccount<'info, Mint>,
+    token_mint_b: &InterfaceAccount<'info, Mint>,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+    fee_rate: u16,
+    referral_fee_rate: u16,
 ) -> Result<PostSwapUpdate> {
     let (input_token_mint, output_token_mint) = if a_to_b {
         (token_mint_a, token_mint_b)
@@ -168,7 +248,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
             calculate_transfer_fee_excluded_amount(input_token_mint, transfer_fee_included_input)?
                 .amount;
 
-        let swap_update = swap(
+        let swap_update = swap_with_fee_rate(
             pool,
             swap_tick_sequence,
             transfer_fee_excluded_input,
@@ -176,6 +256,8 @@ pub fn swap_with_transfer_fee_extension<'info>(
             amount_specified_is_input,
             a_to_b,
             timestamp,
+            fee_rate,
+            referral_fee_rate,
         )?;
 
         let (swap_update_amount_input, swap_update_amount_output) = if a_to_b {
@@ -215,7 +297,9 @@ pub fn swap_with_transfer_fee_extension<'info>(
             next_fee_growth_global: swap_update.next_fee_growth_global,
             next_reward_infos: swap_update.next_reward_infos,
             next_protocol_fee: swap_update.next_protocol_fee,
+            next_referral_fee: swap_update.next_referral_fee,
             fee: swap_update.fee,
+            tick_crossed_count: swap_update.tick_crossed_count,
         });
     }
 
@@ -225,7 +309,7 @@ pub fn swap_with_transfer_fee_extension<'info>(
         calculate_transfer_fee_included_amount(output_token_mint, transfer_fee_excluded_output)?
             .amount;
 
-    let swap_update = swap(
+    let swap_update = swap_with_fee_rate(
         pool,
         swap_tick_sequence,
         transfer_fee_included_output,
@@ -233,6 +317,8 @@ pub fn swap_with_transfer_fee_extension<'info>(
         amount_specified_is_input,
         a_to_b,
         timestamp,
+        fee_rate,
+        referral_fee_rate,
     )?;
 
     let (swap_update_amount_input, swap_update_amount_output) = if a_to_b {
@@ -266,6 +352,8 @@ pub fn swap_with_transfer_fee_extension<'info>(
         next_fee_growth_global: swap_update.next_fee_growth_global,
         next_reward_infos: swap_update.next_reward_infos,
         next_protocol_fee: swap_update.next_protocol_fee,
+        next_referral_fee: swap_update.next_referral_fee,
         fee: swap_update.fee,
+        tick_crossed_count: swap_update.tick_crossed_count,
     })
 }
@@ -0,0 +1,406 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::util::{
+    calculate_transfer_fee_excluded_amount, parse_remaining_accounts, AccountsType,
+    RemainingAccountsInfo,
+};
+use crate::{
+    constants::transfer_memo,
+    errors::ErrorCode,
+    events,
+    manager::swap_manager::*,
+    state::{
+        apply_lp_fee_rebate, LpFeeRebateConfig, PoolsConfig, PoolsConfigExtension, Position,
+        PositionSwapCount, SharedDeadlineContext, TickArrayCache,
+    },
+    util::{
+        consume_deadline_context, get_streamed_protocol_fee, to_timestamp_u64,
+        v2::{pay_referral_fee_v2, stream_protocol_fee_v2, update_and_swap_pool_v2},
+        verify_tick_array_accounts, SwapTickSequence,
+    },
+};
+
+use super::swap::*;
+
+/// Options of a `swap_v3`. The options are versioned so that options added later do not change
+/// the encoding of the earlier versions. A new version is added as a new variant.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum SwapOptions {
+    V1(SwapOptionsV1),
+}
+
+impl Default for SwapOptions {
+    fn default() -> Self {
+        SwapOptions::V1(SwapOptionsV1::default())
+    }
+}
+
+impl SwapOptions {
+    /// The options as of the latest version
+    pub fn latest(self) -> SwapOptionsV1 {
+        match self {
+            SwapOptions::V1(options) => options,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SwapOptionsV1 {
+    pub expected_tick_index: Option<i32>,
+    pub max_tick_deviation: u16,
+    pub use_cache: bool,
+    pub referral_fee_rate: u16,
+    pub max_tick_crossings: u8,
+    pub tick_limit: Option<i32>,
+}
+
+#[derive(Accounts)]
+pub struct SwapV3<'info> {
+    pub swap: SwapV2<'info>,
+
+    #[account(mut, constraint = deadline_context.authority == swap.token_authority.key())]
+    pub deadline_context: Option<Box<Account<'info, SharedDeadlineContext>>>,
+
+    #[account(address = swap.pool.pools_config)]
+    pub pools_config: Box<Account<'info, PoolsConfig>>,
+
+    #[account(constraint = pools_config_extension.pools_config == swap.pool.pools_config)]
+    pub pools_config_extension: Option<Box<Account<'info, PoolsConfigExtension>>>,
+
+    #[account(mut)]
+    pub protocol_treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(constraint = tick_array_cache_0.tick_array == swap.tick_array_0.key())]
+    pub tick_array_cache_0: Option<Box<Account<'info, TickArrayCache>>>,
+
+    #[account(constraint = tick_array_cache_1.tick_array == swap.tick_array_1.key())]
+    pub tick_array_cache_1: Option<Box<Account<'info, TickArrayCache>>>,
+
+    #[account(constraint = tick_array_cache_2.tick_array == swap.tick_array_2.key())]
+    pub tick_array_cache_2: Option<Box<Account<'info, TickArrayCache>>>,
+
+    #[account(mut)]
+    pub referral_fee_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(constraint = lp_fee_rebate_config.pool == swap.pool.key())]
+    pub lp_fee_rebate_config: Option<Box<Account<'info, LpFeeRebateConfig>>>,
+
+    #[account(mut,
+      constraint = position_swap_count.pool == swap.pool.key() @ ErrorCode::InvalidFeeRebatePosition
+    )]
+    pub position_swap_count: Option<Box<Account<'info, PositionSwapCount>>>,
+
+    pub rebate_position: Option<Box<Account<'info, Position>>>,
+
+    #[account(
+      constraint = rebate_position_token_account.owner == swap.token_authority.key()
+        @ ErrorCode::InvalidFeeRebatePosition
+    )]
+    pub rebate_position_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    // remaining accounts
+    // - accounts for transfer hook program of token_mint_a
+    // - accounts for transfer hook program of token_mint_b
+    // - supplemental tick arrays, following tick_array_2 in the swap direction
+}
+
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SwapV3<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool, // Zero for one
+    options: SwapOptions,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let SwapOptionsV1 {
+        expected_tick_index,
+        max_tick_deviation,
+        use_cache,
+        referral_fee_rate,
+        max_tick_crossings,
+        tick_limit,
+    } = options.latest();
+
+    let accounts = &mut *ctx.accounts;
+    let swap = &mut accounts.swap;
+    let pool = &mut swap.pool;
+    let clock = Clock::get()?;
+    // Update the global reward growth which increases as a function of time.
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+    consume_deadline_context(&mut accounts.deadline_context, timestamp)?;
+    check_expected_tick_index(
+        pool.tick_current_index,
+        expected_tick_index,
+        max_tick_deviation,
+    )?;
+    pool.verify_swap_amount(amount)?;
+    let sqrt_price_limit =
+        resolve_sqrt_price_limit(pool.sqrt_price, sqrt_price_limit, tick_limit, a_to_b)?;
+
+    // Process remaining accounts
+    let remaining_accounts = parse_remaining_accounts(
+        &ctx.remaining_accounts,
+        &remaining_accounts_info,
+        &[
+            AccountsType::TransferHookA,
+            AccountsType::TransferHookB,
+            AccountsType::SupplementalTickArrays,
+        ],
+    )?;
+
+    let supplemental_tick_arrays =
+        load_supplemental_tick_arrays(pool.key(), remaining_accounts.supplemental_tick_arrays)?;
+    verify_tick_array_accounts(&[
+        swap.tick_array_0.key(),
+        swap.tick_array_1.key(),
+        swap.tick_array_2.key(),
+    ])?;
+    let mut swap_tick_sequence = SwapTickSequence::from_accounts(
+        &swap.tick_array_0,
+        &swap.tick_array_1,
+        &swap.tick_array_2,
+    )?
+    .with_supplemental_arrays(
+        supplemental_tick_arrays
+            .iter()
+            .map(|tick_array| tick_array.load_mut())
+            .collect::<Result<Vec<_>>>()?,
+        pool.tick_spacing,
+        a_to_b,
+    )?
+    .with_max_tick_crossings(max_tick_crossings);
+    if use_cache {
+        // Expired or stale cache entries fall back to scanning the tick arrays
+        let summaries = [
+            &accounts.tick_array_cache_0,
+            &accounts.tick_array_cache_1,
+            &accounts.tick_array_cache_2,
+        ]
+        .iter()
+        .map(|cache| {
+            cache
+                .as_ref()
+                .and_then(|cache| cache.get_valid_summary(pool, clock.slot))
+        })
+        .collect();
+        swap_tick_sequence = swap_tick_sequence.with_summaries(summaries);
+    }
+
+    // Referral fees are only routed when the swap names a referral account
+    let fee_mint = if a_to_b {
+        pool.token_mint_a
+    } else {
+        pool.token_mint_b
+    };
+    let referral_fee_rate = match &accounts.referral_fee_account {
+        Some(referral_fee_account) if referral_fee_rate > 0 => {
+            if referral_fee_account.mint != fee_mint {
+                return Err(ErrorCode::InvalidReferralFeeAccount.into());
+            }
+            accounts
+                .pools_config
+                .verify_referral_fee_rate(referral_fee_rate)?;
+            referral_fee_rate
+        }
+        _ => 0,
+    };
+
+    // A swapper holding a position with liquidity in the pool earns the LP fee rebate once the
+    // swaps counted for the position reach the threshold of the pool
+    if let Some(position_swap_count) = &accounts.position_swap_count {
+        position_swap_count.verify_rebate_position(
+            accounts
+                .rebate_position
+                .as_ref()
+                .map(|position| (position.key(), position.liquidity)),
+            accounts
+                .rebate_position_token_account
+                .as_ref()
+                .map(|account| (account.mint, account.amount)),
+        )?;
+    }
+    let fee_rate = match (
+        &accounts.lp_fee_rebate_config,
+        &accounts.position_swap_count,
+    ) {
+        (Some(lp_fee_rebate_config), Some(position_swap_count)) => apply_lp_fee_rebate(
+            pool.fee_rate,
+            lp_fee_rebate_config.rebate_bps_for(position_swap_count.swap_count_used),
+        ),
+        _ => pool.fee_rate,
+    };
+
+    let mut swap_update = swap_with_transfer_fee_extension_and_fee_rate(
+        &pool,
+        &swap.token_mint_a,
+        &swap.token_mint_b,
+        &mut swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        fee_rate,
+        referral_fee_rate,
+    )?;
+
+    // The threshold applies to the partial amounts of a swap stopped by the tick crossing limit
+    if amount_specified_is_input {
+        let transfer_fee_excluded_output_amount = if a_to_b {
+            calculate_transfer_fee_excluded_amount(&swap.token_mint_b, swap_update.amount_b)?.amount
+        } else {
+            calculate_transfer_fee_excluded_amount(&swap.token_mint_a, swap_update.amount_a)?.amount
+        };
+        if transfer_fee_excluded_output_amount < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimum.into());
+        }
+    } else {
+        let transfer_fee_included_input_amount = if a_to_b {
+            swap_update.amount_a
+        } else {
+            swap_update.amount_b
+        };
+        if transfer_fee_included_input_amount > other_amount_threshold {
+            return Err(ErrorCode::AmountInAboveMaximum.into());
+        }
+    }
+
+    let tick_crossing_limit = tick_crossing_limit_reached(
+        &swap_update,
+        max_tick_crossings,
+        amount,
+        amount_specified_is_input,
+        a_to_b,
+    );
+
+    let streamed_protocol_fee = get_streamed_protocol_fee(
+        &accounts.pools_config,
+        accounts
+            .pools_config_extension
+            .as_ref()
+            .map(|extension| extension.protocol_treasury),
+        accounts
+            .protocol_treasury_token_account
+            .as_ref()
+            .map(|account| (account.owner, account.mint)),
+        fee_mint,
+        swap_update.next_protocol_fee,
+    )?;
+    // Streamed protocol fee is transferred out below instead of accruing to the pool
+    swap_update.next_protocol_fee -= streamed_protocol_fee;
+    let referral_fee = swap_update.next_referral_fee;
+
+    update_and_swap_pool_v2(
+        pool,
+        &swap.token_authority,
+        &swap.token_mint_a,
+        &swap.token_mint_b,
+        &swap.token_owner_account_a,
+        &swap.token_owner_account_b,
+        &swap.token_vault_a,
+        &swap.token_vault_b,
+        &remaining_accounts.transfer_hook_a,
+        &remaining_accounts.transfer_hook_b,
+        &swap.token_program_a,
+        &swap.token_program_b,
+        &swap.memo_program,
+        swap_update,
+        a_to_b,
+        timestamp,
+        transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
+    )?;
+
+    let (token_mint, token_vault, token_program, transfer_hook_accounts) = if a_to_b {
+        (
+            &swap.token_mint_a,
+            &swap.token_vault_a,
+            &swap.token_program_a,
+            &remaining_accounts.transfer_hook_a,
+        )
+    } else {
+        (
+            &swap.token_mint_b,
+            &swap.token_vault_b,
+            &swap.token_program_b,
+            &remaining_accounts.transfer_hook_b,
+        )
+    };
+    if streamed_protocol_fee > 0 {
+        if let Some(treasury_token_account) = &accounts.protocol_treasury_token_account {
+            stream_protocol_fee_v2(
+                pool,
+                token_mint,
+                token_vault,
+                treasury_token_account,
+                token_program,
+                &swap.memo_program,
+                transfer_hook_accounts,
+                streamed_protocol_fee,
+            )?;
+        }
+    }
+    if referral_fee > 0 {
+        if let Some(referral_fee_account) = &accounts.referral_fee_account {
+            pay_referral_fee_v2(
+                pool,
+                token_mint,
+                token_vault,
+                referral_fee_account,
+                token_program,
+                &swap.memo_program,
+                transfer_hook_accounts,
+                referral_fee_rate,
+                referral_fee,
+            )?;
+        }
+    }
+
+    if let Some((amount_filled, amount_remaining)) = tick_crossing_limit {
+        emit!(events::SwapTickCrossingLimitReachedEvent {
+            pool: pool.key(),
+            crossings_limit: max_tick_crossings,
+            amount_filled,
+            amount_remaining,
+        });
+    }
+    if let Some(position_swap_count) = &mut accounts.position_swap_count {
+        position_swap_count.increment_position_swap_count();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod swap_options_tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_options_v1_encoding() {
+        let options = SwapOptions::V1(SwapOptionsV1 {
+            expected_tick_index: Some(-100),
+            max_tick_deviation: 8,
+            use_cache: true,
+            referral_fee_rate: 50,
+            max_tick_crossings: 4,
+            tick_limit: None,
+        });
+        let encoded = options.try_to_vec().unwrap();
+        // Version tag, then the options in order
+        assert_eq!(encoded[0], 0);
+        assert_eq!(&encoded[1..6], &[1, 0x9c, 0xff, 0xff, 0xff]);
+        assert_eq!(encoded.len(), 1 + 5 + 2 + 1 + 2 + 1 + 1);
+        assert_eq!(SwapOptions::try_from_slice(&encoded).unwrap(), options);
+    }
+
+    #[test]
+    fn test_default_swap_options() {
+        let options = SwapOptions::default().latest();
+        assert_eq!(options.expected_tick_index, None);
+        assert_eq!(options.max_tick_crossings, 0);
+        assert_eq!(options.tick_limit, None);
+        assert!(!options.use_cache);
+        assert_eq!(options.referral_fee_rate, 0);
+    }
+}
@@ -10,8 +10,13 @@ use crate::util::{
 use crate::{
     constants::transfer_memo,
     errors::ErrorCode,
+    events,
+    manager::swap_manager::{
+        check_intermediate_amount, check_two_hop_route, is_second_hop_input_mint,
+        tick_crossing_limit_reached, PostSwapUpdate,
+    },
     state::{Pool, TickArray},
-    util::{to_timestamp_u64, SwapTickSequence},
+    util::{to_timestamp_u64, verify_two_hop_tick_array_accounts, SwapTickSequence},
 };
 
 #[derive(Accounts)]
@@ -30,7 +35,11 @@ pub struct TwoHopSwapV2<'info> {
 
     #[account(address = pool_one.input_token_mint(a_to_b_one))]
     pub token_mint_input: InterfaceAccount<'info, Mint>,
-    #[account(address = pool_one.output_token_mint(a_to_b_one))]
+    #[account(
+        address = pool_one.output_token_mint(a_to_b_one),
+        constraint = is_second_hop_input_mint(&pool_two, a_to_b_two, token_mint_intermediate.key())
+            @ ErrorCode::InvalidIntermediaryMint,
+    )]
     pub token_mint_intermediate: InterfaceAccount<'info, Mint>,
     #[account(address = pool_two.output_token_mint(a_to_b_two))]
     pub token_mint_output: InterfaceAccount<'info, Mint>,
@@ -93,6 +102,34 @@ pub fn handler<'a, 'b, 'c, 'info>(
     sqrt_price_limit_one: u128,
     sqrt_price_limit_two: u128,
     remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    execute_two_hop_swap_v2(
+        ctx,
+        amount,
+        other_amount_threshold,
+        amount_specified_is_input,
+        a_to_b_one,
+        a_to_b_two,
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
+        remaining_accounts_info,
+        None,
+        0,
+    )
+}
+
+pub fn execute_two_hop_swap_v2<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, TwoHopSwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+    intermediate_threshold: Option<u64>,
+    max_tick_crossings: u8,
 ) -> Result<()> {
     let clock = Clock::get()?;
     // Update the global reward growth which increases as a function of time.
@@ -106,20 +143,8 @@ pub fn handler<'a, 'b, 'c, 'info>(
         return Err(ErrorCode::DuplicateTwoHopPool.into());
     }
 
-    let swap_one_output_mint = if a_to_b_one {
-        pool_one.token_mint_b
-    } else {
-        pool_one.token_mint_a
-    };
-
-    let swap_two_input_mint = if a_to_b_two {
-        pool_two.token_mint_a
-    } else {
-        pool_two.token_mint_b
-    };
-    if swap_one_output_mint != swap_two_input_mint {
-        return Err(ErrorCode::InvalidIntermediaryMint.into());
-    }
+    // Also enforced by the token_mint_intermediate constraint, kept as a fallback
+    check_two_hop_route(pool_one, pool_two, a_to_b_one, a_to_b_two)?;
 
     // Process remaining accounts
     let remaining_accounts = parse_remaining_accounts(
@@ -132,148 +157,79 @@ pub fn handler<'a, 'b, 'c, 'info>(
         ],
     )?;
 
-    let mut swap_tick_sequence_one = SwapTickSequence::new(
-        ctx.accounts.tick_array_one_0.load_mut()?,
-        ctx.accounts.tick_array_one_1.load_mut().ok(),
-        ctx.accounts.tick_array_one_2.load_mut().ok(),
-    );
+    verify_two_hop_tick_array_accounts(
+        &[
+            ctx.accounts.tick_array_one_0.key(),
+            ctx.accounts.tick_array_one_1.key(),
+            ctx.accounts.tick_array_one_2.key(),
+        ],
+        &[
+            ctx.accounts.tick_array_two_0.key(),
+            ctx.accounts.tick_array_two_1.key(),
+            ctx.accounts.tick_array_two_2.key(),
+        ],
+    )?;
 
-    let mut swap_tick_sequence_two = SwapTickSequence::new(
-        ctx.accounts.tick_array_two_0.load_mut()?,
-        ctx.accounts.tick_array_two_1.load_mut().ok(),
-        ctx.accounts.tick_array_two_2.load_mut().ok(),
-    );
+    // The tick crossing limit applies to the hop calculated first, the other hop only swaps the
+    // intermediate amount of the (partial) first calculation
+    let (max_tick_crossings_one, max_tick_crossings_two) = if amount_specified_is_input {
+        (max_tick_crossings, 0)
+    } else {
+        (0, max_tick_crossings)
+    };
 
-    // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
-    // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
-    let (swap_update_one, swap_update_two) = if amount_specified_is_input {
-        // If the amount specified is input, this means we are doing exact-in
-        // and the swap calculations occur from Swap 1 => Swap 2
-        // and the swaps occur from Swap 1 => Swap 2
-        let swap_calc_one = swap_with_transfer_fee_extension(
-            &pool_one,
-            if a_to_b_one {
-                &ctx.accounts.token_mint_input
-            } else {
-                &ctx.accounts.token_mint_intermediate
-            },
-            if a_to_b_one {
-                &ctx.accounts.token_mint_intermediate
-            } else {
-                &ctx.accounts.token_mint_input
-            },
-            &mut swap_tick_sequence_one,
+    let mut swap_tick_sequence_one = SwapTickSequence::from_accounts(
+        &ctx.accounts.tick_array_one_0,
+        &ctx.accounts.tick_array_one_1,
+        &ctx.accounts.tick_array_one_2,
+    )?
+    .with_max_tick_crossings(max_tick_crossings_one);
+
+    let mut swap_tick_sequence_two = SwapTickSequence::from_accounts(
+        &ctx.accounts.tick_array_two_0,
+        &ctx.accounts.tick_array_two_1,
+        &ctx.accounts.tick_array_two_2,
+    )?
+    .with_max_tick_crossings(max_tick_crossings_two);
+
+    let (swap_update_one, swap_update_two) = two_hop_swap_with_transfer_fee_extension(
+        &pool_one,
+        &pool_two,
+        &ctx.accounts.token_mint_input,
+        &ctx.accounts.token_mint_intermediate,
+        &ctx.accounts.token_mint_output,
+        &mut swap_tick_sequence_one,
+        &mut swap_tick_sequence_two,
+        amount,
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
+        amount_specified_is_input,
+        a_to_b_one,
+        a_to_b_two,
+        timestamp,
+        intermediate_threshold,
+    )?;
+
+    let tick_crossing_limit = if amount_specified_is_input {
+        tick_crossing_limit_reached(
+            &swap_update_one,
+            max_tick_crossings,
             amount,
-            sqrt_price_limit_one,
-            amount_specified_is_input, // true
+            true,
             a_to_b_one,
-            timestamp,
-        )?;
-
-        // Swap two input is the output of swap one
-        // We use vault to vault transfer, so transfer fee will be collected once.
-        let swap_two_input_amount = if a_to_b_one {
-            swap_calc_one.amount_b
-        } else {
-            swap_calc_one.amount_a
-        };
-
-        let swap_calc_two = swap_with_transfer_fee_extension(
-            &pool_two,
-            if a_to_b_two {
-                &ctx.accounts.token_mint_intermediate
-            } else {
-                &ctx.accounts.token_mint_output
-            },
-            if a_to_b_two {
-                &ctx.accounts.token_mint_output
-            } else {
-                &ctx.accounts.token_mint_intermediate
-            },
-            &mut swap_tick_sequence_two,
-            swap_two_input_amount,
-            sqrt_price_limit_two,
-            amount_specified_is_input, // true
-            a_to_b_two,
-            timestamp,
-        )?;
-        (swap_calc_one, swap_calc_two)
+        )
+        .map(|amounts| (pool_one.key(), amounts))
     } else {
-        // If the amount specified is output, this means we need to invert the ordering of the calculations
-        // and the swap calculations occur from Swap 2 => Swap 1
-        // but the actual swaps occur from Swap 1 => Swap 2 (to ensure that the intermediate token exists in the account)
-        let swap_calc_two = swap_with_transfer_fee_extension(
-            &pool_two,
-            if a_to_b_two {
-                &ctx.accounts.token_mint_intermediate
-            } else {
-                &ctx.accounts.token_mint_output
-            },
-            if a_to_b_two {
-                &ctx.accounts.token_mint_output
-            } else {
-                &ctx.accounts.token_mint_intermediate
-            },
-            &mut swap_tick_sequence_two,
+        tick_crossing_limit_reached(
+            &swap_update_two,
+            max_tick_crossings,
             amount,
-            sqrt_price_limit_two,
-            amount_specified_is_input, // false
+            false,
             a_to_b_two,
-            timestamp,
-        )?;
-
-        // The output of swap 1 is input of swap_calc_two
-        let swap_one_output_amount = if a_to_b_two {
-            calculate_transfer_fee_excluded_amount(
-                &ctx.accounts.token_mint_intermediate,
-                swap_calc_two.amount_a,
-            )?
-            .amount
-        } else {
-            calculate_transfer_fee_excluded_amount(
-                &ctx.accounts.token_mint_intermediate,
-                swap_calc_two.amount_b,
-            )?
-            .amount
-        };
-
-        let swap_calc_one = swap_with_transfer_fee_extension(
-            &pool_one,
-            if a_to_b_one {
-                &ctx.accounts.token_mint_input
-            } else {
-                &ctx.accounts.token_mint_intermediate
-            },
-            if a_to_b_one {
-                &ctx.accounts.token_mint_intermediate
-            } else {
-                &ctx.accounts.token_mint_input
-            },
-            &mut swap_tick_sequence_one,
-            swap_one_output_amount,
-            sqrt_price_limit_one,
-            amount_specified_is_input, // false
-            a_to_b_one,
-            timestamp,
-        )?;
-        (swap_calc_one, swap_calc_two)
+        )
+        .map(|amounts| (pool_two.key(), amounts))
     };
 
-    // All output token should be consumed by the second swap
-    let swap_calc_one_output = if a_to_b_one {
-        swap_update_one.amount_b
-    } else {
-        swap_update_one.amount_a
-    };
-    let swap_calc_two_input = if a_to_b_two {
-        swap_update_two.amount_a
-    } else {
-        swap_update_two.amount_b
-    };
-    if swap_calc_one_output != swap_calc_two_input {
-        return Err(ErrorCode::IntermediateTokenAmountMismatch.into());
-    }
-
     if amount_specified_is_input {
         // If amount_specified_is_input == true, then we have a variable amount of output
         // The slippage we care about is the output of the second swap.
@@ -376,5 +332,176 @@ pub fn handler<'a, 'b, 'c, 'info>(
         &ctx.accounts.memo_program,
         timestamp,
         transfer_memo::TRANSFER_MEMO_SWAP.as_bytes(),
-    )
+    )?;
+
+    if let Some((pool, (amount_filled, amount_remaining))) = tick_crossing_limit {
+        emit!(events::SwapTickCrossingLimitReachedEvent {
+            pool,
+            crossings_limit: max_tick_crossings,
+            amount_filled,
+            amount_remaining,
+        });
+    }
+
+    Ok(())
+}
+
+/// Calculates both legs of a two-hop swap, including the transfer fee of the intermediate token.
+///
+/// For exact-in, the legs are calculated from swap one to swap two. For exact-out, the legs are
+/// calculated from swap two back to swap one, so that the output of swap one is exactly the input
+/// of swap two. Shared by `two_hop_swap_v2` and `simulate_two_hop_swap` so quotes match execution.
+/// An exact-in route fails before swap two is calculated if swap one produces less than
+/// `intermediate_threshold`.
+pub fn two_hop_swap_with_transfer_fee_extension<'info>(
+    pool_one: &Pool,
+    pool_two: &Pool,
+    token_mint_input: &InterfaceAccount<'info, Mint>,
+    token_mint_intermediate: &InterfaceAccount<'info, Mint>,
+    token_mint_output: &InterfaceAccount<'info, Mint>,
+    swap_tick_sequence_one: &mut SwapTickSequence,
+    swap_tick_sequence_two: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    timestamp: u64,
+    intermediate_threshold: Option<u64>,
+) -> Result<(PostSwapUpdate, PostSwapUpdate)> {
+    // TODO: WLOG, we could extend this to N-swaps, but the account inputs to the instruction would
+    // need to be jankier and we may need to programatically map/verify rather than using anchor constraints
+    let (swap_update_one, swap_update_two) = if amount_specified_is_input {
+        // If the amount specified is input, this means we are doing exact-in
+        // and the swap calculations occur from Swap 1 => Swap 2
+        // and the swaps occur from Swap 1 => Swap 2
+        pool_one.verify_swap_amount(amount)?;
+        let swap_calc_one = swap_with_transfer_fee_extension(
+            pool_one,
+            if a_to_b_one {
+                token_mint_input
+            } else {
+                token_mint_intermediate
+            },
+            if a_to_b_one {
+                token_mint_intermediate
+            } else {
+                token_mint_input
+            },
+            swap_tick_sequence_one,
+            amount,
+            sqrt_price_limit_one,
+            amount_specified_is_input, // true
+            a_to_b_one,
+            timestamp,
+        )?;
+
+        // Stop a route whose first hop underdelivers before calculating the second hop
+        check_intermediate_amount(&swap_calc_one, a_to_b_one, intermediate_threshold)?;
+
+        // Swap two input is the output of swap one
+        // We use vault to vault transfer, so transfer fee will be collected once.
+        let swap_two_input_amount = if a_to_b_one {
+            swap_calc_one.amount_b
+        } else {
+            swap_calc_one.amount_a
+        };
+
+        pool_two.verify_swap_amount(swap_two_input_amount)?;
+
+        let swap_calc_two = swap_with_transfer_fee_extension(
+            pool_two,
+            if a_to_b_two {
+                token_mint_intermediate
+            } else {
+                token_mint_output
+            },
+            if a_to_b_two {
+                token_mint_output
+            } else {
+                token_mint_intermediate
+            },
+            swap_tick_sequence_two,
+            swap_two_input_amount,
+            sqrt_price_limit_two,
+            amount_specified_is_input, // true
+            a_to_b_two,
+            timestamp,
+        )?;
+        (swap_calc_one, swap_calc_two)
+    } else {
+        // If the amount specified is output, this means we need to invert the ordering of the calculations
+        // and the swap calculations occur from Swap 2 => Swap 1
+        // but the actual swaps occur from Swap 1 => Swap 2 (to ensure that the intermediate token exists in the account)
+        pool_two.verify_swap_amount(amount)?;
+        let swap_calc_two = swap_with_transfer_fee_extension(
+            pool_two,
+            if a_to_b_two {
+                token_mint_intermediate
+            } else {
+                token_mint_output
+            },
+            if a_to_b_two {
+                token_mint_output
+            } else {
+                token_mint_intermediate
+            },
+            swap_tick_sequence_two,
+            amount,
+            sqrt_price_limit_two,
+            amount_specified_is_input, // false
+            a_to_b_two,
+            timestamp,
+        )?;
+
+        // The output of swap 1 is input of swap_calc_two
+        let swap_one_output_amount = if a_to_b_two {
+            calculate_transfer_fee_excluded_amount(token_mint_intermediate, swap_calc_two.amount_a)?
+                .amount
+        } else {
+            calculate_transfer_fee_excluded_amount(token_mint_intermediate, swap_calc_two.amount_b)?
+                .amount
+        };
+
+        pool_one.verify_swap_amount(swap_one_output_amount)?;
+
+        let swap_calc_one = swap_with_transfer_fee_extension(
+            pool_one,
+            if a_to_b_one {
+                token_mint_input
+            } else {
+                token_mint_intermediate
+            },
+            if a_to_b_one {
+                token_mint_intermediate
+            } else {
+                token_mint_input
+            },
+            swap_tick_sequence_one,
+            swap_one_output_amount,
+            sqrt_price_limit_one,
+            amount_specified_is_input, // false
+            a_to_b_one,
+            timestamp,
+        )?;
+        (swap_calc_one, swap_calc_two)
+    };
+
+    // All output token should be consumed by the second swap
+    let swap_calc_one_output = if a_to_b_one {
+        swap_update_one.amount_b
+    } else {
+        swap_update_one.amount_a
+    };
+    let swap_calc_two_input = if a_to_b_two {
+        swap_update_two.amount_a
+    } else {
+        swap_update_two.amount_b
+    };
+    if swap_calc_one_output != swap_calc_two_input {
+        return Err(ErrorCode::IntermediateTokenAmountMismatch.into());
+    }
+
+    Ok((swap_update_one, swap_update_two))
 }
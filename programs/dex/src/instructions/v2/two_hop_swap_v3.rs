@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::util::RemainingAccountsInfo;
+
+use super::two_hop_swap::*;
+
+/// Options of a `two_hop_swap_v3`. The options are versioned so that options added later do not
+/// change the encoding of the earlier versions. A new version is added as a new variant.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TwoHopSwapOptions {
+    V1(TwoHopSwapOptionsV1),
+}
+
+impl Default for TwoHopSwapOptions {
+    fn default() -> Self {
+        TwoHopSwapOptions::V1(TwoHopSwapOptionsV1::default())
+    }
+}
+
+impl TwoHopSwapOptions {
+    /// The options as of the latest version
+    pub fn latest(self) -> TwoHopSwapOptionsV1 {
+        match self {
+            TwoHopSwapOptions::V1(options) => options,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TwoHopSwapOptionsV1 {
+    pub intermediate_threshold: Option<u64>,
+    pub max_tick_crossings: u8,
+}
+
+/*
+  Performs a two-hop swap as in `two_hop_swap_v2`, with the options of the route.
+*/
+pub fn handler<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, TwoHopSwapV2<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+    sqrt_price_limit_one: u128,
+    sqrt_price_limit_two: u128,
+    options: TwoHopSwapOptions,
+    remaining_accounts_info: Option<RemainingAccountsInfo>,
+) -> Result<()> {
+    let TwoHopSwapOptionsV1 {
+        intermediate_threshold,
+        max_tick_crossings,
+    } = options.latest();
+
+    execute_two_hop_swap_v2(
+        ctx,
+        amount,
+        other_amount_threshold,
+        amount_specified_is_input,
+        a_to_b_one,
+        a_to_b_two,
+        sqrt_price_limit_one,
+        sqrt_price_limit_two,
+        remaining_accounts_info,
+        intermediate_threshold,
+        max_tick_crossings,
+    )
+}
+
+#[cfg(test)]
+mod two_hop_swap_options_tests {
+    use super::*;
+
+    #[test]
+    fn test_two_hop_swap_options_v1_encoding() {
+        let options = TwoHopSwapOptions::V1(TwoHopSwapOptionsV1 {
+            intermediate_threshold: Some(1_000),
+            max_tick_crossings: 4,
+        });
+        let encoded = options.try_to_vec().unwrap();
+        // Version tag, then the options in order
+        assert_eq!(encoded, vec![0, 1, 0xe8, 0x03, 0, 0, 0, 0, 0, 0, 4]);
+        assert_eq!(
+            TwoHopSwapOptions::try_from_slice(&encoded).unwrap(),
+            options
+        );
+    }
+
+    #[test]
+    fn test_default_two_hop_swap_options() {
+        let options = TwoHopSwapOptions::default().latest();
+        assert_eq!(options.intermediate_threshold, None);
+        assert_eq!(options.max_tick_crossings, 0);
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+use crate::{
+    errors::ErrorCode,
+    manager::position_manager::{BundleValidationResult, BundledPositionStatus},
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ValidatePositionBundle<'info> {
+    pub position_bundle: Box<Account<'info, PositionBundle>>,
+    // remaining accounts
+    // - bundled positions of the open bundle indexes of position_bundle, in ascending order
+}
+
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ValidatePositionBundle<'info>>,
+) -> Result<()> {
+    let position_bundle = &ctx.accounts.position_bundle;
+    let bundle_indexes = position_bundle.open_bundle_indexes();
+
+    if ctx.remaining_accounts.len() > bundle_indexes.len() {
+        return Err(ErrorCode::InvalidBundlePositionAccounts.into());
+    }
+
+    let statuses: Vec<BundledPositionStatus> = bundle_indexes
+        .iter()
+        .enumerate()
+        .map(|(i, bundle_index)| match ctx.remaining_accounts.get(i) {
+            Some(account_info) => {
+                bundled_position_status(position_bundle, *bundle_index, account_info)
+            }
+            None => BundledPositionStatus::Missing,
+        })
+        .collect();
+
+    let result = BundleValidationResult::new(&statuses);
+    set_return_data(&result.try_to_vec()?);
+
+    Ok(())
+}
+
+fn bundled_position_status<'info>(
+    position_bundle: &PositionBundle,
+    bundle_index: u16,
+    account_info: &'info AccountInfo<'info>,
+) -> BundledPositionStatus {
+    if account_info.key() != position_bundle.bundled_position_address(bundle_index) {
+        return BundledPositionStatus::Mismatched;
+    }
+    if is_orphaned_bundled_position(account_info) {
+        return BundledPositionStatus::Orphaned;
+    }
+
+    match Account::<Position>::try_from(account_info) {
+        Ok(position) if position.position_mint == position_bundle.position_bundle_mint => {
+            BundledPositionStatus::Valid
+        }
+        _ => BundledPositionStatus::Mismatched,
+    }
+}
+
+/// A bundled position address whose account was closed, so that it no longer holds a position.
+pub fn is_orphaned_bundled_position(account_info: &AccountInfo) -> bool {
+    account_info.owner != &crate::ID || account_info.data_is_empty()
+}
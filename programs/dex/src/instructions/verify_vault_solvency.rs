@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{events::VaultInsolvencyDetectedEvent, manager::pool_manager::*, state::*};
+
+#[derive(Accounts)]
+pub struct VerifyVaultSolvency<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(address = pool.token_vault_a)]
+    pub token_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.token_vault_b)]
+    pub token_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    // remaining accounts
+    // - positions of pool
+}
+
+pub fn handler<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, VerifyVaultSolvency<'info>>,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    let mut positions = Vec::with_capacity(ctx.remaining_accounts.len());
+    for account_info in ctx.remaining_accounts.iter() {
+        let position: Account<Position> = Account::try_from(account_info)?;
+        if position.pool != pool.key() {
+            return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+        }
+        positions.push(position.into_inner());
+    }
+
+    let result = calculate_vault_solvency(
+        pool,
+        &positions,
+        ctx.accounts.token_vault_a.amount,
+        ctx.accounts.token_vault_b.amount,
+    )?;
+
+    if !result.is_solvent() {
+        emit!(VaultInsolvencyDetectedEvent {
+            pool: pool.key(),
+            shortfall_a: result.shortfall_a,
+            shortfall_b: result.shortfall_b,
+        });
+    }
+
+    set_return_data(&result.try_to_vec()?);
+
+    Ok(())
+}
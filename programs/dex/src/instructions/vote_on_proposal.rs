@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+use crate::{
+    errors::ErrorCode,
+    events::VoteCastEvent,
+    state::*,
+    util::{to_timestamp_u64, transfer_from_owner_to_vault, verify_position_authority},
+};
+
+#[derive(Accounts)]
+pub struct VoteOnProposal<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(constraint = pool.pools_config == proposal.pools_config)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub position_authority: Signer<'info>,
+
+    #[account(has_one = pool)]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(address = position.position_mint)]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut,
+        constraint = position_token_account.mint == position.position_mint,
+        constraint = position_token_account.amount == 1
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    // One vote per position per proposal
+    #[account(init,
+      payer = funder,
+      seeds = [b"vote", proposal.key().as_ref(), position.key().as_ref()],
+      bump,
+      space = ProposalVote::LEN)]
+    pub vote: Account<'info, ProposalVote>,
+
+    // Holds the position NFT until voting has ended
+    #[account(init,
+      payer = funder,
+      associated_token::mint = position_mint,
+      associated_token::authority = vote,
+    )]
+    pub vote_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<VoteOnProposal>, support: bool) -> Result<()> {
+    verify_position_authority(
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.position_authority,
+    )?;
+
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    let pool = &ctx.accounts.pool;
+    let position = &ctx.accounts.position;
+    let liquidity = position.liquidity;
+
+    // Weigh the vote by the value of the position in the vote mint rather than by its raw
+    // liquidity, which is not comparable across ranges or pools
+    let proposal = &mut ctx.accounts.proposal;
+    let value_in_a = if pool.token_mint_a == proposal.vote_mint {
+        true
+    } else if pool.token_mint_b == proposal.vote_mint {
+        false
+    } else {
+        return Err(ErrorCode::InvalidVoteMint.into());
+    };
+    let weight = calculate_vote_weight(
+        liquidity,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        pool.tick_current_index,
+        pool.sqrt_price,
+        value_in_a,
+    )?;
+    proposal.cast_vote(weight, support, timestamp)?;
+
+    ctx.accounts.vote.initialize(
+        proposal.key(),
+        position.key(),
+        liquidity,
+        weight,
+        support,
+        ctx.accounts.position_token_account.owner,
+        position.position_mint,
+        ctx.bumps.vote,
+    )?;
+
+    // Lock the liquidity that was counted until voting has ended
+    transfer_from_owner_to_vault(
+        &ctx.accounts.position_authority,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.vote_position_token_account,
+        &ctx.accounts.token_program,
+        1,
+    )?;
+
+    emit!(VoteCastEvent {
+        proposal: proposal.key(),
+        position: position.key(),
+        support,
+        liquidity,
+        weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+
+use crate::errors::ErrorCode;
+use crate::state::{Pool, TickArray, TickArrayCache, TickArraySummary, MAX_WARM_TICK_ARRAYS};
+
+#[derive(Accounts)]
+pub struct WarmTickArrays<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub pool: Box<Account<'info, Pool>>,
+
+    pub system_program: Program<'info, System>,
+    // remaining accounts
+    // - for each tick array: tick_array, tick_array_cache
+}
+
+/*
+  Writes a summary of the initialized ticks of each tick array to its cache, which swaps in this
+  or the next slot can use instead of scanning the tick array.
+*/
+pub fn handler<'a, 'b, 'info>(
+    ctx: Context<'a, 'b, 'info, 'info, WarmTickArrays<'info>>,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    if remaining_accounts.is_empty()
+        || remaining_accounts.len() % 2 != 0
+        || remaining_accounts.len() / 2 > MAX_WARM_TICK_ARRAYS
+    {
+        return Err(ErrorCode::InvalidTickArrayCacheAccounts.into());
+    }
+
+    let pool = &ctx.accounts.pool;
+    let slot = Clock::get()?.slot;
+
+    for accounts in remaining_accounts.chunks(2) {
+        let (tick_array_info, cache_info) = (&accounts[0], &accounts[1]);
+
+        let summary = {
+            let tick_array = AccountLoader::<TickArray>::try_from(tick_array_info)?;
+            let tick_array = tick_array.load()?;
+            if tick_array.pool != pool.key() {
+                return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+            }
+            TickArraySummary::new(&tick_array, pool.tick_spacing)
+        };
+
+        let (cache_address, cache_bump) =
+            Pubkey::find_program_address(&[b"ta_cache", tick_array_info.key.as_ref()], &crate::ID);
+        if cache_info.key() != cache_address {
+            return Err(anchor_lang::error::ErrorCode::ConstraintSeeds.into());
+        }
+
+        // Caches are created on first use
+        if cache_info.owner == &System::id() {
+            create_account(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    CreateAccount {
+                        from: ctx.accounts.funder.to_account_info(),
+                        to: cache_info.clone(),
+                    },
+                    &[&[b"ta_cache", tick_array_info.key.as_ref(), &[cache_bump]]],
+                ),
+                Rent::get()?.minimum_balance(TickArrayCache::LEN),
+                TickArrayCache::LEN as u64,
+                &crate::ID,
+            )?;
+            TickArrayCache::default()
+                .try_serialize(&mut &mut cache_info.try_borrow_mut_data()?[..])?;
+        }
+
+        let mut cache = Account::<TickArrayCache>::try_from(cache_info)?;
+        cache.update(tick_array_info.key(), pool, summary, slot);
+        // Accounts loaded from remaining accounts are not persisted by Anchor
+        cache.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events;
+use crate::manager::liquidity_manager::{
+    calculate_liquidity_token_deltas, calculate_modify_liquidity, sync_modify_liquidity_values,
+};
+use crate::math::convert_to_liquidity_delta;
+use crate::util::{to_timestamp_u64, transfer_from_vault_to_owner};
+
+use super::ModifyProtocolLiquidity;
+
+/*
+  Removes liquidity from the protocol-owned position once the timelock has expired.
+  Fees earned by the position are swept to the protocol treasury along with the liquidity.
+*/
+pub fn handler(
+    ctx: Context<ModifyProtocolLiquidity>,
+    liquidity_amount: u128,
+    token_min_a: u64,
+    token_min_b: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    if liquidity_amount == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+    let liquidity_delta =
+        convert_to_liquidity_delta(liquidity_amount, false).map_err(ErrorCode::from)?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    ctx.accounts
+        .protocol_liquidity_manager
+        .check_withdraw_unlocked(timestamp)?;
+
+    let update = calculate_modify_liquidity(
+        &ctx.accounts.pool,
+        &ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        liquidity_delta,
+        timestamp,
+    )?;
+
+    sync_modify_liquidity_values(
+        &mut ctx.accounts.pool,
+        &mut ctx.accounts.position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        update,
+        timestamp,
+    )?;
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        ctx.accounts.pool.tick_current_index,
+        ctx.accounts.pool.sqrt_price,
+        &ctx.accounts.position,
+        liquidity_delta,
+    )?;
+
+    if delta_a < token_min_a {
+        return Err(ErrorCode::TokenMinSubceeded.into());
+    } else if delta_b < token_min_b {
+        return Err(ErrorCode::TokenMinSubceeded.into());
+    }
+
+    let position = &mut ctx.accounts.position;
+    let fee_owed_a = position.fee_owed_a;
+    let fee_owed_b = position.fee_owed_b;
+    position.reset_fees_owed();
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_vault_a,
+        &ctx.accounts.treasury_token_account_a,
+        &ctx.accounts.token_program,
+        delta_a
+            .checked_add(fee_owed_a)
+            .ok_or(ErrorCode::TokenMaxExceeded)?,
+    )?;
+
+    transfer_from_vault_to_owner(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_vault_b,
+        &ctx.accounts.treasury_token_account_b,
+        &ctx.accounts.token_program,
+        delta_b
+            .checked_add(fee_owed_b)
+            .ok_or(ErrorCode::TokenMaxExceeded)?,
+    )?;
+
+    emit!(events::ProtocolLiquidityWithdrawnEvent {
+        position: ctx.accounts.position.key(),
+        liquidity: liquidity_amount,
+        amount_a: delta_a,
+        amount_b: delta_b,
+        fee_a: fee_owed_a,
+        fee_b: fee_owed_b,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::{
+    state::*,
+    util::{to_timestamp_u64, transfer_position_token_from_vote_and_close},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawVotePosition<'info> {
+    #[account(mut,
+      close = receiver,
+      seeds = [b"vote", vote.proposal.as_ref(), vote.position.as_ref()],
+      bump,
+    )]
+    pub vote: Account<'info, ProposalVote>,
+
+    #[account(address = vote.proposal)]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(mut,
+      constraint = vote_position_token_account.mint == vote.position_mint,
+      constraint = vote_position_token_account.owner == vote.key(),
+    )]
+    pub vote_position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+      constraint = position_token_account.mint == vote.position_mint,
+      constraint = position_token_account.owner == vote.owner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: safe, for receiving rent only
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/*
+  Returns the position NFT held in escrow by a vote to the voter once voting has ended, and closes
+  the vote. Anyone may return the NFT, it can only go to a token account of the voter.
+*/
+pub fn handler(ctx: Context<WithdrawVotePosition>) -> Result<()> {
+    let clock = Clock::get()?;
+    let timestamp = to_timestamp_u64(clock.unix_timestamp)?;
+
+    ctx.accounts.proposal.check_voting_ended(timestamp)?;
+
+    transfer_position_token_from_vote_and_close(
+        &ctx.accounts.vote,
+        &ctx.accounts.receiver,
+        &ctx.accounts.vote_position_token_account,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+    )
+}
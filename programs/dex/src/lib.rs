@@ -20,7 +20,9 @@ pub mod state;
 #[doc(hidden)]
 pub mod util;
 
-use crate::state::{OpenPositionBumps, OpenPositionWithMetadataBumps, PoolBumps};
+use crate::state::{
+    OpenPositionBumps, OpenPositionWithMetadataBumps, PoolBumps, RewardEmissionsSchedulePoint,
+};
 use crate::util::RemainingAccountsInfo;
 use instructions::*;
 
@@ -63,6 +65,8 @@ pub mod anchor_dex {
     /// #### Special Errors
     /// `InvalidTokenMintOrder` - The order of mints have to be ordered by
     /// `SqrtPriceOutOfBounds` - provided initial_sqrt_price is not between 2^-64 to 2^64
+    /// `TickSpacingFeeTierMismatch` - The tick spacing of the fee tier differs from `tick_spacing`
+    /// `VaultNotEmpty` - A token vault holds tokens before the pool was created
     ///
     pub fn initialize_pool(
         ctx: Context<InitializePool>,
@@ -78,6 +82,36 @@ pub mod anchor_dex {
         );
     }
 
+    /// Initializes a pool account together with the three tick-arrays around its initial price,
+    /// so that the pool can be traded as soon as liquidity is added.
+    /// Fee rate is set to the default values on the config and supplied fee_tier.
+    ///
+    /// ### Parameters
+    /// - `bumps` - The bump value when deriving the PDA of the pool address.
+    /// - `tick_spacing` - The desired tick spacing for this pool.
+    /// - `initial_sqrt_price` - The desired initial sqrt-price for this pool
+    ///
+    /// #### Special Errors
+    /// `InvalidTokenMintOrder` - The order of mints have to be ordered by
+    /// `SqrtPriceOutOfBounds` - provided initial_sqrt_price is not between 2^-64 to 2^64
+    /// `TickSpacingFeeTierMismatch` - The tick spacing of the fee tier differs from `tick_spacing`
+    /// `VaultNotEmpty` - A token vault holds tokens before the pool was created
+    /// `InvalidStartTick` - The tick spacing does not leave room for three tick-arrays
+    ///
+    pub fn initialize_pool_with_tick_arrays(
+        ctx: Context<InitializePoolWithTickArrays>,
+        bumps: PoolBumps,
+        tick_spacing: u16,
+        initial_sqrt_price: u128,
+    ) -> Result<()> {
+        return instructions::initialize_pool_with_tick_arrays::handler(
+            ctx,
+            bumps,
+            tick_spacing,
+            initial_sqrt_price,
+        );
+    }
+
     pub fn initialize_tokens_pool(
         ctx: Context<InitializePoolTokens>,
         bumps: PoolBumps,
@@ -104,7 +138,8 @@ pub mod anchor_dex {
     /// Initializes a fee_tier account usable by Dex_X  in a poolConfig space.
     ///
     /// ### Authority
-    /// - "fee_authority" - Set authority in the poolConfig
+    /// - "fee_authority" - Set authority in the poolConfig, or its operations authority. The fee
+    ///                     tier authority of the poolConfig while fee tier creation is locked.
     ///
     /// ### Parameters
     /// - `tick_spacing` - The tick-spacing that this fee-tier suggests the default_fee_rate for.
@@ -121,6 +156,17 @@ pub mod anchor_dex {
         return instructions::initialize_fee_tier::handler(ctx, tick_spacing, default_fee_rate);
     }
 
+    /// Decrement the pool count of a fee tier when one of its pools is archived.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the poolConfig
+    ///
+    /// #### Special Errors
+    /// - `FeeTierPoolCountZero` - The pool count of the fee tier is already zero.
+    pub fn decrement_pool_count(ctx: Context<DecrementPoolCount>) -> Result<()> {
+        return instructions::decrement_pool_count::handler(ctx);
+    }
+
     /// Initialize reward for a pool. A pool can only support up to a set number of rewards.
     ///
     /// ### Authority
@@ -138,7 +184,8 @@ pub mod anchor_dex {
         return instructions::initialize_reward::handler(ctx, reward_index);
     }
 
-    /// Set the reward emissions for a reward in a pool.
+    /// Set the reward emissions for a reward in a pool. Replaces any emissions schedule of the
+    /// reward.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -167,6 +214,108 @@ pub mod anchor_dex {
         );
     }
 
+    /// Set a schedule of emission rates for a reward in a pool. Each point sets the rate from its
+    /// start timestamp until the next point. Nothing is emitted before the first point, and after
+    /// the last point the reward keeps its final rate if `hold_final_rate` is set, otherwise the
+    /// last point ends the emissions. Replaces any previous schedule of the reward.
+    ///
+    /// The schedule is stored in the pool, so swaps, liquidity changes and fee and reward updates
+    /// accrue the reward at the rate of each interval of the schedule. Setting the emission rate of
+    /// the reward with `set_reward_emissions` removes its schedule.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
+    ///                        reward-index in this pool
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index <= NUM_REWARDS) that we'd like to modify.
+    /// - `points` - Up to 8 `(start_timestamp, emissions_per_second_x64)` points.
+    /// - `hold_final_rate` - Whether the rate of the last point is held after it starts.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardEmissionsSchedule` - No points, more than 8 points, or start timestamps that
+    ///                                      are not strictly increasing.
+    pub fn set_reward_emissions_schedule(
+        ctx: Context<SetRewardEmissionsSchedule>,
+        reward_index: u8,
+        points: Vec<RewardEmissionsSchedulePoint>,
+        hold_final_rate: bool,
+    ) -> Result<()> {
+        return instructions::set_reward_emissions_schedule::handler(
+            ctx,
+            reward_index,
+            points,
+            hold_final_rate,
+        );
+    }
+
+    /// Pause the emissions of a reward in a pool. The reward stops accruing at the current
+    /// timestamp, rewards accrued before the pause remain collectable.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
+    ///                        reward-index in this pool
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index <= NUM_REWARDS) that we'd like to pause.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    /// - `InvalidRewardIndex` - If the provided reward index exceeds NUM_REWARDS.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn pause_reward_emission(ctx: Context<PauseRewardEmission>, reward_index: u8) -> Result<()> {
+        return instructions::pause_reward_emission::handler(ctx, reward_index);
+    }
+
+    /// Resume the emissions of a paused reward in a pool. The reward does not accrue for the
+    /// time it was paused.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
+    ///                        reward-index in this pool
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index <= NUM_REWARDS) that we'd like to resume.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTimestamp` - Provided timestamp is not in order with the previous timestamp.
+    /// - `InvalidRewardIndex` - If the provided reward index exceeds NUM_REWARDS.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn resume_reward_emission(
+        ctx: Context<ResumeRewardEmission>,
+        reward_index: u8,
+    ) -> Result<()> {
+        return instructions::resume_reward_emission::handler(ctx, reward_index);
+    }
+
+    /// Set the share of 30 days of emissions below which the balance of a reward vault is reported
+    /// as low. When the emissions of the reward are set while the vault holds less, a
+    /// `RewardVaultLowBalanceEvent` is emitted.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
+    ///                        reward-index in this pool
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward index (0 <= index <= NUM_REWARDS) that we'd like to modify.
+    /// - `threshold_bps` - The warning threshold in basis points. Zero disables the warning.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - If the provided reward index exceeds NUM_REWARDS.
+    /// - `RewardVaultWarningThresholdMaxExceeded` - `threshold_bps` exceeds 10_000.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn set_reward_vault_warning_threshold(
+        ctx: Context<SetRewardVaultWarningThreshold>,
+        reward_index: u8,
+        threshold_bps: u16,
+    ) -> Result<()> {
+        return instructions::set_reward_vault_warning_threshold::handler(
+            ctx,
+            reward_index,
+            threshold_bps,
+        );
+    }
+
     /// Open a position in a pool. A unique token will be minted to represent the position
     /// in the users wallet. The position will start off with 0 liquidity.
     ///
@@ -175,8 +324,9 @@ pub mod anchor_dex {
     /// - `tick_upper_index` - The tick specifying the upper end of the position range.
     ///
     /// #### Special Errors
-    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
-    ///                        the tick-spacing in this pool.
+    /// - `TickIndexOutOfBounds` - If a provided tick is out of bounds.
+    /// - `TickNotAlignedToSpacing` - If a provided tick is not a multiple of the tick-spacing in this pool.
+    /// - `InvalidTickIndex` - If the provided ticks are out of order.
     pub fn open_position(
         ctx: Context<OpenPosition>,
         bumps: OpenPositionBumps,
@@ -200,8 +350,9 @@ pub mod anchor_dex {
     /// - `tick_upper_index` - The tick specifying the upper end of the position range.
     ///
     /// #### Special Errors
-    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
-    ///                        the tick-spacing in this pool.
+    /// - `TickIndexOutOfBounds` - If a provided tick is out of bounds.
+    /// - `TickNotAlignedToSpacing` - If a provided tick is not a multiple of the tick-spacing in this pool.
+    /// - `InvalidTickIndex` - If the provided ticks are out of order.
     pub fn open_position_with_metadata(
         ctx: Context<OpenPositionWithMetadata>,
         bumps: OpenPositionWithMetadataBumps,
@@ -230,6 +381,8 @@ pub mod anchor_dex {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `TickArrayDoesNotContainPositionTick` - The tick arrays do not contain the boundary ticks of the position.
+    /// - `TickArrayNotInitialized` - A tick array of the position was not created with `initialize_tick_array`.
     pub fn increase_liquidity(
         ctx: Context<ModifyLiquidity>,
         liquidity_amount: u128,
@@ -244,6 +397,34 @@ pub mod anchor_dex {
         );
     }
 
+    /// Add liquidity to a position in the pool, deriving the liquidity from the token amounts the
+    /// user is willing to deposit. The token whose amount provides less liquidity at the current
+    /// price constrains the deposit, the leftover of the other token is not transferred.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `token_a_amount` - The maximum amount of tokenA the user is willing to deposit.
+    /// - `token_b_amount` - The maximum amount of tokenB the user is willing to deposit.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - The token amounts provide no liquidity in the range of the position.
+    /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `TickArrayDoesNotContainPositionTick` - The tick arrays do not contain the boundary ticks of the position.
+    /// - `TickArrayNotInitialized` - A tick array of the position was not created with `initialize_tick_array`.
+    pub fn increase_liquidity_by_token_amounts(
+        ctx: Context<ModifyLiquidity>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<()> {
+        return instructions::increase_liquidity::handler_by_token_amounts(
+            ctx,
+            token_a_amount,
+            token_b_amount,
+        );
+    }
+
     /// Withdraw liquidity from a position in the pool. This call also updates the position's accrued fees and rewards.
     ///
     /// ### Authority
@@ -258,6 +439,8 @@ pub mod anchor_dex {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
+    /// - `TickArrayDoesNotContainPositionTick` - The tick arrays do not contain the boundary ticks of the position.
+    /// - `TickArrayNotInitialized` - A tick array of the position was not created with `initialize_tick_array`.
     pub fn decrease_liquidity(
         ctx: Context<ModifyLiquidity>,
         liquidity_amount: u128,
@@ -272,10 +455,39 @@ pub mod anchor_dex {
         );
     }
 
+    /// Withdraw liquidity from a position in the pool and collect the position's accrued fees in
+    /// the same call. The withdrawn tokens and the fees are transferred in a single transfer per token.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity the user desires to withdraw.
+    /// - `token_min_a` - The minimum amount of tokenA, withdrawn tokens and fees combined, the user is willing to receive.
+    /// - `token_min_b` - The minimum amount of tokenB, withdrawn tokens and fees combined, the user is willing to receive.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
+    /// - `TokenMinSubceeded` - The tokens received subceed the user defined amount.
+    pub fn decrease_liquidity_and_collect(
+        ctx: Context<DecreaseLiquidityAndCollect>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        return instructions::decrease_liquidity_and_collect::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        );
+    }
+
     /// Update the accrued fees and rewards for a position.
     ///
     /// #### Special Errors
-    /// - `TickNotFound` - Provided tick array account does not contain the tick for this position.
+    /// - `TickArrayDoesNotContainPositionTick` - Provided tick array account does not contain the tick for this position.
     /// - `LiquidityZero` - Position has zero liquidity and therefore already has the most updated fees and reward values.
     pub fn update_fees_and_rewards(ctx: Context<UpdateFeesAndRewards>) -> Result<()> {
         return instructions::update_fees_and_rewards::handler(ctx);
@@ -285,10 +497,44 @@ pub mod anchor_dex {
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// The fees are transferred to `token_owner_account_a` and `token_owner_account_b`, which can be
+    /// any token accounts of the pool's mints, e.g. those of a strategy the fees are routed to.
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeDestination` - A destination token account is a vault of the pool.
     pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
         return instructions::collect_fees::handler(ctx);
     }
 
+    /// Collect fees accrued for this position and close the position afterwards, burning its token
+    /// and returning the rent to `receiver`. The position token account has to be writable.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// #### Special Errors
+    /// - `ClosePositionNotEmpty` - The position has liquidity or owed rewards.
+    /// - `InvalidFeeDestination` - A destination token account is a vault of the pool.
+    pub fn collect_fees_and_close_position(
+        ctx: Context<CollectFeesAndClosePosition>,
+    ) -> Result<()> {
+        return instructions::collect_fees_and_close_position::handler(ctx);
+    }
+
+    /// Transfer a position to a new owner, paying the fees accrued by the position to the current
+    /// owner first so that the new owner starts without fees owed.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// #### Special Errors
+    /// - `PositionRewardsNotSettled` - The position has rewards owed, which must be collected with
+    ///                                 `collect_reward` before the transfer.
+    pub fn transfer_position(ctx: Context<TransferPosition>) -> Result<()> {
+        return instructions::transfer_position::handler(ctx);
+    }
+
     /// Collect rewards accrued for this position.
     ///
     /// ### Authority
@@ -301,6 +547,11 @@ pub mod anchor_dex {
     ///
     /// ### Authority
     /// - `collect_protocol_fees_authority` - assigned authority in the poolConfig that can collect protocol fees
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeDestination` - A destination token account is a vault of the pool.
+    /// - `ProtocolFeeDestinationOverflow` - Crediting the owed fees would overflow a destination token account.
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
     pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
         return instructions::collect_protocol_fees::handler(ctx);
     }
@@ -317,15 +568,25 @@ pub mod anchor_dex {
     /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
     /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
     ///
+    /// Every swap is counted in the pool. Swaps with a shared deadline, a quoted tick, a tick limit,
+    /// a tick crossing limit, cached tick arrays, protocol fee streaming, a referral fee or an LP
+    /// fee rebate use `swap_v3`.
+    ///
     /// #### Special Errors
     /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
     /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over ppool's max/min bounds for sqrt-price.
     /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `DuplicateTickArrayAccount` - A tick-array is passed again after a different tick-array.
+    /// - `TickArrayLoadFailed` - A tick-array after the first one is not repeated and fails to load.
     /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `SwapDirectionDisabled` - Swaps in the direction of the trade are disabled for the pool.
+    /// - `SwapAmountBelowMinimum` - `amount` is below the min_swap_amount of the pool.
+    /// - `SwapAmountAboveMaximum` - `amount` is above the max_swap_amount of the pool.
+    /// - `TransferFeeNotSupportedInLegacySwap` - A vault is not a legacy token account, fee-bearing tokens must use `swap_v2`.
     pub fn swap(
         ctx: Context<Swap>,
         amount: u64,
@@ -344,6 +605,23 @@ pub mod anchor_dex {
         );
     }
 
+    /// Summarize the initialized ticks of up to 8 tick arrays of a pool into their caches.
+    /// Swaps with `use_cache` set read the summaries instead of scanning the tick arrays.
+    /// A cache stays valid until the end of the next slot, or until a tick of the pool is
+    /// initialized or uninitialized. Caches are created on first use, the funder pays the rent.
+    ///
+    /// The tick arrays are passed as remaining accounts, each followed by its cache at
+    /// `[b"ta_cache", tick_array]`.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickArrayCacheAccounts` - The remaining accounts are not up to 8 pairs of tick
+    ///                                     array and tick array cache.
+    pub fn warm_tick_arrays<'a, 'b, 'info>(
+        ctx: Context<'a, 'b, 'info, 'info, WarmTickArrays<'info>>,
+    ) -> Result<()> {
+        return instructions::warm_tick_arrays::handler(ctx);
+    }
+
     /// Close a position in a pool. Burns the position token in the owner's wallet.
     ///
     /// ### Authority
@@ -355,6 +633,27 @@ pub mod anchor_dex {
         return instructions::close_position::handler(ctx);
     }
 
+    /// Close up to 8 positions without liquidity, collecting their fees first. Burns the position
+    /// tokens in the owner's wallet and returns the rent of each position to its receiver.
+    /// Returns the total fees collected as `BatchClosePositionsResult`.
+    ///
+    /// ### Authority
+    /// - "position_authority" - The authority that owns the position tokens.
+    ///
+    /// The positions are passed as remaining accounts, each as position, position_mint,
+    /// position_token_account, pool, token_vault_a, token_vault_b, token_owner_account_a,
+    /// token_owner_account_b and receiver.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBatchClosePositionAccounts` - No positions, more than 8 positions, or an incomplete
+    ///                                         set of accounts for a position.
+    /// - `ClosePositionNotEmpty` - A position has liquidity or owed rewards.
+    pub fn batch_close_positions<'a, 'b, 'info>(
+        ctx: Context<'a, 'b, 'info, 'info, BatchClosePositions<'info>>,
+    ) -> Result<()> {
+        return instructions::batch_close_positions::handler(ctx);
+    }
+
     /// Set the default_fee_rate for a FeeTier
     /// Only the current fee authority has permission to invoke this instruction.
     ///
@@ -386,6 +685,7 @@ pub mod anchor_dex {
     ///
     /// #### Special Errors
     /// - `ProtocolFeeRateMaxExceeded` - If the provided default_protocol_fee_rate exceeds MAX_PROTOCOL_FEE_RATE.
+    /// - `ProtocolFeeRateOutOfBounds` - If the provided default_protocol_fee_rate is outside the protocol fee rate bounds of the poolConfig.
     pub fn set_default_protocol_fee_rate(
         ctx: Context<SetDefaultProtocolFeeRate>,
         default_protocol_fee_rate: u16,
@@ -408,10 +708,44 @@ pub mod anchor_dex {
     ///
     /// #### Special Errors
     /// - `FeeRateMaxExceeded` - If the provided fee_rate exceeds MAX_FEE_RATE.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
     pub fn set_fee_rate(ctx: Context<SetFeeRate>, fee_rate: u16) -> Result<()> {
         return instructions::set_fee_rate::handler(ctx, fee_rate);
     }
 
+    /// Sets the minimum amount of a swap on a pool. Swaps below the minimum would only accumulate
+    /// dust fees. Only the current fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `min_swap_amount` - The minimum `amount` of `swap` and `swap_v2`. Zero for no minimum.
+    ///
+    /// #### Special Errors
+    /// - `InvalidSwapAmountBounds` - The minimum exceeds the maximum swap amount of the pool.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn set_min_swap_amount(ctx: Context<SetMinSwapAmount>, min_swap_amount: u64) -> Result<()> {
+        return instructions::set_min_swap_amount::handler(ctx, min_swap_amount);
+    }
+
+    /// Sets the maximum amount of a swap on a pool, e.g. to limit flash loan attacks on pools
+    /// without circuit breakers. Only the current fee authority has permission to invoke this
+    /// instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `max_swap_amount` - The maximum `amount` of `swap` and `swap_v2`. Zero for no maximum.
+    ///
+    /// #### Special Errors
+    /// - `InvalidSwapAmountBounds` - The maximum is below the minimum swap amount of the pool.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn set_max_swap_amount(ctx: Context<SetMaxSwapAmount>, max_swap_amount: u64) -> Result<()> {
+        return instructions::set_max_swap_amount::handler(ctx, max_swap_amount);
+    }
+
     /// Sets the protocol fee rate for a pool.
     /// Protocol fee rate is represented as a basis point.
     /// Only the current fee authority has permission to invoke this instruction.
@@ -424,6 +758,9 @@ pub mod anchor_dex {
     ///
     /// #### Special Errors
     /// - `ProtocolFeeRateMaxExceeded` - If the provided default_protocol_fee_rate exceeds MAX_PROTOCOL_FEE_RATE.
+    /// - `ProtocolFeeRateOutOfBounds` - If the provided protocol_fee_rate is outside the protocol fee rate bounds of the poolConfig.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
     pub fn set_protocol_fee_rate(
         ctx: Context<SetProtocolFeeRate>,
         protocol_fee_rate: u16,
@@ -431,96 +768,297 @@ pub mod anchor_dex {
         return instructions::set_protocol_fee_rate::handler(ctx, protocol_fee_rate);
     }
 
-    /// Sets the fee authority for a poolConfig.
-    /// The fee authority can set the fee & protocol fee rate for individual pools or
-    /// set the default fee rate for newly minted pools.
-    /// Only the current fee authority has permission to invoke this instruction.
+    /// Enables or disables the automatic adjustment of the protocol fee rate of a pool, and sets
+    /// the daily protocol fee revenue it targets. The volume of the first adjustment is measured
+    /// from now. Only the current fee authority has permission to invoke this instruction.
     ///
     /// ### Authority
     /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
-    pub fn set_fee_authority(ctx: Context<SetFeeAuthority>) -> Result<()> {
-        return instructions::set_fee_authority::handler(ctx);
+    ///
+    /// ### Parameters
+    /// - `enabled` - Whether `auto_adjust_protocol_fee` may adjust the protocol fee rate.
+    /// - `target_protocol_fee_revenue_daily` - The protocol fee revenue targeted per day, in token A.
+    ///
+    /// #### Special Errors
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn set_auto_protocol_fee(
+        ctx: Context<SetAutoProtocolFee>,
+        enabled: bool,
+        target_protocol_fee_revenue_daily: u64,
+    ) -> Result<()> {
+        return instructions::set_auto_protocol_fee::handler(
+            ctx,
+            enabled,
+            target_protocol_fee_revenue_daily,
+        );
     }
 
-    /// Sets the fee authority to collect protocol fees for a poolConfig.
-    /// Only the current collect protocol fee authority has permission to invoke this instruction.
+    /// Moves the protocol fee rate of a pool towards the rate at which the token A volume since
+    /// the last adjustment, scaled to a day, earns the targeted daily revenue. The rate changes by
+    /// at most 5 basis points and stays within the protocol fee rate bounds of the poolConfig.
+    /// Anyone may invoke this instruction once a day.
     ///
-    /// ### Authority
-    /// - "fee_authority" - Set authority that can collect protocol fees in the poolConfig
-    pub fn set_collect_protocol_fees_authority(
-        ctx: Context<SetCollectProtocolFeesAuthority>,
-    ) -> Result<()> {
-        return instructions::set_collect_protocol_fees_authority::handler(ctx);
+    /// #### Special Errors
+    /// - `AutoProtocolFeeDisabled` - Automatic adjustment is not enabled for the pool.
+    /// - `AutoProtocolFeeAdjustmentTooEarly` - The last adjustment was less than a day ago.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn auto_adjust_protocol_fee(ctx: Context<AutoAdjustProtocolFee>) -> Result<()> {
+        return instructions::auto_adjust_protocol_fee::handler(ctx);
     }
 
-    /// Set the pool reward authority at the provided `reward_index`.
-    /// Only the current reward authority for this reward index has permission to invoke this instruction.
+    /// Sets the LP fee rebate of a pool, creating its rebate config if needed. Swappers passing the
+    /// rebate config and the swap count of a position with liquidity they hold in the pool pay a
+    /// fee reduced by `rebate_bps` once the position has been used for `swap_count_threshold`
+    /// swaps. Restarts the swap count of the pool since the last reset.
+    /// Only the current fee authority has permission to invoke this instruction.
     ///
     /// ### Authority
-    /// - "reward_authority" - Set authority that can control reward emission for this particular reward.
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `swap_count_threshold` - The number of swaps of a position from which on its holder earns the rebate.
+    /// - `rebate_bps` - The reduction of the swap fee, in basis points.
     ///
     /// #### Special Errors
-    /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
-    ///                          index in this pool, or exceeds NUM_REWARDS, or
-    ///                          all reward slots for this pool has been initialized.
-    pub fn set_reward_authority(ctx: Context<SetRewardAuthority>, reward_index: u8) -> Result<()> {
-        return instructions::set_reward_authority::handler(ctx, reward_index);
+    /// - `LpFeeRebateBpsMaxExceeded` - `rebate_bps` exceeds 10_000.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn set_lp_fee_rebate_config(
+        ctx: Context<SetLpFeeRebateConfig>,
+        swap_count_threshold: u64,
+        rebate_bps: u16,
+    ) -> Result<()> {
+        return instructions::set_lp_fee_rebate_config::handler(
+            ctx,
+            swap_count_threshold,
+            rebate_bps,
+        );
     }
 
-    /// Set the pool reward authority at the provided `reward_index`.
-    /// Only the current reward super authority has permission to invoke this instruction.
+    /// Initializes the account counting the swaps made with a position for the LP fee rebate.
+    /// Anyone may pay for the account.
+    pub fn initialize_position_swap_count(ctx: Context<InitializePositionSwapCount>) -> Result<()> {
+        return instructions::initialize_position_swap_count::handler(ctx);
+    }
+
+    /// Permanently renounce admin control over a pool. Afterwards the fee rate, protocol fee rate
+    /// and reward authorities of the pool can no longer be changed by the pools config
+    /// authorities. The renouncement cannot be undone.
     ///
     /// ### Authority
-    /// - "reward_authority" - Set authority that can control reward emission for this particular reward.
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
     ///
     /// #### Special Errors
-    /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
-    ///                          index in this pool, or exceeds NUM_REWARDS, or
-    ///                          all reward slots for this pool has been initialized.
-    pub fn set_reward_authority_by_super_authority(
-        ctx: Context<SetRewardAuthorityBySuperAuthority>,
-        reward_index: u8,
-    ) -> Result<()> {
-        return instructions::set_reward_authority_by_super_authority::handler(ctx, reward_index);
+    /// - `PoolAuthorityRenounced` - Admin control over the pool was already renounced.
+    pub fn renounce_pool_authority(ctx: Context<RenouncePoolAuthority>) -> Result<()> {
+        return instructions::renounce_pool_authority::handler(ctx);
     }
 
-    /// Set the pool reward super authority for a poolConfig
-    /// Only the current reward super authority has permission to invoke this instruction.
-    /// This instruction will not change the authority on any `poolRewardInfo` pool rewards.
+    /// Enable or disable swaps in each direction of a pool, e.g. to only allow buying a depegged
+    /// token out of the pool. Liquidity can still be added and removed.
     ///
     /// ### Authority
-    /// - "reward_emissions_super_authority" - Set authority that can control reward authorities for all pools in this config space.
-    pub fn set_reward_emissions_super_authority(
-        ctx: Context<SetRewardEmissionsSuperAuthority>,
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `swap_a_to_b_enabled` - Whether swaps from token A to token B are allowed.
+    /// - `swap_b_to_a_enabled` - Whether swaps from token B to token A are allowed.
+    ///
+    /// #### Special Errors
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn set_directional_swap_status(
+        ctx: Context<SetDirectionalSwapStatus>,
+        swap_a_to_b_enabled: bool,
+        swap_b_to_a_enabled: bool,
     ) -> Result<()> {
-        return instructions::set_reward_emissions_super_authority::handler(ctx);
+        return instructions::set_directional_swap_status::handler(
+            ctx,
+            swap_a_to_b_enabled,
+            swap_b_to_a_enabled,
+        );
     }
 
-    /// Perform a two-hop swap in this pool
+    /// Register a human-readable name for a pool. Names are unique within a poolConfig.
     ///
     /// ### Authority
-    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
     ///
     /// ### Parameters
-    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
-    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
-    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
-    /// - `a_to_b_one` - The direction of the swap of hop one. True if swapping from A to B. False if swapping from B to A.
-    /// - `a_to_b_two` - The direction of the swap of hop two. True if swapping from A to B. False if swapping from B to A.
-    /// - `sqrt_price_limit_one` - The maximum/minimum price the swap will swap to in the first hop.
-    /// - `sqrt_price_limit_two` - The maximum/minimum price the swap will swap to in the second hop.
+    /// - `name` - The name of the pool, 1 to 32 bytes of UTF-8.
     ///
     /// #### Special Errors
-    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
-    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
-    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over ppool's max/min bounds for sqrt-price.
-    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
-    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
-    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
+    /// - `InvalidPoolName` - The name is empty, longer than 32 bytes or contains a null character.
+    /// - `FeatureDisabled` - FEATURE_POOL_NAME_REGISTRY is disabled for the poolConfig.
+    pub fn register_pool_name(ctx: Context<RegisterPoolName>, name: String) -> Result<()> {
+        return instructions::register_pool_name::handler(ctx, name);
+    }
+
+    /// Remove a registered pool name, so that it can be registered again.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    pub fn unregister_pool_name(ctx: Context<UnregisterPoolName>) -> Result<()> {
+        return instructions::unregister_pool_name::handler(ctx);
+    }
+
+    /// Read the pool registered under a name. The pool address is written via return data.
+    ///
+    /// ### Parameters
+    /// - `name` - The registered name of the pool.
+    ///
+    /// #### Special Errors
+    /// - `InvalidPoolName` - The entry was registered for another name with the same seed.
+    pub fn lookup_pool_by_name(ctx: Context<LookupPoolByName>, name: String) -> Result<()> {
+        return instructions::lookup_pool_by_name::handler(ctx, name);
+    }
+
+    /// Sets the fee authority for a poolConfig.
+    /// The fee authority can set the fee & protocol fee rate for individual pools or
+    /// set the default fee rate for newly minted pools.
+    /// Only the current fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// #### Special Errors
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
+    pub fn set_fee_authority(ctx: Context<SetFeeAuthority>) -> Result<()> {
+        return instructions::set_fee_authority::handler(ctx);
+    }
+
+    /// Sets the operations authority for a poolConfig.
+    /// The operations authority can initialize fee tiers and token badges, but cannot modify
+    /// fees or authorities. Setting the default pubkey removes the operations authority.
+    /// Only the current fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// #### Special Errors
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
+    pub fn set_operations_authority(ctx: Context<SetOperationsAuthority>) -> Result<()> {
+        return instructions::set_operations_authority::handler(ctx);
+    }
+
+    /// Sets the fee tier authority for a poolConfig.
+    /// While fee tier creation is locked, the fee tier authority is the only authority that can
+    /// initialize fee tiers. Only the current fee authority has permission to invoke this
+    /// instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// #### Special Errors
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
+    pub fn set_fee_tier_authority(ctx: Context<SetFeeTierAuthority>) -> Result<()> {
+        return instructions::set_fee_tier_authority::handler(ctx);
+    }
+
+    /// Locks or unlocks the creation of fee tiers in a poolConfig. While locked, only the fee
+    /// tier authority can initialize fee tiers, and no one can if it is not set.
+    /// Only the current fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `fee_tier_creation_locked` - Whether fee tier creation is restricted to the fee tier authority.
+    ///
+    /// #### Special Errors
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
+    pub fn set_fee_tier_creation_locked(
+        ctx: Context<SetFeeTierCreationLocked>,
+        fee_tier_creation_locked: bool,
+    ) -> Result<()> {
+        return instructions::set_fee_tier_creation_locked::handler(ctx, fee_tier_creation_locked);
+    }
+
+    /// Sets the fee authority to collect protocol fees for a poolConfig.
+    /// Only the current collect protocol fee authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can collect protocol fees in the poolConfig
+    pub fn set_collect_protocol_fees_authority(
+        ctx: Context<SetCollectProtocolFeesAuthority>,
+    ) -> Result<()> {
+        return instructions::set_collect_protocol_fees_authority::handler(ctx);
+    }
+
+    /// Set the pool reward authority at the provided `reward_index`.
+    /// Only the current reward authority for this reward index has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - Set authority that can control reward emission for this particular reward.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
+    ///                          index in this pool, or exceeds NUM_REWARDS, or
+    ///                          all reward slots for this pool has been initialized.
+    pub fn set_reward_authority(ctx: Context<SetRewardAuthority>, reward_index: u8) -> Result<()> {
+        return instructions::set_reward_authority::handler(ctx, reward_index);
+    }
+
+    /// Set the pool reward authority at the provided `reward_index`.
+    /// Only the current reward super authority has permission to invoke this instruction.
+    ///
+    /// ### Authority
+    /// - "reward_authority" - Set authority that can control reward emission for this particular reward.
+    ///
+    /// #### Special Errors
+    /// - `InvalidRewardIndex` - If the provided reward index doesn't match the lowest uninitialized
+    ///                          index in this pool, or exceeds NUM_REWARDS, or
+    ///                          all reward slots for this pool has been initialized.
+    /// - `PoolAuthorityRenounced` - Admin control over the pool has been renounced.
+    pub fn set_reward_authority_by_super_authority(
+        ctx: Context<SetRewardAuthorityBySuperAuthority>,
+        reward_index: u8,
+    ) -> Result<()> {
+        return instructions::set_reward_authority_by_super_authority::handler(ctx, reward_index);
+    }
+
+    /// Set the pool reward super authority for a poolConfig
+    /// Only the current reward super authority has permission to invoke this instruction.
+    /// This instruction will not change the authority on any `poolRewardInfo` pool rewards.
+    ///
+    /// ### Authority
+    /// - "reward_emissions_super_authority" - Set authority that can control reward authorities for all pools in this config space.
+    pub fn set_reward_emissions_super_authority(
+        ctx: Context<SetRewardEmissionsSuperAuthority>,
+    ) -> Result<()> {
+        return instructions::set_reward_emissions_super_authority::handler(ctx);
+    }
+
+    /// Perform a two-hop swap in this pool
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b_one` - The direction of the swap of hop one. True if swapping from A to B. False if swapping from B to A.
+    /// - `a_to_b_two` - The direction of the swap of hop two. True if swapping from A to B. False if swapping from B to A.
+    /// - `sqrt_price_limit_one` - The maximum/minimum price the swap will swap to in the first hop.
+    /// - `sqrt_price_limit_two` - The maximum/minimum price the swap will swap to in the second hop.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over ppool's max/min bounds for sqrt-price.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `DuplicateTickArrayAccount` - A tick-array is passed again after a different tick-array, or is shared by both swaps.
+    /// - `TickArrayLoadFailed` - A tick-array after the first one is not repeated and fails to load.
+    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
+    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `SwapDirectionDisabled` - Swaps in the direction of the trade are disabled for the pool.
+    /// - `SwapAmountBelowMinimum` - The amount of a hop is below the min_swap_amount of its pool.
+    /// - `SwapAmountAboveMaximum` - The amount of a hop is above the max_swap_amount of its pool.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if pool one & two are the same pool.
+    /// - `CircularRoute` - Error if the route starts and ends with the same token.
+    /// - `TransferFeeNotSupportedInLegacySwap` - A vault is not a legacy token account, fee-bearing tokens must use `two_hop_swap_v2`.
     pub fn two_hop_swap(
         ctx: Context<TwoHopSwap>,
         amount: u64,
@@ -543,8 +1081,55 @@ pub mod anchor_dex {
         );
     }
 
+    /// Perform a two-hop swap whose input or output mint is native SOL. The input SOL is wrapped
+    /// into the input WSOL account before the route. WSOL accounts of the SOL ends that were empty
+    /// before the instruction are closed after it, returning the output and any unused input to the
+    /// owner as SOL. WSOL accounts that already held tokens stay open, so the output and unused
+    /// input stay wrapped in them and WSOL the owner already held is not unwrapped.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account. Must
+    ///                       also be passed as the writable `sol_owner`.
+    ///
+    /// ### Parameters
+    /// Same as `two_hop_swap`. For exact-out routes with SOL input, `other_amount_threshold` of SOL
+    /// is wrapped.
+    /// - `intermediate_threshold` - Optional minimum amount of intermediate token out of the first hop. Only checked for exact-in swaps.
+    /// - `max_tick_crossings` - The number of initialized ticks the hop calculated first (hop one for exact-in, hop two for exact-out) may cross before it stops. The route then settles the amounts swapped so far. Zero for no limit.
+    ///
+    /// #### Special Errors
+    /// Same as `two_hop_swap`.
+    /// - `IntermediateAmountBelowMinimum` - The first hop of an exact-in swap produced less than `intermediate_threshold`.
+    pub fn two_hop_swap_with_sol(
+        ctx: Context<TwoHopSwapWithSol>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        sqrt_price_limit_one: u128,
+        sqrt_price_limit_two: u128,
+        intermediate_threshold: Option<u64>,
+        max_tick_crossings: u8,
+    ) -> Result<()> {
+        return instructions::two_hop_swap_with_sol::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            a_to_b_one,
+            a_to_b_two,
+            sqrt_price_limit_one,
+            sqrt_price_limit_two,
+            intermediate_threshold,
+            max_tick_crossings,
+        );
+    }
+
     /// Initializes a PositionBundle account that bundles several positions.
     /// A unique token will be minted to represent the position bundle in the users wallet.
+    /// Whoever holds the token owns every position in the bundle, so transferring it moves
+    /// all bundled positions at once.
     pub fn initialize_position_bundle(ctx: Context<InitializePositionBundle>) -> Result<()> {
         return instructions::initialize_position_bundle::handler(ctx);
     }
@@ -558,63 +1143,828 @@ pub mod anchor_dex {
         return instructions::initialize_position_bundle_with_metadata::handler(ctx);
     }
 
-    /// Delete a PositionBundle account. Burns the position bundle token in the owner's wallet.
+    /// Expand or shrink a PositionBundle to change the number of bundled positions it can hold.
+    /// The account is reallocated to the new size, the funder pays the additional rent of a larger
+    /// account and receives the rent freed by a smaller one.
+    ///
+    /// ### Authority
+    /// - `position_bundle_authority` - authority that owns the token corresponding to this desired position bundle.
+    ///
+    /// ### Parameters
+    /// - `new_capacity` - The number of positions the bundle can hold after the reallocation.
+    ///
+    /// #### Special Errors
+    /// - `InvalidPositionBundleCapacity` - new_capacity is not a multiple of 8, equals the current
+    ///                                     capacity, is below 256 or exceeds 1024.
+    /// - `BundledPositionBeyondCapacity` - A bundled position is open at an index the new capacity
+    ///                                     excludes.
+    pub fn expand_position_bundle(
+        ctx: Context<ExpandPositionBundle>,
+        new_capacity: u16,
+    ) -> Result<()> {
+        return instructions::expand_position_bundle::handler(ctx, new_capacity);
+    }
+
+    /// Delete a PositionBundle account. Burns the position bundle token in the owner's wallet.
+    /// Only the holder of the position bundle token can delete the bundle, after all bundled
+    /// positions have been closed.
+    ///
+    /// ### Authority
+    /// - `position_bundle_owner` - The owner that owns the position bundle token.
+    ///
+    /// ### Special Errors
+    /// - `PositionBundleNotDeletable` - The provided position bundle has open positions.
+    pub fn delete_position_bundle(ctx: Context<DeletePositionBundle>) -> Result<()> {
+        return instructions::delete_position_bundle::handler(ctx);
+    }
+
+    /// Open a bundled position in a pool. No new tokens are issued
+    /// because the owner of the position bundle becomes the owner of the position.
+    /// The position will start off with 0 liquidity.
+    ///
+    /// ### Authority
+    /// - `position_bundle_authority` - authority that owns the token corresponding to this desired position bundle.
+    ///
+    /// ### Parameters
+    /// - `bundle_index` - The bundle index that we'd like to open.
+    /// - `tick_lower_index` - The tick specifying the lower end of the position range.
+    /// - `tick_upper_index` - The tick specifying the upper end of the position range.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
+    /// - `TickIndexOutOfBounds` - If a provided tick is out of bounds.
+    /// - `TickNotAlignedToSpacing` - If a provided tick is not a multiple of the tick-spacing in this pool.
+    /// - `InvalidTickIndex` - If the provided ticks are out of order.
+    pub fn open_bundled_position(
+        ctx: Context<OpenBundledPosition>,
+        bundle_index: u16,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        return instructions::open_bundled_position::handler(
+            ctx,
+            bundle_index,
+            tick_lower_index,
+            tick_upper_index,
+        );
+    }
+
+    /// Close a bundled position in a pool.
+    ///
+    /// ### Authority
+    /// - `position_bundle_authority` - authority that owns the token corresponding to this desired position bundle.
+    ///
+    /// ### Parameters
+    /// - `bundle_index` - The bundle index that we'd like to close.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
+    /// - `ClosePositionNotEmpty` - The provided position account is not empty.
+    pub fn close_bundled_position(
+        ctx: Context<CloseBundledPosition>,
+        bundle_index: u16,
+    ) -> Result<()> {
+        return instructions::close_bundled_position::handler(ctx, bundle_index);
+    }
+
+    /// Emit the liquidity curve of a pool across the span covered by the provided tick-arrays.
+    /// Each initialized tick crossed in the walk direction is reported with its liquidity_net and
+    /// the active liquidity after crossing it, starting from the pool's current liquidity.
+    /// Results are emitted as one or more `LiquidityDistributionEvent`s.
+    ///
+    /// ### Parameters
+    /// - `a_to_b` - The direction of the walk. True walks down from the current tick, false walks up.
+    ///
+    /// #### Special Errors
+    /// - `InvalidTickArraySequence` - The tick-arrays are not in sequential order for the walk direction.
+    /// - `LiquidityUnderflow` - The cumulative liquidity went below zero, indicating inconsistent tick state.
+    /// - `TickArrayLoadFailed` - A tick-array after the first one is not repeated and fails to load.
+    pub fn get_liquidity_distribution(
+        ctx: Context<GetLiquidityDistribution>,
+        a_to_b: bool,
+    ) -> Result<()> {
+        return instructions::get_liquidity_distribution::handler(ctx, a_to_b);
+    }
+
+    /// Export the fee growth checkpoints of a position alongside the fees it has accrued since
+    /// its last checkpoint, without updating the position. The result is written as
+    /// a `FeeGrowthCheckpointExport` via return data.
+    ///
+    /// #### Special Errors
+    /// - `TickNotFound` - The provided tick-arrays do not hold the position's boundary ticks.
+    pub fn export_fee_growth_checkpoint(ctx: Context<ExportFeeGrowthCheckpoint>) -> Result<()> {
+        return instructions::export_fee_growth_checkpoint::handler(ctx);
+    }
+
+    /// Create a governance proposal to change a protocol parameter of a PoolsConfig.
+    /// Liquidity providers vote on the proposal with the value of their positions in `vote_mint`
+    /// at the current price of the pool. Only positions in pools that trade `vote_mint` can vote.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the PoolsConfig
+    ///
+    /// ### Parameters
+    /// - `proposal_id` - An id unique within the PoolsConfig, used to derive the proposal address.
+    /// - `proposal_type` - The parameter to change. 0 sets the default protocol fee rate.
+    /// - `proposed_value` - The new value of the parameter.
+    /// - `voting_end_ts` - The last timestamp at which votes are accepted.
+    /// - `quorum_liquidity` - A quarter of this vote weight must be cast in favor beyond the votes against.
+    ///
+    /// #### Special Errors
+    /// - `InvalidProposalType` - The proposal type is not supported.
+    /// - `ProtocolFeeRateMaxExceeded` - The proposed protocol fee rate exceeds the maximum.
+    /// - `InvalidTimestamp` - The voting end timestamp is not in the future.
+    pub fn create_protocol_proposal(
+        ctx: Context<CreateProtocolProposal>,
+        proposal_id: u64,
+        proposal_type: u8,
+        proposed_value: u64,
+        voting_end_ts: u64,
+        quorum_liquidity: u128,
+    ) -> Result<()> {
+        return instructions::create_protocol_proposal::handler(
+            ctx,
+            proposal_id,
+            proposal_type,
+            proposed_value,
+            voting_end_ts,
+            quorum_liquidity,
+        );
+    }
+
+    /// Vote on a governance proposal with the value of a position in the vote mint of the proposal.
+    /// Each position may vote once per proposal.
+    /// The position NFT is held in escrow by the vote until voting has ended, so the liquidity
+    /// cannot be withdrawn or moved to another position to vote again. A position can therefore
+    /// only vote on one proposal at a time.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `support` - True to vote for the proposal, false to vote against it.
+    ///
+    /// #### Special Errors
+    /// - `ProposalVotingClosed` - The voting period has ended or the proposal was executed.
+    /// - `InvalidVoteMint` - The pool of the position does not trade the vote mint of the proposal.
+    /// - `LiquidityZero` - The position has no value in the vote mint.
+    pub fn vote_on_proposal(ctx: Context<VoteOnProposal>, support: bool) -> Result<()> {
+        return instructions::vote_on_proposal::handler(ctx, support);
+    }
+
+    /// Return the position NFT held in escrow by a vote to the token account of the voter and
+    /// close the vote. Anyone may call this instruction once voting has ended.
+    ///
+    /// #### Special Errors
+    /// - `ProposalVotingNotEnded` - The voting period has not ended.
+    pub fn withdraw_vote_position(ctx: Context<WithdrawVotePosition>) -> Result<()> {
+        return instructions::withdraw_vote_position::handler(ctx);
+    }
+
+    /// Queue a passed governance proposal as a timelocked parameter change of its PoolsConfig.
+    /// Anyone may execute a proposal once voting has ended. The change is applied by
+    /// `execute_parameter_change` once the governance delay elapsed, and the fee authority may
+    /// cancel it until then.
+    ///
+    /// #### Special Errors
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
+    /// - `ProposalVotingNotEnded` - The voting period has not ended.
+    /// - `ProposalNotPassed` - votes_for did not exceed votes_against + quorum_liquidity / 4.
+    /// - `ProposalAlreadyExecuted` - The proposal was already executed.
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        return instructions::execute_proposal::handler(ctx);
+    }
+
+    /// Propose a change of a fee parameter which can only be executed after
+    /// MIN_GOVERNANCE_DELAY_SECONDS, so that pending transactions are not affected by it.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `nonce` - Distinguishes parameter changes of the poolConfig.
+    /// - `change_type` - PARAMETER_CHANGE_FEE_RATE or PARAMETER_CHANGE_PROTOCOL_FEE_RATE of the
+    ///                   optional `pool`, or PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE or
+    ///                   PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS.
+    /// - `new_value` - The value the parameter is set to.
+    ///
+    /// #### Special Errors
+    /// - `InvalidProposalType` - Unknown change type, or `pool` is provided for a config-level
+    ///                           change or missing for a pool-level change.
+    /// - `FeeRateMaxExceeded` - The new fee rate exceeds MAX_FEE_RATE.
+    /// - `ProtocolFeeRateMaxExceeded` - The new protocol fee rate exceeds MAX_PROTOCOL_FEE_RATE.
+    /// - `InvalidProtocolFeeRateBounds` - The new protocol fee rate bounds are not ordered or exceed MAX_PROTOCOL_FEE_RATE.
+    /// - `FeatureDisabled` - FEATURE_PARAMETER_CHANGES is disabled for the poolConfig.
+    pub fn propose_parameter_change(
+        ctx: Context<ProposeParameterChange>,
+        nonce: u64,
+        change_type: u8,
+        new_value: u64,
+    ) -> Result<()> {
+        return instructions::propose_parameter_change::handler(
+            ctx,
+            nonce,
+            change_type,
+            new_value,
+        );
+    }
+
+    /// Schedule new protocol fee rate bounds for a poolConfig. The bounds are proposed as a
+    /// parameter change, so they only apply once `execute_parameter_change` is called after
+    /// MIN_GOVERNANCE_DELAY_SECONDS.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `nonce` - Distinguishes parameter changes of the poolConfig.
+    /// - `min_protocol_fee_rate` - The lowest protocol fee rate of the poolConfig and its pools.
+    /// - `max_protocol_fee_rate` - The highest protocol fee rate of the poolConfig and its pools.
+    ///
+    /// #### Special Errors
+    /// - `InvalidProtocolFeeRateBounds` - The bounds are not ordered or exceed MAX_PROTOCOL_FEE_RATE.
+    /// - `FeatureDisabled` - FEATURE_PARAMETER_CHANGES is disabled for the poolConfig.
+    pub fn set_protocol_fee_rate_bounds(
+        ctx: Context<SetProtocolFeeRateBounds>,
+        nonce: u64,
+        min_protocol_fee_rate: u16,
+        max_protocol_fee_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_protocol_fee_rate_bounds::handler(
+            ctx,
+            nonce,
+            min_protocol_fee_rate,
+            max_protocol_fee_rate,
+        );
+    }
+
+    /// Apply a proposed parameter change. Anyone may execute a change once its delay elapsed.
+    ///
+    /// #### Special Errors
+    /// - `ParameterChangeDelayNotElapsed` - The current time is before `execute_after`.
+    /// - `PoolAuthorityRenounced` - Admin control over the target pool has been renounced.
+    /// - `ProtocolFeeRateOutOfBounds` - A new protocol fee rate is outside the protocol fee rate bounds of the poolConfig.
+    pub fn execute_parameter_change(ctx: Context<ExecuteParameterChange>) -> Result<()> {
+        return instructions::execute_parameter_change::handler(ctx);
+    }
+
+    /// Cancel a proposed parameter change before it is executed.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    pub fn cancel_parameter_change(ctx: Context<CancelParameterChange>) -> Result<()> {
+        return instructions::cancel_parameter_change::handler(ctx);
+    }
+
+    /// Grow a pool account created before `max_liquidity_observed`, `authority_renounced`, the
+    /// swap volume, the swap count or the reward emission schedules were added to the Pool, and
+    /// initialize the high-water mark to the pool's current liquidity.
+    /// Anyone may migrate a pool; the funder pays the additional rent.
+    ///
+    /// #### Special Errors
+    /// - `PoolAlreadyMigrated` - The pool account already has the current size.
+    pub fn migrate_pool_max_liquidity(ctx: Context<MigratePoolMaxLiquidity>) -> Result<()> {
+        return instructions::migrate_pool_max_liquidity::handler(ctx);
+    }
+
+    /// Grow a poolConfig account created before `feature_flags`, `max_referral_fee_rate`, the
+    /// protocol fee rate bounds or the fee tier creation lock were added to the PoolsConfig. Every
+    /// feature is enabled for configs without feature flags, and the bounds allow every protocol
+    /// fee rate for configs without bounds. Fee tier creation starts unlocked. Anyone may migrate a poolConfig; the funder pays the
+    /// additional rent.
+    ///
+    /// #### Special Errors
+    /// - `PoolsConfigAlreadyMigrated` - The poolConfig account already has the current size.
+    pub fn migrate_pools_config(ctx: Context<MigratePoolsConfig>) -> Result<()> {
+        return instructions::migrate_pools_config::handler(ctx);
+    }
+
+    /// Initializes the ProtocolLiquidityManager of a PoolsConfig, which holds the position token
+    /// of the protocol-owned position and the treasury token accounts that fund it.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the PoolsConfig
+    ///
+    /// ### Parameters
+    /// - `min_tick_lower` - The lowest tick the protocol-owned position may start at.
+    /// - `max_tick_upper` - The highest tick the protocol-owned position may end at.
+    ///
+    /// #### Special Errors
+    /// - `ProtocolLiquidityRangeOutOfBounds` - The position's range is outside of the provided bounds.
+    pub fn initialize_protocol_liquidity_manager(
+        ctx: Context<InitializeProtocolLiquidityManager>,
+        min_tick_lower: i32,
+        max_tick_upper: i32,
+    ) -> Result<()> {
+        return instructions::initialize_protocol_liquidity_manager::handler(
+            ctx,
+            min_tick_lower,
+            max_tick_upper,
+        );
+    }
+
+    /// Move tokens from the protocol treasury into the protocol-owned position.
+    /// Each deployment locks the position's liquidity for 48 hours.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the PoolsConfig
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity the protocol is willing to deposit.
+    /// - `token_max_a` - The maximum amount of tokenA allowed to withdraw from the treasury.
+    /// - `token_max_b` - The maximum amount of tokenB allowed to withdraw from the treasury.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
+    /// - `FeatureDisabled` - FEATURE_PROTOCOL_LIQUIDITY is disabled for the poolConfig.
+    pub fn deploy_protocol_liquidity(
+        ctx: Context<ModifyProtocolLiquidity>,
+        liquidity_amount: u128,
+        token_max_a: u64,
+        token_max_b: u64,
+    ) -> Result<()> {
+        return instructions::deploy_protocol_liquidity::handler(
+            ctx,
+            liquidity_amount,
+            token_max_a,
+            token_max_b,
+        );
+    }
+
+    /// Withdraw liquidity from the protocol-owned position back into the protocol treasury,
+    /// together with all fees the position has earned.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority in the PoolsConfig
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity to withdraw.
+    /// - `token_min_a` - The minimum amount of tokenA to return to the treasury.
+    /// - `token_min_b` - The minimum amount of tokenB to return to the treasury.
+    ///
+    /// #### Special Errors
+    /// - `ProtocolLiquidityWithdrawLocked` - Less than 48 hours have passed since the last deployment.
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `TokenMinSubceeded` - The withdrawn amount is below the user defined minimum.
+    pub fn withdraw_protocol_liquidity(
+        ctx: Context<ModifyProtocolLiquidity>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+    ) -> Result<()> {
+        return instructions::withdraw_protocol_liquidity::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+        );
+    }
+
+    /// Grow a fee tier account created before the pool count was added to the FeeTier. Anyone may
+    /// migrate a fee tier; the funder pays the additional rent.
+    ///
+    /// #### Special Errors
+    /// - `FeeTierAlreadyMigrated` - The fee tier account already has the current size.
+    pub fn migrate_fee_tier_pool_count(ctx: Context<MigrateFeeTierPoolCount>) -> Result<()> {
+        return instructions::migrate_fee_tier_pool_count::handler(ctx);
+    }
+
+    /// Read the cumulative swap volume of the pools of a fee tier. Pools record their own volume
+    /// so that swaps do not write to the shared fee tier account, and the volume is summed over
+    /// the pools passed as remaining accounts. The result is written as a `FeeTierVolume` via
+    /// return data.
+    ///
+    /// ### Parameters
+    /// - `volume_milestone` - A `FeeRateVolumeUpdatedEvent` is emitted if either total volume
+    ///                        reached the milestone. Zero disables the event.
+    ///
+    /// #### Special Errors
+    /// - `DuplicateFeeTierPool` - A pool is passed more than once.
+    pub fn get_fee_tier_volume<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, GetFeeTierVolume<'info>>,
+        volume_milestone: u128,
+    ) -> Result<()> {
+        return instructions::get_fee_tier_volume::handler(ctx, volume_milestone);
+    }
+
+    /// Find the initialized ticks of a tick-array next to `current_tick` with the initialized
+    /// bitmap of the tick-array. The ticks are written as a `NextInitializedTicks` via return
+    /// data, the tick below includes `current_tick` itself. A tick outside the tick-array is
+    /// searched over the whole tick-array.
+    ///
+    /// ### Parameters
+    /// - `current_tick` - The tick index to search from.
+    pub fn get_next_initialized_tick_compact(
+        ctx: Context<GetNextInitializedTickCompact>,
+        current_tick: i32,
+    ) -> Result<()> {
+        return instructions::get_next_initialized_tick_compact::handler(ctx, current_tick);
+    }
+
+    /// Find the tick-array a swap in the given direction traverses after the tick-array starting at
+    /// `start_tick_index`, so that clients can build a contiguous tick-array sequence. The start
+    /// tick index and the address of the next tick-array are written as a `NextTickArrayStart`
+    /// via return data.
+    ///
+    /// ### Parameters
+    /// - `start_tick_index` - The start tick index of the current tick-array.
+    /// - `a_to_b` - The direction of the swap.
+    ///
+    /// #### Special Errors
+    /// - `InvalidStartTick` - `start_tick_index` is not a valid start tick index for the pool.
+    /// - `TickArraySequenceInvalidIndex` - No tick-array follows the min or max tick-array.
+    pub fn find_next_tick_array_start(
+        ctx: Context<FindNextTickArrayStart>,
+        start_tick_index: i32,
+        a_to_b: bool,
+    ) -> Result<()> {
+        return instructions::find_next_tick_array_start::handler(ctx, start_tick_index, a_to_b);
+    }
+
+    /// Score the health of a position for risk dashboards. Combines whether the position is in
+    /// range, the share of the full tick range it covers, its impermanent loss since the deposits
+    /// in its history and the annualized yield of its fees owed. Without the optional position
+    /// history the impermanent loss and the fee yield are reported as zero. The result is written as a
+    /// `PositionHealthScore` via return data.
+    ///
+    /// ### Parameters
+    /// - `daily_volatility_bps` - The expected daily price volatility of the pool, e.g. taken from
+    ///                            an off-chain oracle, to estimate the days until the position
+    ///                            leaves its range. Zero skips the estimate.
+    pub fn get_position_health_score(
+        ctx: Context<GetPositionHealthScore>,
+        daily_volatility_bps: u16,
+    ) -> Result<()> {
+        return instructions::get_position_health_score::handler(ctx, daily_volatility_bps);
+    }
+
+    /// Create a SharedDeadlineContext that lets a batch of operations share a common deadline.
+    /// The operations that receive the context check its deadline and count themselves against
+    /// `remaining_ops`. `swap_v3` and `decrease_liquidity_and_collect` take it as an account, the v2
+    /// fee collection and liquidity instructions as the `DeadlineContext` remaining accounts slice.
+    ///
+    /// ### Authority
+    /// - "authority" - The signer of the operations in the batch.
+    ///
+    /// ### Parameters
+    /// - `nonce` - A value distinguishing the contexts of the same authority.
+    /// - `deadline` - The unix timestamp after which the operations of the batch fail.
+    /// - `remaining_ops` - The number of operations that may use the context.
+    ///
+    /// #### Special Errors
+    /// - `DeadlineExceeded` - The deadline has already passed.
+    /// - `DeadlineContextExhausted` - `remaining_ops` is zero.
+    pub fn create_deadline_context(
+        ctx: Context<CreateDeadlineContext>,
+        nonce: u64,
+        deadline: u64,
+        remaining_ops: u8,
+    ) -> Result<()> {
+        return instructions::create_deadline_context::handler(
+            ctx,
+            nonce,
+            deadline,
+            remaining_ops,
+        );
+    }
+
+    /// Close a SharedDeadlineContext once the operations of the batch completed, and return its
+    /// rent to the receiver.
+    ///
+    /// ### Authority
+    /// - "authority" - The authority of the deadline context.
+    pub fn close_deadline_context(ctx: Context<CloseDeadlineContext>) -> Result<()> {
+        return instructions::close_deadline_context::handler(ctx);
+    }
+
+    /// Read the bundled positions of a PositionBundle in a pool, sorted by price range. The
+    /// positions are sorted by tick_lower_index ascending, breaking ties by tick_upper_index, and
+    /// written as a `Vec<BundlePositionSorted>` via return data.
+    ///
+    /// ### Parameters
+    /// - `bundle_indexes` - The bundle index of each bundled position passed as a remaining account.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundlePositionAccounts` - More than 64 positions were passed, or a position is not
+    ///                                     the bundled position at its bundle index.
+    pub fn get_bundle_positions_sorted<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, GetBundlePositionsSorted<'info>>,
+        bundle_indexes: Vec<u16>,
+    ) -> Result<()> {
+        return instructions::get_bundle_positions_sorted::handler(ctx, bundle_indexes);
+    }
+
+    /// Read the liquidity of the bundled positions of a PositionBundle in a pool. Positions are
+    /// active if the current tick of the pool is within their range. The totals and the summary of
+    /// each position are written as a `BundleLiquiditySnapshot` via return data.
+    ///
+    /// ### Parameters
+    /// - `bundle_indexes` - The bundle index of each bundled position passed as a remaining account.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundlePositionAccounts` - More than 64 positions were passed, or a position is not
+    ///                                     the bundled position at its bundle index.
+    pub fn get_bundle_liquidity_snapshot<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, GetBundleLiquiditySnapshot<'info>>,
+        bundle_indexes: Vec<u16>,
+    ) -> Result<()> {
+        return instructions::get_bundle_liquidity_snapshot::handler(ctx, bundle_indexes);
+    }
+
+    /// Check that the bitmap of a PositionBundle matches its bundled position accounts. Every
+    /// open bundle index must have its bundled position passed as a remaining account, in
+    /// ascending bundle index order. The result is written as a `BundleValidationResult` via
+    /// return data.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundlePositionAccounts` - More accounts were passed than the bundle has open bundle indexes.
+    pub fn validate_position_bundle<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ValidatePositionBundle<'info>>,
+    ) -> Result<()> {
+        return instructions::validate_position_bundle::handler(ctx);
+    }
+
+    /// Clear the bitmap bits of bundled positions whose accounts no longer exist.
+    ///
+    /// ### Authority
+    /// - `position_bundle_authority` - authority that owns the token corresponding to this desired position bundle.
+    ///
+    /// ### Parameters
+    /// - `bundle_indexes` - The bundle index of each orphaned bundled position address passed as a remaining account.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundlePositionAccounts` - An account is not the bundled position address at its bundle index.
+    /// - `BundledPositionNotOrphaned` - A bundled position account still exists.
+    /// - `BundledPositionAlreadyClosed` - The bit of a bundle index is not set.
+    pub fn repair_position_bundle<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RepairPositionBundle<'info>>,
+        bundle_indexes: Vec<u16>,
+    ) -> Result<()> {
+        return instructions::repair_position_bundle::handler(ctx, bundle_indexes);
+    }
+
+    /// Initialize the history account of a position. Once initialized, the liquidity changes and
+    /// fee collections of the position are recorded in it by the instructions that receive it. The
+    /// v2 fee collection and liquidity instructions receive it as the `PositionHistory` remaining
+    /// accounts slice. Anyone may initialize the history of a position; the funder pays the rent.
+    pub fn initialize_position_history(ctx: Context<InitializePositionHistory>) -> Result<()> {
+        return instructions::initialize_position_history::handler(ctx);
+    }
+
+    /// Read the latest liquidity changes and fee collections of a position, oldest first. The
+    /// result is written as a `Vec<PositionHistoryEntry>` via return data.
+    pub fn get_position_history(ctx: Context<GetPositionHistory>) -> Result<()> {
+        return instructions::get_position_history::handler(ctx);
+    }
+
+    /// Enable streaming of protocol fees. While enabled, `swap_v3` transfers each protocol fee
+    /// above `min_stream_amount` directly to the treasury instead of accruing it in the pool, and
+    /// fails if the pools config extension or a treasury token account is not passed. The other
+    /// swap instructions, whose accounts were fixed before streaming existed, keep accruing
+    /// protocol fees in the pool, where `collect_protocol_fees` collects them.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `min_stream_amount` - Protocol fees at or below this amount keep accruing in the pool.
+    /// - `protocol_treasury` - The owner of the token accounts that receive streamed protocol fees.
+    ///
+    /// #### Special Errors
+    /// - `InvalidAuthorityKey` - `protocol_treasury` is the default pubkey.
+    /// - `FeatureDisabled` - FEATURE_PROTOCOL_FEE_STREAMING is disabled for the poolConfig.
+    pub fn enable_protocol_fee_streaming(
+        ctx: Context<EnableProtocolFeeStreaming>,
+        min_stream_amount: u64,
+        protocol_treasury: Pubkey,
+    ) -> Result<()> {
+        return instructions::enable_protocol_fee_streaming::handler(
+            ctx,
+            min_stream_amount,
+            protocol_treasury,
+        );
+    }
+
+    /// Disable streaming of protocol fees. Protocol fees accrue in the pool again.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    pub fn disable_protocol_fee_streaming(ctx: Context<DisableProtocolFeeStreaming>) -> Result<()> {
+        return instructions::disable_protocol_fee_streaming::handler(ctx);
+    }
+
+    /// Require `set_fee_authority`, `set_protocol_fee_rate` and `collect_protocol_fees(_v2)` to be
+    /// invoked directly by the transaction rather than through CPI.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `require_direct_call` - Whether direct calls are required.
+    pub fn set_require_direct_call(
+        ctx: Context<SetRequireDirectCall>,
+        require_direct_call: bool,
+    ) -> Result<()> {
+        return instructions::set_require_direct_call::handler(ctx, require_direct_call);
+    }
+
+    /// Set the maximum share of the LP fee a swap can route to a referral account.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `max_referral_fee_rate` - The maximum referral fee rate, in basis points of the LP fee. Zero disables referral fees.
+    ///
+    /// #### Special Errors
+    /// - `ReferralFeeRateMaxExceeded` - Provided rate exceeds MAX_REFERRAL_FEE_RATE.
+    pub fn set_max_referral_fee_rate(
+        ctx: Context<SetMaxReferralFeeRate>,
+        max_referral_fee_rate: u16,
+    ) -> Result<()> {
+        return instructions::set_max_referral_fee_rate::handler(ctx, max_referral_fee_rate);
+    }
+
+    /// Set the features enabled for a poolConfig. Instructions of a disabled feature revert.
+    ///
+    /// ### Authority
+    /// - "fee_authority" - Set authority that can modify pool fees in the poolConfig
+    ///
+    /// ### Parameters
+    /// - `feature_flags` - Bitmask of the enabled FEATURE_* flags.
+    pub fn set_feature_flags(ctx: Context<SetFeatureFlags>, feature_flags: u64) -> Result<()> {
+        return instructions::set_feature_flags::handler(ctx, feature_flags);
+    }
+
+    /// Compare the vault balances of a pool against what it owes: the principal and fees owed to
+    /// the positions passed as remaining accounts plus the protocol fees owed. The result is
+    /// written as a `VaultSolvencyResult` via return data, and a `VaultInsolvencyDetectedEvent` is
+    /// emitted if either vault falls short. Anyone may call this instruction.
+    ///
+    /// Fees are counted up to the last update of each position, so the obligations never exceed
+    /// what the pool really owes and a reported shortfall is real. All positions of the pool must
+    /// be passed for the surplus to be meaningful.
+    pub fn verify_vault_solvency<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, VerifyVaultSolvency<'info>>,
+    ) -> Result<()> {
+        return instructions::verify_vault_solvency::handler(ctx);
+    }
+
+    /// Compare the vault balances of a pool against the principal and fees owed to the positions
+    /// passed as remaining accounts plus the protocol fees owed. For each vault a
+    /// `FeeGrowthDriftDetectedEvent` is emitted if the drift exceeds `max_drift_bps`, otherwise a
+    /// `FeeGrowthReconciledEvent`. No state is modified. Anyone may call this instruction.
+    ///
+    /// Fees are counted up to the last update of each position, so positions should be updated
+    /// with `update_fees_and_rewards` in the same transaction.
+    ///
+    /// ### Parameters
+    /// - `max_drift_bps` - The drift between the expected and actual balance tolerated per vault.
+    pub fn reconcile_fee_growth<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ReconcileFeeGrowth<'info>>,
+        max_drift_bps: u16,
+    ) -> Result<()> {
+        return instructions::reconcile_fee_growth::handler(ctx, max_drift_bps);
+    }
+
+    /// Compare the vault balances of a pool against its expected reserves: the principal and fees
+    /// owed to the positions passed as remaining accounts plus the protocol fees owed. Emits a
+    /// `PoolReconciliationEvent` with the deltas and the transfer fees withheld in Token-2022
+    /// vaults, which are not part of the vault balances. The result is written as a
+    /// `PoolReconciliation` via return data. No state is modified. Anyone may call this instruction.
+    ///
+    /// All positions of the pool must be passed for the deltas to be meaningful, and fees are
+    /// counted up to the last update of each position.
+    ///
+    /// ### Parameters
+    /// - `strict` - If true, the instruction fails when either delta is not zero.
+    ///
+    /// #### Special Errors
+    /// - `PoolReserveMismatch` - `strict` is set and a vault balance differs from the expected reserve.
+    pub fn reconcile_pool<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ReconcilePool<'info>>,
+        strict: bool,
+    ) -> Result<()> {
+        return instructions::reconcile_pool::handler(ctx, strict);
+    }
+
+    /// Move all liquidity and pending fees of a position into a new position in another pool of
+    /// the same token pair, e.g. a pool of a lower fee tier. As much of the withdrawn tokens as
+    /// the new range can hold is deposited; the rest is returned to the owner's token accounts.
+    /// Rewards stay claimable on the source position.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to the source position.
+    ///
+    /// ### Parameters
+    /// - `new_tick_lower` - The tick specifying the lower end of the new position range.
+    /// - `new_tick_upper` - The tick specifying the upper end of the new position range.
+    /// - `slippage_bps` - The maximum price difference between the two pools, in basis points.
+    ///
+    /// #### Special Errors
+    /// - `InvalidMigrationPool` - The destination pool is the source pool or has another token pair.
+    /// - `MigrationSlippageExceeded` - The pool prices differ by more than `slippage_bps`.
+    /// - `TickIndexOutOfBounds` - If a provided tick is out of bounds.
+    /// - `TickNotAlignedToSpacing` - If a provided tick is not a multiple of the tick-spacing of
+    ///                               the destination pool.
+    /// - `InvalidTickIndex` - If the provided ticks are out of order.
+    /// - `LiquidityZero` - The source position has no liquidity, or the withdrawn tokens do not
+    ///                     provide any liquidity in the new range.
+    pub fn migrate_position_to_pool(
+        ctx: Context<MigratePositionToPool>,
+        new_tick_lower: i32,
+        new_tick_upper: i32,
+        slippage_bps: u16,
+    ) -> Result<()> {
+        return instructions::migrate_position_to_pool::handler(
+            ctx,
+            new_tick_lower,
+            new_tick_upper,
+            slippage_bps,
+        );
+    }
+
+    /// Move a position NFT into a vault and mint fungible Token-2022 fractions of the position,
+    /// so that the position can be traded in parts. While fractionalized, fees of the position are
+    /// collected by the holders of deposited fractions.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this position.
+    ///
+    /// ### Parameters
+    /// - `total_shares` - The number of fractions minted to the position authority.
+    ///
+    /// #### Special Errors
+    /// - `InvalidFractionShares` - `total_shares` is zero.
+    pub fn fractionalize_position(
+        ctx: Context<FractionalizePosition>,
+        total_shares: u64,
+    ) -> Result<()> {
+        return instructions::fractionalize_position::handler(ctx, total_shares);
+    }
+
+    /// Burn all fractions of a fractionalized position and return the position NFT.
+    ///
+    /// ### Authority
+    /// - `fraction_authority` - authority that owns all fractions of the position.
+    ///
+    /// #### Special Errors
+    /// - `IncompleteFractionOwnership` - The fraction token account does not hold all fractions.
+    pub fn redeem_position_fractions(ctx: Context<RedeemPositionFractions>) -> Result<()> {
+        return instructions::redeem_position_fractions::handler(ctx);
+    }
+
+    /// Deposit fractions of a fractionalized position to earn a share of the fees of the
+    /// position, proportional to the deposited fractions.
     ///
     /// ### Authority
-    /// - `position_bundle_owner` - The owner that owns the position bundle token.
+    /// - `fraction_authority` - authority that owns the fractions.
     ///
-    /// ### Special Errors
-    /// - `PositionBundleNotDeletable` - The provided position bundle has open positions.
-    pub fn delete_position_bundle(ctx: Context<DeletePositionBundle>) -> Result<()> {
-        return instructions::delete_position_bundle::handler(ctx);
+    /// ### Parameters
+    /// - `amount` - The number of fractions to deposit.
+    pub fn deposit_position_fractions(
+        ctx: Context<DepositPositionFractions>,
+        amount: u64,
+    ) -> Result<()> {
+        return instructions::deposit_position_fractions::handler(ctx, amount);
     }
 
-    /// Open a bundled position in a pool. No new tokens are issued
-    /// because the owner of the position bundle becomes the owner of the position.
-    /// The position will start off with 0 liquidity.
+    /// Withdraw deposited fractions of a fractionalized position and collect the fees they earned.
     ///
     /// ### Authority
-    /// - `position_bundle_authority` - authority that owns the token corresponding to this desired position bundle.
+    /// - `fraction_authority` - authority that deposited the fractions.
     ///
     /// ### Parameters
-    /// - `bundle_index` - The bundle index that we'd like to open.
-    /// - `tick_lower_index` - The tick specifying the lower end of the position range.
-    /// - `tick_upper_index` - The tick specifying the upper end of the position range.
+    /// - `amount` - The number of fractions to withdraw.
     ///
     /// #### Special Errors
-    /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
-    /// - `InvalidTickIndex` - If a provided tick is out of bounds, out of order or not a multiple of
-    ///                        the tick-spacing in this pool.
-    pub fn open_bundled_position(
-        ctx: Context<OpenBundledPosition>,
-        bundle_index: u16,
-        tick_lower_index: i32,
-        tick_upper_index: i32,
+    /// - `InsufficientFractionShares` - `amount` exceeds the deposited fractions.
+    pub fn withdraw_position_fractions<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, WithdrawPositionFractions<'info>>,
+        amount: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
-        return instructions::open_bundled_position::handler(
+        return instructions::withdraw_position_fractions::handler(
             ctx,
-            bundle_index,
-            tick_lower_index,
-            tick_upper_index,
+            amount,
+            remaining_accounts_info,
         );
     }
 
-    /// Close a bundled position in a pool.
+    /// Collect the fees earned by the deposited fractions of a fractionalized position. The fees
+    /// of the position are updated from its tick arrays first.
     ///
     /// ### Authority
-    /// - `position_bundle_authority` - authority that owns the token corresponding to this desired position bundle.
-    ///
-    /// ### Parameters
-    /// - `bundle_index` - The bundle index that we'd like to close.
-    ///
-    /// #### Special Errors
-    /// - `InvalidBundleIndex` - If the provided bundle index is out of bounds.
-    /// - `ClosePositionNotEmpty` - The provided position account is not empty.
-    pub fn close_bundled_position(
-        ctx: Context<CloseBundledPosition>,
-        bundle_index: u16,
+    /// - `fraction_authority` - authority that deposited the fractions.
+    pub fn collect_position_fraction_fees<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectPositionFractionFees<'info>>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
-        return instructions::close_bundled_position::handler(ctx, bundle_index);
+        return instructions::collect_position_fraction_fees::handler(
+            ctx,
+            remaining_accounts_info,
+        );
     }
 
     ////////////////////////////////////////////////////////////////////////////////
@@ -627,17 +1977,62 @@ pub mod anchor_dex {
     ///
     /// ### Authority
     /// - `position_authority` - authority that owns the token corresponding to this desired position.
-    pub fn collect_fees_v2<'a, 'b, 'c, 'info>(
+    ///
+    /// The fees are transferred to `token_owner_account_a` and `token_owner_account_b`, which can be
+    /// any token accounts of the pool's mints, e.g. those of a strategy the fees are routed to.
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeDestination` - A destination token account is a vault of the pool.
+    pub fn collect_fees_v2<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CollectFeesV2<'info>>,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
     ) -> Result<()> {
         return instructions::v2::collect_fees::handler(ctx, remaining_accounts_info);
     }
 
+    /// Collect fees accrued for this position and convert the fee token that is not
+    /// `output_token_mint` into the output token, so that all fees are received in a single token.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `output_token_mint` - The token to receive all fees in. Must be token A or B of the pool.
+    /// - `conversion_pool` - The pool to convert in. Either the pool itself, in which case the fee
+    ///                       token is swapped without leaving the vault, or a pool of the same pair.
+    /// - `min_output_amount` - The minimum amount of output token to receive for the converted fees.
+    ///
+    /// #### Special Errors
+    /// - `InvalidConversionPool` - The output mint or conversion pool does not match the position's pool.
+    /// - `ConversionNotFullyFilled` - The conversion pool could not absorb all of the collected fee token.
+    /// - `AmountOutBelowMinimum` - The converted amount is below `min_output_amount`.
+    /// - `SwapAmountBelowMinimum` - The fee token amount is below the min_swap_amount of the conversion pool.
+    /// - `SwapAmountAboveMaximum` - The fee token amount is above the max_swap_amount of the conversion pool.
+    pub fn collect_fees_and_convert_v2<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectFeesAndConvertV2<'info>>,
+        output_token_mint: Pubkey,
+        conversion_pool: Pubkey,
+        min_output_amount: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::collect_fees_and_convert::handler(
+            ctx,
+            output_token_mint,
+            conversion_pool,
+            min_output_amount,
+            remaining_accounts_info,
+        );
+    }
+
     /// Collect the protocol fees accrued in this pool
     ///
     /// ### Authority
     /// - `collect_protocol_fees_authority` - assigned authority in the poolConfig that can collect protocol fees
+    ///
+    /// #### Special Errors
+    /// - `InvalidFeeDestination` - A destination token account is a vault of the pool.
+    /// - `ProtocolFeeDestinationOverflow` - Crediting the owed fees would overflow a destination token account.
+    /// - `DirectCallRequired` - The pools config requires direct calls and the instruction was invoked through CPI.
     pub fn collect_protocol_fees_v2<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, CollectProtocolFeesV2<'info>>,
         remaining_accounts_info: Option<RemainingAccountsInfo>,
@@ -661,6 +2056,69 @@ pub mod anchor_dex {
         );
     }
 
+    /// Collect rewards accrued for this position and swap them into `desired_output_mint` in a
+    /// conversion pool. Rewards below `min_swap_amount` are delivered in the reward token.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward to harvest. Acceptable values are 0, 1, and 2.
+    /// - `desired_output_mint` - The token to receive the rewards in.
+    /// - `min_swap_amount` - The minimum amount of reward token to swap.
+    /// - `min_output_amount` - The minimum amount of output token to receive for the swapped rewards.
+    ///
+    /// #### Special Errors
+    /// - `InvalidConversionPool` - The conversion pool is the position's pool or does not trade the reward token against `desired_output_mint`.
+    /// - `ConversionNotFullyFilled` - The conversion pool could not absorb all of the collected rewards.
+    /// - `AmountOutBelowMinimum` - The swapped amount is below `min_output_amount`.
+    /// - `SwapAmountBelowMinimum` - The reward amount is below the min_swap_amount of the conversion pool.
+    /// - `SwapAmountAboveMaximum` - The reward amount is above the max_swap_amount of the conversion pool.
+    pub fn collect_reward_and_swap_v2<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, CollectRewardAndSwapV2<'info>>,
+        reward_index: u8,
+        desired_output_mint: Pubkey,
+        min_swap_amount: u64,
+        min_output_amount: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::collect_reward_and_swap::handler(
+            ctx,
+            reward_index,
+            desired_output_mint,
+            min_swap_amount,
+            min_output_amount,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Collect a reward accrued for bundled positions of a pool, and transfer the total in a
+    /// single transfer.
+    ///
+    /// ### Authority
+    /// - `position_bundle_authority` - authority that owns the token corresponding to this desired position bundle.
+    ///
+    /// ### Parameters
+    /// - `reward_index` - The reward to harvest. Acceptable values are 0, 1, and 2.
+    /// - `bundle_indexes` - The bundle indexes of the positions passed as remaining accounts.
+    ///
+    /// #### Special Errors
+    /// - `InvalidBundlePositionAccounts` - No positions, more than 32 positions, a duplicated bundle
+    ///                                     index, or a position that is not the bundled position of its index.
+    pub fn batch_collect_bundle_rewards_v2<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, BatchCollectBundleRewardsV2<'info>>,
+        reward_index: u8,
+        bundle_indexes: Vec<u16>,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::batch_collect_bundle_rewards::handler(
+            ctx,
+            reward_index,
+            bundle_indexes,
+            remaining_accounts_info,
+        );
+    }
+
     /// Withdraw liquidity from a position in the pool. This call also updates the position's accrued fees and rewards.
     ///
     /// ### Authority
@@ -675,7 +2133,9 @@ pub mod anchor_dex {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMinSubceeded` - The required token to perform this operation subceeds the user defined amount.
-    pub fn decrease_liquidity_v2<'a, 'b, 'c, 'info>(
+    /// - `TickArrayDoesNotContainPositionTick` - The tick arrays do not contain the boundary ticks of the position.
+    /// - `TickArrayNotInitialized` - A tick array of the position was not created with `initialize_tick_array`.
+    pub fn decrease_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
         token_min_a: u64,
@@ -691,6 +2151,37 @@ pub mod anchor_dex {
         );
     }
 
+    /// Withdraw liquidity from a position in the pool and collect the position's accrued fees in
+    /// the same call. The withdrawn tokens and the fees are transferred in a single transfer per token.
+    ///
+    /// ### Authority
+    /// - `position_authority` - authority that owns the token corresponding to this desired position.
+    ///
+    /// ### Parameters
+    /// - `liquidity_amount` - The total amount of Liquidity the user desires to withdraw.
+    /// - `token_min_a` - The minimum amount of tokenA, withdrawn tokens and fees combined and transfer fee excluded, the user is willing to receive.
+    /// - `token_min_b` - The minimum amount of tokenB, withdrawn tokens and fees combined and transfer fee excluded, the user is willing to receive.
+    ///
+    /// #### Special Errors
+    /// - `LiquidityZero` - Provided liquidity amount is zero.
+    /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
+    /// - `TokenMinSubceeded` - The tokens received subceed the user defined amount.
+    pub fn decrease_liquidity_and_collect_v2<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityV2<'info>>,
+        liquidity_amount: u128,
+        token_min_a: u64,
+        token_min_b: u64,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::decrease_liquidity_and_collect::handler(
+            ctx,
+            liquidity_amount,
+            token_min_a,
+            token_min_b,
+            remaining_accounts_info,
+        );
+    }
+
     /// Add liquidity to a position in the pool. This call also updates the position's accrued fees and rewards.
     ///
     /// ### Authority
@@ -705,7 +2196,9 @@ pub mod anchor_dex {
     /// - `LiquidityZero` - Provided liquidity amount is zero.
     /// - `LiquidityTooHigh` - Provided liquidity exceeds u128::max.
     /// - `TokenMaxExceeded` - The required token to perform this operation exceeds the user defined amount.
-    pub fn increase_liquidity_v2<'a, 'b, 'c, 'info>(
+    /// - `TickArrayDoesNotContainPositionTick` - The tick arrays do not contain the boundary ticks of the position.
+    /// - `TickArrayNotInitialized` - A tick array of the position was not created with `initialize_tick_array`.
+    pub fn increase_liquidity_v2<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, ModifyLiquidityV2<'info>>,
         liquidity_amount: u128,
         token_max_a: u64,
@@ -721,7 +2214,8 @@ pub mod anchor_dex {
         );
     }
 
-    /// Initializes a pool account.
+    /// Initializes a pool account and its token vaults. Each vault is created by the token program
+    /// owning its mint, so a pool can pair a Token mint with a Token-2022 mint.
     /// Fee rate is set to the default values on the config and supplied fee_tier.
     ///
     /// ### Parameters
@@ -732,6 +2226,8 @@ pub mod anchor_dex {
     /// #### Special Errors
     /// `InvalidTokenMintOrder` - The order of mints have to be ordered by
     /// `SqrtPriceOutOfBounds` - provided initial_sqrt_price is not between 2^-64 to 2^64
+    /// `TickSpacingFeeTierMismatch` - The tick spacing of the fee tier differs from `tick_spacing`
+    /// `VaultNotEmpty` - A token vault holds tokens before the pool was created
     ///
     pub fn initialize_pool_v2(
         ctx: Context<InitializePoolV2>,
@@ -758,7 +2254,8 @@ pub mod anchor_dex {
         return instructions::v2::initialize_reward::handler(ctx, reward_index);
     }
 
-    /// Set the reward emissions for a reward in a pool.
+    /// Set the reward emissions for a reward in a pool. Replaces any emissions schedule of the
+    /// reward.
     ///
     /// ### Authority
     /// - "reward_authority" - assigned authority by the reward_super_authority for the specified
@@ -798,17 +2295,28 @@ pub mod anchor_dex {
     /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
     /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
     /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `remaining_accounts_info` - Slices of the remaining accounts. Up to MAX_SWAP_TICK_ARRAYS - 3 supplemental tick-arrays following `tick_array_2` can be passed to swap across a wider range.
+    ///
+    /// Every swap is counted in the pool. Swaps with a shared deadline, a quoted tick, a tick limit,
+    /// a tick crossing limit, cached tick arrays, protocol fee streaming, a referral fee or an LP
+    /// fee rebate use `swap_v3`.
     ///
     /// #### Special Errors
     /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
     /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over ppool's max/min bounds for sqrt-price.
-    /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays, including supplemental tick-arrays, are not in sequential order required to proceed in this trade direction.
+    /// - `DuplicateTickArrayAccount` - A tick-array is passed again after a different tick-array.
+    /// - `TickArrayLoadFailed` - A tick-array after the first one is not repeated and fails to load.
+    /// - `RemainingAccountsInvalidSlice` - More than MAX_SWAP_TICK_ARRAYS - 3 supplemental tick-arrays were provided.
     /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
-    pub fn swap_v2<'a, 'b, 'c, 'info>(
+    /// - `SwapDirectionDisabled` - Swaps in the direction of the trade are disabled for the pool.
+    /// - `SwapAmountBelowMinimum` - `amount` is below the min_swap_amount of the pool.
+    /// - `SwapAmountAboveMaximum` - `amount` is above the max_swap_amount of the pool.
+    pub fn swap_v2<'a, 'b, 'c: 'info, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, SwapV2<'info>>,
         amount: u64,
         other_amount_threshold: u64,
@@ -828,6 +2336,79 @@ pub mod anchor_dex {
         );
     }
 
+    /// Perform a swap in this pool with the options of `options`
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `other_amount_threshold` - The maximum/minimum of input/output token to swap into (depending on amount_specified_is_input).
+    /// - `sqrt_price_limit` - The maximum/minimum price the swap will swap to.
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b` - The direction of the swap. True if swapping from A to B. False if swapping from B to A.
+    /// - `options` - The versioned options of the swap:
+    ///   - `expected_tick_index` - The current tick of the pool the swap was quoted at. If provided, the swap fails when the pool has moved further than `max_tick_deviation` from it.
+    ///   - `max_tick_deviation` - The number of ticks the pool may have moved from `expected_tick_index`.
+    ///   - `use_cache` - If true, the tick array caches written by `warm_tick_arrays` in this or the previous slot are used to look up initialized ticks.
+    ///   - `referral_fee_rate` - The share of the LP fee paid to `referral_fee_account`, in basis points. Ignored without a referral account. The referral fee is transferred with the transfer fee of the input token.
+    ///   - `max_tick_crossings` - The number of initialized ticks the swap may cross before it stops and settles the amounts swapped so far. Zero for no limit.
+    ///   - `tick_limit` - The tick whose price the swap will swap to at most. If provided together with `sqrt_price_limit`, the limit closer to the current price applies.
+    /// - `remaining_accounts_info` - Slices of the remaining accounts. Up to MAX_SWAP_TICK_ARRAYS - 3 supplemental tick-arrays following `tick_array_2` can be passed to swap across a wider range.
+    ///
+    /// Every swap is counted in the pool. If the swapper passes a `position_swap_count` with its
+    /// `rebate_position` and the `rebate_position_token_account` holding the position, the swap is
+    /// counted for the position, and the LP fee rebate of the optional `lp_fee_rebate_config`
+    /// applies to its fee.
+    ///
+    /// While protocol fee streaming is enabled for the poolConfig, the protocol fee of the swap is
+    /// transferred to the `protocol_treasury_token_account` of the treasury of the poolConfig
+    /// extension, which must both be provided.
+    ///
+    /// #### Special Errors
+    /// - `ZeroTradableAmount` - User provided parameter `amount` is 0.
+    /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` or the price of `tick_limit` does not match the direction of the trade.
+    /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over ppool's max/min bounds for sqrt-price.
+    /// - `TickIndexOutOfBounds` - User provided parameter `tick_limit` is outside the supported tick range.
+    /// - `InvalidTickArraySequence` - User provided tick-arrays, including supplemental tick-arrays, are not in sequential order required to proceed in this trade direction.
+    /// - `DuplicateTickArrayAccount` - A tick-array is passed again after a different tick-array.
+    /// - `TickArrayLoadFailed` - A tick-array after the first one is not repeated and fails to load.
+    /// - `RemainingAccountsInvalidSlice` - More than MAX_SWAP_TICK_ARRAYS - 3 supplemental tick-arrays were provided.
+    /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
+    /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
+    /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
+    /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `SwapDirectionDisabled` - Swaps in the direction of the trade are disabled for the pool.
+    /// - `SwapAmountBelowMinimum` - `amount` is below the min_swap_amount of the pool.
+    /// - `SwapAmountAboveMaximum` - `amount` is above the max_swap_amount of the pool.
+    /// - `PoolTickMoved` - The current tick of the pool deviates from `expected_tick_index` by more than `max_tick_deviation`.
+    /// - `InvalidProtocolTreasuryAccount` - Protocol fee streaming applies and the treasury token account is not owned by the protocol treasury or has the wrong mint.
+    /// - `MissingProtocolTreasuryAccount` - Protocol fee streaming applies and the poolConfig extension or the treasury token account is not provided.
+    /// - `InvalidReferralFeeAccount` - The referral fee account does not hold the input token of the swap.
+    /// - `ReferralFeeRateMaxExceeded` - `referral_fee_rate` exceeds the max_referral_fee_rate of the poolConfig.
+    /// - `InvalidFeeRebatePosition` - The position swap count is not of this pool, `rebate_position` is missing, not its position or has no liquidity, or `rebate_position_token_account` is missing or does not hold its position token.
+    pub fn swap_v3<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SwapV3<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit: u128,
+        amount_specified_is_input: bool,
+        a_to_b: bool,
+        options: SwapOptions,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::swap_v3::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            options,
+            remaining_accounts_info,
+        );
+    }
+
     /// Perform a two-hop swap in this pool
     ///
     /// ### Authority
@@ -847,12 +2428,18 @@ pub mod anchor_dex {
     /// - `InvalidSqrtPriceLimitDirection` - User provided parameter `sqrt_price_limit` does not match the direction of the trade.
     /// - `SqrtPriceOutOfBounds` - User provided parameter `sqrt_price_limit` is over ppool's max/min bounds for sqrt-price.
     /// - `InvalidTickArraySequence` - User provided tick-arrays are not in sequential order required to proceed in this trade direction.
+    /// - `DuplicateTickArrayAccount` - A tick-array is passed again after a different tick-array, or is shared by both swaps.
+    /// - `TickArrayLoadFailed` - A tick-array after the first one is not repeated and fails to load.
     /// - `TickArraySequenceInvalidIndex` - The swap loop attempted to access an invalid array index during the query of the next initialized tick.
     /// - `TickArrayIndexOutofBounds` - The swap loop attempted to access an invalid array index during tick crossing.
     /// - `LiquidityOverflow` - Liquidity value overflowed 128bits during tick crossing.
     /// - `InvalidTickSpacing` - The swap pool was initialized with tick-spacing of 0.
+    /// - `SwapDirectionDisabled` - Swaps in the direction of the trade are disabled for the pool.
+    /// - `SwapAmountBelowMinimum` - The amount of a hop is below the min_swap_amount of its pool.
+    /// - `SwapAmountAboveMaximum` - The amount of a hop is above the max_swap_amount of its pool.
     /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
     /// - `DuplicateTwoHopPool` - Error if pool one & two are the same pool.
+    /// - `CircularRoute` - Error if the route starts and ends with the same token.
     pub fn two_hop_swap_v2<'a, 'b, 'c, 'info>(
         ctx: Context<'a, 'b, 'c, 'info, TwoHopSwapV2<'info>>,
         amount: u64,
@@ -877,6 +2464,166 @@ pub mod anchor_dex {
         );
     }
 
+    /// Perform a two-hop swap in this pool with the options of `options`
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// Same as `two_hop_swap_v2`.
+    /// - `options` - The versioned options of the route:
+    ///   - `intermediate_threshold` - Optional minimum amount of intermediate token out of the first hop. Only checked for exact-in swaps.
+    ///   - `max_tick_crossings` - The number of initialized ticks the hop calculated first (hop one for exact-in, hop two for exact-out) may cross before it stops. The route then settles the amounts swapped so far. Zero for no limit.
+    ///
+    /// #### Special Errors
+    /// Same as `two_hop_swap_v2`.
+    /// - `IntermediateAmountBelowMinimum` - The first hop of an exact-in swap produced less than `intermediate_threshold`.
+    pub fn two_hop_swap_v3<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, TwoHopSwapV2<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        amount_specified_is_input: bool,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        sqrt_price_limit_one: u128,
+        sqrt_price_limit_two: u128,
+        options: TwoHopSwapOptions,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::two_hop_swap_v3::handler(
+            ctx,
+            amount,
+            other_amount_threshold,
+            amount_specified_is_input,
+            a_to_b_one,
+            a_to_b_two,
+            sqrt_price_limit_one,
+            sqrt_price_limit_two,
+            options,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Quote a two-hop swap without transferring tokens or mutating the pools and tick-arrays.
+    /// The legs are chained exactly as in `two_hop_swap_v2`, including the transfer fees on the
+    /// intermediate mint, and the result is emitted as a `TwoHopQuoteEvent`.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input or output token to swap from (depending on amount_specified_is_input).
+    /// - `amount_specified_is_input` - Specifies the token the parameter `amount`represents. If true, the amount represents the input token of the swap.
+    /// - `a_to_b_one` - The direction of the swap of hop one. True if swapping from A to B. False if swapping from B to A.
+    /// - `a_to_b_two` - The direction of the swap of hop two. True if swapping from A to B. False if swapping from B to A.
+    /// - `sqrt_price_limit_one` - The maximum/minimum price the swap will swap to in the first hop.
+    /// - `sqrt_price_limit_two` - The maximum/minimum price the swap will swap to in the second hop.
+    /// - `remaining_accounts_info` - The transfer hook accounts of the input, intermediate and output mints, as in `two_hop_swap_v2`.
+    ///
+    /// #### Special Errors
+    /// - `InvalidIntermediaryMint` - Error if the intermediary mint between hop one and two do not equal.
+    /// - `DuplicateTwoHopPool` - Error if pool one & two are the same pool.
+    /// - `CircularRoute` - Error if the route starts and ends with the same token.
+    /// - `IntermediateTokenAmountMismatch` - The second hop would not consume the full output of the first hop.
+    /// - `NoExtraAccountsForTransferHook` - A mint has a transfer hook, but its accounts were not provided.
+    pub fn simulate_two_hop_swap<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SimulateTwoHopSwap<'info>>,
+        amount: u64,
+        amount_specified_is_input: bool,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        sqrt_price_limit_one: u128,
+        sqrt_price_limit_two: u128,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::simulate_two_hop_swap::handler(
+            ctx,
+            amount,
+            amount_specified_is_input,
+            a_to_b_one,
+            a_to_b_two,
+            sqrt_price_limit_one,
+            sqrt_price_limit_two,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Execute a closed loop of exact-in swaps over two or three pools, starting and ending with
+    /// the same token, and only succeed if the cycle returns at least the input amount minus the
+    /// allowed slippage.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `amount` - The amount of input token to send into the first pool.
+    /// - `max_slippage_bps` - The maximum loss, in basis points of `amount`, that the cycle may incur.
+    /// - `a_to_b_one` - The direction of the swap on pool one. True if swapping from A to B.
+    /// - `a_to_b_two` - The direction of the swap on pool two. True if swapping from A to B.
+    /// - `a_to_b_three` - The direction of the swap on pool three. Ignored for two-pool cycles.
+    ///
+    /// #### Special Errors
+    /// - `InvalidArbitrageCycle` - The pools do not form a closed loop, a pool is repeated, or the third leg is only partially provided.
+    /// - `ArbitrageNotProfitable` - The output of the final leg is below the slippage-adjusted input amount. Reports the profit in bps and the lowest accepted profit in bps.
+    /// - `IntermediateTokenAmountMismatch` - A leg did not consume the full output of the previous leg.
+    /// - `SwapAmountBelowMinimum` - The input of a leg is below the min_swap_amount of its pool.
+    /// - `SwapAmountAboveMaximum` - The input of a leg is above the max_swap_amount of its pool.
+    pub fn execute_arbitrage_cycle<'a, 'b, 'info>(
+        ctx: Context<'a, 'b, 'info, 'info, ExecuteArbitrageCycle<'info>>,
+        amount: u64,
+        max_slippage_bps: u16,
+        a_to_b_one: bool,
+        a_to_b_two: bool,
+        a_to_b_three: bool,
+        remaining_accounts_info: Option<RemainingAccountsInfo>,
+    ) -> Result<()> {
+        return instructions::v2::execute_arbitrage_cycle::handler(
+            ctx,
+            amount,
+            max_slippage_bps,
+            a_to_b_one,
+            a_to_b_two,
+            a_to_b_three,
+            remaining_accounts_info,
+        );
+    }
+
+    /// Swap an exact input amount through a route of up to 4 pools.
+    ///
+    /// Only exact input swaps are supported: the output of each hop is the input of the next one,
+    /// so an exact output route would have to be simulated backwards from the last pool.
+    /// Each hop is checked like a single swap against the min and max swap amount and the
+    /// enabled swap directions of its pool.
+    ///
+    /// The pools of the route are passed as remaining accounts, for each hop:
+    /// pool, token_vault_input, token_vault_output, tick_array_0, tick_array_1, tick_array_2.
+    /// They are followed by token_mint and token_program of each intermediate token.
+    /// Tokens with a transfer hook are not supported.
+    ///
+    /// ### Authority
+    /// - "token_authority" - The authority to withdraw tokens from the input token account.
+    ///
+    /// ### Parameters
+    /// - `route` - The pool, direction and sqrt price limit of each hop, in swap order.
+    /// - `amount` - The amount of input token to swap.
+    /// - `other_amount_threshold` - The minimum amount of output token to receive.
+    ///
+    /// #### Special Errors
+    /// - `InvalidSwapRoute` - The route is empty, longer than 4 hops, repeats a pool or does not
+    ///                        match the remaining accounts.
+    /// - `InvalidIntermediaryMint` - The output token of a hop is not the input of the next hop.
+    /// - `CircularRoute` - The route ends in its input token.
+    /// - `IntermediateTokenAmountMismatch` - A hop does not swap the full output of the
+    ///                                       previous hop.
+    /// - `AmountOutBelowMinimum` - The output is below other_amount_threshold.
+    /// - `SwapAmountBelowMinimum` - The input of a hop is below the min swap amount of its pool.
+    /// - `SwapAmountAboveMaximum` - The input of a hop is above the max swap amount of its pool.
+    pub fn routed_swap_v2<'a, 'b, 'info>(
+        ctx: Context<'a, 'b, 'info, 'info, RoutedSwapV2<'info>>,
+        route: Vec<RouteHop>,
+        amount: u64,
+        other_amount_threshold: u64,
+    ) -> Result<()> {
+        return instructions::v2::routed_swap::handler(ctx, route, amount, other_amount_threshold);
+    }
+
     pub fn initialize_config_extension(ctx: Context<InitializeConfigExtension>) -> Result<()> {
         return instructions::v2::initialize_config_extension::handler(ctx);
     }
@@ -889,6 +2636,11 @@ pub mod anchor_dex {
         return instructions::v2::set_token_badge_authority::handler(ctx);
     }
 
+    /// Initializes a token badge that allows pools with the token mint to be initialized.
+    ///
+    /// ### Authority
+    /// - "token_badge_authority" - Set authority in the poolConfigExtension, or an operations
+    ///                             authority of the poolConfig
     pub fn initialize_token_badge(ctx: Context<InitializeTokenBadge>) -> Result<()> {
         return instructions::v2::initialize_token_badge::handler(ctx);
     }
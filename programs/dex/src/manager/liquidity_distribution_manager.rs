@@ -0,0 +1,195 @@
+use crate::{
+    errors::ErrorCode,
+    math::add_liquidity_delta,
+    state::{TickArray, TICK_ARRAY_SIZE},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct LiquidityDistributionPoint {
+    pub tick_index: i32,
+    pub liquidity_net: i128,
+    // Active liquidity of the pool once the price has crossed this tick in the search direction
+    pub liquidity: u128,
+}
+
+/// Walks the initialized ticks of a sequence of tick-arrays in the given direction and
+/// accumulates the active liquidity of the pool as each tick is crossed.
+///
+/// The walk starts from the pool's active `liquidity` at `tick_current_index`. For a_to_b
+/// searches the price moves left, so ticks at or below the current tick are crossed and their
+/// liquidity_net is subtracted. For b_to_a searches the price moves right, so ticks above the
+/// current tick are crossed and their liquidity_net is added.
+///
+/// # Parameters
+/// - `liquidity` - The active liquidity of the pool at `tick_current_index`
+/// - `tick_current_index` - The current tick index of the pool
+/// - `tick_spacing` - The tick spacing of the pool
+/// - `tick_arrays` - The tick-arrays to walk, in sequential order for the search direction
+/// - `a_to_b` - The direction of the walk
+///
+/// # Errors
+/// - `InvalidTickArraySequence` - The tick-arrays are not sequential, or the first array does not hold the current tick.
+/// - `LiquidityUnderflow` / `LiquidityOverflow` - The accumulated liquidity left the u128 range.
+pub fn calculate_liquidity_distribution(
+    liquidity: u128,
+    tick_current_index: i32,
+    tick_spacing: u16,
+    tick_arrays: &[&TickArray],
+    a_to_b: bool,
+) -> Result<Vec<LiquidityDistributionPoint>> {
+    if tick_spacing == 0 {
+        return Err(ErrorCode::InvalidTickSpacing.into());
+    }
+
+    let first_array = match tick_arrays.first() {
+        Some(array) => array,
+        None => return Err(ErrorCode::InvalidTickArraySequence.into()),
+    };
+    if !first_array.in_search_range(tick_current_index, tick_spacing, !a_to_b) {
+        return Err(ErrorCode::InvalidTickArraySequence.into());
+    }
+
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let first_start_tick_index = first_array.start_tick_index;
+
+    let mut points = Vec::new();
+    let mut running_liquidity = liquidity;
+
+    for (array_index, array) in tick_arrays.iter().enumerate() {
+        let start_tick_index = array.start_tick_index;
+        let expected_start_tick_index = if a_to_b {
+            first_start_tick_index - ticks_in_array * array_index as i32
+        } else {
+            first_start_tick_index + ticks_in_array * array_index as i32
+        };
+        if start_tick_index != expected_start_tick_index {
+            return Err(ErrorCode::InvalidTickArraySequence.into());
+        }
+
+        for i in 0..TICK_ARRAY_SIZE {
+            let offset = if a_to_b { TICK_ARRAY_SIZE - 1 - i } else { i };
+            let tick = &array.ticks[offset as usize];
+            if !tick.initialized {
+                continue;
+            }
+
+            let tick_index = start_tick_index + offset * tick_spacing as i32;
+            let is_crossed = if a_to_b {
+                tick_index <= tick_current_index
+            } else {
+                tick_index > tick_current_index
+            };
+            if !is_crossed {
+                continue;
+            }
+
+            let liquidity_net = tick.liquidity_net;
+            let signed_liquidity_net = if a_to_b {
+                liquidity_net
+                    .checked_neg()
+                    .ok_or(ErrorCode::LiquidityNetError)?
+            } else {
+                liquidity_net
+            };
+            running_liquidity = add_liquidity_delta(running_liquidity, signed_liquidity_net)
+                .map_err(ErrorCode::from)?;
+
+            points.push(LiquidityDistributionPoint {
+                tick_index,
+                liquidity_net,
+                liquidity: running_liquidity,
+            });
+        }
+    }
+
+    Ok(points)
+}
+
+#[cfg(test)]
+mod liquidity_distribution_tests {
+    use super::*;
+    use crate::state::tick_builder::TickBuilder;
+
+    fn array_with_ticks(start_tick_index: i32, ticks: &[(i32, i128)]) -> TickArray {
+        let mut array = TickArray::default();
+        array.start_tick_index = start_tick_index;
+        for (offset, liquidity_net) in ticks {
+            array.ticks[*offset as usize] = TickBuilder::default()
+                .initialized(true)
+                .liquidity_net(*liquidity_net)
+                .liquidity_gross(liquidity_net.unsigned_abs())
+                .build();
+        }
+        array
+    }
+
+    #[test]
+    fn test_b_to_a_adds_liquidity_net() {
+        // Position [-880, 880) with 100 liquidity; current tick 5
+        let lower = array_with_ticks(-88 * 10, &[(0, 100)]);
+        let upper = array_with_ticks(0, &[]);
+        let upper_end = array_with_ticks(TICK_ARRAY_SIZE * 10, &[(0, -100)]);
+
+        let points =
+            calculate_liquidity_distribution(100, 5, 10, &[&upper, &upper_end], false).unwrap();
+        assert_eq!(
+            points,
+            vec![LiquidityDistributionPoint {
+                tick_index: 880,
+                liquidity_net: -100,
+                liquidity: 0,
+            }]
+        );
+
+        let points = calculate_liquidity_distribution(0, -900, 10, &[&lower], false);
+        assert!(points.is_err());
+    }
+
+    #[test]
+    fn test_a_to_b_subtracts_liquidity_net() {
+        // Positions [-880, 880) with 100 and [-440, 440) with 50; current tick 0
+        let current = array_with_ticks(0, &[]);
+        let lower = array_with_ticks(-880, &[(0, 100), (44, 50)]);
+
+        let points =
+            calculate_liquidity_distribution(150, 0, 10, &[&current, &lower], true).unwrap();
+        assert_eq!(
+            points,
+            vec![
+                LiquidityDistributionPoint {
+                    tick_index: -440,
+                    liquidity_net: 50,
+                    liquidity: 100,
+                },
+                LiquidityDistributionPoint {
+                    tick_index: -880,
+                    liquidity_net: 100,
+                    liquidity: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_a_to_b_skips_ticks_above_current() {
+        let current = array_with_ticks(0, &[(0, 100), (10, -100)]);
+
+        let points = calculate_liquidity_distribution(100, 50, 10, &[&current], true).unwrap();
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].tick_index, 0);
+        assert_eq!(points[0].liquidity, 0);
+    }
+
+    #[test]
+    fn test_non_sequential_arrays() {
+        let current = array_with_ticks(0, &[]);
+        let skipped = array_with_ticks(-1760, &[]);
+
+        let result = calculate_liquidity_distribution(0, 0, 10, &[&current, &skipped], true);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidTickArraySequence.into()
+        );
+    }
+}
@@ -7,7 +7,10 @@ use super::{
 };
 use crate::{
     errors::ErrorCode,
-    math::{get_amount_delta_a, get_amount_delta_b, sqrt_price_from_tick_index},
+    math::{
+        convert_to_liquidity_delta, get_liquidity_from_amount_a, get_liquidity_from_amount_b,
+        sqrt_price_from_tick_index, token_amounts_from_liquidity,
+    },
     state::*,
 };
 use anchor_lang::prelude::{AccountLoader, *};
@@ -33,10 +36,13 @@ pub fn calculate_modify_liquidity<'info>(
     timestamp: u64,
 ) -> Result<ModifyLiquidityUpdate> {
     let tick_array_lower = tick_array_lower.load()?;
-    let tick_lower = tick_array_lower.get_tick(position.tick_lower_index, pool.tick_spacing)?;
-
     let tick_array_upper = tick_array_upper.load()?;
-    let tick_upper = tick_array_upper.get_tick(position.tick_upper_index, pool.tick_spacing)?;
+    let (tick_lower, tick_upper) = get_position_ticks(
+        position,
+        &tick_array_lower,
+        &tick_array_upper,
+        pool.tick_spacing,
+    )?;
 
     Ok(_calculate_modify_liquidity(
         pool,
@@ -58,10 +64,13 @@ pub fn calculate_fee_and_reward_growths<'info>(
     timestamp: u64,
 ) -> Result<(PositionUpdate, [PoolRewardInfo; NUM_REWARDS])> {
     let tick_array_lower = tick_array_lower.load()?;
-    let tick_lower = tick_array_lower.get_tick(position.tick_lower_index, pool.tick_spacing)?;
-
     let tick_array_upper = tick_array_upper.load()?;
-    let tick_upper = tick_array_upper.get_tick(position.tick_upper_index, pool.tick_spacing)?;
+    let (tick_lower, tick_upper) = get_position_ticks(
+        position,
+        &tick_array_lower,
+        &tick_array_upper,
+        pool.tick_spacing,
+    )?;
 
     // Pass in a liquidity_delta value of 0 to trigger only calculations for fee and reward growths.
     // Calculating fees and rewards for positions with zero liquidity will result in an error.
@@ -78,6 +87,50 @@ pub fn calculate_fee_and_reward_growths<'info>(
     Ok((update.position_update, update.reward_infos))
 }
 
+// Brings the fees and rewards owed by the position up to date, as update_fees_and_rewards does.
+// Fees and rewards of a position without liquidity were settled when the liquidity was removed.
+pub fn update_position_fees_and_rewards<'info>(
+    pool: &mut Pool,
+    position: &mut Position,
+    tick_array_lower: &AccountLoader<'info, TickArray>,
+    tick_array_upper: &AccountLoader<'info, TickArray>,
+    timestamp: u64,
+) -> Result<()> {
+    if position.liquidity == 0 {
+        return Ok(());
+    }
+
+    let (position_update, reward_infos) = calculate_fee_and_reward_growths(
+        pool,
+        position,
+        tick_array_lower,
+        tick_array_upper,
+        timestamp,
+    )?;
+    pool.update_rewards(reward_infos, timestamp);
+    position.update(&position_update);
+    Ok(())
+}
+
+// Looks up the boundary ticks of the position. The supplied tick arrays must contain the
+// boundary ticks, otherwise the tick updates would be written to the wrong ticks.
+fn get_position_ticks<'a>(
+    position: &Position,
+    tick_array_lower: &'a TickArray,
+    tick_array_upper: &'a TickArray,
+    tick_spacing: u16,
+) -> Result<(&'a Tick, &'a Tick)> {
+    if !tick_array_lower.check_in_array_bounds(position.tick_lower_index, tick_spacing)
+        || !tick_array_upper.check_in_array_bounds(position.tick_upper_index, tick_spacing)
+    {
+        return Err(ErrorCode::TickArrayDoesNotContainPositionTick.into());
+    }
+
+    let tick_lower = tick_array_lower.get_tick(position.tick_lower_index, tick_spacing)?;
+    let tick_upper = tick_array_upper.get_tick(position.tick_upper_index, tick_spacing)?;
+    Ok((tick_lower, tick_upper))
+}
+
 // Calculates the state changes after modifying liquidity of a pool position.
 fn _calculate_modify_liquidity(
     pool: &Pool,
@@ -94,6 +147,8 @@ fn _calculate_modify_liquidity(
         return Err(ErrorCode::LiquidityZero.into());
     }
 
+    // Rewards are settled with the liquidity before the delta, so that liquidity is not credited
+    // for the time before it was added
     let next_reward_infos = next_pool_reward_infos(pool, timestamp)?;
 
     let next_global_liquidity = next_pool_liquidity(
@@ -171,28 +226,96 @@ pub fn calculate_liquidity_token_deltas(
         return Err(ErrorCode::LiquidityZero.into());
     }
 
-    let mut delta_a: u64 = 0;
-    let mut delta_b: u64 = 0;
-
     let liquidity: u128 = liquidity_delta.abs() as u128;
     let round_up = liquidity_delta > 0;
 
-    let lower_price = sqrt_price_from_tick_index(position.tick_lower_index);
-    let upper_price = sqrt_price_from_tick_index(position.tick_upper_index);
+    Ok(token_amounts_from_liquidity(
+        liquidity,
+        current_tick_index,
+        sqrt_price,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        round_up,
+    )
+    .map_err(ErrorCode::from)?)
+}
+
+// Calculates the largest liquidity a position in the given tick range can hold with at most
+// amount_a of token_a and amount_b of token_b at the current price of the pool.
+pub fn calculate_liquidity_from_token_amounts(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u128> {
+    let lower_price = sqrt_price_from_tick_index(tick_lower_index);
+    let upper_price = sqrt_price_from_tick_index(tick_upper_index);
 
-    if current_tick_index < position.tick_lower_index {
+    let liquidity = if current_tick_index < tick_lower_index {
         // current tick below position
-        delta_a = get_amount_delta_a(lower_price, upper_price, liquidity, round_up)?;
-    } else if current_tick_index < position.tick_upper_index {
+        get_liquidity_from_amount_a(lower_price, upper_price, amount_a).map_err(ErrorCode::from)?
+    } else if current_tick_index < tick_upper_index {
         // current tick inside position
-        delta_a = get_amount_delta_a(sqrt_price, upper_price, liquidity, round_up)?;
-        delta_b = get_amount_delta_b(lower_price, sqrt_price, liquidity, round_up)?;
+        let liquidity_a = get_liquidity_from_amount_a(sqrt_price, upper_price, amount_a)
+            .map_err(ErrorCode::from)?;
+        if sqrt_price == lower_price {
+            // no token_b is required at the lower bound
+            liquidity_a
+        } else {
+            let liquidity_b = get_liquidity_from_amount_b(lower_price, sqrt_price, amount_b)
+                .map_err(ErrorCode::from)?;
+            liquidity_a.min(liquidity_b)
+        }
     } else {
         // current tick above position
-        delta_b = get_amount_delta_b(lower_price, upper_price, liquidity, round_up)?;
+        get_liquidity_from_amount_b(lower_price, upper_price, amount_b).map_err(ErrorCode::from)?
+    };
+
+    Ok(liquidity)
+}
+
+// Calculates the liquidity to add to a position from the token amounts the caller is willing to
+// deposit, along with the token amounts the liquidity requires. The side with the smaller liquidity
+// constrains the deposit, the leftover of the other side stays with the caller.
+pub fn calculate_increase_liquidity_from_token_amounts(
+    current_tick_index: i32,
+    sqrt_price: u128,
+    position: &Position,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<(u128, u64, u64)> {
+    let liquidity = calculate_liquidity_from_token_amounts(
+        current_tick_index,
+        sqrt_price,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        token_a_amount,
+        token_b_amount,
+    )?;
+    if liquidity == 0 {
+        return Err(ErrorCode::LiquidityZero.into());
+    }
+
+    let (delta_a, delta_b) = calculate_liquidity_token_deltas(
+        current_tick_index,
+        sqrt_price,
+        position,
+        convert_to_liquidity_delta(liquidity, true).map_err(ErrorCode::from)?,
+    )?;
+    if delta_a > token_a_amount || delta_b > token_b_amount {
+        return Err(ErrorCode::TokenMaxExceeded.into());
     }
+    Ok((liquidity, delta_a, delta_b))
+}
 
-    Ok((delta_a, delta_b))
+// Whether the boundary ticks of a position flipped between initialized and uninitialized when
+// a liquidity update was applied. Callers tracking initialized ticks should toggle these ticks.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TickFlipUpdate {
+    pub tick_lower_flipped: bool,
+    pub tick_upper_flipped: bool,
 }
 
 pub fn sync_modify_liquidity_values<'info>(
@@ -202,16 +325,16 @@ pub fn sync_modify_liquidity_values<'info>(
     tick_array_upper: &AccountLoader<'info, TickArray>,
     modify_liquidity_update: ModifyLiquidityUpdate,
     reward_last_updated_timestamp: u64,
-) -> Result<()> {
+) -> Result<TickFlipUpdate> {
     position.update(&modify_liquidity_update.position_update);
 
-    tick_array_lower.load_mut()?.update_tick(
+    let tick_lower_flipped = tick_array_lower.load_mut()?.update_tick(
         position.tick_lower_index,
         pool.tick_spacing,
         &modify_liquidity_update.tick_lower_update,
     )?;
 
-    tick_array_upper.load_mut()?.update_tick(
+    let tick_upper_flipped = tick_array_upper.load_mut()?.update_tick(
         position.tick_upper_index,
         pool.tick_spacing,
         &modify_liquidity_update.tick_upper_update,
@@ -223,5 +346,377 @@ pub fn sync_modify_liquidity_values<'info>(
         reward_last_updated_timestamp,
     );
 
-    Ok(())
+    // Invalidates the tick array caches of the pool
+    pool.tick_flip_count = pool
+        .tick_flip_count
+        .wrapping_add(tick_lower_flipped as u64 + tick_upper_flipped as u64);
+
+    Ok(TickFlipUpdate {
+        tick_lower_flipped,
+        tick_upper_flipped,
+    })
+}
+
+#[cfg(test)]
+mod reward_settlement_tests {
+    use super::*;
+
+    const EMISSIONS_PER_SECOND: u128 = 1_000;
+
+    fn pool_with_reward(liquidity: u128) -> Pool {
+        let mut pool = Pool::default();
+        pool.liquidity = liquidity;
+        pool.reward_infos[0].mint = Pubkey::new_unique();
+        pool.reward_infos[0].emissions_per_second_x64 = EMISSIONS_PER_SECOND << 64;
+        pool
+    }
+
+    #[test]
+    fn test_liquidity_added_mid_emission_not_credited_retroactively() {
+        // Another LP provides 1_000 liquidity in range from timestamp 0
+        let mut pool = pool_with_reward(1_000);
+        let mut position = Position::default();
+        position.tick_lower_index = -64;
+        position.tick_upper_index = 64;
+        let mut tick_lower = Tick::default();
+        let mut tick_upper = Tick::default();
+
+        // Add 1_000 liquidity at timestamp 100, after 100_000 tokens were emitted
+        let update = _calculate_modify_liquidity(
+            &pool,
+            &position,
+            &tick_lower,
+            &tick_upper,
+            -64,
+            64,
+            1_000,
+            100,
+        )
+        .unwrap();
+        // The pool growth is settled with the liquidity before the add
+        assert_eq!(update.reward_infos[0].growth_global_x64, 100 << 64);
+        assert_eq!(update.position_update.reward_infos[0].amount_owed, 0);
+
+        position.update(&update.position_update);
+        tick_lower.update(&update.tick_lower_update);
+        tick_upper.update(&update.tick_upper_update);
+        pool.update_rewards_and_liquidity(update.reward_infos, update.pool_liquidity, 100);
+
+        // Settle at timestamp 200, the position earned half of the 100_000 tokens emitted since
+        let update = _calculate_modify_liquidity(
+            &pool,
+            &position,
+            &tick_lower,
+            &tick_upper,
+            -64,
+            64,
+            0,
+            200,
+        )
+        .unwrap();
+        assert_eq!(update.position_update.reward_infos[0].amount_owed, 50_000);
+    }
+
+    #[test]
+    fn test_liquidity_removed_settles_rewards_before_delta() {
+        let mut pool = pool_with_reward(1_000);
+        let mut position = Position::default();
+        position.tick_lower_index = -64;
+        position.tick_upper_index = 64;
+        let mut tick_lower = Tick::default();
+        let mut tick_upper = Tick::default();
+
+        let update = _calculate_modify_liquidity(
+            &pool,
+            &position,
+            &tick_lower,
+            &tick_upper,
+            -64,
+            64,
+            1_000,
+            100,
+        )
+        .unwrap();
+        position.update(&update.position_update);
+        tick_lower.update(&update.tick_lower_update);
+        tick_upper.update(&update.tick_upper_update);
+        pool.update_rewards_and_liquidity(update.reward_infos, update.pool_liquidity, 100);
+
+        // Removing all liquidity still credits the rewards earned while it was provided
+        let update = _calculate_modify_liquidity(
+            &pool,
+            &position,
+            &tick_lower,
+            &tick_upper,
+            -64,
+            64,
+            -1_000,
+            200,
+        )
+        .unwrap();
+        assert_eq!(update.position_update.liquidity, 0);
+        assert_eq!(update.position_update.reward_infos[0].amount_owed, 50_000);
+    }
+
+    #[test]
+    fn test_scheduled_reward_settled_at_schedule_rates() {
+        let mut pool = pool_with_reward(1_000);
+        let points = [
+            RewardEmissionsSchedulePoint {
+                start_timestamp: 0,
+                emissions_per_second_x64: 1_000 << 64,
+            },
+            RewardEmissionsSchedulePoint {
+                start_timestamp: 50,
+                emissions_per_second_x64: 3_000 << 64,
+            },
+        ];
+        pool.reward_emissions_schedules[0]
+            .update_points(&points, true)
+            .unwrap();
+        let mut position = Position::default();
+        position.tick_lower_index = -64;
+        position.tick_upper_index = 64;
+        let mut tick_lower = Tick::default();
+        let mut tick_upper = Tick::default();
+
+        let update = _calculate_modify_liquidity(
+            &pool,
+            &position,
+            &tick_lower,
+            &tick_upper,
+            -64,
+            64,
+            1_000,
+            0,
+        )
+        .unwrap();
+        position.update(&update.position_update);
+        tick_lower.update(&update.tick_lower_update);
+        tick_upper.update(&update.tick_upper_update);
+        pool.update_rewards_and_liquidity(update.reward_infos, update.pool_liquidity, 0);
+
+        // The rate changes at timestamp 50 without any instruction applying it, the position still
+        // earns half of 50 * 1_000 + 50 * 3_000 tokens
+        let update = _calculate_modify_liquidity(
+            &pool,
+            &position,
+            &tick_lower,
+            &tick_upper,
+            -64,
+            64,
+            -1_000,
+            100,
+        )
+        .unwrap();
+        assert_eq!(update.position_update.reward_infos[0].amount_owed, 100_000);
+        assert_eq!(update.reward_infos[0].emissions_per_second_x64, 3_000 << 64);
+    }
+}
+
+#[cfg(test)]
+mod position_tick_coverage_tests {
+    use super::*;
+
+    fn tick_array(start_tick_index: i32) -> TickArray {
+        TickArray {
+            start_tick_index,
+            ..TickArray::default()
+        }
+    }
+
+    fn position(tick_lower_index: i32, tick_upper_index: i32) -> Position {
+        Position {
+            tick_lower_index,
+            tick_upper_index,
+            ..Position::default()
+        }
+    }
+
+    #[test]
+    fn test_tick_arrays_containing_position_ticks() {
+        // With tick spacing 64 an array covers 88 * 64 = 5632 ticks
+        let position = position(-128, 5632);
+        assert!(get_position_ticks(&position, &tick_array(-5632), &tick_array(5632), 64).is_ok());
+    }
+
+    #[test]
+    fn test_mismatched_lower_tick_array() {
+        let position = position(-128, 128);
+        let (tick_array_lower, tick_array_upper) = (tick_array(0), tick_array(0));
+        let result = get_position_ticks(&position, &tick_array_lower, &tick_array_upper, 64);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickArrayDoesNotContainPositionTick.into()
+        );
+    }
+
+    #[test]
+    fn test_mismatched_upper_tick_array() {
+        let position = position(-128, 5632);
+        let (tick_array_lower, tick_array_upper) = (tick_array(-5632), tick_array(0));
+        let result = get_position_ticks(&position, &tick_array_lower, &tick_array_upper, 64);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickArrayDoesNotContainPositionTick.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod update_position_fees_and_rewards_tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    // Updates a position from 0 to 64 in a pool with tick spacing 64, whose tick arrays start at 0
+    fn update(position: &mut Position) -> Pool {
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let mut data = vec![0u8; TickArray::LEN];
+        data[..8].copy_from_slice(&TickArray::discriminator());
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let tick_array =
+            AccountLoader::<TickArray>::try_from_unchecked(&crate::ID, &account_info).unwrap();
+        // Initialize the boundary ticks, without fee growth outside of them
+        {
+            let mut tick_array = tick_array.load_mut().unwrap();
+            tick_array.ticks[0].initialized = true;
+            tick_array.ticks[1].initialized = true;
+        }
+
+        let mut pool = Pool {
+            tick_spacing: 64,
+            tick_current_index: 32,
+            liquidity: 1_000,
+            fee_growth_global_a: 10 << 64,
+            ..Pool::default()
+        };
+        update_position_fees_and_rewards(&mut pool, position, &tick_array, &tick_array, 100)
+            .unwrap();
+        pool
+    }
+
+    #[test]
+    fn test_fees_owed_updated_from_tick_arrays() {
+        let mut position = Position {
+            liquidity: 1_000,
+            tick_lower_index: 0,
+            tick_upper_index: 64,
+            ..Position::default()
+        };
+        let pool = update(&mut position);
+        assert_eq!(pool.reward_last_updated_timestamp, 100);
+        assert_eq!(position.fee_owed_a, 10_000);
+        assert_eq!(position.fee_growth_checkpoint_a, 10 << 64);
+    }
+
+    #[test]
+    fn test_position_without_liquidity_unchanged() {
+        let mut position = Position {
+            tick_lower_index: 0,
+            tick_upper_index: 64,
+            fee_owed_a: 7,
+            ..Position::default()
+        };
+        let pool = update(&mut position);
+        assert_eq!(pool.reward_last_updated_timestamp, 0);
+        assert_eq!(position.fee_owed_a, 7);
+        assert_eq!(position.fee_growth_checkpoint_a, 0);
+    }
+}
+
+#[cfg(test)]
+mod increase_liquidity_from_token_amounts_tests {
+    use super::*;
+
+    fn position() -> Position {
+        Position {
+            tick_lower_index: -128,
+            tick_upper_index: 128,
+            ..Position::default()
+        }
+    }
+
+    fn increase_liquidity(current_tick_index: i32) -> Result<(u128, u64, u64)> {
+        calculate_increase_liquidity_from_token_amounts(
+            current_tick_index,
+            sqrt_price_from_tick_index(current_tick_index),
+            &position(),
+            1_000_000,
+            1_000_000,
+        )
+    }
+
+    #[test]
+    fn test_below_range_uses_only_token_a() {
+        let (liquidity, delta_a, delta_b) = increase_liquidity(-256).unwrap();
+        assert_eq!(
+            liquidity,
+            get_liquidity_from_amount_a(
+                sqrt_price_from_tick_index(-128),
+                sqrt_price_from_tick_index(128),
+                1_000_000
+            )
+            .unwrap()
+        );
+        assert!(delta_a <= 1_000_000 && delta_a >= 999_999);
+        assert_eq!(delta_b, 0);
+    }
+
+    #[test]
+    fn test_in_range_constrained_by_smaller_side() {
+        // Above the middle of the range the token B side is wider, so token B adds less liquidity
+        let sqrt_price = sqrt_price_from_tick_index(64);
+        let liquidity_a =
+            get_liquidity_from_amount_a(sqrt_price, sqrt_price_from_tick_index(128), 1_000_000)
+                .unwrap();
+        let liquidity_b =
+            get_liquidity_from_amount_b(sqrt_price_from_tick_index(-128), sqrt_price, 1_000_000)
+                .unwrap();
+        assert!(liquidity_b < liquidity_a);
+
+        let (liquidity, delta_a, delta_b) = increase_liquidity(64).unwrap();
+        assert_eq!(liquidity, liquidity_b);
+        assert!(delta_b <= 1_000_000 && delta_b >= 999_999);
+        // The leftover of token A stays with the caller
+        assert!(delta_a < 1_000_000);
+    }
+
+    #[test]
+    fn test_above_range_uses_only_token_b() {
+        let (liquidity, delta_a, delta_b) = increase_liquidity(256).unwrap();
+        assert_eq!(
+            liquidity,
+            get_liquidity_from_amount_b(
+                sqrt_price_from_tick_index(-128),
+                sqrt_price_from_tick_index(128),
+                1_000_000
+            )
+            .unwrap()
+        );
+        assert_eq!(delta_a, 0);
+        assert!(delta_b <= 1_000_000 && delta_b >= 999_999);
+    }
+
+    #[test]
+    fn test_missing_constraining_token() {
+        let result = calculate_increase_liquidity_from_token_amounts(
+            -256,
+            sqrt_price_from_tick_index(-256),
+            &position(),
+            0,
+            1_000_000,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityZero.into());
+    }
 }
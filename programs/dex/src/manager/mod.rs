@@ -1,3 +1,4 @@
+pub mod liquidity_distribution_manager;
 pub mod liquidity_manager;
 pub mod pool_manager;
 pub mod position_manager;
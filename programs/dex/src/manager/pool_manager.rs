@@ -1,9 +1,57 @@
 use crate::errors::ErrorCode;
-use crate::math::{add_liquidity_delta, checked_mul_div};
+use crate::math::{
+    add_liquidity_delta, checked_mul_div, checked_mul_shift_right, token_amounts_from_liquidity,
+    FEE_RATE_MUL_VALUE, PROTOCOL_FEE_RATE_MUL_VALUE,
+};
 use crate::state::*;
+use anchor_lang::prelude::*;
+use std::result::Result;
+
+const DAY_IN_SECONDS: u128 = 60 * 60 * 24;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct VaultSolvencyResult {
+    pub is_solvent_a: bool,
+    pub is_solvent_b: bool,
+    pub surplus_a: u64,
+    pub surplus_b: u64,
+    pub shortfall_a: u64,
+    pub shortfall_b: u64,
+}
+
+impl VaultSolvencyResult {
+    pub fn is_solvent(&self) -> bool {
+        self.is_solvent_a && self.is_solvent_b
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolReconciliation {
+    pub expected_reserve_a: u64,
+    pub expected_reserve_b: u64,
+    pub actual_reserve_a: u64,
+    pub actual_reserve_b: u64,
+}
+
+impl PoolReconciliation {
+    // Positive if the vault holds more than expected, negative if it holds less
+    pub fn delta_a(&self) -> i128 {
+        self.actual_reserve_a as i128 - self.expected_reserve_a as i128
+    }
+
+    pub fn delta_b(&self) -> i128 {
+        self.actual_reserve_b as i128 - self.expected_reserve_b as i128
+    }
+
+    pub fn is_reconciled(&self) -> bool {
+        self.delta_a() == 0 && self.delta_b() == 0
+    }
+}
 
 // Calculates the next global reward growth variables based on the given timestamp.
 // The provided timestamp must be greater than or equal to the last updated timestamp.
+// A reward with an emissions schedule accrues each interval between rate changes at the rate of
+// its schedule, and its emission rate is set to the rate active at next_timestamp.
 pub fn next_pool_reward_infos(
     pool: &Pool,
     next_timestamp: u64,
@@ -13,29 +61,43 @@ pub fn next_pool_reward_infos(
         return Err(ErrorCode::InvalidTimestamp.into());
     }
 
-    // No-op if no liquidity or no change in timestamp
-    if pool.liquidity == 0 || next_timestamp == curr_timestamp {
-        return Ok(pool.reward_infos);
-    }
-
-    // Calculate new global reward growth
     let mut next_reward_infos = pool.reward_infos;
-    let time_delta = u128::from(next_timestamp.checked_sub(curr_timestamp).unwrap_or(0));
     for i in 0..NUM_REWARDS {
+        let schedule = &pool.reward_emissions_schedules[i];
         if let Some(reward_info) = next_reward_infos.get_mut(i) {
-            if !reward_info.initialized() {
+            if schedule.is_set() {
+                reward_info.emissions_per_second_x64 =
+                    schedule.emissions_per_second_at(next_timestamp);
+            }
+
+            // No-op if no liquidity or no change in timestamp.
+            // Paused rewards stay frozen at their last checkpoint.
+            if pool.liquidity == 0
+                || next_timestamp == curr_timestamp
+                || !reward_info.initialized()
+                || pool.reward_paused[i]
+            {
                 continue;
             }
 
             // Calculate the new reward growth delta.
             // If the calculation overflows, set the delta value to zero.
             // This will halt reward distributions for this reward.
-            let reward_growth_delta = checked_mul_div(
-                time_delta,
-                reward_info.emissions_per_second_x64,
-                pool.liquidity,
-            )
-            .unwrap_or(0);
+            let reward_growth_delta = if schedule.is_set() {
+                next_scheduled_reward_growth_delta(
+                    schedule,
+                    curr_timestamp,
+                    next_timestamp,
+                    pool.liquidity,
+                )
+            } else {
+                checked_mul_div(
+                    u128::from(next_timestamp - curr_timestamp),
+                    pool.reward_infos[i].emissions_per_second_x64,
+                    pool.liquidity,
+                )
+                .unwrap_or(0)
+            };
 
             // Add the reward growth delta to the global reward growth.
             let curr_growth_global = reward_info.growth_global_x64;
@@ -46,6 +108,96 @@ pub fn next_pool_reward_infos(
     Ok(next_reward_infos)
 }
 
+// Sums the reward growth of each interval between rate changes of the schedule from
+// curr_timestamp to next_timestamp, at the rate of the interval.
+fn next_scheduled_reward_growth_delta(
+    schedule: &RewardEmissionsSchedule,
+    curr_timestamp: u64,
+    next_timestamp: u64,
+    liquidity: u128,
+) -> u128 {
+    let mut reward_growth_delta: u128 = 0;
+    let mut timestamp = curr_timestamp;
+    while timestamp < next_timestamp {
+        let interval_end = schedule
+            .next_rate_change_after(timestamp)
+            .unwrap_or(next_timestamp)
+            .min(next_timestamp);
+        // As for an unscheduled reward, an overflowing delta halts the reward distribution
+        let interval_growth = checked_mul_div(
+            u128::from(interval_end - timestamp),
+            schedule.emissions_per_second_at(timestamp),
+            liquidity,
+        )
+        .unwrap_or(0);
+        reward_growth_delta = reward_growth_delta.wrapping_add(interval_growth);
+        timestamp = interval_end;
+    }
+    reward_growth_delta
+}
+
+// Checks whether the vault of a reward holds less than reward_vault_warning_threshold_bps of the
+// emissions over REWARD_VAULT_WARNING_PERIOD_SECONDS at the current emission rate. Returns the
+// number of days of emissions the vault still holds if it does.
+pub fn reward_vault_low_balance_days(
+    pool: &Pool,
+    reward_index: usize,
+    vault_balance: u64,
+) -> Option<u32> {
+    let threshold_bps = *pool.reward_vault_warning_threshold_bps.get(reward_index)?;
+    let emissions_per_second_x64 = pool.reward_infos[reward_index].emissions_per_second_x64;
+    if threshold_bps == 0 || emissions_per_second_x64 == 0 {
+        return None;
+    }
+
+    let period_emissions = checked_mul_shift_right(
+        REWARD_VAULT_WARNING_PERIOD_SECONDS as u128,
+        emissions_per_second_x64,
+    )
+    .unwrap_or(u64::MAX);
+    let threshold = period_emissions as u128 * threshold_bps as u128
+        / MAX_REWARD_VAULT_WARNING_THRESHOLD_BPS as u128;
+    if vault_balance as u128 >= threshold {
+        return None;
+    }
+
+    let emissions_per_day = checked_mul_shift_right(DAY_IN_SECONDS, emissions_per_second_x64)
+        .unwrap_or(u64::MAX)
+        .max(1);
+    Some((vault_balance / emissions_per_day).min(u32::MAX as u64) as u32)
+}
+
+// Calculates the protocol fee rate at which the daily volume earns the target revenue, bounded
+// by the protocol fee rate bounds of the pools config and moved by at most
+// MAX_AUTO_PROTOCOL_FEE_RATE_CHANGE from the current rate. Without fees the rate moves towards
+// the maximum.
+pub fn calculate_auto_protocol_fee_rate(
+    protocol_fee_rate: u16,
+    fee_rate: u16,
+    daily_volume: u64,
+    target_revenue: u64,
+    min_protocol_fee_rate: u16,
+    max_protocol_fee_rate: u16,
+) -> u16 {
+    // revenue = volume * (fee_rate / FEE_RATE_MUL_VALUE)
+    //                  * (protocol_fee_rate / PROTOCOL_FEE_RATE_MUL_VALUE)
+    let fee_amount_scaled = daily_volume as u128 * fee_rate as u128;
+    let target_rate = if fee_amount_scaled == 0 {
+        max_protocol_fee_rate as u128
+    } else {
+        let target_scaled =
+            target_revenue as u128 * FEE_RATE_MUL_VALUE * PROTOCOL_FEE_RATE_MUL_VALUE;
+        (target_scaled + fee_amount_scaled - 1) / fee_amount_scaled
+    };
+    let target_rate = target_rate.min(max_protocol_fee_rate as u128) as u16;
+
+    target_rate
+        .max(protocol_fee_rate.saturating_sub(MAX_AUTO_PROTOCOL_FEE_RATE_CHANGE))
+        .min(protocol_fee_rate.saturating_add(MAX_AUTO_PROTOCOL_FEE_RATE_CHANGE))
+        .max(min_protocol_fee_rate)
+        .min(max_protocol_fee_rate)
+}
+
 // Calculates the next global liquidity for a pool depending on its position relative
 // to the lower and upper tick indexes and the liquidity_delta.
 pub fn next_pool_liquidity(
@@ -55,8 +207,562 @@ pub fn next_pool_liquidity(
     liquidity_delta: i128,
 ) -> Result<u128, ErrorCode> {
     if pool.tick_current_index < tick_upper_index && pool.tick_current_index >= tick_lower_index {
-        add_liquidity_delta(pool.liquidity, liquidity_delta)
+        add_liquidity_delta(pool.liquidity, liquidity_delta).map_err(ErrorCode::from)
     } else {
         Ok(pool.liquidity)
     }
 }
+
+// Compares the vault balances of a pool against what it owes: the principal and fees owed to the
+// given positions plus the protocol fees owed. Fees are only counted up to the last update of each
+// position and principal is rounded down, so the obligations are a lower bound and any shortfall
+// is real. Rewards are paid from the reward vaults and do not draw on the token vaults.
+pub fn calculate_vault_solvency(
+    pool: &Pool,
+    positions: &[Position],
+    vault_amount_a: u64,
+    vault_amount_b: u64,
+) -> Result<VaultSolvencyResult, ErrorCode> {
+    let reconciliation =
+        calculate_pool_reconciliation(pool, positions, vault_amount_a, vault_amount_b)?;
+    let (surplus_a, shortfall_a) =
+        vault_surplus_and_shortfall(vault_amount_a, reconciliation.expected_reserve_a);
+    let (surplus_b, shortfall_b) =
+        vault_surplus_and_shortfall(vault_amount_b, reconciliation.expected_reserve_b);
+
+    Ok(VaultSolvencyResult {
+        is_solvent_a: shortfall_a == 0,
+        is_solvent_b: shortfall_b == 0,
+        surplus_a,
+        surplus_b,
+        shortfall_a,
+        shortfall_b,
+    })
+}
+
+fn vault_surplus_and_shortfall(vault_amount: u64, owed: u64) -> (u64, u64) {
+    if vault_amount >= owed {
+        (vault_amount - owed, 0)
+    } else {
+        (0, owed - vault_amount)
+    }
+}
+
+// Calculates the token amounts a vault owes a position: the principal of its liquidity at the
+// current price, rounded down as on withdrawal, plus the fees owed to it. Fees are only counted up
+// to the last update of the position.
+pub fn position_vault_claims(pool: &Pool, position: &Position) -> Result<(u64, u64), ErrorCode> {
+    let (principal_a, principal_b) = token_amounts_from_liquidity(
+        position.liquidity,
+        pool.tick_current_index,
+        pool.sqrt_price,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        false,
+    )?;
+
+    Ok((
+        principal_a
+            .checked_add(position.fee_owed_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?,
+        principal_b
+            .checked_add(position.fee_owed_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?,
+    ))
+}
+
+// Calculates the difference between the actual and the expected balance of a vault in basis
+// points of the expected balance, saturating at u16::MAX.
+pub fn calculate_drift_bps(expected_vault_balance: u64, actual_vault_balance: u64) -> u16 {
+    let drift = expected_vault_balance.abs_diff(actual_vault_balance) as u128;
+    if drift == 0 {
+        return 0;
+    }
+    if expected_vault_balance == 0 {
+        return u16::MAX;
+    }
+    (drift * 10_000 / expected_vault_balance as u128).min(u16::MAX as u128) as u16
+}
+
+// Compares the vault balances of a pool against its expected reserves: the principal and fees
+// owed to the given positions plus the protocol fees owed.
+pub fn calculate_pool_reconciliation(
+    pool: &Pool,
+    positions: &[Position],
+    vault_amount_a: u64,
+    vault_amount_b: u64,
+) -> Result<PoolReconciliation, ErrorCode> {
+    let mut expected_reserve_a = pool.protocol_fee_owed_a;
+    let mut expected_reserve_b = pool.protocol_fee_owed_b;
+    for position in positions {
+        let (claim_a, claim_b) = position_vault_claims(pool, position)?;
+        expected_reserve_a = expected_reserve_a
+            .checked_add(claim_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        expected_reserve_b = expected_reserve_b
+            .checked_add(claim_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+    }
+
+    Ok(PoolReconciliation {
+        expected_reserve_a,
+        expected_reserve_b,
+        actual_reserve_a: vault_amount_a,
+        actual_reserve_b: vault_amount_b,
+    })
+}
+
+// Verifies that a position can be migrated between two pools. Both pools must hold the same
+// token pair and their prices may differ by at most slippage_bps. Each tick is a 1bp price move,
+// so the price difference is measured as the distance between the current ticks of the pools.
+pub fn check_position_migration_pools(
+    source_pool: &Pool,
+    destination_pool: &Pool,
+    slippage_bps: u16,
+) -> Result<(), ErrorCode> {
+    if source_pool.token_mint_a != destination_pool.token_mint_a
+        || source_pool.token_mint_b != destination_pool.token_mint_b
+    {
+        return Err(ErrorCode::InvalidMigrationPool);
+    }
+
+    let tick_distance = (source_pool.tick_current_index as i64
+        - destination_pool.tick_current_index as i64)
+        .unsigned_abs();
+    if tick_distance > slippage_bps as u64 {
+        return Err(ErrorCode::MigrationSlippageExceeded);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod reward_pause_tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+
+    fn pool_with_rewards(reward_paused: [bool; NUM_REWARDS]) -> Pool {
+        let mut pool = Pool {
+            liquidity: 1 << 64,
+            reward_last_updated_timestamp: 100,
+            reward_paused,
+            ..Default::default()
+        };
+        for reward_info in pool.reward_infos.iter_mut() {
+            reward_info.mint = Pubkey::new_unique();
+            reward_info.emissions_per_second_x64 = 1 << 64;
+        }
+        pool
+    }
+
+    #[test]
+    fn test_paused_reward_does_not_accrue() {
+        let pool = pool_with_rewards([false, true, false]);
+        let next_reward_infos = next_pool_reward_infos(&pool, 110).unwrap();
+        assert_eq!(next_reward_infos[0].growth_global_x64, 10);
+        assert_eq!(next_reward_infos[1].growth_global_x64, 0);
+        assert_eq!(next_reward_infos[2].growth_global_x64, 10);
+    }
+
+    #[test]
+    fn test_resumed_reward_skips_paused_period() {
+        let mut pool = pool_with_rewards([true; NUM_REWARDS]);
+        let next_reward_infos = next_pool_reward_infos(&pool, 200).unwrap();
+        pool.update_reward_paused(0, next_reward_infos, 200, false)
+            .unwrap();
+
+        let next_reward_infos = next_pool_reward_infos(&pool, 205).unwrap();
+        assert_eq!(next_reward_infos[0].growth_global_x64, 5);
+        assert_eq!(next_reward_infos[1].growth_global_x64, 0);
+    }
+}
+
+#[cfg(test)]
+mod reward_emissions_schedule_tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+
+    fn pool_with_reward(schedule: RewardEmissionsSchedule) -> Pool {
+        let mut pool = Pool {
+            liquidity: 1 << 64,
+            reward_last_updated_timestamp: 0,
+            ..Default::default()
+        };
+        pool.reward_infos[1].mint = Pubkey::new_unique();
+        pool.reward_infos[1].emissions_per_second_x64 = 1_000 << 64;
+        pool.reward_emissions_schedules[1] = schedule;
+        pool
+    }
+
+    fn schedule(hold_final_rate: bool) -> RewardEmissionsSchedule {
+        let points: Vec<RewardEmissionsSchedulePoint> = [(100, 30), (200, 20), (300, 10)]
+            .iter()
+            .map(
+                |(start_timestamp, emissions_per_second)| RewardEmissionsSchedulePoint {
+                    start_timestamp: *start_timestamp,
+                    emissions_per_second_x64: *emissions_per_second << 64,
+                },
+            )
+            .collect();
+        let mut schedule = RewardEmissionsSchedule::default();
+        schedule.update_points(&points, hold_final_rate).unwrap();
+        schedule
+    }
+
+    // Advances the rewards of the pool to each timestamp and returns the growth accrued in each
+    // interval
+    fn advance(pool: &mut Pool, timestamps: &[u64]) -> Vec<u128> {
+        timestamps
+            .iter()
+            .map(|timestamp| {
+                let growth = pool.reward_infos[1].growth_global_x64;
+                let next_reward_infos = next_pool_reward_infos(pool, *timestamp).unwrap();
+                pool.update_rewards(next_reward_infos, *timestamp);
+                pool.reward_infos[1].growth_global_x64 - growth
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rate_applied_in_each_interval() {
+        let mut pool = pool_with_reward(schedule(true));
+
+        let growths = advance(&mut pool, &[50, 100, 150, 250, 400]);
+        // Nothing is emitted before the first point, regardless of the current rate
+        assert_eq!(
+            growths,
+            vec![0, 0, 50 * 30, 50 * 30 + 50 * 20, 50 * 20 + 100 * 10]
+        );
+        assert_eq!(pool.reward_infos[1].emissions_per_second_x64, 10 << 64);
+    }
+
+    #[test]
+    fn test_single_update_across_all_points() {
+        let mut pool = pool_with_reward(schedule(true));
+        let growths = advance(&mut pool, &[1_000]);
+        assert_eq!(growths, vec![100 * 30 + 100 * 20 + 700 * 10]);
+    }
+
+    #[test]
+    fn test_emissions_stop_after_last_point_without_hold() {
+        let mut pool = pool_with_reward(schedule(false));
+        let growths = advance(&mut pool, &[250, 1_000]);
+        assert_eq!(growths, vec![100 * 30 + 50 * 20, 50 * 20]);
+        assert_eq!(pool.reward_infos[1].emissions_per_second_x64, 0);
+    }
+
+    #[test]
+    fn test_other_rewards_keep_their_rate() {
+        let mut pool = pool_with_reward(schedule(true));
+        pool.reward_infos[0].mint = Pubkey::new_unique();
+        pool.reward_infos[0].emissions_per_second_x64 = 5 << 64;
+        let next_reward_infos = next_pool_reward_infos(&pool, 150).unwrap();
+        assert_eq!(next_reward_infos[0].growth_global_x64, 150 * 5);
+        assert_eq!(next_reward_infos[1].growth_global_x64, 50 * 30);
+    }
+
+    #[test]
+    fn test_rate_follows_schedule_without_liquidity() {
+        let mut pool = pool_with_reward(schedule(true));
+        pool.liquidity = 0;
+        let growths = advance(&mut pool, &[250]);
+        assert_eq!(growths, vec![0]);
+        assert_eq!(pool.reward_infos[1].emissions_per_second_x64, 20 << 64);
+    }
+
+    #[test]
+    fn test_rate_set_directly_replaces_schedule() {
+        let mut pool = pool_with_reward(schedule(true));
+        let next_reward_infos = next_pool_reward_infos(&pool, 150).unwrap();
+        pool.update_emissions(1, next_reward_infos, 150, 7 << 64)
+            .unwrap();
+        assert!(!pool.reward_emissions_schedules[1].is_set());
+
+        let growths = advance(&mut pool, &[250]);
+        assert_eq!(growths, vec![100 * 7]);
+    }
+}
+
+#[cfg(test)]
+mod auto_protocol_fee_rate_tests {
+    use super::*;
+    use crate::math::MAX_PROTOCOL_FEE_RATE;
+
+    // 1_000_000 volume at a 0.3% fee rate earns 3_000 in fees per day
+    fn auto_rate(protocol_fee_rate: u16, target_revenue: u64) -> u16 {
+        calculate_auto_protocol_fee_rate(
+            protocol_fee_rate,
+            3_000,
+            1_000_000,
+            target_revenue,
+            0,
+            MAX_PROTOCOL_FEE_RATE,
+        )
+    }
+
+    #[test]
+    fn test_rate_moves_towards_target() {
+        // The target of 300 is earned at a rate of 1_000 bps
+        assert_eq!(auto_rate(1_000, 300), 1_000);
+        assert_eq!(auto_rate(990, 300), 995);
+        assert_eq!(auto_rate(1_010, 300), 1_005);
+        assert_eq!(auto_rate(998, 300), 1_000);
+    }
+
+    #[test]
+    fn test_rate_within_bounds() {
+        assert_eq!(
+            auto_rate(MAX_PROTOCOL_FEE_RATE, u64::MAX),
+            MAX_PROTOCOL_FEE_RATE
+        );
+        assert_eq!(auto_rate(2, 0), 0);
+        assert_eq!(
+            calculate_auto_protocol_fee_rate(100, 3_000, 1_000_000, 0, 98, 2_000),
+            98
+        );
+        // A rate outside of the bounds is moved into them at once
+        assert_eq!(
+            calculate_auto_protocol_fee_rate(2_200, 3_000, 1_000_000, 600, 0, 2_000),
+            2_000
+        );
+    }
+
+    #[test]
+    fn test_no_volume_raises_rate() {
+        assert_eq!(
+            calculate_auto_protocol_fee_rate(100, 3_000, 0, 300, 0, 2_000),
+            105
+        );
+    }
+}
+
+#[cfg(test)]
+mod vault_solvency_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+
+    fn pool_and_position() -> (Pool, Position) {
+        let mut pool = PoolBuilder::new()
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .build();
+        pool.protocol_fee_owed_a = 500;
+        pool.protocol_fee_owed_b = 500;
+        // Fee growth of positions which were not updated is not owed yet
+        pool.fee_growth_global_a = u128::MAX;
+        pool.fee_growth_global_b = u128::MAX;
+        let position = Position {
+            liquidity: 1_000_000,
+            tick_lower_index: -64,
+            tick_upper_index: 64,
+            fee_owed_a: 10,
+            fee_owed_b: 20,
+            ..Default::default()
+        };
+        (pool, position)
+    }
+
+    #[test]
+    fn test_solvent_pool_reports_surplus() {
+        let (pool, position) = pool_and_position();
+        let (claim_a, claim_b) = position_vault_claims(&pool, &position).unwrap();
+        let result =
+            calculate_vault_solvency(&pool, &[position], claim_a + 1_000, claim_b + 500).unwrap();
+        assert_eq!(
+            result,
+            VaultSolvencyResult {
+                is_solvent_a: true,
+                is_solvent_b: true,
+                surplus_a: 500,
+                surplus_b: 0,
+                shortfall_a: 0,
+                shortfall_b: 0,
+            }
+        );
+        assert!(result.is_solvent());
+    }
+
+    #[test]
+    fn test_insolvent_pool_reports_shortfall() {
+        let (pool, position) = pool_and_position();
+        let (claim_a, claim_b) = position_vault_claims(&pool, &position).unwrap();
+        let result =
+            calculate_vault_solvency(&pool, &[position], claim_a + 400, claim_b + 1_000).unwrap();
+        assert!(!result.is_solvent_a);
+        assert!(result.is_solvent_b);
+        assert_eq!(result.shortfall_a, 100);
+        assert_eq!(result.surplus_b, 500);
+        assert!(!result.is_solvent());
+    }
+
+    #[test]
+    fn test_pool_without_positions_owes_protocol_fees() {
+        let (pool, _) = pool_and_position();
+        let result = calculate_vault_solvency(&pool, &[], 500, 499).unwrap();
+        assert!(result.is_solvent_a);
+        assert_eq!(result.surplus_a, 0);
+        assert_eq!(result.shortfall_b, 1);
+    }
+}
+
+#[cfg(test)]
+mod position_migration_tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+
+    fn pool(token_mint_a: Pubkey, token_mint_b: Pubkey, tick_current_index: i32) -> Pool {
+        Pool {
+            token_mint_a,
+            token_mint_b,
+            tick_current_index,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_position_migration_pools() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let source = pool(mint_a, mint_b, 100);
+
+        assert!(check_position_migration_pools(&source, &pool(mint_a, mint_b, 110), 10).is_ok());
+        assert!(check_position_migration_pools(&source, &pool(mint_a, mint_b, 90), 10).is_ok());
+        assert_eq!(
+            check_position_migration_pools(&source, &pool(mint_a, mint_b, 111), 10).unwrap_err(),
+            ErrorCode::MigrationSlippageExceeded
+        );
+    }
+
+    #[test]
+    fn test_check_position_migration_pools_rejects_other_pair() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let source = pool(mint_a, mint_b, 0);
+        let destination = pool(mint_a, Pubkey::new_unique(), 0);
+
+        assert_eq!(
+            check_position_migration_pools(&source, &destination, 0).unwrap_err(),
+            ErrorCode::InvalidMigrationPool
+        );
+    }
+}
+
+#[cfg(test)]
+mod fee_growth_drift_tests {
+    use super::*;
+
+    #[test]
+    fn test_position_vault_claims_include_fees_owed() {
+        let pool = Pool {
+            sqrt_price: 1 << 64,
+            tick_current_index: 0,
+            ..Default::default()
+        };
+        let position = Position {
+            liquidity: 1_000_000,
+            tick_lower_index: -64,
+            tick_upper_index: 64,
+            fee_owed_a: 10,
+            fee_owed_b: 20,
+            ..Default::default()
+        };
+
+        let (principal_a, principal_b) =
+            token_amounts_from_liquidity(1_000_000, 0, 1 << 64, -64, 64, false).unwrap();
+        assert_eq!(
+            position_vault_claims(&pool, &position).unwrap(),
+            (principal_a + 10, principal_b + 20)
+        );
+    }
+
+    #[test]
+    fn test_drift_bps() {
+        assert_eq!(calculate_drift_bps(10_000, 10_000), 0);
+        assert_eq!(calculate_drift_bps(10_000, 10_001), 1);
+        assert_eq!(calculate_drift_bps(10_000, 9_900), 100);
+        assert_eq!(calculate_drift_bps(0, 0), 0);
+        assert_eq!(calculate_drift_bps(0, 1), u16::MAX);
+        assert_eq!(calculate_drift_bps(1, u64::MAX), u16::MAX);
+    }
+}
+
+#[cfg(test)]
+mod pool_reconciliation_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+
+    #[test]
+    fn test_fresh_pool_has_zero_deltas() {
+        let pool = PoolBuilder::new()
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .build();
+        let result = calculate_pool_reconciliation(&pool, &[], 0, 0).unwrap();
+        assert_eq!(result, PoolReconciliation::default());
+        assert_eq!((result.delta_a(), result.delta_b()), (0, 0));
+        assert!(result.is_reconciled());
+    }
+
+    #[test]
+    fn test_reserves_include_positions_and_protocol_fees() {
+        let mut pool = PoolBuilder::new()
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .build();
+        pool.protocol_fee_owed_a = 5;
+        pool.protocol_fee_owed_b = 7;
+        let position = Position {
+            liquidity: 1_000_000,
+            tick_lower_index: -64,
+            tick_upper_index: 64,
+            fee_owed_a: 10,
+            fee_owed_b: 20,
+            ..Default::default()
+        };
+        let (claim_a, claim_b) = position_vault_claims(&pool, &position).unwrap();
+
+        let result =
+            calculate_pool_reconciliation(&pool, &[position.clone()], claim_a + 5, claim_b + 7)
+                .unwrap();
+        assert!(result.is_reconciled());
+
+        let result =
+            calculate_pool_reconciliation(&pool, &[position], claim_a + 6, claim_b).unwrap();
+        assert_eq!(result.delta_a(), 1);
+        assert_eq!(result.delta_b(), -7);
+        assert!(!result.is_reconciled());
+    }
+}
+
+#[cfg(test)]
+mod reward_vault_low_balance_tests {
+    use super::*;
+
+    fn pool(threshold_bps: u16) -> Pool {
+        let mut pool = Pool::default();
+        // 10 tokens per second, 864_000 per day and 25_920_000 per warning period
+        pool.reward_infos[1].emissions_per_second_x64 = 10 << 64;
+        pool.reward_vault_warning_threshold_bps[1] = threshold_bps;
+        pool
+    }
+
+    #[test]
+    fn test_low_balance_reports_days_remaining() {
+        // 10% of the warning period is 2_592_000
+        let pool = pool(1_000);
+        assert_eq!(reward_vault_low_balance_days(&pool, 1, 2_000_000), Some(2));
+        assert_eq!(reward_vault_low_balance_days(&pool, 1, 800_000), Some(0));
+        assert_eq!(reward_vault_low_balance_days(&pool, 1, 2_592_000), None);
+    }
+
+    #[test]
+    fn test_no_warning_when_disabled_or_not_emitting() {
+        assert_eq!(reward_vault_low_balance_days(&pool(0), 1, 0), None);
+        assert_eq!(reward_vault_low_balance_days(&pool(1_000), 0, 0), None);
+        assert_eq!(
+            reward_vault_low_balance_days(&pool(1_000), NUM_REWARDS, 0),
+            None
+        );
+    }
+}
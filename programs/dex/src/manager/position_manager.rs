@@ -1,8 +1,16 @@
 use crate::{
     errors::ErrorCode,
-    math::{add_liquidity_delta, checked_mul_shift_right},
-    state::{Position, PositionUpdate, NUM_REWARDS},
+    math::{
+        add_liquidity_delta, checked_mul_div, checked_mul_shift_right, token_amounts_from_liquidity,
+    },
+    state::{
+        Pool, Position, PositionFractionShare, PositionFractionVault, PositionHistoryEntry,
+        PositionUpdate, MAX_TICK_INDEX, MIN_TICK_INDEX, NUM_REWARDS, POSITION_HISTORY_COLLECT_FEES,
+        POSITION_HISTORY_INCREASE_LIQUIDITY,
+    },
 };
+use anchor_lang::prelude::*;
+use std::result::Result;
 
 pub fn next_position_modify_liquidity_update(
     position: &Position,
@@ -52,3 +60,706 @@ pub fn next_position_modify_liquidity_update(
 
     Ok(update)
 }
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct FeeGrowthCheckpointExport {
+    pub pending_fee_a: u64,
+    pub pending_fee_b: u64,
+    pub checkpoint_a: u128,
+    pub checkpoint_b: u128,
+    pub current_inside_a: u128,
+    pub current_inside_b: u128,
+}
+
+// Calculates the fees a position has accrued since its last checkpoint without modifying it.
+// pending_fee excludes fee_owed that was already settled into the position.
+pub fn next_fee_growth_checkpoint_export(
+    position: &Position,
+    fee_growth_inside_a: u128,
+    fee_growth_inside_b: u128,
+) -> FeeGrowthCheckpointExport {
+    // Mirrors next_position_modify_liquidity_update: overflowing deltas default to zero.
+    let growth_delta_a = fee_growth_inside_a.wrapping_sub(position.fee_growth_checkpoint_a);
+    let pending_fee_a = checked_mul_shift_right(position.liquidity, growth_delta_a).unwrap_or(0);
+
+    let growth_delta_b = fee_growth_inside_b.wrapping_sub(position.fee_growth_checkpoint_b);
+    let pending_fee_b = checked_mul_shift_right(position.liquidity, growth_delta_b).unwrap_or(0);
+
+    FeeGrowthCheckpointExport {
+        pending_fee_a,
+        pending_fee_b,
+        checkpoint_a: position.fee_growth_checkpoint_a,
+        checkpoint_b: position.fee_growth_checkpoint_b,
+        current_inside_a: fee_growth_inside_a,
+        current_inside_b: fee_growth_inside_b,
+    }
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct BundlePositionSorted {
+    pub bundle_slot: u16,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u128,
+    pub in_range: bool,
+}
+
+impl BundlePositionSorted {
+    pub fn new(bundle_slot: u16, position: &Position, tick_current_index: i32) -> Self {
+        BundlePositionSorted {
+            bundle_slot,
+            tick_lower: position.tick_lower_index,
+            tick_upper: position.tick_upper_index,
+            liquidity: position.liquidity,
+            // Same range convention as calculate_liquidity_token_deltas
+            in_range: position.tick_lower_index <= tick_current_index
+                && tick_current_index < position.tick_upper_index,
+        }
+    }
+}
+
+// Sorts by tick_lower ascending, breaking ties by tick_upper ascending.
+pub fn sort_bundle_positions(positions: &mut [BundlePositionSorted]) {
+    positions.sort_by_key(|position| (position.tick_lower, position.tick_upper));
+}
+
+/// State of the bundled position account passed for an open bit of a position bundle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BundledPositionStatus {
+    // The account is the bundled position of the bundle index
+    Valid,
+    // The account at the bundled position address no longer holds a position
+    Orphaned,
+    // No account was passed for the bundle index
+    Missing,
+    // The account is not the bundled position of the bundle index
+    Mismatched,
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct BundleValidationResult {
+    pub valid: bool,
+    pub orphaned_bits: u16,
+    pub missing_positions: u16,
+    pub mismatched_indices: u16,
+}
+
+impl BundleValidationResult {
+    pub fn new(statuses: &[BundledPositionStatus]) -> Self {
+        let count = |status: BundledPositionStatus| {
+            statuses.iter().filter(|s| **s == status).count() as u16
+        };
+        let orphaned_bits = count(BundledPositionStatus::Orphaned);
+        let missing_positions = count(BundledPositionStatus::Missing);
+        let mismatched_indices = count(BundledPositionStatus::Mismatched);
+        BundleValidationResult {
+            valid: orphaned_bits == 0 && missing_positions == 0 && mismatched_indices == 0,
+            orphaned_bits,
+            missing_positions,
+            mismatched_indices,
+        }
+    }
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct PositionLiquiditySummary {
+    pub bundle_index: u16,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity: u128,
+    pub in_range: bool,
+}
+
+impl PositionLiquiditySummary {
+    pub fn new(bundle_index: u16, position: &Position, tick_current_index: i32) -> Self {
+        PositionLiquiditySummary {
+            bundle_index,
+            tick_lower_index: position.tick_lower_index,
+            tick_upper_index: position.tick_upper_index,
+            liquidity: position.liquidity,
+            // Same range convention as calculate_liquidity_token_deltas
+            in_range: position.tick_lower_index <= tick_current_index
+                && tick_current_index < position.tick_upper_index,
+        }
+    }
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct BundleLiquiditySnapshot {
+    pub total_liquidity: u128,
+    // Liquidity of the positions in range of the current tick of the pool
+    pub active_liquidity: u128,
+    pub inactive_liquidity: u128,
+    pub positions: Vec<PositionLiquiditySummary>,
+    pub snapshot_slot: u64,
+}
+
+impl BundleLiquiditySnapshot {
+    pub fn new(
+        positions: Vec<PositionLiquiditySummary>,
+        snapshot_slot: u64,
+    ) -> Result<Self, ErrorCode> {
+        let mut active_liquidity: u128 = 0;
+        let mut inactive_liquidity: u128 = 0;
+        for position in positions.iter() {
+            let liquidity = if position.in_range {
+                &mut active_liquidity
+            } else {
+                &mut inactive_liquidity
+            };
+            *liquidity = liquidity
+                .checked_add(position.liquidity)
+                .ok_or(ErrorCode::LiquidityOverflow)?;
+        }
+
+        Ok(BundleLiquiditySnapshot {
+            total_liquidity: active_liquidity
+                .checked_add(inactive_liquidity)
+                .ok_or(ErrorCode::LiquidityOverflow)?,
+            active_liquidity,
+            inactive_liquidity,
+            positions,
+            snapshot_slot,
+        })
+    }
+}
+
+// Approximate number of slots per year at 400ms per slot, used to annualize the fee yield
+pub const SLOTS_PER_YEAR: u64 = 78_840_000;
+// Impermanent loss at which the impermanent loss part of the health score drops to zero
+pub const HEALTH_SCORE_MAX_IL_BPS: u32 = 1_000;
+// Annual fee yield at which the fee part of the health score is full
+pub const HEALTH_SCORE_TARGET_FEE_APY_BPS: u16 = 2_000;
+
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct PositionHealthScore {
+    // 0 to 100: 40 for being in range, up to 30 each for a low impermanent loss and the fee yield
+    pub overall: u8,
+    pub in_range: bool,
+    // Share of the full tick range covered by the position
+    pub range_utilization_bps: u16,
+    // Value of the recorded deposits relative to holding the deposited tokens, negative for a loss
+    pub il_bps: i32,
+    // Estimated annual fee yield, saturating at u16::MAX
+    pub fee_efficiency: u16,
+    pub days_until_out_of_range_estimate: u32,
+}
+
+// Scores the health of a position at the current price of its pool.
+//
+// The impermanent loss compares the deposits in the history of the position, oldest first, with
+// the value of their liquidity today. The fee yield annualizes the fees owed since the last recorded fee collection,
+// so it is only as recent as the last update of the fees of the position. The days until the
+// position leaves its range assume a random walk of the price with the given daily volatility.
+pub fn calculate_position_health_score(
+    pool: &Pool,
+    position: &Position,
+    history: &[PositionHistoryEntry],
+    current_slot: u64,
+    daily_volatility_bps: u16,
+) -> Result<PositionHealthScore, ErrorCode> {
+    let in_range = pool.tick_current_index >= position.tick_lower_index
+        && pool.tick_current_index < position.tick_upper_index;
+    let range_utilization_bps = ((position.tick_upper_index - position.tick_lower_index) as u64
+        * 10_000
+        / (MAX_TICK_INDEX - MIN_TICK_INDEX) as u64) as u16;
+
+    let il_bps = position_il_bps(pool, position, history)?;
+    let fee_efficiency = position_fee_apy_bps(pool, position, history, current_slot)?;
+
+    let days_until_out_of_range_estimate = if !in_range {
+        0
+    } else if daily_volatility_bps == 0 {
+        u32::MAX
+    } else {
+        // A tick is a price move of about one basis point
+        let distance = (pool.tick_current_index - position.tick_lower_index)
+            .min(position.tick_upper_index - pool.tick_current_index) as u64;
+        let volatility = daily_volatility_bps as u64;
+        (distance * distance / (volatility * volatility)).min(u32::MAX as u64) as u32
+    };
+
+    let in_range_score: u32 = if in_range { 40 } else { 0 };
+    let il_loss_bps = if il_bps < 0 {
+        il_bps.unsigned_abs().min(HEALTH_SCORE_MAX_IL_BPS)
+    } else {
+        0
+    };
+    let il_score = 30 * (HEALTH_SCORE_MAX_IL_BPS - il_loss_bps) / HEALTH_SCORE_MAX_IL_BPS;
+    let fee_score = 30 * fee_efficiency.min(HEALTH_SCORE_TARGET_FEE_APY_BPS) as u32
+        / HEALTH_SCORE_TARGET_FEE_APY_BPS as u32;
+
+    Ok(PositionHealthScore {
+        overall: (in_range_score + il_score + fee_score) as u8,
+        in_range,
+        range_utilization_bps,
+        il_bps,
+        fee_efficiency,
+        days_until_out_of_range_estimate,
+    })
+}
+
+// Value of the token amounts in token B at the given price.
+fn value_in_token_b(amount_a: u64, amount_b: u64, sqrt_price: u128) -> Result<u128, ErrorCode> {
+    let value_a = checked_mul_div(
+        checked_mul_div(amount_a as u128, sqrt_price, 1 << 64)?,
+        sqrt_price,
+        1 << 64,
+    )?;
+    value_a
+        .checked_add(amount_b as u128)
+        .ok_or(ErrorCode::AmountCalcOverflow)
+}
+
+fn position_il_bps(
+    pool: &Pool,
+    position: &Position,
+    history: &[PositionHistoryEntry],
+) -> Result<i32, ErrorCode> {
+    let mut deposited_liquidity: u128 = 0;
+    let mut deposited_a: u64 = 0;
+    let mut deposited_b: u64 = 0;
+    for entry in history {
+        if entry.event_type != POSITION_HISTORY_INCREASE_LIQUIDITY {
+            continue;
+        }
+        deposited_liquidity = deposited_liquidity
+            .checked_add(entry.liquidity_delta.max(0) as u128)
+            .ok_or(ErrorCode::LiquidityOverflow)?;
+        deposited_a = deposited_a
+            .checked_add(entry.amount_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        deposited_b = deposited_b
+            .checked_add(entry.amount_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+    }
+    if deposited_liquidity == 0 {
+        return Ok(0);
+    }
+
+    let (amount_a, amount_b) = token_amounts_from_liquidity(
+        deposited_liquidity,
+        pool.tick_current_index,
+        pool.sqrt_price,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        false,
+    )?;
+    let position_value = value_in_token_b(amount_a, amount_b, pool.sqrt_price)?;
+    let held_value = value_in_token_b(deposited_a, deposited_b, pool.sqrt_price)?;
+    if held_value == 0 {
+        return Ok(0);
+    }
+
+    let il_bps = if position_value >= held_value {
+        checked_mul_div(position_value - held_value, 10_000, held_value)?.min(i32::MAX as u128)
+            as i32
+    } else {
+        -(checked_mul_div(held_value - position_value, 10_000, held_value)?.min(i32::MAX as u128)
+            as i32)
+    };
+    Ok(il_bps)
+}
+
+fn position_fee_apy_bps(
+    pool: &Pool,
+    position: &Position,
+    history: &[PositionHistoryEntry],
+    current_slot: u64,
+) -> Result<u16, ErrorCode> {
+    let fees_since_slot = match history
+        .iter()
+        .rev()
+        .find(|entry| entry.event_type == POSITION_HISTORY_COLLECT_FEES)
+        .or_else(|| history.first())
+    {
+        Some(entry) => entry.slot,
+        None => return Ok(0),
+    };
+    let elapsed_slots = current_slot.saturating_sub(fees_since_slot);
+
+    let (amount_a, amount_b) = token_amounts_from_liquidity(
+        position.liquidity,
+        pool.tick_current_index,
+        pool.sqrt_price,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        false,
+    )?;
+    let position_value = value_in_token_b(amount_a, amount_b, pool.sqrt_price)?;
+    if elapsed_slots == 0 || position_value == 0 {
+        return Ok(0);
+    }
+
+    let fees_value = value_in_token_b(position.fee_owed_a, position.fee_owed_b, pool.sqrt_price)?;
+    let yearly_fees_value =
+        checked_mul_div(fees_value, SLOTS_PER_YEAR as u128, elapsed_slots as u128)?;
+    let fee_apy_bps = checked_mul_div(yearly_fees_value, 10_000, position_value)?;
+    Ok(fee_apy_bps.min(u16::MAX as u128) as u16)
+}
+
+// Adds the fees owed by a position to the tokens withdrawn from it and resets the fees owed, so
+// that the liquidity tokens and the fees can be transferred to the owner together.
+pub fn settle_withdrawal_with_fees(
+    position: &mut Position,
+    withdrawn_a: u64,
+    withdrawn_b: u64,
+) -> Result<(u64, u64), ErrorCode> {
+    let amount_a = withdrawn_a
+        .checked_add(position.fee_owed_a)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+    let amount_b = withdrawn_b
+        .checked_add(position.fee_owed_b)
+        .ok_or(ErrorCode::TokenMaxExceeded)?;
+
+    position.reset_fees_owed();
+
+    Ok((amount_a, amount_b))
+}
+
+// Takes the fees accrued by the deposited fractions of a fractionalized position out of the fees
+// owed by the position, so that they can be transferred to the fraction holder.
+pub fn take_position_fraction_fees(
+    position: &mut Position,
+    vault: &mut PositionFractionVault,
+    share: &mut PositionFractionShare,
+) -> anchor_lang::Result<(u64, u64)> {
+    vault.harvest_fees(position.fee_owed_a, position.fee_owed_b)?;
+    share.settle(vault)?;
+
+    let (fee_a, fee_b) = share.take_fees_owed();
+    vault.claim_fees(fee_a, fee_b)?;
+    position.fee_owed_a = position
+        .fee_owed_a
+        .checked_sub(fee_a)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+    position.fee_owed_b = position
+        .fee_owed_b
+        .checked_sub(fee_b)
+        .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+    Ok((fee_a, fee_b))
+}
+
+#[cfg(test)]
+mod fee_growth_checkpoint_export_tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_fees_since_checkpoint() {
+        let position = Position {
+            liquidity: 1 << 10,
+            fee_growth_checkpoint_a: 1 << 64,
+            fee_growth_checkpoint_b: 0,
+            ..Default::default()
+        };
+
+        let export = next_fee_growth_checkpoint_export(&position, 3 << 64, 1 << 63);
+        assert_eq!(export.pending_fee_a, 2 << 10);
+        assert_eq!(export.pending_fee_b, 1 << 9);
+        assert_eq!(export.checkpoint_a, 1 << 64);
+        assert_eq!(export.current_inside_a, 3 << 64);
+        assert_eq!(position.fee_growth_checkpoint_a, 1 << 64);
+    }
+}
+
+#[cfg(test)]
+mod bundle_position_sorted_tests {
+    use super::*;
+
+    fn position(tick_lower_index: i32, tick_upper_index: i32) -> Position {
+        Position {
+            tick_lower_index,
+            tick_upper_index,
+            liquidity: 100,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_in_range() {
+        let position = position(-64, 64);
+        assert!(!BundlePositionSorted::new(0, &position, -65).in_range);
+        assert!(BundlePositionSorted::new(0, &position, -64).in_range);
+        assert!(BundlePositionSorted::new(0, &position, 63).in_range);
+        assert!(!BundlePositionSorted::new(0, &position, 64).in_range);
+    }
+
+    #[test]
+    fn test_sort_by_tick_lower_then_tick_upper() {
+        let mut positions = vec![
+            BundlePositionSorted::new(0, &position(0, 128), 0),
+            BundlePositionSorted::new(1, &position(-64, 64), 0),
+            BundlePositionSorted::new(2, &position(0, 64), 0),
+            BundlePositionSorted::new(3, &position(-128, 0), 0),
+        ];
+        sort_bundle_positions(&mut positions);
+
+        let slots: Vec<u16> = positions.iter().map(|p| p.bundle_slot).collect();
+        assert_eq!(slots, vec![3, 1, 2, 0]);
+    }
+}
+
+#[cfg(test)]
+mod bundle_validation_result_tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_bundle() {
+        let result = BundleValidationResult::new(&[BundledPositionStatus::Valid; 3]);
+        assert_eq!(
+            result,
+            BundleValidationResult {
+                valid: true,
+                ..Default::default()
+            }
+        );
+        assert!(BundleValidationResult::new(&[]).valid);
+    }
+
+    #[test]
+    fn test_counts_inconsistencies() {
+        let result = BundleValidationResult::new(&[
+            BundledPositionStatus::Valid,
+            BundledPositionStatus::Orphaned,
+            BundledPositionStatus::Mismatched,
+            BundledPositionStatus::Orphaned,
+            BundledPositionStatus::Missing,
+        ]);
+        assert_eq!(
+            result,
+            BundleValidationResult {
+                valid: false,
+                orphaned_bits: 2,
+                missing_positions: 1,
+                mismatched_indices: 1,
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod bundle_liquidity_snapshot_tests {
+    use super::*;
+
+    fn position(tick_lower_index: i32, tick_upper_index: i32, liquidity: u128) -> Position {
+        Position {
+            tick_lower_index,
+            tick_upper_index,
+            liquidity,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_liquidity_split_by_range() {
+        let positions = vec![
+            PositionLiquiditySummary::new(0, &position(-64, 64, 100), 0),
+            PositionLiquiditySummary::new(1, &position(64, 128, 200), 0),
+            PositionLiquiditySummary::new(2, &position(-128, 0, 300), 0),
+            PositionLiquiditySummary::new(3, &position(0, 128, 400), 0),
+        ];
+        let snapshot = BundleLiquiditySnapshot::new(positions.clone(), 42).unwrap();
+        assert_eq!(snapshot.active_liquidity, 500);
+        assert_eq!(snapshot.inactive_liquidity, 500);
+        assert_eq!(snapshot.total_liquidity, 1_000);
+        assert_eq!(snapshot.positions, positions);
+        assert_eq!(snapshot.snapshot_slot, 42);
+    }
+
+    #[test]
+    fn test_empty_bundle() {
+        let snapshot = BundleLiquiditySnapshot::new(vec![], 1).unwrap();
+        assert_eq!(snapshot.total_liquidity, 0);
+        assert!(snapshot.positions.is_empty());
+    }
+
+    #[test]
+    fn test_liquidity_overflow() {
+        let positions = vec![
+            PositionLiquiditySummary::new(0, &position(-64, 64, u128::MAX), 0),
+            PositionLiquiditySummary::new(1, &position(64, 128, 1), 0),
+        ];
+        assert_eq!(
+            BundleLiquiditySnapshot::new(positions, 0).unwrap_err(),
+            ErrorCode::LiquidityOverflow
+        );
+    }
+}
+
+#[cfg(test)]
+mod settle_withdrawal_with_fees_tests {
+    use super::*;
+
+    #[test]
+    fn test_withdrawal_includes_fees_and_resets_owed() {
+        let mut position = Position {
+            fee_owed_a: 30,
+            fee_owed_b: 5,
+            ..Default::default()
+        };
+
+        let (amount_a, amount_b) = settle_withdrawal_with_fees(&mut position, 1_000, 0).unwrap();
+        assert_eq!(amount_a, 1_030);
+        assert_eq!(amount_b, 5);
+        assert_eq!(position.fee_owed_a, 0);
+        assert_eq!(position.fee_owed_b, 0);
+
+        // Fees are only paid out once
+        let (amount_a, amount_b) = settle_withdrawal_with_fees(&mut position, 1_000, 0).unwrap();
+        assert_eq!((amount_a, amount_b), (1_000, 0));
+    }
+
+    #[test]
+    fn test_withdrawal_with_fees_overflow() {
+        let mut position = Position {
+            fee_owed_a: 1,
+            ..Default::default()
+        };
+
+        let result = settle_withdrawal_with_fees(&mut position, u64::MAX, 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::TokenMaxExceeded);
+        assert_eq!(position.fee_owed_a, 1);
+    }
+}
+
+#[cfg(test)]
+mod position_fraction_fees_tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+
+    #[test]
+    fn test_take_position_fraction_fees() {
+        let mut position = Position {
+            fee_owed_a: 100,
+            fee_owed_b: 40,
+            ..Default::default()
+        };
+        let mut vault = PositionFractionVault::default();
+        vault
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                4,
+                255,
+                position.fee_owed_a,
+                position.fee_owed_b,
+            )
+            .unwrap();
+        let mut share = PositionFractionShare::default();
+        share.deposit(&vault, 1).unwrap();
+
+        // Fees owed before fractionalizing are not distributed
+        assert_eq!(
+            take_position_fraction_fees(&mut position, &mut vault, &mut share).unwrap(),
+            (0, 0)
+        );
+
+        position.fee_owed_a += 400;
+        position.fee_owed_b += 800;
+        assert_eq!(
+            take_position_fraction_fees(&mut position, &mut vault, &mut share).unwrap(),
+            (100, 200)
+        );
+        assert_eq!((position.fee_owed_a, position.fee_owed_b), (400, 640));
+        assert_eq!(
+            take_position_fraction_fees(&mut position, &mut vault, &mut share).unwrap(),
+            (0, 0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod position_health_score_tests {
+    use super::*;
+    use crate::math::sqrt_price_from_tick_index;
+
+    fn pool(tick_current_index: i32) -> Pool {
+        Pool {
+            tick_current_index,
+            sqrt_price: sqrt_price_from_tick_index(tick_current_index),
+            ..Pool::default()
+        }
+    }
+
+    // A position over -1000..1000 with 1_000_000 liquidity deposited at slot 100 and price 1
+    fn position() -> Position {
+        Position {
+            tick_lower_index: -1000,
+            tick_upper_index: 1000,
+            liquidity: 1_000_000_000,
+            ..Position::default()
+        }
+    }
+
+    fn history() -> Vec<PositionHistoryEntry> {
+        let (amount_a, amount_b) = token_amounts_from_liquidity(
+            1_000_000_000,
+            0,
+            sqrt_price_from_tick_index(0),
+            -1000,
+            1000,
+            true,
+        )
+        .unwrap();
+        vec![PositionHistoryEntry::new(
+            POSITION_HISTORY_INCREASE_LIQUIDITY,
+            100,
+            1_000_000_000,
+            amount_a,
+            amount_b,
+        )]
+    }
+
+    #[test]
+    fn test_in_range_without_price_move() {
+        let score =
+            calculate_position_health_score(&pool(0), &position(), &history(), 100, 100).unwrap();
+        assert!(score.in_range);
+        assert_eq!(score.range_utilization_bps, 22);
+        assert_eq!(score.il_bps, 0);
+        assert_eq!(score.fee_efficiency, 0);
+        // 1000 ticks to the nearest bound at 100 bps a day
+        assert_eq!(score.days_until_out_of_range_estimate, 100);
+        assert_eq!(score.overall, 70);
+    }
+
+    #[test]
+    fn test_out_of_range_position() {
+        let score = calculate_position_health_score(&pool(2000), &position(), &history(), 100, 100)
+            .unwrap();
+        assert!(!score.in_range);
+        assert!(score.il_bps < 0);
+        assert_eq!(score.days_until_out_of_range_estimate, 0);
+        assert!(score.overall < 30);
+    }
+
+    #[test]
+    fn test_impermanent_loss_grows_with_price_move() {
+        let small_move =
+            calculate_position_health_score(&pool(200), &position(), &history(), 100, 0).unwrap();
+        let large_move =
+            calculate_position_health_score(&pool(800), &position(), &history(), 100, 0).unwrap();
+        assert!(small_move.il_bps <= 0);
+        assert!(large_move.il_bps < small_move.il_bps);
+        assert_eq!(small_move.days_until_out_of_range_estimate, u32::MAX);
+    }
+
+    #[test]
+    fn test_fee_efficiency_annualizes_fees_owed() {
+        let mut position = position();
+        let (amount_a, amount_b) =
+            token_amounts_from_liquidity(position.liquidity, 0, 1 << 64, -1000, 1000, false)
+                .unwrap();
+        // Fees of 1% of the position value over 1% of a year
+        position.fee_owed_b = (amount_a + amount_b) / 100;
+        let current_slot = 100 + SLOTS_PER_YEAR / 100;
+
+        let score =
+            calculate_position_health_score(&pool(0), &position, &history(), current_slot, 100)
+                .unwrap();
+        assert!(score.fee_efficiency >= 9_990 && score.fee_efficiency <= 10_000);
+        assert_eq!(score.overall, 100);
+    }
+}
@@ -5,12 +5,12 @@ use crate::{
     manager::{pool_manager::next_pool_reward_infos, tick_manager::next_tick_cross_update},
     math::*,
     state::*,
-    util::SwapTickSequence,
+    util::{PackedSwapTickSequence, SwapTickSequence},
 };
 use anchor_lang::prelude::*;
-use std::convert::TryInto;
+use dex_math::{swap_with_tick_source, PoolState, SwapError, SwapParams, TickSource};
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct PostSwapUpdate {
     pub amount_a: u64,
     pub amount_b: u64,
@@ -20,7 +20,11 @@ pub struct PostSwapUpdate {
     pub next_fee_growth_global: u128,
     pub next_reward_infos: [PoolRewardInfo; NUM_REWARDS],
     pub next_protocol_fee: u64,
+    // Part of the LP fee routed to the referral account of the swap
+    pub next_referral_fee: u64,
+    // Total fee charged by the swap in input token units, protocol fee included
     pub fee: u64,
+    pub tick_crossed_count: u32,
 }
 
 pub fn swap(
@@ -32,280 +36,1656 @@ pub fn swap(
     a_to_b: bool,
     timestamp: u64,
 ) -> Result<PostSwapUpdate> {
-    if sqrt_price_limit < MIN_SQRT_PRICE_X64 || sqrt_price_limit > MAX_SQRT_PRICE_X64 {
-        return Err(ErrorCode::SqrtPriceOutOfBounds.into());
-    }
+    swap_with_referral(
+        pool,
+        swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        0,
+    )
+}
 
-    if a_to_b && sqrt_price_limit > pool.sqrt_price || !a_to_b && sqrt_price_limit < pool.sqrt_price
-    {
-        return Err(ErrorCode::InvalidSqrtPriceLimitDirection.into());
-    }
+/// Same as `swap`, but `referral_fee_rate` basis points of the LP fee of each step are split off
+/// into `next_referral_fee` after the protocol fee. The protocol fee is unchanged, and the LP fee
+/// growth only includes the LP fee net of the referral fee.
+pub fn swap_with_referral(
+    pool: &Pool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+    referral_fee_rate: u16,
+) -> Result<PostSwapUpdate> {
+    swap_with_fee_rate(
+        pool,
+        swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+        pool.fee_rate,
+        referral_fee_rate,
+    )
+}
 
-    if amount == 0 {
-        return Err(ErrorCode::ZeroTradableAmount.into());
-    }
+/// Same as `swap_with_referral`, but the swap is charged `fee_rate` instead of the fee rate of
+/// the pool, e.g. a fee rate reduced by an LP fee rebate.
+pub fn swap_with_fee_rate(
+    pool: &Pool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+    fee_rate: u16,
+    referral_fee_rate: u16,
+) -> Result<PostSwapUpdate> {
+    pool.verify_swap_direction_enabled(a_to_b)?;
 
-    let tick_spacing = pool.tick_spacing;
-    let fee_rate = pool.fee_rate;
-    let protocol_fee_rate = pool.protocol_fee_rate;
     let next_reward_infos = next_pool_reward_infos(pool, timestamp)?;
+    let max_tick_crossings = swap_tick_sequence.max_tick_crossings();
+    let mut tick_source = SwapTickSequenceSource {
+        swap_tick_sequence,
+        pool,
+        reward_infos: &next_reward_infos,
+        curr_array_index: 0,
+        next_array_index: 0,
+    };
 
-    let mut amount_remaining: u64 = amount;
-    let mut amount_calculated: u64 = 0;
-    let mut curr_sqrt_price = pool.sqrt_price;
-    let mut curr_tick_index = pool.tick_current_index;
-    let mut curr_liquidity = pool.liquidity;
-    let mut fee: u64 = 0;
-    let mut curr_protocol_fee: u64 = 0;
-    let mut curr_array_index: usize = 0;
-    let mut curr_fee_growth_global_input = if a_to_b {
-        pool.fee_growth_global_a
+    let swap_update = swap_over_tick_source(
+        pool,
+        &mut tick_source,
+        fee_rate,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            referral_fee_rate,
+            max_tick_crossings,
+        },
+        next_reward_infos,
+    )?;
+
+    let fee_growth = if a_to_b {
+        swap_update
+            .next_fee_growth_global
+            .checked_sub(pool.fee_growth_global_a)
+            .ok_or(ErrorCode::OverflowOrConversion)?
     } else {
-        pool.fee_growth_global_b
+        swap_update
+            .next_fee_growth_global
+            .checked_sub(pool.fee_growth_global_b)
+            .ok_or(ErrorCode::OverflowOrConversion)?
     };
 
-    while amount_remaining > 0 && sqrt_price_limit != curr_sqrt_price {
-        let (next_array_index, next_tick_index) = swap_tick_sequence
-            .get_next_initialized_tick_index(
-                curr_tick_index,
-                tick_spacing,
-                a_to_b,
-                curr_array_index,
-            )?;
+    // Log delta in fee growth to track pool usage over time with off-chain analytics
+    msg!("fee_growth: {}", fee_growth);
 
-        let (next_tick_sqrt_price, sqrt_price_target) =
-            get_next_sqrt_prices(next_tick_index, sqrt_price_limit, a_to_b);
+    Ok(swap_update)
+}
 
-        let swap_computation = compute_swap(
-            amount_remaining,
-            fee_rate,
-            curr_liquidity,
-            curr_sqrt_price,
-            sqrt_price_target,
+/// Simulate a swap without any account context, e.g. for quotes in off-chain routers.
+///
+/// Runs the same code path as the swap instructions, so the returned update is identical to the
+/// one an instruction applies for the same pool and tick-arrays. Build the sequence with
+/// `SwapTickSequence::new_read_only` to leave the tick-arrays unchanged.
+pub fn simulate_swap(
+    pool: &Pool,
+    swap_tick_sequence: &mut SwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+    timestamp: u64,
+) -> Result<PostSwapUpdate> {
+    swap(
+        pool,
+        swap_tick_sequence,
+        amount,
+        sqrt_price_limit,
+        amount_specified_is_input,
+        a_to_b,
+        timestamp,
+    )
+}
+
+/// Quote a swap over a `PackedSwapTickSequence`.
+///
+/// Calculates the same amounts, price and liquidity as `swap` over the tick-arrays the sequence
+/// was packed from, but does not produce tick updates for crossed ticks, so the result must not be
+/// applied to a pool.
+pub fn swap_packed(
+    pool: &Pool,
+    packed_tick_sequence: &mut PackedSwapTickSequence,
+    amount: u64,
+    sqrt_price_limit: u128,
+    amount_specified_is_input: bool,
+    timestamp: u64,
+) -> Result<PostSwapUpdate> {
+    let a_to_b = packed_tick_sequence.a_to_b();
+
+    pool.verify_swap_direction_enabled(a_to_b)?;
+    packed_tick_sequence.verify_start_tick_index(pool.tick_current_index)?;
+
+    let next_reward_infos = next_pool_reward_infos(pool, timestamp)?;
+    let mut tick_source = PackedSwapTickSequenceSource {
+        packed_tick_sequence,
+        next_liquidity_net: None,
+    };
+
+    // Quotes do not route referral fees
+    swap_over_tick_source(
+        pool,
+        &mut tick_source,
+        pool.fee_rate,
+        SwapParams {
+            amount,
+            sqrt_price_limit,
             amount_specified_is_input,
             a_to_b,
-        )?;
+            referral_fee_rate: 0,
+            max_tick_crossings: 0,
+        },
+        next_reward_infos,
+    )
+}
 
-        if amount_specified_is_input {
-            amount_remaining = amount_remaining
-                .checked_sub(swap_computation.amount_in)
-                .ok_or(ErrorCode::AmountRemainingOverflow)?;
-            amount_remaining = amount_remaining
-                .checked_sub(swap_computation.fee_amount)
-                .ok_or(ErrorCode::AmountRemainingOverflow)?;
-
-            amount_calculated = amount_calculated
-                .checked_add(swap_computation.amount_out)
-                .ok_or(ErrorCode::AmountCalcOverflow)?;
-        } else {
-            amount_remaining = amount_remaining
-                .checked_sub(swap_computation.amount_out)
-                .ok_or(ErrorCode::AmountRemainingOverflow)?;
-
-            amount_calculated = amount_calculated
-                .checked_add(swap_computation.amount_in)
-                .ok_or(ErrorCode::AmountCalcOverflow)?;
-            amount_calculated = amount_calculated
-                .checked_add(swap_computation.fee_amount)
-                .ok_or(ErrorCode::AmountCalcOverflow)?;
-        }
+// Run the swap loop of dex-math over the ticks of `tick_source`, charging `fee_rate`
+fn swap_over_tick_source<T: TickSource<Error = Error>>(
+    pool: &Pool,
+    tick_source: &mut T,
+    fee_rate: u16,
+    params: SwapParams,
+    next_reward_infos: [PoolRewardInfo; NUM_REWARDS],
+) -> Result<PostSwapUpdate> {
+    let state = PoolState {
+        sqrt_price: pool.sqrt_price,
+        tick_current_index: pool.tick_current_index,
+        liquidity: pool.liquidity,
+        fee_rate,
+        protocol_fee_rate: pool.protocol_fee_rate,
+        fee_growth_global_a: pool.fee_growth_global_a,
+        fee_growth_global_b: pool.fee_growth_global_b,
+    };
 
-        let (next_protocol_fee, next_fee_growth_global_input) = calculate_fees(
-            swap_computation.fee_amount,
-            protocol_fee_rate,
-            curr_liquidity,
-            curr_protocol_fee,
-            curr_fee_growth_global_input,
-        )?;
-        curr_protocol_fee = next_protocol_fee;
-        curr_fee_growth_global_input = next_fee_growth_global_input;
-
-        if swap_computation.next_price == next_tick_sqrt_price {
-            let (next_tick, next_tick_initialized) = swap_tick_sequence
-                .get_tick(next_array_index, next_tick_index, tick_spacing)
-                .map_or_else(|_| (None, false), |tick| (Some(tick), tick.initialized));
-
-            if next_tick_initialized {
-                let (fee_growth_global_a, fee_growth_global_b) = if a_to_b {
-                    (curr_fee_growth_global_input, pool.fee_growth_global_b)
-                } else {
-                    (pool.fee_growth_global_a, curr_fee_growth_global_input)
-                };
-
-                let (update, next_liquidity) = calculate_update(
-                    next_tick.ok_or(ErrorCode::TickNotFound)?,
-                    a_to_b,
-                    curr_liquidity,
-                    fee_growth_global_a,
-                    fee_growth_global_b,
-                    &next_reward_infos,
-                )?;
-
-                curr_liquidity = next_liquidity;
-                swap_tick_sequence.update_tick(
-                    next_array_index,
-                    next_tick_index,
-                    tick_spacing,
-                    &update,
-                )?;
-            }
-
-            let tick_offset = swap_tick_sequence.get_tick_offset(
-                next_array_index,
-                next_tick_index,
-                tick_spacing,
+    let result =
+        swap_with_tick_source(&state, tick_source, params).map_err(|error| match error {
+            SwapError::Math(error) => ErrorCode::from(error).into(),
+            SwapError::TickSource(error) => error,
+        })?;
+
+    Ok(PostSwapUpdate {
+        amount_a: result.amount_a,
+        amount_b: result.amount_b,
+        next_liquidity: result.next_liquidity,
+        next_tick_index: result.next_tick_index,
+        next_sqrt_price: result.next_sqrt_price,
+        next_fee_growth_global: result.next_fee_growth_global,
+        next_reward_infos,
+        next_protocol_fee: result.protocol_fee,
+        next_referral_fee: result.referral_fee,
+        fee: result.fee,
+        tick_crossed_count: result.tick_crossed_count,
+    })
+}
+
+// The tick-arrays of a swap instruction. Crossing an initialized tick updates it in its tick-array.
+struct SwapTickSequenceSource<'a, 'info> {
+    swap_tick_sequence: &'a mut SwapTickSequence<'info>,
+    pool: &'a Pool,
+    reward_infos: &'a [PoolRewardInfo; NUM_REWARDS],
+    curr_array_index: usize,
+    // The array index of the tick last returned by next_tick_index
+    next_array_index: usize,
+}
+
+impl TickSource for SwapTickSequenceSource<'_, '_> {
+    type Error = Error;
+
+    fn next_tick_index(&mut self, tick_index: i32, a_to_b: bool) -> Result<i32> {
+        let (next_array_index, next_tick_index) =
+            self.swap_tick_sequence.get_next_initialized_tick_index(
+                tick_index,
+                self.pool.tick_spacing,
+                a_to_b,
+                self.curr_array_index,
             )?;
+        self.next_array_index = next_array_index;
+        Ok(next_tick_index)
+    }
 
-            // Increment to the next tick array if either condition is true:
-            //  - Price is moving left and the current tick is the start of the tick array
-            //  - Price is moving right and the current tick is the end of the tick array
-            curr_array_index = if (a_to_b && tick_offset == 0)
-                || (!a_to_b && tick_offset == TICK_ARRAY_SIZE as isize - 1)
+    fn cross_tick(
+        &mut self,
+        tick_index: i32,
+        a_to_b: bool,
+        fee_growth_global_input: u128,
+    ) -> Result<Option<i128>> {
+        let tick_spacing = self.pool.tick_spacing;
+        let next_array_index = self.next_array_index;
+
+        let liquidity_net =
+            match self
+                .swap_tick_sequence
+                .get_tick(next_array_index, tick_index, tick_spacing)
             {
-                next_array_index + 1
-            } else {
-                next_array_index
+                Ok(tick) if tick.initialized => {
+                    // Use updated fee_growth for crossing tick
+                    let (fee_growth_global_a, fee_growth_global_b) = if a_to_b {
+                        (fee_growth_global_input, self.pool.fee_growth_global_b)
+                    } else {
+                        (self.pool.fee_growth_global_a, fee_growth_global_input)
+                    };
+                    let update = next_tick_cross_update(
+                        tick,
+                        fee_growth_global_a,
+                        fee_growth_global_b,
+                        self.reward_infos,
+                    )?;
+                    let liquidity_net = tick.liquidity_net;
+                    self.swap_tick_sequence.update_tick(
+                        next_array_index,
+                        tick_index,
+                        tick_spacing,
+                        &update,
+                    )?;
+                    Some(liquidity_net)
+                }
+                _ => None,
             };
 
-            // The get_init_tick search is inclusive of the current index in an a_to_b trade.
-            // We therefore have to shift the index by 1 to advance to the next init tick to the left.
-            curr_tick_index = if a_to_b {
-                next_tick_index - 1
-            } else {
-                next_tick_index
-            };
-        } else if swap_computation.next_price != curr_sqrt_price {
-            curr_tick_index = tick_index_from_sqrt_price(&swap_computation.next_price);
-        }
+        let tick_offset =
+            self.swap_tick_sequence
+                .get_tick_offset(next_array_index, tick_index, tick_spacing)?;
 
-        curr_sqrt_price = swap_computation.next_price;
-        fee = swap_computation.fee_amount;
+        // Increment to the next tick array if either condition is true:
+        //  - Price is moving left and the current tick is the start of the tick array
+        //  - Price is moving right and the current tick is the end of the tick array
+        self.curr_array_index = if (a_to_b && tick_offset == 0)
+            || (!a_to_b && tick_offset == TICK_ARRAY_SIZE as isize - 1)
+        {
+            next_array_index + 1
+        } else {
+            next_array_index
+        };
+
+        Ok(liquidity_net)
     }
+}
 
-    let (amount_a, amount_b) = if a_to_b == amount_specified_is_input {
-        (
-            amount
-                .checked_sub(amount_remaining)
-                .ok_or(ErrorCode::OverflowOrConversion)?,
-            amount_calculated,
-        )
+// The ticks of a packed sequence. Crossed ticks are not updated.
+struct PackedSwapTickSequenceSource<'a> {
+    packed_tick_sequence: &'a mut PackedSwapTickSequence,
+    // The liquidity_net of the tick last returned by next_tick_index
+    next_liquidity_net: Option<i128>,
+}
+
+impl TickSource for PackedSwapTickSequenceSource<'_> {
+    type Error = Error;
+
+    fn next_tick_index(&mut self, tick_index: i32, _a_to_b: bool) -> Result<i32> {
+        let (next_tick_index, next_liquidity_net) =
+            self.packed_tick_sequence.next_tick(tick_index)?;
+        self.next_liquidity_net = next_liquidity_net;
+        Ok(next_tick_index)
+    }
+
+    fn cross_tick(
+        &mut self,
+        _tick_index: i32,
+        _a_to_b: bool,
+        _fee_growth_global_input: u128,
+    ) -> Result<Option<i128>> {
+        Ok(self.next_liquidity_net)
+    }
+}
+
+/// Check whether a swap stopped early because it reached the tick crossing limit of its tick
+/// sequence.
+///
+/// # Returns
+/// - `Some((u64, u64))`: The filled and the unfilled part of the specified amount, if the swap
+///                       reached `max_tick_crossings` before the specified amount was filled
+/// - `None`: The swap was not limited by the tick crossing limit
+pub fn tick_crossing_limit_reached(
+    swap_update: &PostSwapUpdate,
+    max_tick_crossings: u8,
+    amount: u64,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Option<(u64, u64)> {
+    if max_tick_crossings == 0 || swap_update.tick_crossed_count < max_tick_crossings as u32 {
+        return None;
+    }
+    let amount_filled = if a_to_b == amount_specified_is_input {
+        swap_update.amount_a
     } else {
-        (
-            amount_calculated,
-            amount
-                .checked_sub(amount_remaining)
-                .ok_or(ErrorCode::OverflowOrConversion)?,
-        )
+        swap_update.amount_b
     };
+    let amount_remaining = amount.saturating_sub(amount_filled);
+    if amount_remaining == 0 {
+        return None;
+    }
+    Some((amount_filled, amount_remaining))
+}
 
-    let fee_growth = if a_to_b {
-        curr_fee_growth_global_input
-            .checked_sub(pool.fee_growth_global_a)
-            .ok_or(ErrorCode::OverflowOrConversion)?
+/// Check the calculated side of a swap against the caller's threshold.
+///
+/// For exact-in swaps the output amount must be at least `other_amount_threshold`. For exact-out
+/// swaps the input amount is derived by the swap loop, which accumulates `amount_in + fee_amount`
+/// for each step, so the swap fee is part of the amount bounded by `other_amount_threshold`.
+pub fn check_other_amount_threshold(
+    swap_update: &PostSwapUpdate,
+    other_amount_threshold: u64,
+    amount_specified_is_input: bool,
+    a_to_b: bool,
+) -> Result<()> {
+    let (amount_input, amount_output) = if a_to_b {
+        (swap_update.amount_a, swap_update.amount_b)
     } else {
-        curr_fee_growth_global_input
-            .checked_sub(pool.fee_growth_global_b)
-            .ok_or(ErrorCode::OverflowOrConversion)?
+        (swap_update.amount_b, swap_update.amount_a)
     };
 
-    // Log delta in fee growth to track pool usage over time with off-chain analytics
-    msg!("fee_growth: {}", fee_growth);
+    if amount_specified_is_input {
+        if amount_output < other_amount_threshold {
+            return Err(ErrorCode::AmountOutBelowMinimum.into());
+        }
+    } else {
+        // Fee-inclusive input amount
+        if amount_input > other_amount_threshold {
+            return Err(ErrorCode::AmountInAboveMaximum.into());
+        }
+    }
+    Ok(())
+}
 
-    Ok(PostSwapUpdate {
-        amount_a,
-        amount_b,
-        next_liquidity: curr_liquidity,
-        next_tick_index: curr_tick_index,
-        next_sqrt_price: curr_sqrt_price,
-        next_fee_growth_global: curr_fee_growth_global_input,
-        next_reward_infos,
-        next_protocol_fee: curr_protocol_fee,
-        fee,
+/// Check that the pool has not moved away from the tick a swap was quoted at.
+///
+/// The swap fails with `PoolTickMoved` if `expected_tick_index` is provided and the current tick
+/// of the pool differs from it by more than `max_tick_deviation` ticks.
+pub fn check_expected_tick_index(
+    tick_current_index: i32,
+    expected_tick_index: Option<i32>,
+    max_tick_deviation: u16,
+) -> Result<()> {
+    if let Some(expected_tick_index) = expected_tick_index {
+        let deviation = (tick_current_index as i64 - expected_tick_index as i64).abs();
+        if deviation > max_tick_deviation as i64 {
+            return Err(ErrorCode::PoolTickMoved.into());
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the sqrt price limit of a swap from `sqrt_price_limit` and an optional `tick_limit`.
+///
+/// The price of `tick_limit` must lie on the side of the current price the swap moves towards.
+/// When both limits are provided, the one closer to the current price applies.
+pub fn resolve_sqrt_price_limit(
+    current_sqrt_price: u128,
+    sqrt_price_limit: u128,
+    tick_limit: Option<i32>,
+    a_to_b: bool,
+) -> Result<u128> {
+    let tick_limit = match tick_limit {
+        Some(tick_limit) => tick_limit,
+        None => return Ok(sqrt_price_limit),
+    };
+    if Tick::check_is_out_of_bounds(tick_limit) {
+        return Err(ErrorCode::TickIndexOutOfBounds.into());
+    }
+
+    let tick_sqrt_price_limit = sqrt_price_from_tick_index(tick_limit);
+    if a_to_b && tick_sqrt_price_limit > current_sqrt_price
+        || !a_to_b && tick_sqrt_price_limit < current_sqrt_price
+    {
+        return Err(ErrorCode::InvalidSqrtPriceLimitDirection.into());
+    }
+
+    Ok(if a_to_b {
+        sqrt_price_limit.max(tick_sqrt_price_limit)
+    } else {
+        sqrt_price_limit.min(tick_sqrt_price_limit)
     })
 }
 
-fn calculate_fees(
-    fee_amount: u64,
-    protocol_fee_rate: u16,
-    curr_liquidity: u128,
-    curr_protocol_fee: u64,
-    curr_fee_growth_global_input: u128,
-) -> Result<(u64, u128)> {
-    let mut next_protocol_fee = curr_protocol_fee;
-    let mut next_fee_growth_global_input = curr_fee_growth_global_input;
-    let mut global_fee = fee_amount;
-    if protocol_fee_rate > 0 {
-        let delta = calculate_protocol_fee(global_fee, protocol_fee_rate)?;
-        global_fee = global_fee
-            .checked_sub(delta)
-            .ok_or(ErrorCode::OverflowOrConversion)?;
-        next_protocol_fee = next_protocol_fee.wrapping_add(delta);
+/// Check that two pools form a valid two-hop route.
+///
+/// The output mint of the first hop must be the input mint of the second hop. Routes that start
+/// and end with the same token (A -> B -> A) are rejected with `CircularRoute`: such a route is
+/// either a mistake or an arbitrage loop, which must go through `execute_arbitrage_cycle` where
+/// the caller states that intent and a profit bound explicitly.
+pub fn check_two_hop_route(
+    pool_one: &Pool,
+    pool_two: &Pool,
+    a_to_b_one: bool,
+    a_to_b_two: bool,
+) -> Result<()> {
+    if pool_one.output_token_mint(a_to_b_one) != pool_two.input_token_mint(a_to_b_two) {
+        return Err(ErrorCode::InvalidIntermediaryMint.into());
+    }
+    if pool_one.input_token_mint(a_to_b_one) == pool_two.output_token_mint(a_to_b_two) {
+        return Err(ErrorCode::CircularRoute.into());
     }
+    Ok(())
+}
+
+/// Check that the intermediate mint of a two-hop swap is the input token of the second hop. Used as
+/// an account constraint, so that a mismatched route fails before any pool is swapped.
+pub fn is_second_hop_input_mint(pool_two: &Pool, a_to_b_two: bool, token_mint: Pubkey) -> bool {
+    pool_two.input_token_mint(a_to_b_two) == token_mint
+}
 
-    if curr_liquidity > 0 {
-        next_fee_growth_global_input = next_fee_growth_global_input
-            .checked_add(((global_fee as u128) << Q64_RESOLUTION) / curr_liquidity)
-            .ok_or(ErrorCode::OverflowOrConversion)?;
+/// Check that the first hop of a two-hop swap produced at least `intermediate_threshold` of the
+/// intermediate token, so that a thin first pool fails the route before the second hop is
+/// calculated.
+pub fn check_intermediate_amount(
+    swap_update_one: &PostSwapUpdate,
+    a_to_b_one: bool,
+    intermediate_threshold: Option<u64>,
+) -> Result<()> {
+    if let Some(intermediate_threshold) = intermediate_threshold {
+        let intermediate_amount = if a_to_b_one {
+            swap_update_one.amount_b
+        } else {
+            swap_update_one.amount_a
+        };
+        if intermediate_amount < intermediate_threshold {
+            return Err(ErrorCode::IntermediateAmountBelowMinimum.into());
+        }
     }
-    Ok((next_protocol_fee, next_fee_growth_global_input))
+    Ok(())
 }
 
-fn calculate_protocol_fee(global_fee: u64, protocol_fee_rate: u16) -> Result<u64> {
-    let global_fee_u128 = global_fee as u128;
-    let protocol_fee_rate_u128 = protocol_fee_rate as u128;
+pub const MAX_ROUTE_HOPS: usize = 4;
 
-    // Perform multiplication with checked_mul
-    let product = global_fee_u128
-        .checked_mul(protocol_fee_rate_u128)
-        .ok_or(ErrorCode::OverflowOrConversion)?;
+/// Verifies that the hops of a swap route, given as (pool, a_to_b) pairs in swap order, connect.
+///
+/// The output mint of each hop must be the input mint of the next hop. As for two-hop swaps,
+/// routes that end with the token they start with are rejected with `CircularRoute`.
+pub fn check_route(hops: &[(&Pool, bool)]) -> Result<()> {
+    if hops.is_empty() || hops.len() > MAX_ROUTE_HOPS {
+        return Err(ErrorCode::InvalidSwapRoute.into());
+    }
 
-    // Perform division with checked_div
-    let result = product
-        .checked_div(PROTOCOL_FEE_RATE_MUL_VALUE)
-        .ok_or(ErrorCode::OverflowOrConversion)?;
+    for hop in hops.windows(2) {
+        let (pool, a_to_b) = hop[0];
+        let (next_pool, next_a_to_b) = hop[1];
+        if pool.output_token_mint(a_to_b) != next_pool.input_token_mint(next_a_to_b) {
+            return Err(ErrorCode::InvalidIntermediaryMint.into());
+        }
+    }
 
-    // Convert to u64 safely
-    let protocol_fee: u64 = result
-        .try_into()
-        .map_err(|_| ErrorCode::OverflowOrConversion)?;
-    Ok(protocol_fee)
+    let (first_pool, first_a_to_b) = hops[0];
+    let (last_pool, last_a_to_b) = hops[hops.len() - 1];
+    if first_pool.input_token_mint(first_a_to_b) == last_pool.output_token_mint(last_a_to_b) {
+        return Err(ErrorCode::CircularRoute.into());
+    }
+
+    Ok(())
 }
 
-fn calculate_update(
-    tick: &Tick,
-    a_to_b: bool,
-    liquidity: u128,
-    fee_growth_global_a: u128,
-    fee_growth_global_b: u128,
-    reward_infos: &[PoolRewardInfo; NUM_REWARDS],
-) -> Result<(TickUpdate, u128)> {
-    // Use updated fee_growth for crossing tick
-    // Use -liquidity_net if going left, +liquidity_net going right
-    let signed_liquidity_net = if a_to_b {
-        -tick.liquidity_net
-    } else {
-        tick.liquidity_net
-    };
+#[cfg(test)]
+mod swap_fee_threshold_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn exact_out_b_to_a(fee_rate: u16, amount_out: u64) -> PostSwapUpdate {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(fee_rate)
+            .build();
+        let mut tick_array = TickArray::default();
+        tick_array.start_tick_index = 0;
+        let tick_array = RefCell::new(tick_array);
+        let mut swap_tick_sequence = SwapTickSequence::new(tick_array.borrow_mut(), None, None);
+
+        swap(
+            &pool,
+            &mut swap_tick_sequence,
+            amount_out,
+            MAX_SQRT_PRICE_X64,
+            false,
+            false,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_exact_out_input_includes_fee() {
+        let no_fee = exact_out_b_to_a(0, 100_000);
+        let with_fee = exact_out_b_to_a(3000, 100_000);
+
+        assert_eq!(no_fee.amount_a, 100_000);
+        assert_eq!(with_fee.amount_a, 100_000);
+        assert!(with_fee.fee > 0);
+        assert_eq!(with_fee.amount_b, no_fee.amount_b + with_fee.fee);
+    }
 
-    let update =
-        next_tick_cross_update(tick, fee_growth_global_a, fee_growth_global_b, reward_infos)?;
+    #[test]
+    fn test_exact_out_max_in_bound_accounts_for_fee() {
+        let no_fee = exact_out_b_to_a(0, 100_000);
+        let with_fee = exact_out_b_to_a(3000, 100_000);
 
-    // Update the global liquidity to reflect the new current tick
-    let next_liquidity = add_liquidity_delta(liquidity, signed_liquidity_net)?;
+        // A threshold covering only the fee-less input must be rejected
+        let result = check_other_amount_threshold(&with_fee, no_fee.amount_b, false, false);
+        assert_eq!(result.unwrap_err(), ErrorCode::AmountInAboveMaximum.into());
 
-    Ok((update, next_liquidity))
+        let result = check_other_amount_threshold(&with_fee, with_fee.amount_b - 1, false, false);
+        assert_eq!(result.unwrap_err(), ErrorCode::AmountInAboveMaximum.into());
+
+        assert!(check_other_amount_threshold(&with_fee, with_fee.amount_b, false, false).is_ok());
+    }
+
+    #[test]
+    fn test_exact_in_min_out_bound() {
+        let swap_update = exact_out_b_to_a(3000, 100_000);
+
+        let result = check_other_amount_threshold(&swap_update, 100_001, true, false);
+        assert_eq!(result.unwrap_err(), ErrorCode::AmountOutBelowMinimum.into());
+        assert!(check_other_amount_threshold(&swap_update, 100_000, true, false).is_ok());
+    }
 }
 
-fn get_next_sqrt_prices(
-    next_tick_index: i32,
-    sqrt_price_limit: u128,
-    a_to_b: bool,
-) -> (u128, u128) {
-    let next_tick_price = sqrt_price_from_tick_index(next_tick_index);
-    let next_sqrt_price_limit = if a_to_b {
-        sqrt_price_limit.max(next_tick_price)
-    } else {
-        sqrt_price_limit.min(next_tick_price)
-    };
-    (next_tick_price, next_sqrt_price_limit)
+#[cfg(test)]
+mod simulate_swap_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn tick_arrays() -> (RefCell<TickArray>, RefCell<TickArray>) {
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = -5632;
+        // Tick -640 holds the lower bound of half of the pool liquidity
+        tick_array_1.ticks[78] = Tick {
+            initialized: true,
+            liquidity_net: 500_000_000,
+            liquidity_gross: 500_000_000,
+            ..Default::default()
+        };
+        (RefCell::new(tick_array_0), RefCell::new(tick_array_1))
+    }
+
+    #[test]
+    fn test_simulate_swap_matches_swap() {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .protocol_fee_rate(300)
+            .build();
+
+        let (ta0, ta1) = tick_arrays();
+        let mut swap_tick_sequence =
+            SwapTickSequence::new(ta0.borrow_mut(), Some(ta1.borrow_mut()), None);
+        let swap_update = swap(
+            &pool,
+            &mut swap_tick_sequence,
+            50_000_000,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            0,
+        )
+        .unwrap();
+
+        let (ta0, ta1) = tick_arrays();
+        let mut swap_tick_sequence =
+            SwapTickSequence::new_read_only(ta0.borrow(), Some(ta1.borrow()), None);
+        let simulated_update = simulate_swap(
+            &pool,
+            &mut swap_tick_sequence,
+            50_000_000,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            0,
+        )
+        .unwrap();
+
+        // The swap crossed the initialized tick
+        assert!(swap_update.next_tick_index < -640);
+        assert_eq!(swap_update.next_liquidity, 500_000_000);
+        assert_eq!(simulated_update, swap_update);
+        // Simulating leaves the tick-arrays unchanged
+        assert_eq!(ta1.borrow().ticks[78], tick_arrays().1.borrow().ticks[78]);
+    }
+}
+
+#[cfg(test)]
+mod tick_crossed_count_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn swap_to_tick(tick_index: i32) -> PostSwapUpdate {
+        swap_to_tick_with_limit(tick_index, 0)
+    }
+
+    fn swap_to_tick_with_limit(tick_index: i32, max_tick_crossings: u8) -> PostSwapUpdate {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .build();
+
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = -5632;
+        // Ticks -128, -256 and -640
+        for offset in [86, 84, 78] {
+            tick_array_1.ticks[offset] = Tick {
+                initialized: true,
+                liquidity_net: 100_000_000,
+                liquidity_gross: 100_000_000,
+                ..Default::default()
+            };
+        }
+        let (ta0, ta1) = (RefCell::new(tick_array_0), RefCell::new(tick_array_1));
+        let mut swap_tick_sequence =
+            SwapTickSequence::new(ta0.borrow_mut(), Some(ta1.borrow_mut()), None)
+                .with_max_tick_crossings(max_tick_crossings);
+
+        swap(
+            &pool,
+            &mut swap_tick_sequence,
+            1_000_000_000,
+            sqrt_price_from_tick_index(tick_index),
+            true,
+            true,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tick_crossed_count() {
+        let swap_update = swap_to_tick(-1000);
+        assert_eq!(
+            swap_update.next_sqrt_price,
+            sqrt_price_from_tick_index(-1000)
+        );
+        assert_eq!(swap_update.tick_crossed_count, 3);
+        assert_eq!(swap_update.next_liquidity, 700_000_000);
+    }
+
+    #[test]
+    fn test_tick_crossed_count_price_limit_mid_array() {
+        let swap_update = swap_to_tick(-400);
+        assert_eq!(
+            swap_update.next_sqrt_price,
+            sqrt_price_from_tick_index(-400)
+        );
+        assert_eq!(swap_update.tick_crossed_count, 2);
+        assert_eq!(swap_update.next_liquidity, 800_000_000);
+    }
+
+    #[test]
+    fn test_no_tick_crossed() {
+        let swap_update = swap_to_tick(-64);
+        assert_eq!(swap_update.tick_crossed_count, 0);
+        assert_eq!(swap_update.next_liquidity, 1_000_000_000);
+    }
+
+    #[test]
+    fn test_max_tick_crossings_stops_swap() {
+        let swap_update = swap_to_tick_with_limit(-1000, 2);
+        // The swap stops at the second initialized tick
+        assert_eq!(swap_update.tick_crossed_count, 2);
+        assert_eq!(
+            swap_update.next_sqrt_price,
+            sqrt_price_from_tick_index(-256)
+        );
+        assert_eq!(swap_update.next_liquidity, 800_000_000);
+
+        let unlimited_update = swap_to_tick(-256);
+        assert_eq!(swap_update.amount_a, unlimited_update.amount_a);
+        assert_eq!(swap_update.amount_b, unlimited_update.amount_b);
+
+        let (amount_filled, amount_remaining) =
+            tick_crossing_limit_reached(&swap_update, 2, 1_000_000_000, true, true).unwrap();
+        assert_eq!(amount_filled, swap_update.amount_a);
+        assert_eq!(amount_filled + amount_remaining, 1_000_000_000);
+    }
+
+    #[test]
+    fn test_max_tick_crossings_not_reached() {
+        let swap_update = swap_to_tick_with_limit(-1000, 4);
+        assert_eq!(swap_update, swap_to_tick(-1000));
+        assert_eq!(
+            tick_crossing_limit_reached(&swap_update, 4, 1_000_000_000, true, true),
+            None
+        );
+        assert_eq!(
+            tick_crossing_limit_reached(&swap_update, 0, 1_000_000_000, true, true),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod supplemental_tick_array_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn tick_array(start_tick_index: i32) -> RefCell<TickArray> {
+        let mut tick_array = TickArray::default();
+        tick_array.start_tick_index = start_tick_index;
+        RefCell::new(tick_array)
+    }
+
+    fn swap_across(num_tick_arrays: usize) -> Result<PostSwapUpdate> {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .build();
+
+        let tick_arrays: Vec<RefCell<TickArray>> = (0..num_tick_arrays)
+            .map(|i| tick_array(-5632 * i as i32))
+            .collect();
+        let mut swap_tick_sequence = SwapTickSequence::new(
+            tick_arrays[0].borrow_mut(),
+            Some(tick_arrays[1].borrow_mut()),
+            Some(tick_arrays[2].borrow_mut()),
+        )
+        .with_supplemental_arrays(
+            tick_arrays[3..].iter().map(|ta| ta.borrow_mut()).collect(),
+            64,
+            true,
+        )?;
+
+        swap(
+            &pool,
+            &mut swap_tick_sequence,
+            10_000_000_000,
+            sqrt_price_from_tick_index(-16000),
+            true,
+            true,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_swap_across_four_tick_arrays() {
+        let swap_update = swap_across(4).unwrap();
+        assert_eq!(
+            swap_update.next_sqrt_price,
+            sqrt_price_from_tick_index(-16000)
+        );
+        assert_eq!(swap_update.next_liquidity, 1_000_000_000);
+    }
+
+    #[test]
+    fn test_swap_across_four_tick_arrays_without_supplemental_array() {
+        assert_eq!(
+            swap_across(3).err().unwrap(),
+            ErrorCode::TickArraySequenceInvalidIndex.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod directional_swap_status_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn swap_in_direction(pool: &Pool, a_to_b: bool) -> Result<PostSwapUpdate> {
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = if a_to_b { -5632 } else { 5632 };
+        let (ta0, ta1) = (RefCell::new(tick_array_0), RefCell::new(tick_array_1));
+        let mut swap_tick_sequence =
+            SwapTickSequence::new(ta0.borrow_mut(), Some(ta1.borrow_mut()), None);
+
+        let sqrt_price_limit = if a_to_b {
+            sqrt_price_from_tick_index(-64)
+        } else {
+            sqrt_price_from_tick_index(64)
+        };
+        swap(
+            pool,
+            &mut swap_tick_sequence,
+            1_000,
+            sqrt_price_limit,
+            true,
+            a_to_b,
+            0,
+        )
+    }
+
+    fn pool() -> Pool {
+        PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .build()
+    }
+
+    #[test]
+    fn test_disabled_direction_reverts() {
+        let mut pool = pool();
+        pool.update_directional_swap_status(false, true).unwrap();
+        assert_eq!(
+            swap_in_direction(&pool, true).unwrap_err(),
+            ErrorCode::SwapDirectionDisabled.into()
+        );
+        assert!(swap_in_direction(&pool, false).is_ok());
+
+        pool.update_directional_swap_status(true, false).unwrap();
+        assert!(swap_in_direction(&pool, true).is_ok());
+        assert_eq!(
+            swap_in_direction(&pool, false).unwrap_err(),
+            ErrorCode::SwapDirectionDisabled.into()
+        );
+    }
+
+    #[test]
+    fn test_both_directions_enabled_by_default() {
+        let pool = pool();
+        assert!(swap_in_direction(&pool, true).is_ok());
+        assert!(swap_in_direction(&pool, false).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod intermediate_threshold_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn swap_leg_one(liquidity: u128, amount: u64) -> PostSwapUpdate {
+        let pool = PoolBuilder::new()
+            .liquidity(liquidity)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .build();
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = -5632;
+        let (ta0, ta1) = (RefCell::new(tick_array_0), RefCell::new(tick_array_1));
+        let mut swap_tick_sequence =
+            SwapTickSequence::new(ta0.borrow_mut(), Some(ta1.borrow_mut()), None);
+
+        swap(
+            &pool,
+            &mut swap_tick_sequence,
+            amount,
+            sqrt_price_from_tick_index(-64),
+            true,
+            true,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_underdelivering_first_hop_reverts() {
+        // The thin pool reaches the price limit after a few units of output
+        let swap_update_one = swap_leg_one(1_000, 1_000_000);
+        assert!(swap_update_one.amount_b < 100);
+        assert_eq!(
+            check_intermediate_amount(&swap_update_one, true, Some(100)).unwrap_err(),
+            ErrorCode::IntermediateAmountBelowMinimum.into()
+        );
+        assert!(check_intermediate_amount(&swap_update_one, true, None).is_ok());
+    }
+
+    #[test]
+    fn test_first_hop_meeting_threshold() {
+        let swap_update_one = swap_leg_one(1_000_000_000, 1_000);
+        let threshold = Some(swap_update_one.amount_b);
+        assert!(check_intermediate_amount(&swap_update_one, true, threshold).is_ok());
+        assert_eq!(
+            check_intermediate_amount(&swap_update_one, true, Some(swap_update_one.amount_b + 1))
+                .unwrap_err(),
+            ErrorCode::IntermediateAmountBelowMinimum.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod two_hop_route_tests {
+    use super::*;
+
+    fn pool(token_mint_a: Pubkey, token_mint_b: Pubkey) -> Pool {
+        let mut pool = Pool::default();
+        pool.token_mint_a = token_mint_a;
+        pool.token_mint_b = token_mint_b;
+        pool
+    }
+
+    #[test]
+    fn test_valid_route() {
+        let (mint_a, mint_b, mint_c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        // A -> B on pool one, B -> C on pool two
+        let pool_one = pool(mint_a, mint_b);
+        let pool_two = pool(mint_c, mint_b);
+        assert!(check_two_hop_route(&pool_one, &pool_two, true, false).is_ok());
+    }
+
+    #[test]
+    fn test_circular_route_rejected() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // A -> B on pool one, B -> A on a second A/B pool
+        let pool_one = pool(mint_a, mint_b);
+        let pool_two = pool(mint_a, mint_b);
+        let result = check_two_hop_route(&pool_one, &pool_two, true, false);
+        assert_eq!(result.unwrap_err(), ErrorCode::CircularRoute.into());
+    }
+
+    #[test]
+    fn test_intermediary_mint_mismatch() {
+        let (mint_a, mint_b, mint_c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let pool_one = pool(mint_a, mint_b);
+        let pool_two = pool(mint_a, mint_c);
+        let result = check_two_hop_route(&pool_one, &pool_two, true, true);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidIntermediaryMint.into()
+        );
+    }
+
+    #[test]
+    fn test_intermediate_mint_account_constraint() {
+        let (mint_a, mint_b, mint_c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        // Output of pool one is B, so B is the intermediate mint account
+        let pool_two = pool(mint_c, mint_b);
+        assert!(is_second_hop_input_mint(&pool_two, false, mint_b));
+        // Swapping C -> B on pool two expects C as the intermediate mint
+        assert!(!is_second_hop_input_mint(&pool_two, true, mint_b));
+        assert!(!is_second_hop_input_mint(
+            &pool(mint_a, mint_c),
+            true,
+            mint_b
+        ));
+    }
+}
+
+#[cfg(test)]
+mod route_tests {
+    use super::*;
+
+    fn pool(token_mint_a: Pubkey, token_mint_b: Pubkey) -> Pool {
+        let mut pool = Pool::default();
+        pool.token_mint_a = token_mint_a;
+        pool.token_mint_b = token_mint_b;
+        pool
+    }
+
+    #[test]
+    fn test_valid_route() {
+        let mints: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        // A -> B -> C -> D -> E, alternating directions
+        let pool_one = pool(mints[0], mints[1]);
+        let pool_two = pool(mints[2], mints[1]);
+        let pool_three = pool(mints[2], mints[3]);
+        let pool_four = pool(mints[4], mints[3]);
+
+        assert!(check_route(&[(&pool_one, true)]).is_ok());
+        assert!(check_route(&[
+            (&pool_one, true),
+            (&pool_two, false),
+            (&pool_three, true),
+            (&pool_four, false),
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn test_route_length() {
+        let (mint_a, mint_b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let pool_one = pool(mint_a, mint_b);
+
+        let result = check_route(&[]);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidSwapRoute.into());
+
+        let hops = vec![(&pool_one, true); MAX_ROUTE_HOPS + 1];
+        let result = check_route(&hops);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidSwapRoute.into());
+    }
+
+    #[test]
+    fn test_route_mint_discontinuity() {
+        let (mint_a, mint_b, mint_c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let pool_one = pool(mint_a, mint_b);
+        let pool_two = pool(mint_b, mint_c);
+
+        // A -> B followed by C -> B
+        let result = check_route(&[(&pool_one, true), (&pool_two, false)]);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidIntermediaryMint.into()
+        );
+    }
+
+    #[test]
+    fn test_circular_route_rejected() {
+        let (mint_a, mint_b, mint_c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        // A -> B -> C -> A
+        let pool_one = pool(mint_a, mint_b);
+        let pool_two = pool(mint_b, mint_c);
+        let pool_three = pool(mint_a, mint_c);
+        let result = check_route(&[(&pool_one, true), (&pool_two, true), (&pool_three, false)]);
+        assert_eq!(result.unwrap_err(), ErrorCode::CircularRoute.into());
+    }
+}
+
+#[cfg(test)]
+mod expected_tick_index_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_expected_tick_index() {
+        assert!(check_expected_tick_index(1000, None, 0).is_ok());
+    }
+
+    #[test]
+    fn test_within_deviation() {
+        assert!(check_expected_tick_index(100, Some(100), 0).is_ok());
+        assert!(check_expected_tick_index(110, Some(100), 10).is_ok());
+        assert!(check_expected_tick_index(90, Some(100), 10).is_ok());
+    }
+
+    #[test]
+    fn test_pool_tick_moved_beyond_deviation() {
+        let result = check_expected_tick_index(111, Some(100), 10);
+        assert_eq!(result.unwrap_err(), ErrorCode::PoolTickMoved.into());
+
+        let result = check_expected_tick_index(-89, Some(-100), 10);
+        assert_eq!(result.unwrap_err(), ErrorCode::PoolTickMoved.into());
+
+        let result = check_expected_tick_index(MAX_TICK_INDEX, Some(MIN_TICK_INDEX), u16::MAX);
+        assert_eq!(result.unwrap_err(), ErrorCode::PoolTickMoved.into());
+    }
+}
+
+#[cfg(test)]
+mod tick_limit_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn pool() -> Pool {
+        PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .build()
+    }
+
+    #[test]
+    fn test_without_tick_limit() {
+        assert_eq!(
+            resolve_sqrt_price_limit(1 << 64, MIN_SQRT_PRICE_X64, None, true).unwrap(),
+            MIN_SQRT_PRICE_X64
+        );
+    }
+
+    #[test]
+    fn test_more_restrictive_limit_applies() {
+        let tick_price = sqrt_price_from_tick_index(-128);
+        assert_eq!(
+            resolve_sqrt_price_limit(1 << 64, MIN_SQRT_PRICE_X64, Some(-128), true).unwrap(),
+            tick_price
+        );
+        let sqrt_price_limit = sqrt_price_from_tick_index(-64);
+        assert_eq!(
+            resolve_sqrt_price_limit(1 << 64, sqrt_price_limit, Some(-128), true).unwrap(),
+            sqrt_price_limit
+        );
+
+        let tick_price = sqrt_price_from_tick_index(128);
+        assert_eq!(
+            resolve_sqrt_price_limit(1 << 64, MAX_SQRT_PRICE_X64, Some(128), false).unwrap(),
+            tick_price
+        );
+        let sqrt_price_limit = sqrt_price_from_tick_index(64);
+        assert_eq!(
+            resolve_sqrt_price_limit(1 << 64, sqrt_price_limit, Some(128), false).unwrap(),
+            sqrt_price_limit
+        );
+    }
+
+    #[test]
+    fn test_tick_limit_out_of_bounds() {
+        let result =
+            resolve_sqrt_price_limit(1 << 64, MIN_SQRT_PRICE_X64, Some(MIN_TICK_INDEX - 1), true);
+        assert_eq!(result.unwrap_err(), ErrorCode::TickIndexOutOfBounds.into());
+        let result =
+            resolve_sqrt_price_limit(1 << 64, MAX_SQRT_PRICE_X64, Some(MAX_TICK_INDEX + 1), false);
+        assert_eq!(result.unwrap_err(), ErrorCode::TickIndexOutOfBounds.into());
+    }
+
+    #[test]
+    fn test_tick_limit_wrong_side_of_current_price() {
+        let result = resolve_sqrt_price_limit(1 << 64, MIN_SQRT_PRICE_X64, Some(64), true);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidSqrtPriceLimitDirection.into()
+        );
+        let result = resolve_sqrt_price_limit(1 << 64, MAX_SQRT_PRICE_X64, Some(-64), false);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidSqrtPriceLimitDirection.into()
+        );
+    }
+
+    #[test]
+    fn test_swap_halts_at_tick_limit() {
+        let pool = pool();
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = -5632;
+        let (ta0, ta1) = (RefCell::new(tick_array_0), RefCell::new(tick_array_1));
+        let mut swap_tick_sequence =
+            SwapTickSequence::new(ta0.borrow_mut(), Some(ta1.borrow_mut()), None);
+
+        let sqrt_price_limit =
+            resolve_sqrt_price_limit(pool.sqrt_price, MIN_SQRT_PRICE_X64, Some(-100), true)
+                .unwrap();
+        let swap_update = swap(
+            &pool,
+            &mut swap_tick_sequence,
+            1_000_000_000,
+            sqrt_price_limit,
+            true,
+            true,
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            swap_update.next_sqrt_price,
+            sqrt_price_from_tick_index(-100)
+        );
+        assert_eq!(swap_update.next_tick_index, -100);
+        assert!(swap_update.amount_a < 1_000_000_000);
+    }
+}
+
+#[cfg(test)]
+mod packed_swap_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn tick_arrays(a_to_b: bool) -> (RefCell<TickArray>, RefCell<TickArray>) {
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+
+        // A single initialized tick 640 ticks away from the current tick, all other slots of the
+        // arrays are uninitialized
+        if a_to_b {
+            tick_array_1.start_tick_index = -5632;
+            tick_array_1.ticks[78].initialized = true;
+            tick_array_1.ticks[78].liquidity_net = 500_000_000;
+            tick_array_1.ticks[78].liquidity_gross = 500_000_000;
+        } else {
+            tick_array_1.start_tick_index = 5632;
+            tick_array_0.ticks[10].initialized = true;
+            tick_array_0.ticks[10].liquidity_net = -500_000_000;
+            tick_array_0.ticks[10].liquidity_gross = 500_000_000;
+        }
+        (RefCell::new(tick_array_0), RefCell::new(tick_array_1))
+    }
+
+    fn assert_packed_swap_matches(a_to_b: bool, sqrt_price_limit: u128) {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .protocol_fee_rate(300)
+            .build();
+
+        let (ta0, ta1) = tick_arrays(a_to_b);
+        let mut swap_tick_sequence =
+            SwapTickSequence::new_read_only(ta0.borrow(), Some(ta1.borrow()), None);
+        let mut packed_tick_sequence = swap_tick_sequence.pack_for_direction(64, a_to_b).unwrap();
+        assert_eq!(packed_tick_sequence.len(), 1);
+
+        let swap_update = swap(
+            &pool,
+            &mut swap_tick_sequence,
+            50_000_000,
+            sqrt_price_limit,
+            true,
+            a_to_b,
+            0,
+        )
+        .unwrap();
+        let packed_update = swap_packed(
+            &pool,
+            &mut packed_tick_sequence,
+            50_000_000,
+            sqrt_price_limit,
+            true,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(swap_update.tick_crossed_count, 1);
+        assert_eq!(packed_update, swap_update);
+    }
+
+    #[test]
+    fn test_packed_swap_matches_swap_a_to_b() {
+        assert_packed_swap_matches(true, MIN_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_packed_swap_matches_swap_b_to_a() {
+        assert_packed_swap_matches(false, MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_packed_swap_past_end_of_sequence() {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .build();
+
+        let (ta0, _) = tick_arrays(true);
+        let swap_tick_sequence = SwapTickSequence::new_read_only(ta0.borrow(), None, None);
+        let mut packed_tick_sequence = swap_tick_sequence.pack_for_direction(64, false).unwrap();
+        assert!(packed_tick_sequence.is_empty());
+
+        let result = swap_packed(
+            &pool,
+            &mut packed_tick_sequence,
+            u64::MAX / 2,
+            MAX_SQRT_PRICE_X64,
+            true,
+            0,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickArraySequenceInvalidIndex.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod two_hop_fee_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn swap_a_to_b(amount: u64) -> PostSwapUpdate {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .protocol_fee_rate(300)
+            .build();
+
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = -5632;
+        tick_array_1.ticks[78].initialized = true;
+        tick_array_1.ticks[78].liquidity_net = 500_000_000;
+        tick_array_1.ticks[78].liquidity_gross = 500_000_000;
+        let tick_array_0 = RefCell::new(tick_array_0);
+        let tick_array_1 = RefCell::new(tick_array_1);
+        let mut swap_tick_sequence = SwapTickSequence::new(
+            tick_array_0.borrow_mut(),
+            Some(tick_array_1.borrow_mut()),
+            None,
+        );
+
+        swap(
+            &pool,
+            &mut swap_tick_sequence,
+            amount,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            0,
+        )
+        .unwrap()
+    }
+
+    // Each step of a swap rounds its fee up, so the total fee may exceed the fee rate of the
+    // input amount by a few units
+    fn assert_fee_charged(swap_update: &PostSwapUpdate, amount_in: u64) {
+        let steps = swap_update.tick_crossed_count as u64 + 1;
+        let expected_fee = amount_in * 3000 / 1_000_000;
+        assert!(swap_update.fee >= expected_fee);
+        assert!(swap_update.fee <= expected_fee + 2 * steps);
+
+        let expected_protocol_fee = swap_update.fee * 300 / 10_000;
+        assert!(swap_update.next_protocol_fee + steps >= expected_protocol_fee);
+        assert!(swap_update.next_protocol_fee <= expected_protocol_fee);
+    }
+
+    #[test]
+    fn test_fee_is_sum_of_swap_steps() {
+        let swap_update = swap_a_to_b(50_000_000);
+
+        // The swap crossed a tick, so it took more than one step
+        assert_eq!(swap_update.tick_crossed_count, 1);
+        assert_fee_charged(&swap_update, swap_update.amount_a);
+    }
+
+    #[test]
+    fn test_per_leg_fees_of_two_hop_swap() {
+        let swap_update_one = swap_a_to_b(50_000_000);
+        let swap_update_two = swap_a_to_b(swap_update_one.amount_b);
+
+        // Each leg reports the fee in its own input token
+        assert_fee_charged(&swap_update_one, swap_update_one.amount_a);
+        assert_fee_charged(&swap_update_two, swap_update_two.amount_a);
+        assert_eq!(swap_update_two.amount_a, swap_update_one.amount_b);
+    }
+}
+
+#[cfg(test)]
+mod dex_math_parity_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    // Liquidity of 1_000_000_000 between the initialized ticks -640 and 640, which each remove
+    // half of it when crossed
+    fn assert_swap_matches_dex_math(amount: u64, amount_specified_is_input: bool, a_to_b: bool) {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .protocol_fee_rate(300)
+            .build();
+
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        if a_to_b {
+            tick_array_1.start_tick_index = -5632;
+            tick_array_1.ticks[78].initialized = true;
+            tick_array_1.ticks[78].liquidity_net = 500_000_000;
+        } else {
+            tick_array_1.start_tick_index = 5632;
+            tick_array_0.ticks[10].initialized = true;
+            tick_array_0.ticks[10].liquidity_net = -500_000_000;
+        }
+        let tick_array_0 = RefCell::new(tick_array_0);
+        let tick_array_1 = RefCell::new(tick_array_1);
+        let mut swap_tick_sequence = SwapTickSequence::new_read_only(
+            tick_array_0.borrow(),
+            Some(tick_array_1.borrow()),
+            None,
+        );
+
+        let sqrt_price_limit = if a_to_b {
+            MIN_SQRT_PRICE_X64
+        } else {
+            MAX_SQRT_PRICE_X64
+        };
+        let swap_update = swap(
+            &pool,
+            &mut swap_tick_sequence,
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            0,
+        )
+        .unwrap();
+
+        let state = dex_math::PoolState {
+            sqrt_price: pool.sqrt_price,
+            tick_current_index: pool.tick_current_index,
+            liquidity: pool.liquidity,
+            fee_rate: pool.fee_rate,
+            protocol_fee_rate: pool.protocol_fee_rate,
+            fee_growth_global_a: pool.fee_growth_global_a,
+            fee_growth_global_b: pool.fee_growth_global_b,
+        };
+        let ticks = [
+            dex_math::TickState {
+                index: -640,
+                liquidity_net: 500_000_000,
+            },
+            dex_math::TickState {
+                index: 640,
+                liquidity_net: -500_000_000,
+            },
+        ];
+        let params = dex_math::SwapParams {
+            amount,
+            sqrt_price_limit,
+            amount_specified_is_input,
+            a_to_b,
+            ..Default::default()
+        };
+        let result = dex_math::simulate_swap(&state, &ticks, params).unwrap();
+
+        assert_eq!(swap_update.tick_crossed_count, 1);
+        assert_eq!(result.amount_a, swap_update.amount_a);
+        assert_eq!(result.amount_b, swap_update.amount_b);
+        assert_eq!(result.next_liquidity, swap_update.next_liquidity);
+        assert_eq!(result.next_tick_index, swap_update.next_tick_index);
+        assert_eq!(result.next_sqrt_price, swap_update.next_sqrt_price);
+        assert_eq!(
+            result.next_fee_growth_global,
+            swap_update.next_fee_growth_global
+        );
+        assert_eq!(result.protocol_fee, swap_update.next_protocol_fee);
+        assert_eq!(result.fee, swap_update.fee);
+        assert_eq!(result.tick_crossed_count, swap_update.tick_crossed_count);
+    }
+
+    #[test]
+    fn test_exact_in_matches_dex_math() {
+        assert_swap_matches_dex_math(50_000_000, true, true);
+        assert_swap_matches_dex_math(50_000_000, true, false);
+    }
+
+    #[test]
+    fn test_exact_out_matches_dex_math() {
+        assert_swap_matches_dex_math(40_000_000, false, true);
+        assert_swap_matches_dex_math(40_000_000, false, false);
+    }
+}
+
+#[cfg(test)]
+mod referral_fee_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn swap_a_to_b(amount: u64, referral_fee_rate: u16) -> PostSwapUpdate {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .protocol_fee_rate(300)
+            .build();
+
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = -5632;
+        let tick_array_0 = RefCell::new(tick_array_0);
+        let tick_array_1 = RefCell::new(tick_array_1);
+        let mut swap_tick_sequence = SwapTickSequence::new(
+            tick_array_0.borrow_mut(),
+            Some(tick_array_1.borrow_mut()),
+            None,
+        );
+
+        swap_with_referral(
+            &pool,
+            &mut swap_tick_sequence,
+            amount,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            0,
+            referral_fee_rate,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_no_referral_matches_swap() {
+        let swap_update = swap_a_to_b(1_000_000, 0);
+        assert_eq!(swap_update.next_referral_fee, 0);
+        assert_eq!(swap_update.fee, 3_000);
+        assert_eq!(swap_update.next_protocol_fee, 90);
+    }
+
+    #[test]
+    fn test_referral_fee_taken_from_lp_fee() {
+        let without_referral = swap_a_to_b(1_000_000, 0);
+        let with_referral = swap_a_to_b(1_000_000, 1_000);
+
+        // The trader pays the same amounts and the protocol fee is unchanged
+        assert_eq!(with_referral.amount_a, without_referral.amount_a);
+        assert_eq!(with_referral.amount_b, without_referral.amount_b);
+        assert_eq!(with_referral.fee, without_referral.fee);
+        assert_eq!(
+            with_referral.next_protocol_fee,
+            without_referral.next_protocol_fee
+        );
+
+        // 10% of the LP fee of 2_910 goes to the referral
+        assert_eq!(with_referral.next_referral_fee, 291);
+        let lp_fee = |swap_update: &PostSwapUpdate| {
+            ((swap_update.next_fee_growth_global * 1_000_000_000) >> 64) as u64
+        };
+        assert_eq!(lp_fee(&without_referral), 2_909);
+        assert_eq!(lp_fee(&with_referral), 2_618);
+        assert!(
+            lp_fee(&with_referral)
+                + with_referral.next_protocol_fee
+                + with_referral.next_referral_fee
+                <= with_referral.fee
+        );
+    }
+}
+
+#[cfg(test)]
+mod fee_rate_tests {
+    use super::*;
+    use crate::state::pool_builder::PoolBuilder;
+    use std::cell::RefCell;
+
+    fn swap_a_to_b(amount: u64, fee_rate: u16) -> PostSwapUpdate {
+        let pool = PoolBuilder::new()
+            .liquidity(1_000_000_000)
+            .sqrt_price(1 << 64)
+            .tick_current_index(0)
+            .tick_spacing(64)
+            .fee_rate(3000)
+            .protocol_fee_rate(300)
+            .build();
+
+        let mut tick_array_0 = TickArray::default();
+        tick_array_0.start_tick_index = 0;
+        let mut tick_array_1 = TickArray::default();
+        tick_array_1.start_tick_index = -5632;
+        let tick_array_0 = RefCell::new(tick_array_0);
+        let tick_array_1 = RefCell::new(tick_array_1);
+        let mut swap_tick_sequence = SwapTickSequence::new(
+            tick_array_0.borrow_mut(),
+            Some(tick_array_1.borrow_mut()),
+            None,
+        );
+
+        swap_with_fee_rate(
+            &pool,
+            &mut swap_tick_sequence,
+            amount,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            0,
+            fee_rate,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pool_fee_rate_matches_swap() {
+        let swap_update = swap_a_to_b(1_000_000, 3000);
+        assert_eq!(swap_update.fee, 3_000);
+        assert_eq!(swap_update.next_protocol_fee, 90);
+    }
+
+    #[test]
+    fn test_rebated_fee_rate() {
+        let without_rebate = swap_a_to_b(1_000_000, 3000);
+        let with_rebate = swap_a_to_b(1_000_000, apply_lp_fee_rebate(3000, 5_000));
+
+        assert_eq!(with_rebate.fee, 1_500);
+        assert_eq!(with_rebate.next_protocol_fee, 45);
+        // The trader swaps the same input for more output
+        assert_eq!(with_rebate.amount_a, without_rebate.amount_a);
+        assert!(with_rebate.amount_b > without_rebate.amount_b);
+    }
 }
@@ -53,11 +53,12 @@ pub fn next_tick_modify_liquidity_update(
 
     let liquidity_gross = add_liquidity_delta(tick.liquidity_gross, liquidity_delta)?;
 
-    // Update to an uninitialized tick if remaining liquidity is being removed
+    // Uninitialize transition: the tick flips off once the remaining liquidity is removed
     if liquidity_gross == 0 {
         return Ok(TickUpdate::default());
     }
 
+    // Initialize transition: the tick flips on the first time liquidity_gross becomes nonzero
     let (fee_growth_outside_a, fee_growth_outside_b, reward_growths_outside) =
         if tick.liquidity_gross == 0 {
             // By convention, assume all prior growth happened below the tick
@@ -201,3 +202,67 @@ pub fn next_reward_growths_inside(
 
     reward_growths_inside
 }
+
+#[cfg(test)]
+mod tick_flip_tests {
+    use super::*;
+    use crate::state::TickArray;
+
+    const TICK_SPACING: u16 = 8;
+    const TICK_INDEX: i32 = 16;
+
+    fn modify_liquidity(array: &mut TickArray, liquidity_delta: i128) -> bool {
+        let tick = array.get_tick(TICK_INDEX, TICK_SPACING).unwrap();
+        let update = next_tick_modify_liquidity_update(
+            tick,
+            TICK_INDEX,
+            0,
+            100,
+            100,
+            &[PoolRewardInfo::default(); NUM_REWARDS],
+            liquidity_delta,
+            false,
+        )
+        .unwrap();
+        array
+            .update_tick(TICK_INDEX, TICK_SPACING, &update)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_tick_flips_on_and_off() {
+        let mut array = TickArray::default();
+
+        // Adding liquidity to a fresh tick flips it on
+        assert!(modify_liquidity(&mut array, 1000));
+        let tick = array.get_tick(TICK_INDEX, TICK_SPACING).unwrap();
+        assert!(tick.initialized);
+
+        // Adding to an initialized tick does not flip it
+        assert!(!modify_liquidity(&mut array, 500));
+
+        // Partially removing liquidity keeps the tick on
+        assert!(!modify_liquidity(&mut array, -1000));
+        let tick = array.get_tick(TICK_INDEX, TICK_SPACING).unwrap();
+        assert!(tick.initialized);
+        assert_eq!({ tick.liquidity_gross }, 500);
+
+        // Fully removing liquidity flips the tick off
+        assert!(modify_liquidity(&mut array, -500));
+        let tick = array.get_tick(TICK_INDEX, TICK_SPACING).unwrap();
+        assert!(!tick.initialized);
+        assert_eq!({ tick.liquidity_gross }, 0);
+    }
+
+    #[test]
+    fn test_zero_liquidity_delta_does_not_flip() {
+        let mut array = TickArray::default();
+        assert!(!modify_liquidity(&mut array, 0));
+        assert!(
+            !array
+                .get_tick(TICK_INDEX, TICK_SPACING)
+                .unwrap()
+                .initialized
+        );
+    }
+}
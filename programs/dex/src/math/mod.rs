@@ -1,14 +1,11 @@
-pub mod bit_math;
 pub mod bn;
-pub mod liquidity_math;
-pub mod swap_math;
-pub mod tick_math;
-pub mod token_math;
-pub mod u256_math;
+
+pub use dex_math::{bit_math, liquidity_math, quote, swap_math, tick_math, token_math, u256_math};
 
 pub use bit_math::*;
 pub use bn::*;
 pub use liquidity_math::*;
+pub use quote::*;
 pub use swap_math::*;
 pub use tick_math::*;
 pub use token_math::*;
@@ -1,18 +1,86 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::TRANSACTION_LEVEL_STACK_HEIGHT;
 
-use crate::{errors::ErrorCode, math::MAX_PROTOCOL_FEE_RATE};
+use crate::{
+    errors::ErrorCode,
+    math::{MAX_PROTOCOL_FEE_RATE, MAX_REFERRAL_FEE_RATE},
+};
+
+// Bits of PoolsConfig.feature_flags, a set bit enables the feature
+pub const FEATURE_POOL_NAME_REGISTRY: u64 = 1 << 0;
+pub const FEATURE_PROTOCOL_LIQUIDITY: u64 = 1 << 1;
+pub const FEATURE_PARAMETER_CHANGES: u64 = 1 << 2;
+pub const FEATURE_PROTOCOL_FEE_STREAMING: u64 = 1 << 3;
+// New and migrated configs start with every feature enabled
+pub const FEATURE_FLAGS_ALL: u64 = u64::MAX;
+
+// Authority types reported by AuthorityRotatedEvent
+pub const AUTHORITY_TYPE_FEE: u8 = 0;
+pub const AUTHORITY_TYPE_COLLECT_PROTOCOL_FEES: u8 = 1;
+pub const AUTHORITY_TYPE_REWARD_EMISSIONS_SUPER: u8 = 2;
+pub const AUTHORITY_TYPE_OPERATIONS: u8 = 3;
+pub const AUTHORITY_TYPE_CONFIG_EXTENSION: u8 = 4;
+pub const AUTHORITY_TYPE_TOKEN_BADGE: u8 = 5;
+pub const AUTHORITY_TYPE_FEE_TIER: u8 = 6;
 
 #[account]
+#[derive(Default)]
 pub struct PoolsConfig {
     pub fee_authority: Pubkey,
     pub collect_protocol_fees_authority: Pubkey,
     pub reward_emissions_super_authority: Pubkey,
 
     pub default_protocol_fee_rate: u16,
+
+    // If enabled, swaps transfer protocol fees above min_stream_amount directly to the
+    // protocol treasury instead of accumulating them in the pool
+    pub protocol_fee_streaming_enabled: bool,
+    pub min_stream_amount: u64,
+
+    // If enabled, privileged fee operations cannot be invoked through CPI
+    pub require_direct_call: bool,
+
+    // Can create fee tiers and token badges, but cannot change fees or authorities. The default
+    // pubkey if no operations authority is set.
+    pub operations_authority: Pubkey,
+
+    // Bitmask of the FEATURE_* instructions enabled for the config
+    pub feature_flags: u64,
+
+    // Maximum share of the LP fee a swap can route to a referral account, in basis points. Zero
+    // disables referral fees.
+    pub max_referral_fee_rate: u16,
+
+    // Range the protocol fee rates of the config and its pools must stay within. Only changed
+    // through a timelocked parameter change.
+    pub min_protocol_fee_rate: u16,
+    pub max_protocol_fee_rate: u16,
+
+    // If locked, only the fee tier authority can create fee tiers. Locking without a fee tier
+    // authority stops fee tier creation.
+    pub fee_tier_creation_locked: bool,
+    pub fee_tier_authority: Pubkey,
+
+    // Position holding the protocol-owned liquidity. The default pubkey if none is managed.
+    pub protocol_liquidity_position: Pubkey,
 }
 
 impl PoolsConfig {
-    pub const LEN: usize = 8 + 96 + 4;
+    pub const LEN: usize = PoolsConfig::LEN_WITHOUT_PROTOCOL_LIQUIDITY_POSITION + 32;
+    // Size of configs created before the protocol liquidity position was added
+    pub const LEN_WITHOUT_PROTOCOL_LIQUIDITY_POSITION: usize =
+        PoolsConfig::LEN_WITHOUT_FEE_TIER_AUTHORITY + 1 + 32;
+    // Size of configs created before the fee tier creation lock was added
+    pub const LEN_WITHOUT_FEE_TIER_AUTHORITY: usize =
+        PoolsConfig::LEN_WITHOUT_PROTOCOL_FEE_RATE_BOUNDS + 4;
+    // Size of configs created before the protocol fee rate bounds were added
+    pub const LEN_WITHOUT_PROTOCOL_FEE_RATE_BOUNDS: usize =
+        PoolsConfig::LEN_WITHOUT_REFERRAL_FEE + 2;
+    // Size of configs created before max_referral_fee_rate was added
+    pub const LEN_WITHOUT_REFERRAL_FEE: usize = 8 + 96 + 4 + 1 + 8 + 1 + 32 + 8;
+
+    pub const FEATURE_FLAGS_OFFSET: usize = 8 + 96 + 2 + 1 + 8 + 1 + 32;
+    pub const MAX_PROTOCOL_FEE_RATE_OFFSET: usize = PoolsConfig::FEATURE_FLAGS_OFFSET + 8 + 2 + 2;
 
     pub fn update_fee_authority(&mut self, fee_authority: Pubkey) {
         self.fee_authority = fee_authority;
@@ -35,8 +103,59 @@ impl PoolsConfig {
         self.fee_authority = fee_authority;
         self.collect_protocol_fees_authority = collect_protocol_fees_authority;
         self.reward_emissions_super_authority = reward_emissions_super_authority;
+        self.reset_protocol_fee_rate_bounds();
         self.update_default_protocol_fee_rate(default_protocol_fee_rate)?;
+        self.protocol_fee_streaming_enabled = false;
+        self.min_stream_amount = 0;
+        self.require_direct_call = false;
+        self.operations_authority = Pubkey::default();
+        self.feature_flags = FEATURE_FLAGS_ALL;
+        self.max_referral_fee_rate = 0;
+        self.fee_tier_creation_locked = false;
+        self.fee_tier_authority = Pubkey::default();
+        self.protocol_liquidity_position = Pubkey::default();
+
+        Ok(())
+    }
+
+    pub fn update_operations_authority(&mut self, operations_authority: Pubkey) {
+        self.operations_authority = operations_authority;
+    }
 
+    /// Returns true if the key may perform operational tasks, which both the fee authority and the
+    /// operations authority can.
+    pub fn is_operations_authority(&self, authority: Pubkey) -> bool {
+        authority == self.fee_authority
+            || (self.operations_authority != Pubkey::default()
+                && authority == self.operations_authority)
+    }
+
+    pub fn update_fee_tier_creation_locked(&mut self, fee_tier_creation_locked: bool) {
+        self.fee_tier_creation_locked = fee_tier_creation_locked;
+    }
+
+    pub fn update_fee_tier_authority(&mut self, fee_tier_authority: Pubkey) {
+        self.fee_tier_authority = fee_tier_authority;
+    }
+
+    /// Returns true if the key may create fee tiers. While fee tier creation is locked only the
+    /// fee tier authority can, otherwise the operations authorities can.
+    pub fn is_fee_tier_creator(&self, authority: Pubkey) -> bool {
+        if self.fee_tier_creation_locked {
+            return self.fee_tier_authority != Pubkey::default()
+                && authority == self.fee_tier_authority;
+        }
+        self.is_operations_authority(authority)
+    }
+
+    pub fn update_feature_flags(&mut self, feature_flags: u64) {
+        self.feature_flags = feature_flags;
+    }
+
+    pub fn verify_feature_enabled(&self, feature: u64) -> Result<()> {
+        if self.feature_flags & feature != feature {
+            return Err(ErrorCode::FeatureDisabled.into());
+        }
         Ok(())
     }
 
@@ -54,8 +173,397 @@ impl PoolsConfig {
         if default_protocol_fee_rate > MAX_PROTOCOL_FEE_RATE {
             return Err(ErrorCode::ProtocolFeeRateMaxExceeded.into());
         }
+        self.verify_protocol_fee_rate(default_protocol_fee_rate)?;
         self.default_protocol_fee_rate = default_protocol_fee_rate;
 
         Ok(())
     }
+
+    /// Allow every protocol fee rate up to MAX_PROTOCOL_FEE_RATE.
+    pub fn reset_protocol_fee_rate_bounds(&mut self) {
+        self.min_protocol_fee_rate = 0;
+        self.max_protocol_fee_rate = MAX_PROTOCOL_FEE_RATE;
+    }
+
+    pub fn update_protocol_fee_rate_bounds(
+        &mut self,
+        min_protocol_fee_rate: u16,
+        max_protocol_fee_rate: u16,
+    ) -> Result<()> {
+        validate_protocol_fee_rate_bounds(min_protocol_fee_rate, max_protocol_fee_rate)?;
+        self.min_protocol_fee_rate = min_protocol_fee_rate;
+        self.max_protocol_fee_rate = max_protocol_fee_rate;
+
+        Ok(())
+    }
+
+    /// Check that a protocol fee rate of the config or one of its pools is within the bounds.
+    /// The error data holds the provided rate and the bounds.
+    pub fn verify_protocol_fee_rate(&self, protocol_fee_rate: u16) -> Result<()> {
+        if protocol_fee_rate < self.min_protocol_fee_rate
+            || protocol_fee_rate > self.max_protocol_fee_rate
+        {
+            return Err(error!(ErrorCode::ProtocolFeeRateOutOfBounds).with_values((
+                protocol_fee_rate,
+                format!(
+                    "[{}, {}]",
+                    self.min_protocol_fee_rate, self.max_protocol_fee_rate
+                ),
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn update_protocol_liquidity_position(&mut self, protocol_liquidity_position: Pubkey) {
+        self.protocol_liquidity_position = protocol_liquidity_position;
+    }
+
+    pub fn enable_protocol_fee_streaming(&mut self, min_stream_amount: u64) {
+        self.protocol_fee_streaming_enabled = true;
+        self.min_stream_amount = min_stream_amount;
+    }
+
+    pub fn disable_protocol_fee_streaming(&mut self) {
+        self.protocol_fee_streaming_enabled = false;
+    }
+
+    pub fn update_require_direct_call(&mut self, require_direct_call: bool) {
+        self.require_direct_call = require_direct_call;
+    }
+
+    /// Verify that a privileged instruction was invoked directly by the transaction, if required.
+    /// `stack_height` is the invocation depth of the instruction, as returned by
+    /// `get_stack_height`.
+    pub fn verify_direct_call(&self, stack_height: usize) -> Result<()> {
+        if self.require_direct_call && stack_height > TRANSACTION_LEVEL_STACK_HEIGHT {
+            return Err(ErrorCode::DirectCallRequired.into());
+        }
+        Ok(())
+    }
+
+    pub fn update_max_referral_fee_rate(&mut self, max_referral_fee_rate: u16) -> Result<()> {
+        if max_referral_fee_rate > MAX_REFERRAL_FEE_RATE {
+            return Err(ErrorCode::ReferralFeeRateMaxExceeded.into());
+        }
+        self.max_referral_fee_rate = max_referral_fee_rate;
+
+        Ok(())
+    }
+
+    pub fn verify_referral_fee_rate(&self, referral_fee_rate: u16) -> Result<()> {
+        if referral_fee_rate > self.max_referral_fee_rate {
+            return Err(ErrorCode::ReferralFeeRateMaxExceeded.into());
+        }
+        Ok(())
+    }
+
+    // Protocol fees at or below min_stream_amount keep accumulating in the pool
+    pub fn should_stream_protocol_fee(&self, protocol_fee: u64) -> bool {
+        self.protocol_fee_streaming_enabled && protocol_fee > self.min_stream_amount
+    }
+}
+
+pub fn validate_protocol_fee_rate_bounds(
+    min_protocol_fee_rate: u16,
+    max_protocol_fee_rate: u16,
+) -> Result<()> {
+    if min_protocol_fee_rate > max_protocol_fee_rate
+        || max_protocol_fee_rate > MAX_PROTOCOL_FEE_RATE
+    {
+        return Err(ErrorCode::InvalidProtocolFeeRateBounds.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod protocol_fee_streaming_tests {
+    use super::*;
+
+    #[test]
+    fn test_should_stream_protocol_fee() {
+        let mut config = PoolsConfig::default();
+        assert!(!config.should_stream_protocol_fee(1_000));
+
+        config.enable_protocol_fee_streaming(100);
+        assert!(!config.should_stream_protocol_fee(0));
+        assert!(!config.should_stream_protocol_fee(100));
+        assert!(config.should_stream_protocol_fee(101));
+
+        config.disable_protocol_fee_streaming();
+        assert!(!config.should_stream_protocol_fee(101));
+    }
+}
+
+#[cfg(test)]
+mod direct_call_tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_direct_call() {
+        let mut config = PoolsConfig::default();
+        assert!(config
+            .verify_direct_call(TRANSACTION_LEVEL_STACK_HEIGHT)
+            .is_ok());
+        assert!(config
+            .verify_direct_call(TRANSACTION_LEVEL_STACK_HEIGHT + 1)
+            .is_ok());
+
+        config.update_require_direct_call(true);
+        assert!(config
+            .verify_direct_call(TRANSACTION_LEVEL_STACK_HEIGHT)
+            .is_ok());
+        assert_eq!(
+            config
+                .verify_direct_call(TRANSACTION_LEVEL_STACK_HEIGHT + 1)
+                .unwrap_err(),
+            ErrorCode::DirectCallRequired.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod operations_authority_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_operations_authority() {
+        let mut config = PoolsConfig::default();
+        let fee_authority = Pubkey::new_unique();
+        let operations_authority = Pubkey::new_unique();
+        config.update_fee_authority(fee_authority);
+
+        assert!(config.is_operations_authority(fee_authority));
+        assert!(!config.is_operations_authority(operations_authority));
+        assert!(!config.is_operations_authority(Pubkey::default()));
+
+        config.update_operations_authority(operations_authority);
+        assert!(config.is_operations_authority(fee_authority));
+        assert!(config.is_operations_authority(operations_authority));
+        assert!(!config.is_operations_authority(Pubkey::new_unique()));
+    }
+}
+
+#[cfg(test)]
+mod feature_flags_tests {
+    use super::*;
+
+    fn config() -> PoolsConfig {
+        let mut config = PoolsConfig::default();
+        config
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                300,
+            )
+            .unwrap();
+        config
+    }
+
+    #[test]
+    fn test_features_enabled_on_initialize() {
+        let config = config();
+        assert_eq!(config.feature_flags, FEATURE_FLAGS_ALL);
+        assert!(config
+            .verify_feature_enabled(FEATURE_POOL_NAME_REGISTRY)
+            .is_ok());
+        assert!(config
+            .verify_feature_enabled(FEATURE_PROTOCOL_FEE_STREAMING)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_disabled_feature_reverts() {
+        let mut config = config();
+        config.update_feature_flags(FEATURE_FLAGS_ALL & !FEATURE_PARAMETER_CHANGES);
+        assert_eq!(
+            config
+                .verify_feature_enabled(FEATURE_PARAMETER_CHANGES)
+                .unwrap_err(),
+            ErrorCode::FeatureDisabled.into()
+        );
+        assert!(config
+            .verify_feature_enabled(FEATURE_PROTOCOL_LIQUIDITY)
+            .is_ok());
+
+        config.update_feature_flags(FEATURE_FLAGS_ALL);
+        assert!(config
+            .verify_feature_enabled(FEATURE_PARAMETER_CHANGES)
+            .is_ok());
+    }
+}
+
+#[cfg(test)]
+mod referral_fee_rate_tests {
+    use super::*;
+
+    #[test]
+    fn test_referral_fees_disabled_on_initialize() {
+        let mut pools_config = PoolsConfig::default();
+        pools_config
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                300,
+            )
+            .unwrap();
+
+        assert!(pools_config.verify_referral_fee_rate(0).is_ok());
+        assert_eq!(
+            pools_config.verify_referral_fee_rate(1).unwrap_err(),
+            ErrorCode::ReferralFeeRateMaxExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_referral_fee_rate_capped() {
+        let mut pools_config = PoolsConfig::default();
+        assert_eq!(
+            pools_config
+                .update_max_referral_fee_rate(MAX_REFERRAL_FEE_RATE + 1)
+                .unwrap_err(),
+            ErrorCode::ReferralFeeRateMaxExceeded.into()
+        );
+
+        pools_config.update_max_referral_fee_rate(1_000).unwrap();
+        assert!(pools_config.verify_referral_fee_rate(1_000).is_ok());
+        assert_eq!(
+            pools_config.verify_referral_fee_rate(1_001).unwrap_err(),
+            ErrorCode::ReferralFeeRateMaxExceeded.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod protocol_fee_rate_bounds_tests {
+    use super::*;
+
+    fn pools_config() -> PoolsConfig {
+        let mut pools_config = PoolsConfig::default();
+        pools_config
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                300,
+            )
+            .unwrap();
+        pools_config
+    }
+
+    #[test]
+    fn test_initialize_allows_all_rates() {
+        let pools_config = pools_config();
+        assert_eq!(pools_config.min_protocol_fee_rate, 0);
+        assert_eq!(pools_config.max_protocol_fee_rate, MAX_PROTOCOL_FEE_RATE);
+        assert!(pools_config.verify_protocol_fee_rate(0).is_ok());
+        assert!(pools_config
+            .verify_protocol_fee_rate(MAX_PROTOCOL_FEE_RATE)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rates_outside_bounds_rejected() {
+        let mut pools_config = pools_config();
+        pools_config
+            .update_protocol_fee_rate_bounds(100, 500)
+            .unwrap();
+
+        assert!(pools_config.verify_protocol_fee_rate(100).is_ok());
+        assert!(pools_config.verify_protocol_fee_rate(500).is_ok());
+        assert_eq!(
+            pools_config.verify_protocol_fee_rate(99).unwrap_err(),
+            ErrorCode::ProtocolFeeRateOutOfBounds.into()
+        );
+        assert_eq!(
+            pools_config
+                .update_default_protocol_fee_rate(501)
+                .unwrap_err(),
+            ErrorCode::ProtocolFeeRateOutOfBounds.into()
+        );
+        assert_eq!(pools_config.default_protocol_fee_rate, 300);
+    }
+
+    #[test]
+    fn test_invalid_bounds() {
+        let mut pools_config = pools_config();
+        assert_eq!(
+            pools_config
+                .update_protocol_fee_rate_bounds(200, 100)
+                .unwrap_err(),
+            ErrorCode::InvalidProtocolFeeRateBounds.into()
+        );
+        assert_eq!(
+            pools_config
+                .update_protocol_fee_rate_bounds(0, MAX_PROTOCOL_FEE_RATE + 1)
+                .unwrap_err(),
+            ErrorCode::InvalidProtocolFeeRateBounds.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod fee_tier_creation_lock_tests {
+    use super::*;
+
+    #[test]
+    fn test_unlocked_operations_authorities_create_fee_tiers() {
+        let mut config = PoolsConfig::default();
+        let fee_authority = Pubkey::new_unique();
+        let fee_tier_authority = Pubkey::new_unique();
+        config.update_fee_authority(fee_authority);
+        config.update_fee_tier_authority(fee_tier_authority);
+
+        assert!(config.is_fee_tier_creator(fee_authority));
+        assert!(!config.is_fee_tier_creator(fee_tier_authority));
+    }
+
+    #[test]
+    fn test_locked_requires_fee_tier_authority() {
+        let mut config = PoolsConfig::default();
+        let fee_authority = Pubkey::new_unique();
+        let operations_authority = Pubkey::new_unique();
+        config.update_fee_authority(fee_authority);
+        config.update_operations_authority(operations_authority);
+        config.update_fee_tier_creation_locked(true);
+
+        // Nobody can create fee tiers without a fee tier authority
+        assert!(!config.is_fee_tier_creator(fee_authority));
+        assert!(!config.is_fee_tier_creator(Pubkey::default()));
+
+        let fee_tier_authority = Pubkey::new_unique();
+        config.update_fee_tier_authority(fee_tier_authority);
+        assert!(config.is_fee_tier_creator(fee_tier_authority));
+        assert!(!config.is_fee_tier_creator(fee_authority));
+        assert!(!config.is_fee_tier_creator(operations_authority));
+
+        config.update_fee_tier_creation_locked(false);
+        assert!(config.is_fee_tier_creator(operations_authority));
+    }
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_offsets() {
+        let mut config = PoolsConfig::default();
+        config.update_feature_flags(FEATURE_FLAGS_ALL);
+        config.reset_protocol_fee_rate_bounds();
+
+        let mut data = vec![0u8; PoolsConfig::LEN];
+        config.try_serialize(&mut &mut data[..]).unwrap();
+
+        assert_eq!(
+            data[PoolsConfig::FEATURE_FLAGS_OFFSET..PoolsConfig::FEATURE_FLAGS_OFFSET + 8],
+            FEATURE_FLAGS_ALL.to_le_bytes()
+        );
+        assert_eq!(
+            data[PoolsConfig::MAX_PROTOCOL_FEE_RATE_OFFSET
+                ..PoolsConfig::MAX_PROTOCOL_FEE_RATE_OFFSET + 2],
+            MAX_PROTOCOL_FEE_RATE.to_le_bytes()
+        );
+    }
 }
@@ -1,28 +1,117 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
 #[account]
 pub struct PoolsConfigExtension {
     pub pools_config: Pubkey,               // 32
     pub config_extension_authority: Pubkey, // 32
     pub token_badge_authority: Pubkey,      // 32
-                                            // 512 RESERVE
+    pub protocol_treasury: Pubkey,          // 32
+                                            // 480 RESERVE
 }
 
 impl PoolsConfigExtension {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 512;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 480;
 
     pub fn initialize(&mut self, pools_config: Pubkey, default_authority: Pubkey) -> Result<()> {
         self.pools_config = pools_config;
         self.config_extension_authority = default_authority;
         self.token_badge_authority = default_authority;
+        self.protocol_treasury = Pubkey::default();
         Ok(())
     }
 
-    pub fn update_config_extension_authority(&mut self, config_extension_authority: Pubkey) {
+    // Rotating to the default pubkey would permanently lock out administration of the extension
+    pub fn update_config_extension_authority(
+        &mut self,
+        config_extension_authority: Pubkey,
+    ) -> Result<()> {
+        if config_extension_authority == Pubkey::default() {
+            return Err(ErrorCode::InvalidAuthorityKey.into());
+        }
         self.config_extension_authority = config_extension_authority;
+        Ok(())
+    }
+
+    pub fn update_protocol_treasury(&mut self, protocol_treasury: Pubkey) -> Result<()> {
+        if protocol_treasury == Pubkey::default() {
+            return Err(ErrorCode::InvalidAuthorityKey.into());
+        }
+        self.protocol_treasury = protocol_treasury;
+        Ok(())
     }
 
-    pub fn update_token_badge_authority(&mut self, token_badge_authority: Pubkey) {
+    pub fn update_token_badge_authority(&mut self, token_badge_authority: Pubkey) -> Result<()> {
+        if token_badge_authority == Pubkey::default() {
+            return Err(ErrorCode::InvalidAuthorityKey.into());
+        }
         self.token_badge_authority = token_badge_authority;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod config_extension_authority_tests {
+    use super::*;
+
+    fn config_extension() -> PoolsConfigExtension {
+        let authority = Pubkey::new_unique();
+        PoolsConfigExtension {
+            pools_config: Pubkey::new_unique(),
+            config_extension_authority: authority,
+            token_badge_authority: authority,
+            protocol_treasury: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_update_config_extension_authority() {
+        let mut extension = config_extension();
+        let new_authority = Pubkey::new_unique();
+        extension
+            .update_config_extension_authority(new_authority)
+            .unwrap();
+        assert_eq!(extension.config_extension_authority, new_authority);
+    }
+
+    #[test]
+    fn test_update_config_extension_authority_rejects_default_pubkey() {
+        let mut extension = config_extension();
+        let authority = extension.config_extension_authority;
+        let result = extension.update_config_extension_authority(Pubkey::default());
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidAuthorityKey.into());
+        assert_eq!(extension.config_extension_authority, authority);
+    }
+
+    #[test]
+    fn test_update_token_badge_authority() {
+        let mut extension = config_extension();
+        let new_authority = Pubkey::new_unique();
+        extension
+            .update_token_badge_authority(new_authority)
+            .unwrap();
+        assert_eq!(extension.token_badge_authority, new_authority);
+    }
+
+    #[test]
+    fn test_update_token_badge_authority_rejects_default_pubkey() {
+        let mut extension = config_extension();
+        let authority = extension.token_badge_authority;
+        let result = extension.update_token_badge_authority(Pubkey::default());
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidAuthorityKey.into());
+        assert_eq!(extension.token_badge_authority, authority);
+    }
+
+    #[test]
+    fn test_update_protocol_treasury() {
+        let mut extension = config_extension();
+        let treasury = Pubkey::new_unique();
+        extension.update_protocol_treasury(treasury).unwrap();
+        assert_eq!(extension.protocol_treasury, treasury);
+
+        let result = extension.update_protocol_treasury(Pubkey::default());
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidAuthorityKey.into());
+        assert_eq!(extension.protocol_treasury, treasury);
     }
 }
@@ -1,4 +1,4 @@
-use crate::state::PoolsConfig;
+use crate::state::{Pool, PoolsConfig};
 use crate::{errors::ErrorCode, math::MAX_FEE_RATE};
 use anchor_lang::prelude::*;
 
@@ -7,10 +7,15 @@ pub struct FeeTier {
     pub pools_config: Pubkey,
     pub tick_spacing: u16,
     pub default_fee_rate: u16,
+
+    // Number of pools initialized with this fee tier, minus the pools decremented by the fee
+    // authority. Pools initialized before the counter was added are not counted.
+    pub pool_count: u32,
 }
 
 impl FeeTier {
-    pub const LEN: usize = 8 + 32 + 4;
+    pub const LEN: usize = 8 + 32 + 4 + 4;
+    pub const LEN_WITHOUT_POOL_COUNT: usize = 8 + 32 + 4;
 
     pub fn initialize(
         &mut self,
@@ -32,4 +37,91 @@ impl FeeTier {
 
         Ok(())
     }
+
+    pub fn verify_tick_spacing(&self, tick_spacing: u16) -> Result<()> {
+        if self.tick_spacing != tick_spacing {
+            return Err(ErrorCode::TickSpacingFeeTierMismatch.into());
+        }
+        Ok(())
+    }
+
+    pub fn increment_pool_count(&mut self) -> Result<()> {
+        self.pool_count = self
+            .pool_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn decrement_pool_count(&mut self) -> Result<()> {
+        if self.pool_count == 0 {
+            return Err(ErrorCode::FeeTierPoolCountZero.into());
+        }
+        self.pool_count -= 1;
+        Ok(())
+    }
+
+    /// Returns true if the pool was initialized with this fee tier.
+    pub fn is_fee_tier_of(&self, pool: &Pool) -> bool {
+        pool.pools_config == self.pools_config && pool.tick_spacing == self.tick_spacing
+    }
+}
+
+#[cfg(test)]
+mod fee_tier_tests {
+    use super::*;
+
+    fn fee_tier() -> FeeTier {
+        FeeTier {
+            pools_config: Pubkey::default(),
+            tick_spacing: 64,
+            default_fee_rate: 3000,
+            pool_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_is_fee_tier_of() {
+        let fee_tier = fee_tier();
+        let mut pool = Pool {
+            tick_spacing: 64,
+            ..Default::default()
+        };
+        assert!(fee_tier.is_fee_tier_of(&pool));
+
+        pool.tick_spacing = 128;
+        assert!(!fee_tier.is_fee_tier_of(&pool));
+
+        pool.tick_spacing = 64;
+        pool.pools_config = Pubkey::new_unique();
+        assert!(!fee_tier.is_fee_tier_of(&pool));
+    }
+
+    #[test]
+    fn test_verify_tick_spacing() {
+        let fee_tier = fee_tier();
+        assert!(fee_tier.verify_tick_spacing(64).is_ok());
+
+        let result = fee_tier.verify_tick_spacing(128);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickSpacingFeeTierMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_pool_count() {
+        let mut fee_tier = fee_tier();
+        fee_tier.increment_pool_count().unwrap();
+        fee_tier.increment_pool_count().unwrap();
+        assert_eq!(fee_tier.pool_count, 2);
+
+        fee_tier.decrement_pool_count().unwrap();
+        fee_tier.decrement_pool_count().unwrap();
+        assert_eq!(fee_tier.pool_count, 0);
+        assert_eq!(
+            fee_tier.decrement_pool_count().unwrap_err(),
+            ErrorCode::FeeTierPoolCountZero.into()
+        );
+    }
 }
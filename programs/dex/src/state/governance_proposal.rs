@@ -0,0 +1,434 @@
+use crate::errors::ErrorCode;
+use crate::math::{
+    div_round_up_if_u256, mul_u256, token_amounts_from_liquidity, U256Muldiv, MAX_PROTOCOL_FEE_RATE,
+};
+use crate::state::PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE;
+use anchor_lang::prelude::*;
+
+// Sets PoolsConfig.default_protocol_fee_rate to proposed_value
+pub const PROPOSAL_TYPE_DEFAULT_PROTOCOL_FEE_RATE: u8 = 0;
+
+/// A governance proposal to change a protocol parameter of a PoolsConfig.
+///
+/// Votes are weighted by the value of the voting position at the current price of its pool,
+/// denominated in vote_mint. Only positions in pools that trade vote_mint can vote, so that the
+/// weights of positions in different pools are comparable.
+#[account]
+#[derive(Default)]
+pub struct GovernanceProposal {
+    pub pools_config: Pubkey,   // 32
+    pub proposal_id: u64,       // 8
+    pub proposal_type: u8,      // 1
+    pub proposed_value: u64,    // 8
+    pub votes_for: u128,        // 16
+    pub votes_against: u128,    // 16
+    pub voting_end_ts: u64,     // 8
+    pub executed: bool,         // 1
+    pub quorum_liquidity: u128, // 16
+    pub vote_mint: Pubkey,      // 32
+                                // 32 RESERVE
+}
+
+impl GovernanceProposal {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 8 + 16 + 16 + 8 + 1 + 16 + 32 + 32;
+
+    pub fn initialize(
+        &mut self,
+        pools_config: Pubkey,
+        proposal_id: u64,
+        proposal_type: u8,
+        proposed_value: u64,
+        voting_end_ts: u64,
+        quorum_liquidity: u128,
+        vote_mint: Pubkey,
+        timestamp: u64,
+    ) -> Result<()> {
+        validate_proposed_value(proposal_type, proposed_value)?;
+        if voting_end_ts <= timestamp {
+            return Err(ErrorCode::InvalidTimestamp.into());
+        }
+
+        self.pools_config = pools_config;
+        self.proposal_id = proposal_id;
+        self.proposal_type = proposal_type;
+        self.proposed_value = proposed_value;
+        self.voting_end_ts = voting_end_ts;
+        self.quorum_liquidity = quorum_liquidity;
+        self.vote_mint = vote_mint;
+        // votes and executed flag are initialized using Default trait
+        Ok(())
+    }
+
+    pub fn cast_vote(&mut self, weight: u128, support: bool, timestamp: u64) -> Result<()> {
+        if timestamp > self.voting_end_ts || self.executed {
+            return Err(ErrorCode::ProposalVotingClosed.into());
+        }
+        if weight == 0 {
+            return Err(ErrorCode::LiquidityZero.into());
+        }
+
+        if support {
+            self.votes_for = self
+                .votes_for
+                .checked_add(weight)
+                .ok_or(ErrorCode::LiquidityOverflow)?;
+        } else {
+            self.votes_against = self
+                .votes_against
+                .checked_add(weight)
+                .ok_or(ErrorCode::LiquidityOverflow)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_passed(&self) -> bool {
+        match self.votes_against.checked_add(self.quorum_liquidity / 4) {
+            Some(threshold) => self.votes_for > threshold,
+            None => false,
+        }
+    }
+
+    pub fn check_voting_ended(&self, timestamp: u64) -> Result<()> {
+        if timestamp <= self.voting_end_ts {
+            return Err(ErrorCode::ProposalVotingNotEnded.into());
+        }
+        Ok(())
+    }
+
+    pub fn mark_executed(&mut self, timestamp: u64) -> Result<()> {
+        if self.executed {
+            return Err(ErrorCode::ProposalAlreadyExecuted.into());
+        }
+        self.check_voting_ended(timestamp)?;
+        if !self.is_passed() {
+            return Err(ErrorCode::ProposalNotPassed.into());
+        }
+        self.executed = true;
+        Ok(())
+    }
+
+    /// The timelocked parameter change that applies a passed proposal.
+    pub fn parameter_change_type(&self) -> Result<u8> {
+        match self.proposal_type {
+            PROPOSAL_TYPE_DEFAULT_PROTOCOL_FEE_RATE => {
+                Ok(PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE)
+            }
+            _ => Err(ErrorCode::InvalidProposalType.into()),
+        }
+    }
+}
+
+/// Calculate the vote weight of a position: the value of its tokens at the current price of its
+/// pool, in token A if value_in_a is true and in token B otherwise. Token amounts and the
+/// conversion round down.
+pub fn calculate_vote_weight(
+    liquidity: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_current_index: i32,
+    sqrt_price: u128,
+    value_in_a: bool,
+) -> Result<u128> {
+    let (amount_a, amount_b) = token_amounts_from_liquidity(
+        liquidity,
+        tick_current_index,
+        sqrt_price,
+        tick_lower_index,
+        tick_upper_index,
+        false,
+    )
+    .map_err(ErrorCode::from)?;
+
+    let weight = if value_in_a {
+        // amount_b / price = amount_b * 2^128 / sqrt_price^2
+        let amount_b_in_a = div_round_up_if_u256(
+            U256Muldiv::new(0, amount_b as u128).shift_left(128),
+            mul_u256(sqrt_price, sqrt_price),
+            false,
+        )
+        .map_err(ErrorCode::from)?;
+        (amount_a as u128).checked_add(amount_b_in_a)
+    } else {
+        // amount_a * price = amount_a * sqrt_price^2 / 2^128, in two steps to stay within 256 bits
+        let amount_a_in_b = mul_u256(amount_a as u128, sqrt_price)
+            .shift_right(64)
+            .try_into_u128()
+            .map_err(ErrorCode::from)?;
+        let amount_a_in_b = mul_u256(amount_a_in_b, sqrt_price)
+            .shift_right(64)
+            .try_into_u128()
+            .map_err(ErrorCode::from)?;
+        (amount_b as u128).checked_add(amount_a_in_b)
+    };
+    Ok(weight.ok_or(ErrorCode::LiquidityOverflow)?)
+}
+
+pub fn validate_proposed_value(proposal_type: u8, proposed_value: u64) -> Result<()> {
+    match proposal_type {
+        PROPOSAL_TYPE_DEFAULT_PROTOCOL_FEE_RATE => {
+            if proposed_value > MAX_PROTOCOL_FEE_RATE as u64 {
+                return Err(ErrorCode::ProtocolFeeRateMaxExceeded.into());
+            }
+            Ok(())
+        }
+        _ => Err(ErrorCode::InvalidProposalType.into()),
+    }
+}
+
+/// The vote of a position on a proposal.
+///
+/// The position NFT is held in escrow by this account until voting has ended, so the liquidity
+/// that was counted cannot be withdrawn or moved to another position to vote again.
+#[account]
+#[derive(Default)]
+pub struct ProposalVote {
+    pub proposal: Pubkey,      // 32
+    pub position: Pubkey,      // 32
+    pub liquidity: u128,       // 16
+    pub support: bool,         // 1
+    pub owner: Pubkey,         // 32
+    pub position_mint: Pubkey, // 32
+    pub vote_bump: [u8; 1],    // 1
+    pub weight: u128,          // 16
+}
+
+impl ProposalVote {
+    pub const LEN: usize = 8 + 32 + 32 + 16 + 1 + 32 + 32 + 1 + 16;
+
+    pub fn seeds(&self) -> [&[u8]; 4] {
+        [
+            &b"vote"[..],
+            self.proposal.as_ref(),
+            self.position.as_ref(),
+            self.vote_bump.as_ref(),
+        ]
+    }
+
+    pub fn initialize(
+        &mut self,
+        proposal: Pubkey,
+        position: Pubkey,
+        liquidity: u128,
+        weight: u128,
+        support: bool,
+        owner: Pubkey,
+        position_mint: Pubkey,
+        bump: u8,
+    ) -> Result<()> {
+        self.proposal = proposal;
+        self.position = position;
+        self.liquidity = liquidity;
+        self.weight = weight;
+        self.support = support;
+        self.owner = owner;
+        self.position_mint = position_mint;
+        self.vote_bump = [bump];
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod governance_proposal_tests {
+    use super::*;
+    use crate::math::tick_index_from_sqrt_price;
+
+    fn proposal(quorum_liquidity: u128) -> GovernanceProposal {
+        let mut proposal = GovernanceProposal::default();
+        proposal
+            .initialize(
+                Pubkey::new_unique(),
+                0,
+                PROPOSAL_TYPE_DEFAULT_PROTOCOL_FEE_RATE,
+                300,
+                100,
+                quorum_liquidity,
+                Pubkey::new_unique(),
+                0,
+            )
+            .unwrap();
+        proposal
+    }
+
+    #[test]
+    fn test_initialize_rejects_invalid_proposal() {
+        let mut proposal = GovernanceProposal::default();
+        let result = proposal.initialize(
+            Pubkey::new_unique(),
+            0,
+            1,
+            300,
+            100,
+            0,
+            Pubkey::new_unique(),
+            0,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidProposalType.into());
+
+        let result = proposal.initialize(
+            Pubkey::new_unique(),
+            0,
+            PROPOSAL_TYPE_DEFAULT_PROTOCOL_FEE_RATE,
+            MAX_PROTOCOL_FEE_RATE as u64 + 1,
+            100,
+            0,
+            Pubkey::new_unique(),
+            0,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::ProtocolFeeRateMaxExceeded.into()
+        );
+
+        let result = proposal.initialize(
+            Pubkey::new_unique(),
+            0,
+            PROPOSAL_TYPE_DEFAULT_PROTOCOL_FEE_RATE,
+            300,
+            100,
+            0,
+            Pubkey::new_unique(),
+            100,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidTimestamp.into());
+    }
+
+    #[test]
+    fn test_passes_with_quorum_margin() {
+        let mut proposal = proposal(400);
+        proposal.cast_vote(200, true, 50).unwrap();
+        proposal.cast_vote(100, false, 50).unwrap();
+        // 200 > 100 + 400 / 4 is false
+        assert!(!proposal.is_passed());
+
+        proposal.cast_vote(1, true, 100).unwrap();
+        assert!(proposal.is_passed());
+    }
+
+    #[test]
+    fn test_cast_vote_after_voting_end() {
+        let mut proposal = proposal(0);
+        let result = proposal.cast_vote(100, true, 101);
+        assert_eq!(result.unwrap_err(), ErrorCode::ProposalVotingClosed.into());
+    }
+
+    #[test]
+    fn test_cast_vote_zero_liquidity() {
+        let mut proposal = proposal(0);
+        let result = proposal.cast_vote(0, true, 50);
+        assert_eq!(result.unwrap_err(), ErrorCode::LiquidityZero.into());
+    }
+
+    #[test]
+    fn test_mark_executed() {
+        let mut proposal = proposal(0);
+        proposal.cast_vote(100, true, 50).unwrap();
+
+        let result = proposal.mark_executed(100);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::ProposalVotingNotEnded.into()
+        );
+
+        proposal.mark_executed(101).unwrap();
+        assert!(proposal.executed);
+
+        let result = proposal.mark_executed(102);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::ProposalAlreadyExecuted.into()
+        );
+    }
+
+    #[test]
+    fn test_check_voting_ended() {
+        let proposal = proposal(0);
+        let result = proposal.check_voting_ended(100);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::ProposalVotingNotEnded.into()
+        );
+        assert!(proposal.check_voting_ended(101).is_ok());
+    }
+
+    #[test]
+    fn test_mark_executed_not_passed() {
+        let mut proposal = proposal(0);
+        proposal.cast_vote(100, true, 50).unwrap();
+        proposal.cast_vote(100, false, 50).unwrap();
+
+        let result = proposal.mark_executed(101);
+        assert_eq!(result.unwrap_err(), ErrorCode::ProposalNotPassed.into());
+    }
+
+    #[test]
+    fn test_parameter_change_type() {
+        let mut proposal = proposal(0);
+        assert_eq!(
+            proposal.parameter_change_type().unwrap(),
+            PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE
+        );
+
+        proposal.proposal_type = 1;
+        assert_eq!(
+            proposal.parameter_change_type().unwrap_err(),
+            ErrorCode::InvalidProposalType.into()
+        );
+    }
+
+    #[test]
+    fn test_vote_weight_values_both_tokens() {
+        // price 4: 1 token A is worth 4 token B
+        let sqrt_price = 2u128 << 64;
+        let tick_current_index = tick_index_from_sqrt_price(&sqrt_price);
+        let (amount_a, amount_b) = token_amounts_from_liquidity(
+            1_000_000,
+            tick_current_index,
+            sqrt_price,
+            -443584,
+            443584,
+            false,
+        )
+        .unwrap();
+
+        let weight_in_b = calculate_vote_weight(
+            1_000_000,
+            -443584,
+            443584,
+            tick_current_index,
+            sqrt_price,
+            false,
+        )
+        .unwrap();
+        assert_eq!(weight_in_b, amount_b as u128 + amount_a as u128 * 4);
+
+        let weight_in_a = calculate_vote_weight(
+            1_000_000,
+            -443584,
+            443584,
+            tick_current_index,
+            sqrt_price,
+            true,
+        )
+        .unwrap();
+        assert_eq!(weight_in_a, amount_a as u128 + amount_b as u128 / 4);
+    }
+
+    #[test]
+    fn test_vote_weight_tracks_value_not_liquidity() {
+        // The same liquidity on a narrow range holds far fewer tokens than on the full range
+        let sqrt_price = 1u128 << 64;
+        let narrow = calculate_vote_weight(1_000_000, -64, 64, 0, sqrt_price, false).unwrap();
+        let full = calculate_vote_weight(1_000_000, -443584, 443584, 0, sqrt_price, false).unwrap();
+        assert!(narrow < full / 100);
+    }
+
+    #[test]
+    fn test_vote_weight_out_of_range_position() {
+        // Only token A below the range, valued at the current price
+        let sqrt_price = 1u128 << 64;
+        let weight_in_b = calculate_vote_weight(1_000_000, 64, 128, 0, sqrt_price, false).unwrap();
+        let weight_in_a = calculate_vote_weight(1_000_000, 64, 128, 0, sqrt_price, true).unwrap();
+        assert!(weight_in_b > 0);
+        assert_eq!(weight_in_a, weight_in_b);
+    }
+}
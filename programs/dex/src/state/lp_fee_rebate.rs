@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+pub const MAX_LP_FEE_REBATE_BPS: u16 = 10_000;
+
+/// Fee rebate for swappers who provide liquidity to the pool.
+///
+/// A swap earns the rebate if it passes a `PositionSwapCount` of a position with liquidity held by
+/// the swapper, which has reached `swap_count_threshold` swaps.
+#[account]
+#[derive(Default)]
+pub struct LpFeeRebateConfig {
+    pub pool: Pubkey,              // 32
+    pub swap_count_threshold: u64, // 8
+    pub rebate_bps: u16,           // 2
+                                   // 80 RESERVE
+}
+
+impl LpFeeRebateConfig {
+    pub const LEN: usize = 8 + 32 + 8 + 2 + 80;
+
+    /// Set the rebate of the pool.
+    pub fn update(
+        &mut self,
+        pool: Pubkey,
+        swap_count_threshold: u64,
+        rebate_bps: u16,
+    ) -> Result<()> {
+        if rebate_bps > MAX_LP_FEE_REBATE_BPS {
+            return Err(ErrorCode::LpFeeRebateBpsMaxExceeded.into());
+        }
+        self.pool = pool;
+        self.swap_count_threshold = swap_count_threshold;
+        self.rebate_bps = rebate_bps;
+        Ok(())
+    }
+
+    /// The rebate in basis points of the swap fee for a position with `swap_count_used` swaps.
+    pub fn rebate_bps_for(&self, swap_count_used: u64) -> u16 {
+        if swap_count_used >= self.swap_count_threshold {
+            self.rebate_bps
+        } else {
+            0
+        }
+    }
+}
+
+/// Number of swaps a position holder made in the pool of the position, used for the LP fee rebate.
+#[account]
+#[derive(Default)]
+pub struct PositionSwapCount {
+    pub pool: Pubkey,          // 32
+    pub position: Pubkey,      // 32
+    pub position_mint: Pubkey, // 32
+    pub swap_count_used: u64,  // 8
+}
+
+impl PositionSwapCount {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8;
+
+    pub fn initialize(&mut self, pool: Pubkey, position: Pubkey, position_mint: Pubkey) {
+        self.pool = pool;
+        self.position = position;
+        self.position_mint = position_mint;
+    }
+
+    pub fn increment_position_swap_count(&mut self) {
+        self.swap_count_used = self.swap_count_used.saturating_add(1);
+    }
+
+    /// The swapper must hold the position token of the position its swaps are counted for, and
+    /// the position must provide liquidity.
+    pub fn verify_rebate_position(
+        &self,
+        position: Option<(Pubkey, u128)>,
+        position_token_account: Option<(Pubkey, u64)>,
+    ) -> Result<()> {
+        match position {
+            Some((key, liquidity)) if key == self.position && liquidity > 0 => {}
+            _ => return Err(ErrorCode::InvalidFeeRebatePosition.into()),
+        }
+        match position_token_account {
+            Some((mint, amount)) if mint == self.position_mint && amount == 1 => Ok(()),
+            _ => Err(ErrorCode::InvalidFeeRebatePosition.into()),
+        }
+    }
+}
+
+/// Reduce the fee rate of a swap by the rebate in basis points, rounding the reduction down.
+pub fn apply_lp_fee_rebate(fee_rate: u16, rebate_bps: u16) -> u16 {
+    let rebate = fee_rate as u32 * rebate_bps as u32 / MAX_LP_FEE_REBATE_BPS as u32;
+    fee_rate - rebate as u16
+}
+
+#[cfg(test)]
+mod lp_fee_rebate_tests {
+    use super::*;
+
+    #[test]
+    fn test_update_rejects_rebate_above_max() {
+        let mut config = LpFeeRebateConfig::default();
+        let result = config.update(Pubkey::new_unique(), 10, MAX_LP_FEE_REBATE_BPS + 1);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::LpFeeRebateBpsMaxExceeded.into()
+        );
+        assert!(config
+            .update(Pubkey::new_unique(), 10, MAX_LP_FEE_REBATE_BPS)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rebate_from_threshold() {
+        let mut config = LpFeeRebateConfig::default();
+        config.update(Pubkey::new_unique(), 10, 2_500).unwrap();
+        assert_eq!(config.rebate_bps_for(9), 0);
+        assert_eq!(config.rebate_bps_for(10), 2_500);
+        assert_eq!(config.rebate_bps_for(11), 2_500);
+    }
+
+    fn position_swap_count() -> PositionSwapCount {
+        let mut position_swap_count = PositionSwapCount::default();
+        position_swap_count.initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        position_swap_count
+    }
+
+    #[test]
+    fn test_rebate_position_held_with_liquidity() {
+        let position_swap_count = position_swap_count();
+        assert!(position_swap_count
+            .verify_rebate_position(
+                Some((position_swap_count.position, 1)),
+                Some((position_swap_count.position_mint, 1)),
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rebate_position_token_missing_or_not_held() {
+        let position_swap_count = position_swap_count();
+        let position_mint = position_swap_count.position_mint;
+        for position_token_account in [
+            None,
+            Some((position_mint, 0)),
+            Some((Pubkey::new_unique(), 1)),
+        ] {
+            assert_eq!(
+                position_swap_count
+                    .verify_rebate_position(
+                        Some((position_swap_count.position, 1)),
+                        position_token_account,
+                    )
+                    .unwrap_err(),
+                ErrorCode::InvalidFeeRebatePosition.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rebate_position_missing_or_without_liquidity() {
+        let position_swap_count = position_swap_count();
+        for position in [
+            None,
+            Some((position_swap_count.position, 0)),
+            Some((Pubkey::new_unique(), 1)),
+        ] {
+            assert_eq!(
+                position_swap_count
+                    .verify_rebate_position(position, Some((position_swap_count.position_mint, 1)),)
+                    .unwrap_err(),
+                ErrorCode::InvalidFeeRebatePosition.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_apply_lp_fee_rebate() {
+        assert_eq!(apply_lp_fee_rebate(3_000, 0), 3_000);
+        assert_eq!(apply_lp_fee_rebate(3_000, 2_500), 2_250);
+        assert_eq!(apply_lp_fee_rebate(3_001, 5_000), 1_501);
+        assert_eq!(apply_lp_fee_rebate(u16::MAX, MAX_LP_FEE_REBATE_BPS), 0);
+    }
+}
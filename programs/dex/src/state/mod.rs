@@ -1,17 +1,37 @@
 pub mod config;
 pub mod config_extension;
 pub mod fee_tier;
+pub mod governance_proposal;
+pub mod lp_fee_rebate;
 pub mod pool;
+pub mod pool_name_entry;
 pub mod position;
 pub mod position_bundle;
+pub mod position_fraction_vault;
+pub mod position_history;
+pub mod protocol_liquidity_manager;
+pub mod protocol_parameter_change;
+pub mod reward_emissions_schedule;
+pub mod shared_deadline_context;
 pub mod tick;
+pub mod tick_array_cache;
 pub mod token_badge;
 
 pub use self::pool::*;
 pub use config::*;
 pub use config_extension::*;
 pub use fee_tier::*;
+pub use governance_proposal::*;
+pub use lp_fee_rebate::*;
+pub use pool_name_entry::*;
 pub use position::*;
 pub use position_bundle::*;
+pub use position_fraction_vault::*;
+pub use position_history::*;
+pub use protocol_liquidity_manager::*;
+pub use protocol_parameter_change::*;
+pub use reward_emissions_schedule::*;
+pub use shared_deadline_context::*;
 pub use tick::*;
+pub use tick_array_cache::*;
 pub use token_badge::*;
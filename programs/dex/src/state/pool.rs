@@ -8,7 +8,7 @@ use crate::{
 };
 use anchor_lang::prelude::*;
 
-use super::PoolsConfig;
+use super::{PoolsConfig, RewardEmissionsSchedule};
 
 #[account]
 #[derive(Default)]
@@ -52,13 +52,90 @@ pub struct Pool {
     pub reward_last_updated_timestamp: u64, // 8
 
     pub reward_infos: [PoolRewardInfo; NUM_REWARDS], // 384
+
+    // Highest liquidity the pool has held since the field was added
+    pub max_liquidity_observed: u128, // 16
+
+    // Set once the fee authority gives up admin control over the pool, can never be unset
+    pub authority_renounced: bool, // 1
+
+    // Number of times a tick of the pool flipped between initialized and uninitialized, used to
+    // detect stale tick array caches
+    pub tick_flip_count: u64, // 8
+
+    // Swaps in a direction can be disabled by the fee authority, e.g. to only allow exiting a
+    // depegged token. Stored as disabled flags so that pools created before the flags were added
+    // keep both directions enabled.
+    pub swap_a_to_b_disabled: bool, // 1
+    pub swap_b_to_a_disabled: bool, // 1
+
+    // Emissions of a paused reward stop accruing until the reward authority resumes them, stored
+    // per reward index next to reward_infos to keep the PoolRewardInfo layout unchanged
+    pub reward_paused: [bool; NUM_REWARDS], // 3
+
+    // Share of REWARD_VAULT_WARNING_PERIOD_SECONDS of emissions, in basis points, below which the
+    // balance of a reward vault is reported as low when emissions are set. Zero disables the warning.
+    pub reward_vault_warning_threshold_bps: [u16; NUM_REWARDS], // 6
+
+    // Bounds of the amount of a swap, set by the fee authority. Zero disables a bound.
+    pub min_swap_amount: u64, // 8
+    pub max_swap_amount: u64, // 8
+
+    // If enabled, anyone can adjust the protocol fee rate once per day towards the rate at which
+    // the daily volume earns target_protocol_fee_revenue_daily, denominated in token A. The token
+    // A amount of every swap since the last adjustment is its volume.
+    pub auto_protocol_fee_enabled: bool,        // 1
+    pub target_protocol_fee_revenue_daily: u64, // 8
+    pub auto_protocol_fee_adjusted_at: u64,     // 8
+    pub auto_protocol_fee_volume_a: u64,        // 8
+
+    // Cumulative swap amounts of the pool, aggregated per fee tier on demand
+    pub total_volume_a: u128, // 16
+    pub total_volume_b: u128, // 16
+
+    // Number of swaps of the pool, and since the LP fee rebate of the pool was last set
+    pub total_swap_count: u64,             // 8
+    pub total_swap_count_since_reset: u64, // 8
+
+    // Emission schedules of the rewards, applied wherever the rewards accrue
+    pub reward_emissions_schedules: [RewardEmissionsSchedule; NUM_REWARDS], // 582
+                                                                            // 3 RESERVE
 }
 
 // Number of rewards supported by pools
 pub const NUM_REWARDS: usize = 3;
 
+// Emissions over this period are the reference of reward_vault_warning_threshold_bps
+pub const REWARD_VAULT_WARNING_PERIOD_SECONDS: u64 = 30 * 24 * 60 * 60;
+pub const MAX_REWARD_VAULT_WARNING_THRESHOLD_BPS: u16 = 10_000;
+
+// Minimum time between automatic protocol fee rate adjustments, which may each change the rate by
+// at most MAX_AUTO_PROTOCOL_FEE_RATE_CHANGE basis points
+pub const AUTO_PROTOCOL_FEE_ADJUSTMENT_PERIOD_SECONDS: u64 = 24 * 60 * 60;
+pub const MAX_AUTO_PROTOCOL_FEE_RATE_CHANGE: u16 = 5;
+
+// Swap counts from which on every power of 10 is reported as a milestone
+pub const MIN_SWAP_COUNT_MILESTONE: u64 = 1_000;
+
 impl Pool {
-    pub const LEN: usize = 8 + 261 + 384;
+    pub const LEN: usize =
+        8 + 261 + 384 + 16 + 1 + 8 + 1 + 1 + 3 + 6 + 8 + 8 + 1 + 8 + 8 + 8 + 16 + 16 + 8 + 8
+            + RewardEmissionsSchedule::LEN * NUM_REWARDS
+            + 3;
+    // Size of pools created before reward_emissions_schedules was added
+    pub const LEN_WITHOUT_REWARD_EMISSIONS_SCHEDULES: usize =
+        Pool::LEN - RewardEmissionsSchedule::LEN * NUM_REWARDS;
+    // Size of pools created before total_swap_count and total_swap_count_since_reset were added
+    pub const LEN_WITHOUT_SWAP_COUNT: usize = Pool::LEN_WITHOUT_REWARD_EMISSIONS_SCHEDULES - 8 - 8;
+    // Size of pools created before total_volume_a and total_volume_b were added
+    pub const LEN_WITHOUT_VOLUME: usize = Pool::LEN_WITHOUT_SWAP_COUNT - 16 - 16;
+    // Size of pools created before max_liquidity_observed was added
+    pub const LEN_WITHOUT_MAX_LIQUIDITY_OBSERVED: usize = 8 + 261 + 384;
+    // Size of pools created before authority_renounced was added
+    pub const LEN_WITHOUT_AUTHORITY_RENOUNCED: usize = 8 + 261 + 384 + 16;
+    // Offsets into the account data (including the 8 byte discriminator)
+    pub const LIQUIDITY_OFFSET: usize = 8 + 32 + 1 + 2 + 2 + 2 + 2;
+    pub const MAX_LIQUIDITY_OBSERVED_OFFSET: usize = Pool::LEN_WITHOUT_MAX_LIQUIDITY_OBSERVED;
     pub fn seeds(&self) -> [&[u8]; 6] {
         [
             &b"pool"[..],
@@ -102,6 +179,38 @@ impl Pool {
         }
     }
 
+    /// Check that collected fees are not sent back into the vaults of this pool, which would
+    /// inflate the vault balances without a matching change in the pool's accounting.
+    pub fn verify_fee_destinations(
+        &self,
+        token_destination_a: Pubkey,
+        token_destination_b: Pubkey,
+    ) -> Result<()> {
+        if token_destination_a == self.token_vault_a || token_destination_b == self.token_vault_b {
+            return Err(ErrorCode::InvalidFeeDestination.into());
+        }
+        Ok(())
+    }
+
+    /// Check that the destinations can be credited with the owed protocol fees before any of them
+    /// is transferred, so an overflowing destination fails with a clear error.
+    pub fn verify_protocol_fee_destination_balances(
+        &self,
+        destination_balance_a: u64,
+        destination_balance_b: u64,
+    ) -> Result<()> {
+        if destination_balance_a
+            .checked_add(self.protocol_fee_owed_a)
+            .is_none()
+            || destination_balance_b
+                .checked_add(self.protocol_fee_owed_b)
+                .is_none()
+        {
+            return Err(ErrorCode::ProtocolFeeDestinationOverflow.into());
+        }
+        Ok(())
+    }
+
     pub fn initialize(
         &mut self,
         pools_config: &Account<PoolsConfig>,
@@ -132,6 +241,11 @@ impl Pool {
         self.update_protocol_fee_rate(pools_config.default_protocol_fee_rate)?;
 
         self.liquidity = 0;
+        self.max_liquidity_observed = 0;
+        self.authority_renounced = false;
+        self.tick_flip_count = 0;
+        self.swap_a_to_b_disabled = false;
+        self.swap_b_to_a_disabled = false;
         self.sqrt_price = sqrt_price;
         self.tick_current_index = tick_index_from_sqrt_price(&sqrt_price);
 
@@ -148,6 +262,7 @@ impl Pool {
 
         self.reward_infos =
             [PoolRewardInfo::new(pools_config.reward_emissions_super_authority); NUM_REWARDS];
+        self.reward_paused = [false; NUM_REWARDS];
 
         Ok(())
     }
@@ -173,7 +288,14 @@ impl Pool {
         reward_last_updated_timestamp: u64,
     ) {
         self.update_rewards(reward_infos, reward_last_updated_timestamp);
+        self.update_liquidity(liquidity);
+    }
+
+    fn update_liquidity(&mut self, liquidity: u128) {
         self.liquidity = liquidity;
+        if liquidity > self.max_liquidity_observed {
+            self.max_liquidity_observed = liquidity;
+        }
     }
 
     /// Update the reward authority at the specified pool reward index.
@@ -198,6 +320,65 @@ impl Pool {
         }
         self.update_rewards(reward_infos, timestamp);
         self.reward_infos[index].emissions_per_second_x64 = emissions_per_second_x64;
+        // A rate set directly replaces the schedule of the reward
+        self.reward_emissions_schedules[index] = RewardEmissionsSchedule::default();
+
+        Ok(())
+    }
+
+    /// Replace the emission schedule of the reward at the specified pool reward index. The
+    /// rewards must be updated to the given timestamp, so that the previous rates apply up to it.
+    pub fn update_emissions_schedule(
+        &mut self,
+        index: usize,
+        reward_infos: [PoolRewardInfo; NUM_REWARDS],
+        timestamp: u64,
+        schedule: RewardEmissionsSchedule,
+    ) -> Result<()> {
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.update_rewards(reward_infos, timestamp);
+        self.reward_infos[index].emissions_per_second_x64 =
+            schedule.emissions_per_second_at(timestamp);
+        self.reward_emissions_schedules[index] = schedule;
+
+        Ok(())
+    }
+
+    /// Pause or resume the emissions of the reward at the specified pool reward index. The
+    /// rewards must be updated to the given timestamp so that accrual stops or restarts exactly at
+    /// the checkpoint.
+    pub fn update_reward_paused(
+        &mut self,
+        index: usize,
+        reward_infos: [PoolRewardInfo; NUM_REWARDS],
+        timestamp: u64,
+        paused: bool,
+    ) -> Result<()> {
+        self.verify_authority_not_renounced()?;
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        self.update_rewards(reward_infos, timestamp);
+        self.reward_paused[index] = paused;
+
+        Ok(())
+    }
+
+    pub fn update_reward_vault_warning_threshold(
+        &mut self,
+        index: usize,
+        threshold_bps: u16,
+    ) -> Result<()> {
+        self.verify_authority_not_renounced()?;
+        if index >= NUM_REWARDS {
+            return Err(ErrorCode::InvalidRewardIndex.into());
+        }
+        if threshold_bps > MAX_REWARD_VAULT_WARNING_THRESHOLD_BPS {
+            return Err(ErrorCode::RewardVaultWarningThresholdMaxExceeded.into());
+        }
+        self.reward_vault_warning_threshold_bps[index] = threshold_bps;
 
         Ok(())
     }
@@ -235,7 +416,7 @@ impl Pool {
     ) {
         self.tick_current_index = tick_index;
         self.sqrt_price = sqrt_price;
-        self.liquidity = liquidity;
+        self.update_liquidity(liquidity);
         self.reward_infos = reward_infos;
         self.reward_last_updated_timestamp = reward_last_updated_timestamp;
         if is_token_fee_in_a {
@@ -250,6 +431,7 @@ impl Pool {
     }
 
     pub fn update_fee_rate(&mut self, fee_rate: u16) -> Result<()> {
+        self.verify_authority_not_renounced()?;
         if fee_rate > MAX_FEE_RATE {
             return Err(ErrorCode::FeeRateMaxExceeded.into());
         }
@@ -259,6 +441,7 @@ impl Pool {
     }
 
     pub fn update_protocol_fee_rate(&mut self, protocol_fee_rate: u16) -> Result<()> {
+        self.verify_authority_not_renounced()?;
         if protocol_fee_rate > MAX_PROTOCOL_FEE_RATE {
             return Err(ErrorCode::ProtocolFeeRateMaxExceeded.into());
         }
@@ -271,6 +454,170 @@ impl Pool {
         self.protocol_fee_owed_a = 0;
         self.protocol_fee_owed_b = 0;
     }
+
+    pub fn update_directional_swap_status(
+        &mut self,
+        swap_a_to_b_enabled: bool,
+        swap_b_to_a_enabled: bool,
+    ) -> Result<()> {
+        self.verify_authority_not_renounced()?;
+        self.swap_a_to_b_disabled = !swap_a_to_b_enabled;
+        self.swap_b_to_a_disabled = !swap_b_to_a_enabled;
+
+        Ok(())
+    }
+
+    pub fn swap_a_to_b_enabled(&self) -> bool {
+        !self.swap_a_to_b_disabled
+    }
+
+    pub fn swap_b_to_a_enabled(&self) -> bool {
+        !self.swap_b_to_a_disabled
+    }
+
+    pub fn verify_swap_direction_enabled(&self, a_to_b: bool) -> Result<()> {
+        let enabled = if a_to_b {
+            self.swap_a_to_b_enabled()
+        } else {
+            self.swap_b_to_a_enabled()
+        };
+        if !enabled {
+            return Err(ErrorCode::SwapDirectionDisabled.into());
+        }
+        Ok(())
+    }
+
+    pub fn update_min_swap_amount(&mut self, min_swap_amount: u64) -> Result<()> {
+        self.verify_authority_not_renounced()?;
+        if self.max_swap_amount != 0 && min_swap_amount > self.max_swap_amount {
+            return Err(ErrorCode::InvalidSwapAmountBounds.into());
+        }
+        self.min_swap_amount = min_swap_amount;
+
+        Ok(())
+    }
+
+    pub fn update_max_swap_amount(&mut self, max_swap_amount: u64) -> Result<()> {
+        self.verify_authority_not_renounced()?;
+        if max_swap_amount != 0 && self.min_swap_amount > max_swap_amount {
+            return Err(ErrorCode::InvalidSwapAmountBounds.into());
+        }
+        self.max_swap_amount = max_swap_amount;
+
+        Ok(())
+    }
+
+    /// Check the amount of a swap against the min_swap_amount and max_swap_amount of the pool.
+    /// The error data holds the provided amount and the violated bound.
+    pub fn verify_swap_amount(&self, amount: u64) -> Result<()> {
+        if amount < self.min_swap_amount {
+            return Err(error!(ErrorCode::SwapAmountBelowMinimum)
+                .with_values((amount, self.min_swap_amount)));
+        }
+        if self.max_swap_amount != 0 && amount > self.max_swap_amount {
+            return Err(error!(ErrorCode::SwapAmountAboveMaximum)
+                .with_values((amount, self.max_swap_amount)));
+        }
+        Ok(())
+    }
+
+    /// Enable or disable the automatic protocol fee rate adjustment. The volume of the first
+    /// adjustment is measured from now.
+    pub fn update_auto_protocol_fee(
+        &mut self,
+        enabled: bool,
+        target_protocol_fee_revenue_daily: u64,
+        timestamp: u64,
+    ) -> Result<()> {
+        self.verify_authority_not_renounced()?;
+        self.auto_protocol_fee_enabled = enabled;
+        self.target_protocol_fee_revenue_daily = target_protocol_fee_revenue_daily;
+        self.auto_protocol_fee_adjusted_at = timestamp;
+        self.auto_protocol_fee_volume_a = 0;
+
+        Ok(())
+    }
+
+    pub fn record_swap_volume(&mut self, amount_a: u64, amount_b: u64) {
+        self.total_volume_a = self.total_volume_a.saturating_add(amount_a as u128);
+        self.total_volume_b = self.total_volume_b.saturating_add(amount_b as u128);
+        if self.auto_protocol_fee_enabled {
+            self.auto_protocol_fee_volume_a =
+                self.auto_protocol_fee_volume_a.saturating_add(amount_a);
+        }
+    }
+
+    /// Count a swap of the pool.
+    ///
+    /// # Returns
+    /// - `Option<u64>`: The milestone reached by total_swap_count, if any
+    pub fn record_swap_count(&mut self) -> Option<u64> {
+        self.total_swap_count = self.total_swap_count.saturating_add(1);
+        self.total_swap_count_since_reset = self.total_swap_count_since_reset.saturating_add(1);
+        if is_swap_count_milestone(self.total_swap_count) {
+            Some(self.total_swap_count)
+        } else {
+            None
+        }
+    }
+
+    pub fn reset_swap_count_since_reset(&mut self) {
+        self.total_swap_count_since_reset = 0;
+    }
+
+    /// Returns the token A volume since the last automatic adjustment, scaled to a day. Fails if
+    /// the adjustment is disabled or the last adjustment was less than a day ago.
+    pub fn auto_protocol_fee_daily_volume(&self, timestamp: u64) -> Result<u64> {
+        if !self.auto_protocol_fee_enabled {
+            return Err(ErrorCode::AutoProtocolFeeDisabled.into());
+        }
+        let elapsed = timestamp.saturating_sub(self.auto_protocol_fee_adjusted_at);
+        if elapsed < AUTO_PROTOCOL_FEE_ADJUSTMENT_PERIOD_SECONDS {
+            return Err(ErrorCode::AutoProtocolFeeAdjustmentTooEarly.into());
+        }
+        Ok((self.auto_protocol_fee_volume_a as u128
+            * AUTO_PROTOCOL_FEE_ADJUSTMENT_PERIOD_SECONDS as u128
+            / elapsed as u128) as u64)
+    }
+
+    pub fn apply_auto_protocol_fee_rate(
+        &mut self,
+        protocol_fee_rate: u16,
+        timestamp: u64,
+    ) -> Result<()> {
+        self.update_protocol_fee_rate(protocol_fee_rate)?;
+        self.auto_protocol_fee_adjusted_at = timestamp;
+        self.auto_protocol_fee_volume_a = 0;
+
+        Ok(())
+    }
+
+    /// Permanently give up admin control over the pool. Fee rates and reward authorities can no
+    /// longer be changed by the pools config authorities afterwards.
+    pub fn renounce_authority(&mut self) -> Result<()> {
+        self.verify_authority_not_renounced()?;
+        self.authority_renounced = true;
+
+        Ok(())
+    }
+
+    pub fn verify_authority_not_renounced(&self) -> Result<()> {
+        if self.authority_renounced {
+            return Err(ErrorCode::PoolAuthorityRenounced.into());
+        }
+        Ok(())
+    }
+}
+
+fn is_swap_count_milestone(count: u64) -> bool {
+    let mut milestone = MIN_SWAP_COUNT_MILESTONE;
+    while milestone < count {
+        match milestone.checked_mul(10) {
+            Some(next_milestone) => milestone = next_milestone,
+            None => return false,
+        }
+    }
+    milestone == count
 }
 
 /// Stores the state relevant for tracking liquidity mining rewards at the `pool` level.
@@ -335,6 +682,307 @@ fn test_pool_reward_info_initialized() {
     assert_eq!(reward_info.initialized(), true);
 }
 
+#[test]
+fn test_max_liquidity_observed_persists_at_peak() {
+    let mut pool = Pool::default();
+    let reward_infos = [PoolRewardInfo::default(); NUM_REWARDS];
+
+    pool.update_rewards_and_liquidity(reward_infos, 100, 0);
+    pool.update_rewards_and_liquidity(reward_infos, 300, 0);
+    assert_eq!(pool.max_liquidity_observed, 300);
+
+    pool.update_rewards_and_liquidity(reward_infos, 50, 0);
+    assert_eq!(pool.liquidity, 50);
+    assert_eq!(pool.max_liquidity_observed, 300);
+
+    pool.update_after_swap(400, 0, 0, 0, reward_infos, 0, true, 0);
+    assert_eq!(pool.max_liquidity_observed, 400);
+
+    pool.update_after_swap(0, 0, 0, 0, reward_infos, 0, true, 0);
+    assert_eq!(pool.liquidity, 0);
+    assert_eq!(pool.max_liquidity_observed, 400);
+}
+
+#[test]
+fn test_verify_fee_destinations_rejects_vaults() {
+    let mut pool = Pool::default();
+    pool.token_vault_a = Pubkey::new_unique();
+    pool.token_vault_b = Pubkey::new_unique();
+    let destination_a = Pubkey::new_unique();
+    let destination_b = Pubkey::new_unique();
+
+    assert!(pool
+        .verify_fee_destinations(destination_a, destination_b)
+        .is_ok());
+
+    let result = pool.verify_fee_destinations(pool.token_vault_a, destination_b);
+    assert_eq!(result.unwrap_err(), ErrorCode::InvalidFeeDestination.into());
+
+    let result = pool.verify_fee_destinations(destination_a, pool.token_vault_b);
+    assert_eq!(result.unwrap_err(), ErrorCode::InvalidFeeDestination.into());
+}
+
+#[test]
+fn test_verify_protocol_fee_destination_balances() {
+    let mut pool = Pool::default();
+    pool.protocol_fee_owed_a = 100;
+    pool.protocol_fee_owed_b = 200;
+
+    assert!(pool
+        .verify_protocol_fee_destination_balances(u64::MAX - 100, u64::MAX - 200)
+        .is_ok());
+
+    // The second destination overflowing rejects the collection before any transfer
+    let result = pool.verify_protocol_fee_destination_balances(0, u64::MAX - 199);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::ProtocolFeeDestinationOverflow.into()
+    );
+    let result = pool.verify_protocol_fee_destination_balances(u64::MAX, 0);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::ProtocolFeeDestinationOverflow.into()
+    );
+    assert_eq!(pool.protocol_fee_owed_a, 100);
+    assert_eq!(pool.protocol_fee_owed_b, 200);
+}
+
+#[test]
+fn test_renounce_authority_blocks_admin_updates() {
+    let mut pool = Pool::default();
+    pool.update_fee_rate(3000).unwrap();
+    pool.update_protocol_fee_rate(300).unwrap();
+
+    pool.renounce_authority().unwrap();
+    assert!(pool.authority_renounced);
+
+    let result = pool.update_fee_rate(500);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::PoolAuthorityRenounced.into()
+    );
+    let result = pool.update_protocol_fee_rate(100);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::PoolAuthorityRenounced.into()
+    );
+    let result = pool.update_reward_paused(0, pool.reward_infos, 0, true);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::PoolAuthorityRenounced.into()
+    );
+    let result = pool.update_reward_vault_warning_threshold(0, 1000);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::PoolAuthorityRenounced.into()
+    );
+    let result = pool.renounce_authority();
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::PoolAuthorityRenounced.into()
+    );
+
+    assert_eq!(pool.fee_rate, 3000);
+    assert_eq!(pool.protocol_fee_rate, 300);
+}
+
+#[test]
+fn test_directional_swap_status() {
+    let mut pool = Pool::default();
+    assert!(pool.verify_swap_direction_enabled(true).is_ok());
+    assert!(pool.verify_swap_direction_enabled(false).is_ok());
+
+    pool.update_directional_swap_status(false, true).unwrap();
+    assert_eq!(
+        pool.verify_swap_direction_enabled(true).unwrap_err(),
+        ErrorCode::SwapDirectionDisabled.into()
+    );
+    assert!(pool.verify_swap_direction_enabled(false).is_ok());
+
+    pool.update_directional_swap_status(true, false).unwrap();
+    assert!(pool.verify_swap_direction_enabled(true).is_ok());
+    assert_eq!(
+        pool.verify_swap_direction_enabled(false).unwrap_err(),
+        ErrorCode::SwapDirectionDisabled.into()
+    );
+
+    pool.update_directional_swap_status(false, false).unwrap();
+    assert!(!pool.swap_a_to_b_enabled());
+    assert!(!pool.swap_b_to_a_enabled());
+
+    pool.update_directional_swap_status(true, true).unwrap();
+    assert!(pool.swap_a_to_b_enabled());
+    assert!(pool.swap_b_to_a_enabled());
+}
+
+#[test]
+fn test_renounce_authority_keeps_swaps_and_liquidity_updates() {
+    let mut pool = Pool::default();
+    let reward_infos = [PoolRewardInfo::default(); NUM_REWARDS];
+    pool.renounce_authority().unwrap();
+
+    pool.update_rewards_and_liquidity(reward_infos, 1_000, 10);
+    assert_eq!(pool.liquidity, 1_000);
+
+    pool.update_after_swap(2_000, 5, 1 << 64, 100, reward_infos, 7, true, 20);
+    assert_eq!(pool.liquidity, 2_000);
+    assert_eq!(pool.tick_current_index, 5);
+    assert_eq!(pool.fee_growth_global_a, 100);
+    assert_eq!(pool.protocol_fee_owed_a, 7);
+    assert!(pool.authority_renounced);
+}
+
+#[test]
+fn test_update_reward_paused() {
+    let mut pool = Pool::default();
+    let mut reward_infos = [PoolRewardInfo::default(); NUM_REWARDS];
+    reward_infos[1].growth_global_x64 = 500;
+
+    pool.update_reward_paused(1, reward_infos, 10, true)
+        .unwrap();
+    assert_eq!(pool.reward_paused, [false, true, false]);
+    assert_eq!(pool.reward_last_updated_timestamp, 10);
+    assert_eq!(pool.reward_infos[1].growth_global_x64, 500);
+
+    pool.update_reward_paused(1, reward_infos, 20, false)
+        .unwrap();
+    assert_eq!(pool.reward_paused, [false; NUM_REWARDS]);
+
+    let result = pool.update_reward_paused(NUM_REWARDS, reward_infos, 30, true);
+    assert_eq!(result.unwrap_err(), ErrorCode::InvalidRewardIndex.into());
+}
+
+#[test]
+fn test_update_reward_vault_warning_threshold() {
+    let mut pool = Pool::default();
+    pool.update_reward_vault_warning_threshold(2, 2_500)
+        .unwrap();
+    assert_eq!(pool.reward_vault_warning_threshold_bps, [0, 0, 2_500]);
+
+    let result = pool.update_reward_vault_warning_threshold(0, 10_001);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::RewardVaultWarningThresholdMaxExceeded.into()
+    );
+    let result = pool.update_reward_vault_warning_threshold(NUM_REWARDS, 100);
+    assert_eq!(result.unwrap_err(), ErrorCode::InvalidRewardIndex.into());
+}
+
+#[test]
+fn test_verify_swap_amount() {
+    let mut pool = Pool::default();
+    assert!(pool.verify_swap_amount(1).is_ok());
+    assert!(pool.verify_swap_amount(u64::MAX).is_ok());
+
+    pool.update_min_swap_amount(1_000).unwrap();
+    assert_eq!(
+        pool.verify_swap_amount(999).unwrap_err(),
+        ErrorCode::SwapAmountBelowMinimum.into()
+    );
+    assert!(pool.verify_swap_amount(1_000).is_ok());
+    assert!(pool.verify_swap_amount(u64::MAX).is_ok());
+
+    pool.update_max_swap_amount(5_000).unwrap();
+    assert!(pool.verify_swap_amount(5_000).is_ok());
+    assert_eq!(
+        pool.verify_swap_amount(5_001).unwrap_err(),
+        ErrorCode::SwapAmountAboveMaximum.into()
+    );
+}
+
+#[test]
+fn test_auto_protocol_fee_daily_volume() {
+    let mut pool = Pool::default();
+    pool.record_swap_volume(1_000, 10);
+    assert_eq!(pool.auto_protocol_fee_volume_a, 0);
+    let result = pool.auto_protocol_fee_daily_volume(AUTO_PROTOCOL_FEE_ADJUSTMENT_PERIOD_SECONDS);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::AutoProtocolFeeDisabled.into()
+    );
+
+    pool.update_auto_protocol_fee(true, 500, 100).unwrap();
+    pool.record_swap_volume(1_000, 10);
+    pool.record_swap_volume(2_000, 20);
+    let result =
+        pool.auto_protocol_fee_daily_volume(100 + AUTO_PROTOCOL_FEE_ADJUSTMENT_PERIOD_SECONDS - 1);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::AutoProtocolFeeAdjustmentTooEarly.into()
+    );
+    assert_eq!(
+        pool.auto_protocol_fee_daily_volume(100 + AUTO_PROTOCOL_FEE_ADJUSTMENT_PERIOD_SECONDS)
+            .unwrap(),
+        3_000
+    );
+    // Volume over two days is halved
+    assert_eq!(
+        pool.auto_protocol_fee_daily_volume(100 + 2 * AUTO_PROTOCOL_FEE_ADJUSTMENT_PERIOD_SECONDS)
+            .unwrap(),
+        1_500
+    );
+
+    pool.apply_auto_protocol_fee_rate(300, 200_000).unwrap();
+    assert_eq!(pool.protocol_fee_rate, 300);
+    assert_eq!(pool.auto_protocol_fee_volume_a, 0);
+    assert_eq!(pool.auto_protocol_fee_adjusted_at, 200_000);
+}
+
+#[test]
+fn test_update_swap_amount_bounds() {
+    let mut pool = Pool::default();
+    pool.update_max_swap_amount(5_000).unwrap();
+    let result = pool.update_min_swap_amount(5_001);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::InvalidSwapAmountBounds.into()
+    );
+
+    pool.update_min_swap_amount(5_000).unwrap();
+    let result = pool.update_max_swap_amount(4_999);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::InvalidSwapAmountBounds.into()
+    );
+    // Zero removes the maximum
+    pool.update_max_swap_amount(0).unwrap();
+
+    pool.authority_renounced = true;
+    let result = pool.update_min_swap_amount(0);
+    assert_eq!(
+        result.unwrap_err(),
+        ErrorCode::PoolAuthorityRenounced.into()
+    );
+}
+
+#[test]
+fn test_record_swap_count_milestones() {
+    let mut pool = Pool::default();
+    pool.total_swap_count = 998;
+    assert_eq!(pool.record_swap_count(), None);
+    assert_eq!(pool.record_swap_count(), Some(1_000));
+    assert_eq!(pool.record_swap_count(), None);
+
+    pool.total_swap_count = 9_999;
+    assert_eq!(pool.record_swap_count(), Some(10_000));
+    pool.total_swap_count = 99;
+    assert_eq!(pool.record_swap_count(), None);
+    assert_eq!(pool.total_swap_count_since_reset, 5);
+
+    pool.reset_swap_count_since_reset();
+    assert_eq!(pool.total_swap_count, 100);
+    assert_eq!(pool.total_swap_count_since_reset, 0);
+}
+
+#[test]
+fn test_record_swap_volume() {
+    let mut pool = Pool::default();
+    pool.record_swap_volume(u64::MAX, 10);
+    pool.record_swap_volume(1, 20);
+    assert_eq!(pool.total_volume_a, u64::MAX as u128 + 1);
+    assert_eq!(pool.total_volume_b, 30);
+}
+
 #[cfg(test)]
 pub mod pool_builder {
     use super::{Pool, PoolRewardInfo, NUM_REWARDS};
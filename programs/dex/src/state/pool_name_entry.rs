@@ -0,0 +1,108 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+pub const MAX_POOL_NAME_LEN: usize = 32;
+
+#[account]
+#[derive(Default)]
+pub struct PoolNameEntry {
+    pub pools_config: Pubkey,          // 32
+    pub pool: Pubkey,                  // 32
+    pub name: [u8; MAX_POOL_NAME_LEN], // 32
+    pub registered_at: u64,            // 8
+}
+
+impl PoolNameEntry {
+    pub const LEN: usize = 8 + 32 + 32 + MAX_POOL_NAME_LEN + 8;
+
+    pub fn initialize(
+        &mut self,
+        pools_config: Pubkey,
+        pool: Pubkey,
+        name: &str,
+        timestamp: u64,
+    ) -> Result<()> {
+        self.pools_config = pools_config;
+        self.pool = pool;
+        self.name = encode_pool_name(name)?;
+        self.registered_at = timestamp;
+        Ok(())
+    }
+
+    /// The entry address only commits to a prefix of the name hash, so lookups must compare the
+    /// stored name as well.
+    pub fn verify_name(&self, name: &str) -> Result<()> {
+        if self.name != encode_pool_name(name)? {
+            return Err(ErrorCode::InvalidPoolName.into());
+        }
+        Ok(())
+    }
+}
+
+/// Seed of the `PoolNameEntry` of a name: the first 8 bytes of the sha256 hash of the name.
+pub fn pool_name_seed(name: &str) -> [u8; 8] {
+    let mut seed = [0u8; 8];
+    seed.copy_from_slice(&hash(name.as_bytes()).to_bytes()[..8]);
+    seed
+}
+
+/// Encode a pool name as UTF-8, null-terminated unless it uses all MAX_POOL_NAME_LEN bytes.
+pub fn encode_pool_name(name: &str) -> Result<[u8; MAX_POOL_NAME_LEN]> {
+    let bytes = name.as_bytes();
+    if bytes.is_empty() || bytes.len() > MAX_POOL_NAME_LEN || bytes.contains(&0) {
+        return Err(ErrorCode::InvalidPoolName.into());
+    }
+
+    let mut encoded = [0u8; MAX_POOL_NAME_LEN];
+    encoded[..bytes.len()].copy_from_slice(bytes);
+    Ok(encoded)
+}
+
+#[cfg(test)]
+mod pool_name_entry_tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_pool_name() {
+        let encoded = encode_pool_name("SOL/USDC 0.3%").unwrap();
+        assert_eq!(&encoded[..13], "SOL/USDC 0.3%".as_bytes());
+        assert!(encoded[13..].iter().all(|b| *b == 0));
+
+        let name = "a".repeat(MAX_POOL_NAME_LEN);
+        assert_eq!(encode_pool_name(&name).unwrap(), [b'a'; MAX_POOL_NAME_LEN]);
+    }
+
+    #[test]
+    fn test_encode_invalid_pool_name() {
+        for name in ["", "nul\0name", &"a".repeat(MAX_POOL_NAME_LEN + 1)] {
+            assert_eq!(
+                encode_pool_name(name).unwrap_err(),
+                ErrorCode::InvalidPoolName.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_pool_name_seed() {
+        assert_eq!(pool_name_seed("SOL/USDC"), pool_name_seed("SOL/USDC"));
+        assert_ne!(pool_name_seed("SOL/USDC"), pool_name_seed("SOL/USDT"));
+        assert_eq!(
+            pool_name_seed("SOL/USDC"),
+            hash(b"SOL/USDC").to_bytes()[..8]
+        );
+    }
+
+    #[test]
+    fn test_verify_name() {
+        let mut entry = PoolNameEntry::default();
+        entry
+            .initialize(Pubkey::new_unique(), Pubkey::new_unique(), "SOL/USDC", 100)
+            .unwrap();
+        assert!(entry.verify_name("SOL/USDC").is_ok());
+        assert_eq!(
+            entry.verify_name("SOL/USDT").unwrap_err(),
+            ErrorCode::InvalidPoolName.into()
+        );
+    }
+}
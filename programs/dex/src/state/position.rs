@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::ErrorCode, state::NUM_REWARDS};
+use crate::{
+    errors::ErrorCode,
+    state::{validate_tick_index, NUM_REWARDS},
+};
 
-use super::{Pool, Tick};
+use super::Pool;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
 pub struct OpenPositionBumps {
@@ -62,10 +65,9 @@ impl Position {
         tick_lower_index: i32,
         tick_upper_index: i32,
     ) -> Result<()> {
-        if !Tick::check_is_usable_tick(tick_lower_index, pool.tick_spacing)
-            || !Tick::check_is_usable_tick(tick_upper_index, pool.tick_spacing)
-            || tick_lower_index >= tick_upper_index
-        {
+        validate_tick_index(tick_lower_index, pool.tick_spacing)?;
+        validate_tick_index(tick_upper_index, pool.tick_spacing)?;
+        if tick_lower_index >= tick_upper_index {
             return Err(ErrorCode::InvalidTickIndex.into());
         }
 
@@ -3,26 +3,77 @@ use anchor_lang::prelude::*;
 
 pub const POSITION_BITMAP_USIZE: usize = 32;
 pub const POSITION_BUNDLE_SIZE: u16 = 8 * POSITION_BITMAP_USIZE as u16;
+// Maximum capacity a position bundle can be expanded to
+pub const MAX_POSITION_BUNDLE_SIZE: u16 = 1024;
 
 #[account]
 #[derive(Default)]
 pub struct PositionBundle {
-    pub position_bundle_mint: Pubkey, // 32
+    pub position_bundle_mint: Pubkey,                 // 32
     pub position_bitmap: [u8; POSITION_BITMAP_USIZE], // 32
-                                      // 64 RESERVE
+    // Zero for bundles created before capacity was added, which hold POSITION_BUNDLE_SIZE
+    pub capacity: u16, // 2
+    // Bitmap of the bundle indexes from POSITION_BUNDLE_SIZE up to capacity
+    pub extended_position_bitmap: Vec<u8>, // 4 + (capacity - POSITION_BUNDLE_SIZE) / 8
+                                           // 58 RESERVE
 }
 
 impl PositionBundle {
-    pub const LEN: usize = 8 + 32 + 32 + 64;
+    pub const LEN: usize = PositionBundle::space(POSITION_BUNDLE_SIZE);
+
+    /// Account size of a position bundle holding `capacity` positions.
+    pub const fn space(capacity: u16) -> usize {
+        8 + 32 + 32 + 2 + 4 + capacity.saturating_sub(POSITION_BUNDLE_SIZE) as usize / 8 + 58
+    }
 
     pub fn initialize(&mut self, position_bundle_mint: Pubkey) -> Result<()> {
         self.position_bundle_mint = position_bundle_mint;
+        self.capacity = POSITION_BUNDLE_SIZE;
         // position_bitmap is initialized using Default trait
         Ok(())
     }
 
+    pub fn capacity(&self) -> u16 {
+        if self.capacity == 0 {
+            POSITION_BUNDLE_SIZE
+        } else {
+            self.capacity
+        }
+    }
+
+    /// Change the number of positions the bundle can hold. Shrinking is only possible while no
+    /// bundled position is open at an index the new capacity excludes. The account must already
+    /// have been reallocated to `PositionBundle::space(new_capacity)`.
+    pub fn expand(&mut self, new_capacity: u16) -> Result<()> {
+        if new_capacity == self.capacity()
+            || new_capacity < POSITION_BUNDLE_SIZE
+            || new_capacity > MAX_POSITION_BUNDLE_SIZE
+            || new_capacity % 8 != 0
+        {
+            return Err(ErrorCode::InvalidPositionBundleCapacity.into());
+        }
+
+        let new_len = ((new_capacity - POSITION_BUNDLE_SIZE) / 8) as usize;
+        if self
+            .extended_position_bitmap
+            .iter()
+            .skip(new_len)
+            .any(|bitmap| *bitmap != 0)
+        {
+            return Err(ErrorCode::BundledPositionBeyondCapacity.into());
+        }
+
+        self.extended_position_bitmap.resize(new_len, 0);
+        self.capacity = new_capacity;
+        Ok(())
+    }
+
     pub fn is_deletable(&self) -> bool {
-        for bitmap in self.position_bitmap.iter() {
+        for bitmap in self
+            .position_bitmap
+            .iter()
+            .chain(self.extended_position_bitmap.iter())
+        {
             if *bitmap != 0 {
                 return false;
             }
@@ -30,6 +81,33 @@ impl PositionBundle {
         true
     }
 
+    /// Bundle indexes of the open bundled positions, in ascending order.
+    pub fn open_bundle_indexes(&self) -> Vec<u16> {
+        self.position_bitmap
+            .iter()
+            .chain(self.extended_position_bitmap.iter())
+            .enumerate()
+            .flat_map(|(slot, bitmap)| {
+                (0..8)
+                    .filter(move |offset| bitmap & (1 << offset) != 0)
+                    .map(move |offset| slot as u16 * 8 + offset)
+            })
+            .collect()
+    }
+
+    /// Address of the bundled position account at `bundle_index`.
+    pub fn bundled_position_address(&self, bundle_index: u16) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"bundled_position".as_ref(),
+                self.position_bundle_mint.as_ref(),
+                bundle_index.to_string().as_bytes(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
     pub fn open_bundled_position(&mut self, bundle_index: u16) -> Result<()> {
         self.update_bitmap(bundle_index, true)
     }
@@ -39,13 +117,17 @@ impl PositionBundle {
     }
 
     fn update_bitmap(&mut self, bundle_index: u16, open: bool) -> Result<()> {
-        if !PositionBundle::is_valid_bundle_index(bundle_index) {
+        if !self.is_valid_bundle_index(bundle_index) {
             return Err(ErrorCode::InvalidBundleIndex.into());
         }
 
-        let bitmap_index = bundle_index / 8;
         let bitmap_offset = bundle_index % 8;
-        let bitmap = self.position_bitmap[bitmap_index as usize];
+        let bitmap_slot = if bundle_index < POSITION_BUNDLE_SIZE {
+            &mut self.position_bitmap[(bundle_index / 8) as usize]
+        } else {
+            &mut self.extended_position_bitmap[((bundle_index - POSITION_BUNDLE_SIZE) / 8) as usize]
+        };
+        let bitmap = *bitmap_slot;
 
         let mask = 1 << bitmap_offset;
         let bit = bitmap & mask;
@@ -63,12 +145,206 @@ impl PositionBundle {
         }
 
         let updated_bitmap = bitmap ^ mask;
-        self.position_bitmap[bitmap_index as usize] = updated_bitmap;
+        *bitmap_slot = updated_bitmap;
 
         Ok(())
     }
 
-    fn is_valid_bundle_index(bundle_index: u16) -> bool {
-        bundle_index < POSITION_BUNDLE_SIZE
+    fn is_valid_bundle_index(&self, bundle_index: u16) -> bool {
+        bundle_index < self.capacity()
+    }
+}
+
+#[cfg(test)]
+mod position_bundle_deletion_tests {
+    use super::*;
+
+    #[test]
+    fn test_not_deletable_with_open_position() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+        assert!(position_bundle.is_deletable());
+
+        position_bundle.open_bundled_position(0).unwrap();
+        position_bundle
+            .open_bundled_position(POSITION_BUNDLE_SIZE - 1)
+            .unwrap();
+        position_bundle.close_bundled_position(0).unwrap();
+        assert!(!position_bundle.is_deletable());
+    }
+
+    #[test]
+    fn test_deletable_after_all_positions_closed() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+
+        for bundle_index in 0..POSITION_BUNDLE_SIZE {
+            position_bundle.open_bundled_position(bundle_index).unwrap();
+        }
+        for bundle_index in 0..POSITION_BUNDLE_SIZE {
+            assert!(!position_bundle.is_deletable());
+            position_bundle
+                .close_bundled_position(bundle_index)
+                .unwrap();
+        }
+        assert!(position_bundle.is_deletable());
+    }
+}
+
+#[cfg(test)]
+mod position_bundle_expansion_tests {
+    use super::*;
+
+    #[test]
+    fn test_space() {
+        assert_eq!(PositionBundle::LEN, 8 + 32 + 32 + 64);
+        assert_eq!(
+            PositionBundle::space(MAX_POSITION_BUNDLE_SIZE),
+            PositionBundle::LEN + 96
+        );
+    }
+
+    #[test]
+    fn test_bundle_without_capacity_holds_default_size() {
+        let mut position_bundle = PositionBundle::default();
+        assert_eq!(position_bundle.capacity(), POSITION_BUNDLE_SIZE);
+
+        let result = position_bundle.open_bundled_position(POSITION_BUNDLE_SIZE);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidBundleIndex.into());
+    }
+
+    #[test]
+    fn test_expand_opens_extended_indexes() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+        position_bundle.open_bundled_position(3).unwrap();
+
+        position_bundle.expand(512).unwrap();
+        assert_eq!(position_bundle.capacity(), 512);
+        assert_eq!(position_bundle.extended_position_bitmap, vec![0; 32]);
+
+        position_bundle.close_bundled_position(3).unwrap();
+        position_bundle
+            .open_bundled_position(POSITION_BUNDLE_SIZE + 9)
+            .unwrap();
+        assert_eq!(position_bundle.extended_position_bitmap[1], 0b10);
+        assert!(!position_bundle.is_deletable());
+
+        let result = position_bundle.open_bundled_position(512);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidBundleIndex.into());
+
+        position_bundle
+            .close_bundled_position(POSITION_BUNDLE_SIZE + 9)
+            .unwrap();
+        assert!(position_bundle.is_deletable());
+    }
+
+    #[test]
+    fn test_expand_rejects_invalid_capacity() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+
+        for new_capacity in [
+            POSITION_BUNDLE_SIZE,
+            POSITION_BUNDLE_SIZE + 4,
+            MAX_POSITION_BUNDLE_SIZE + 8,
+        ] {
+            let result = position_bundle.expand(new_capacity);
+            assert_eq!(
+                result.unwrap_err(),
+                ErrorCode::InvalidPositionBundleCapacity.into()
+            );
+        }
+
+        position_bundle.expand(MAX_POSITION_BUNDLE_SIZE).unwrap();
+        let result = position_bundle.expand(MAX_POSITION_BUNDLE_SIZE);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::InvalidPositionBundleCapacity.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod position_bundle_shrink_tests {
+    use super::*;
+
+    #[test]
+    fn test_grow_and_open_new_slot() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+
+        position_bundle.expand(POSITION_BUNDLE_SIZE + 64).unwrap();
+        assert_eq!(position_bundle.capacity(), POSITION_BUNDLE_SIZE + 64);
+        assert_eq!(position_bundle.extended_position_bitmap, vec![0; 8]);
+
+        position_bundle
+            .open_bundled_position(POSITION_BUNDLE_SIZE + 63)
+            .unwrap();
+        assert_eq!(position_bundle.extended_position_bitmap[7], 0b1000_0000);
+    }
+
+    #[test]
+    fn test_shrink_rejected_below_open_slot() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+        position_bundle.expand(512).unwrap();
+        position_bundle
+            .open_bundled_position(POSITION_BUNDLE_SIZE + 100)
+            .unwrap();
+
+        let result = position_bundle.expand(POSITION_BUNDLE_SIZE + 96);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::BundledPositionBeyondCapacity.into()
+        );
+        assert_eq!(position_bundle.capacity(), 512);
+
+        // The open slot is still covered by the new capacity
+        position_bundle.expand(POSITION_BUNDLE_SIZE + 104).unwrap();
+        assert_eq!(position_bundle.capacity(), POSITION_BUNDLE_SIZE + 104);
+        assert_eq!(position_bundle.extended_position_bitmap.len(), 13);
+
+        position_bundle
+            .close_bundled_position(POSITION_BUNDLE_SIZE + 100)
+            .unwrap();
+        position_bundle.expand(POSITION_BUNDLE_SIZE).unwrap();
+        assert!(position_bundle.extended_position_bitmap.is_empty());
+    }
+
+    #[test]
+    fn test_shrink_rejects_invalid_capacity() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+        position_bundle.expand(512).unwrap();
+
+        for new_capacity in [POSITION_BUNDLE_SIZE - 8, POSITION_BUNDLE_SIZE + 4, 512] {
+            let result = position_bundle.expand(new_capacity);
+            assert_eq!(
+                result.unwrap_err(),
+                ErrorCode::InvalidPositionBundleCapacity.into()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod open_bundle_indexes_tests {
+    use super::*;
+
+    #[test]
+    fn test_open_bundle_indexes_ascending() {
+        let mut position_bundle = PositionBundle::default();
+        position_bundle.initialize(Pubkey::new_unique()).unwrap();
+        position_bundle.expand(POSITION_BUNDLE_SIZE + 16).unwrap();
+        assert!(position_bundle.open_bundle_indexes().is_empty());
+
+        for bundle_index in [POSITION_BUNDLE_SIZE + 9, 7, 0, POSITION_BUNDLE_SIZE - 1, 8] {
+            position_bundle.open_bundled_position(bundle_index).unwrap();
+        }
+        assert_eq!(
+            position_bundle.open_bundle_indexes(),
+            vec![0, 7, 8, POSITION_BUNDLE_SIZE - 1, POSITION_BUNDLE_SIZE + 9]
+        );
     }
 }
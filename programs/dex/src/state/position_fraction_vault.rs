@@ -0,0 +1,271 @@
+use crate::errors::ErrorCode;
+use crate::math::checked_mul_shift_right;
+use anchor_lang::prelude::*;
+
+/// Holds the NFT of a fractionalized position and tracks the fees earned by its fractions.
+///
+/// Fees are distributed over all shares through a fee-per-share accumulator. Only shares deposited
+/// in a `PositionFractionShare` earn fees, the fees of the other shares stay in the position and
+/// go to the holder redeeming the position.
+#[account]
+#[derive(Default)]
+pub struct PositionFractionVault {
+    pub position: Pubkey,          // 32
+    pub position_mint: Pubkey,     // 32
+    pub fraction_mint: Pubkey,     // 32
+    pub total_shares: u64,         // 8
+    pub fee_per_share_a_x64: u128, // 16
+    pub fee_per_share_b_x64: u128, // 16
+    pub fee_tracked_a: u64,        // 8
+    pub fee_tracked_b: u64,        // 8
+    pub vault_bump: [u8; 1],       // 1
+                                   // 64 RESERVE
+}
+
+impl PositionFractionVault {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 16 + 16 + 8 + 8 + 1 + 64;
+
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [
+            &b"position_fraction_vault"[..],
+            self.position_mint.as_ref(),
+            self.vault_bump.as_ref(),
+        ]
+    }
+
+    pub fn initialize(
+        &mut self,
+        position: Pubkey,
+        position_mint: Pubkey,
+        fraction_mint: Pubkey,
+        total_shares: u64,
+        bump: u8,
+        fee_owed_a: u64,
+        fee_owed_b: u64,
+    ) -> Result<()> {
+        if total_shares == 0 {
+            return Err(ErrorCode::InvalidFractionShares.into());
+        }
+
+        self.position = position;
+        self.position_mint = position_mint;
+        self.fraction_mint = fraction_mint;
+        self.total_shares = total_shares;
+        self.vault_bump = [bump];
+        // Fees owed before the position was fractionalized are not distributed
+        self.fee_tracked_a = fee_owed_a;
+        self.fee_tracked_b = fee_owed_b;
+        Ok(())
+    }
+
+    /// Distribute the fees the position earned since the last harvest over all shares.
+    ///
+    /// # Parameters
+    /// - `fee_owed_a` - The current fee_owed_a of the position
+    /// - `fee_owed_b` - The current fee_owed_b of the position
+    pub fn harvest_fees(&mut self, fee_owed_a: u64, fee_owed_b: u64) -> Result<()> {
+        let new_fee_a = fee_owed_a
+            .checked_sub(self.fee_tracked_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        let new_fee_b = fee_owed_b
+            .checked_sub(self.fee_tracked_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+
+        // Rounding dust stays in the position
+        self.fee_per_share_a_x64 = self
+            .fee_per_share_a_x64
+            .wrapping_add(((new_fee_a as u128) << 64) / self.total_shares as u128);
+        self.fee_per_share_b_x64 = self
+            .fee_per_share_b_x64
+            .wrapping_add(((new_fee_b as u128) << 64) / self.total_shares as u128);
+        self.fee_tracked_a = fee_owed_a;
+        self.fee_tracked_b = fee_owed_b;
+        Ok(())
+    }
+
+    /// Record fees collected from the position by a share holder.
+    pub fn claim_fees(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        self.fee_tracked_a = self
+            .fee_tracked_a
+            .checked_sub(amount_a)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        self.fee_tracked_b = self
+            .fee_tracked_b
+            .checked_sub(amount_b)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        Ok(())
+    }
+}
+
+/// Fractions of a position deposited by a holder to earn the fees of the position.
+#[account]
+#[derive(Default)]
+pub struct PositionFractionShare {
+    pub vault: Pubkey,              // 32
+    pub owner: Pubkey,              // 32
+    pub shares: u64,                // 8
+    pub fee_checkpoint_a_x64: u128, // 16
+    pub fee_checkpoint_b_x64: u128, // 16
+    pub fee_owed_a: u64,            // 8
+    pub fee_owed_b: u64,            // 8
+}
+
+impl PositionFractionShare {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 16 + 16 + 8 + 8;
+
+    /// Accrue the fees earned by the deposited shares since the last settlement. The vault must
+    /// have harvested the fees of the position first.
+    pub fn settle(&mut self, vault: &PositionFractionVault) -> Result<()> {
+        let fee_delta_a = vault
+            .fee_per_share_a_x64
+            .wrapping_sub(self.fee_checkpoint_a_x64);
+        let fee_delta_b = vault
+            .fee_per_share_b_x64
+            .wrapping_sub(self.fee_checkpoint_b_x64);
+
+        self.fee_owed_a = self
+            .fee_owed_a
+            .checked_add(
+                checked_mul_shift_right(self.shares as u128, fee_delta_a)
+                    .map_err(ErrorCode::from)?,
+            )
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        self.fee_owed_b = self
+            .fee_owed_b
+            .checked_add(
+                checked_mul_shift_right(self.shares as u128, fee_delta_b)
+                    .map_err(ErrorCode::from)?,
+            )
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        self.fee_checkpoint_a_x64 = vault.fee_per_share_a_x64;
+        self.fee_checkpoint_b_x64 = vault.fee_per_share_b_x64;
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, vault: &PositionFractionVault, amount: u64) -> Result<()> {
+        self.settle(vault)?;
+        self.shares = self
+            .shares
+            .checked_add(amount)
+            .ok_or(ErrorCode::AmountCalcOverflow)?;
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, vault: &PositionFractionVault, amount: u64) -> Result<()> {
+        self.settle(vault)?;
+        self.shares = self
+            .shares
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFractionShares)?;
+        Ok(())
+    }
+
+    /// Take the accrued fees.
+    ///
+    /// # Returns
+    /// - `(u64, u64)`: The accrued fees of token A and token B
+    pub fn take_fees_owed(&mut self) -> (u64, u64) {
+        let fees_owed = (self.fee_owed_a, self.fee_owed_b);
+        self.fee_owed_a = 0;
+        self.fee_owed_b = 0;
+        fees_owed
+    }
+}
+
+#[cfg(test)]
+mod position_fraction_vault_tests {
+    use super::*;
+
+    fn vault(total_shares: u64) -> PositionFractionVault {
+        let mut vault = PositionFractionVault::default();
+        vault
+            .initialize(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                total_shares,
+                255,
+                0,
+                0,
+            )
+            .unwrap();
+        vault
+    }
+
+    #[test]
+    fn test_initialize_zero_shares() {
+        let mut vault = PositionFractionVault::default();
+        let result = vault.initialize(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            255,
+            0,
+            0,
+        );
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidFractionShares.into());
+    }
+
+    #[test]
+    fn test_fees_proportional_to_shares() {
+        let mut vault = vault(1_000);
+        let mut share_1 = PositionFractionShare::default();
+        let mut share_2 = PositionFractionShare::default();
+        share_1.deposit(&vault, 250).unwrap();
+        share_2.deposit(&vault, 750).unwrap();
+
+        vault.harvest_fees(10_000, 2_000).unwrap();
+        share_1.settle(&vault).unwrap();
+        share_2.settle(&vault).unwrap();
+        assert_eq!(share_1.take_fees_owed(), (2_500, 500));
+        assert_eq!(share_2.take_fees_owed(), (7_500, 1_500));
+    }
+
+    #[test]
+    fn test_fees_only_earned_while_deposited() {
+        let mut vault = vault(1_000);
+        let mut share = PositionFractionShare::default();
+
+        vault.harvest_fees(10_000, 0).unwrap();
+        share.deposit(&vault, 500).unwrap();
+        assert_eq!(share.fee_owed_a, 0);
+
+        vault.harvest_fees(20_000, 0).unwrap();
+        share.withdraw(&vault, 500).unwrap();
+        assert_eq!(share.fee_owed_a, 5_000);
+
+        vault.harvest_fees(30_000, 0).unwrap();
+        share.settle(&vault).unwrap();
+        assert_eq!(share.fee_owed_a, 5_000);
+    }
+
+    #[test]
+    fn test_claimed_fees_not_redistributed() {
+        let mut vault = vault(100);
+        let mut share = PositionFractionShare::default();
+        share.deposit(&vault, 100).unwrap();
+
+        vault.harvest_fees(1_000, 1_000).unwrap();
+        share.settle(&vault).unwrap();
+        let (fee_a, fee_b) = share.take_fees_owed();
+        vault.claim_fees(fee_a, fee_b).unwrap();
+        assert_eq!((vault.fee_tracked_a, vault.fee_tracked_b), (0, 0));
+
+        // The position owes the remaining fees after the claim
+        vault.harvest_fees(0, 0).unwrap();
+        share.settle(&vault).unwrap();
+        assert_eq!(share.take_fees_owed(), (0, 0));
+    }
+
+    #[test]
+    fn test_withdraw_more_than_deposited() {
+        let vault = vault(100);
+        let mut share = PositionFractionShare::default();
+        share.deposit(&vault, 10).unwrap();
+        assert_eq!(
+            share.withdraw(&vault, 11).unwrap_err(),
+            ErrorCode::InsufficientFractionShares.into()
+        );
+    }
+}
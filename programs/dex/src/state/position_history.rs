@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+pub const POSITION_HISTORY_SIZE: usize = 8;
+
+// event_type of a PositionHistoryEntry. Entries that were never written keep the default of 0.
+pub const POSITION_HISTORY_INCREASE_LIQUIDITY: u8 = 1;
+pub const POSITION_HISTORY_DECREASE_LIQUIDITY: u8 = 2;
+pub const POSITION_HISTORY_COLLECT_FEES: u8 = 3;
+
+// Latest changes to a position, kept apart from the Position so that positions without a history
+// keep their size. Seeds: [b"position_history", position]
+#[account]
+#[derive(Default)]
+pub struct PositionHistory {
+    pub position: Pubkey, // 32
+
+    // Ring buffer of the latest changes; head is the next entry written
+    pub entries: [PositionHistoryEntry; POSITION_HISTORY_SIZE], // 264 = 33 * 8
+    pub head: u8,                                               // 1
+}
+
+impl PositionHistory {
+    pub const LEN: usize = 8 + 32 + 264 + 1;
+
+    pub fn initialize(&mut self, position: Pubkey) {
+        self.position = position;
+    }
+
+    /// Record a change to the position in the ring buffer, overwriting the oldest entry once the
+    /// buffer is full.
+    pub fn record(&mut self, entry: PositionHistoryEntry) {
+        let head = self.head as usize % POSITION_HISTORY_SIZE;
+        self.entries[head] = entry;
+        self.head = ((head + 1) % POSITION_HISTORY_SIZE) as u8;
+    }
+
+    /// Returns the recorded entries, oldest first.
+    pub fn recorded_entries(&self) -> Vec<PositionHistoryEntry> {
+        let head = self.head as usize % POSITION_HISTORY_SIZE;
+        self.entries[head..]
+            .iter()
+            .chain(self.entries[..head].iter())
+            .filter(|entry| entry.event_type != 0)
+            .copied()
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, AnchorSerialize, AnchorDeserialize, Default, Debug, PartialEq)]
+pub struct PositionHistoryEntry {
+    pub event_type: u8,
+    pub slot: u64,
+    // Saturates at the bounds of i64
+    pub liquidity_delta: i64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+impl PositionHistoryEntry {
+    pub fn new(
+        event_type: u8,
+        slot: u64,
+        liquidity_delta: i128,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Self {
+        let liquidity_delta = if liquidity_delta > i64::MAX as i128 {
+            i64::MAX
+        } else if liquidity_delta < i64::MIN as i128 {
+            i64::MIN
+        } else {
+            liquidity_delta as i64
+        };
+
+        PositionHistoryEntry {
+            event_type,
+            slot,
+            liquidity_delta,
+            amount_a,
+            amount_b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod position_history_tests {
+    use super::*;
+
+    fn entry(slot: u64) -> PositionHistoryEntry {
+        PositionHistoryEntry::new(POSITION_HISTORY_INCREASE_LIQUIDITY, slot, 100, 10, 20)
+    }
+
+    #[test]
+    fn test_history_entries_oldest_first() {
+        let mut history = PositionHistory::default();
+        assert!(history.recorded_entries().is_empty());
+
+        history.record(entry(1));
+        history.record(entry(2));
+        let slots: Vec<u64> = history.recorded_entries().iter().map(|e| e.slot).collect();
+        assert_eq!(slots, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_history_overwrites_oldest() {
+        let mut history = PositionHistory::default();
+        for slot in 1..=10 {
+            history.record(entry(slot));
+        }
+        assert_eq!(history.head, 2);
+        let slots: Vec<u64> = history.recorded_entries().iter().map(|e| e.slot).collect();
+        assert_eq!(slots, vec![3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_liquidity_delta_saturates() {
+        let increase =
+            PositionHistoryEntry::new(POSITION_HISTORY_INCREASE_LIQUIDITY, 0, i128::MAX, 0, 0);
+        assert_eq!(increase.liquidity_delta, i64::MAX);
+
+        let decrease =
+            PositionHistoryEntry::new(POSITION_HISTORY_DECREASE_LIQUIDITY, 0, -500, 0, 0);
+        assert_eq!(decrease.liquidity_delta, -500);
+
+        let decrease =
+            PositionHistoryEntry::new(POSITION_HISTORY_DECREASE_LIQUIDITY, 0, i128::MIN, 0, 0);
+        assert_eq!(decrease.liquidity_delta, i64::MIN);
+    }
+}
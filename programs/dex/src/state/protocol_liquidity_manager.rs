@@ -0,0 +1,139 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+// Protocol-owned liquidity cannot be withdrawn for 48 hours after it was last deployed
+pub const PROTOCOL_LIQUIDITY_WITHDRAW_TIMELOCK: u64 = 48 * 60 * 60;
+
+#[account]
+#[derive(Default)]
+pub struct ProtocolLiquidityManager {
+    pub pools_config: Pubkey,             // 32
+    pub managed_position: Pubkey,         // 32
+    pub treasury_token_account_a: Pubkey, // 32
+    pub treasury_token_account_b: Pubkey, // 32
+    pub min_tick_lower: i32,              // 4
+    pub max_tick_upper: i32,              // 4
+    pub withdraw_unlock_timestamp: u64,   // 8
+    pub manager_bump: [u8; 1],            // 1
+                                          // 64 RESERVE
+}
+
+impl ProtocolLiquidityManager {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 4 + 4 + 8 + 1 + 64;
+
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [
+            &b"pol_manager"[..],
+            self.pools_config.as_ref(),
+            self.manager_bump.as_ref(),
+        ]
+    }
+
+    pub fn initialize(
+        &mut self,
+        pools_config: Pubkey,
+        bump: u8,
+        managed_position: Pubkey,
+        treasury_token_account_a: Pubkey,
+        treasury_token_account_b: Pubkey,
+        min_tick_lower: i32,
+        max_tick_upper: i32,
+        position_tick_lower_index: i32,
+        position_tick_upper_index: i32,
+    ) -> Result<()> {
+        if min_tick_lower >= max_tick_upper
+            || position_tick_lower_index < min_tick_lower
+            || position_tick_upper_index > max_tick_upper
+        {
+            return Err(ErrorCode::ProtocolLiquidityRangeOutOfBounds.into());
+        }
+
+        self.pools_config = pools_config;
+        self.manager_bump = [bump];
+        self.managed_position = managed_position;
+        self.treasury_token_account_a = treasury_token_account_a;
+        self.treasury_token_account_b = treasury_token_account_b;
+        self.min_tick_lower = min_tick_lower;
+        self.max_tick_upper = max_tick_upper;
+        self.withdraw_unlock_timestamp = 0;
+        Ok(())
+    }
+
+    pub fn record_deploy(&mut self, timestamp: u64) -> Result<()> {
+        self.withdraw_unlock_timestamp = timestamp
+            .checked_add(PROTOCOL_LIQUIDITY_WITHDRAW_TIMELOCK)
+            .ok_or(ErrorCode::InvalidTimestamp)?;
+        Ok(())
+    }
+
+    pub fn check_withdraw_unlocked(&self, timestamp: u64) -> Result<()> {
+        if timestamp < self.withdraw_unlock_timestamp {
+            return Err(ErrorCode::ProtocolLiquidityWithdrawLocked.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod protocol_liquidity_manager_tests {
+    use super::*;
+
+    fn initialize(
+        min_tick_lower: i32,
+        max_tick_upper: i32,
+        position_tick_lower_index: i32,
+        position_tick_upper_index: i32,
+    ) -> Result<ProtocolLiquidityManager> {
+        let mut manager = ProtocolLiquidityManager::default();
+        manager.initialize(
+            Pubkey::new_unique(),
+            255,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            min_tick_lower,
+            max_tick_upper,
+            position_tick_lower_index,
+            position_tick_upper_index,
+        )?;
+        Ok(manager)
+    }
+
+    #[test]
+    fn test_initialize_position_within_bounds() {
+        assert!(initialize(-1000, 1000, -1000, 1000).is_ok());
+        assert!(initialize(-1000, 1000, -64, 64).is_ok());
+    }
+
+    #[test]
+    fn test_initialize_position_out_of_bounds() {
+        for (min_tick_lower, max_tick_upper, lower, upper) in [
+            (-1000, 1000, -1064, 64),
+            (-1000, 1000, -64, 1064),
+            (1000, -1000, -64, 64),
+        ] {
+            let result = initialize(min_tick_lower, max_tick_upper, lower, upper);
+            assert_eq!(
+                result.err().unwrap(),
+                ErrorCode::ProtocolLiquidityRangeOutOfBounds.into()
+            );
+        }
+    }
+
+    #[test]
+    fn test_withdraw_timelock() {
+        let mut manager = initialize(-1000, 1000, -64, 64).unwrap();
+        assert!(manager.check_withdraw_unlocked(0).is_ok());
+
+        manager.record_deploy(1_000).unwrap();
+        let result =
+            manager.check_withdraw_unlocked(1_000 + PROTOCOL_LIQUIDITY_WITHDRAW_TIMELOCK - 1);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::ProtocolLiquidityWithdrawLocked.into()
+        );
+        assert!(manager
+            .check_withdraw_unlocked(1_000 + PROTOCOL_LIQUIDITY_WITHDRAW_TIMELOCK)
+            .is_ok());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::errors::ErrorCode;
+use crate::math::{MAX_FEE_RATE, MAX_PROTOCOL_FEE_RATE};
+use crate::state::validate_protocol_fee_rate_bounds;
+use anchor_lang::prelude::*;
+
+// Parameter changes can be executed 48 hours after they were proposed at the earliest
+pub const MIN_GOVERNANCE_DELAY_SECONDS: u64 = 48 * 60 * 60;
+
+// Sets Pool.fee_rate of the target pool to new_value
+pub const PARAMETER_CHANGE_FEE_RATE: u8 = 0;
+// Sets Pool.protocol_fee_rate of the target pool to new_value
+pub const PARAMETER_CHANGE_PROTOCOL_FEE_RATE: u8 = 1;
+// Sets PoolsConfig.default_protocol_fee_rate to new_value
+pub const PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE: u8 = 2;
+// Sets PoolsConfig.min_protocol_fee_rate and max_protocol_fee_rate to the bounds packed in new_value
+pub const PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS: u8 = 3;
+
+#[account]
+#[derive(Default)]
+pub struct ProtocolParameterChange {
+    pub pools_config: Pubkey, // 32
+    pub nonce: u64,           // 8
+    pub change_type: u8,      // 1
+    // Pool the change applies to, the default pubkey for config-level changes
+    pub pool: Pubkey,       // 32
+    pub new_value: u64,     // 8
+    pub proposed_at: u64,   // 8
+    pub execute_after: u64, // 8
+    pub proposer: Pubkey,   // 32
+                            // 64 RESERVE
+}
+
+impl ProtocolParameterChange {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 32 + 8 + 8 + 8 + 32 + 64;
+
+    pub fn initialize(
+        &mut self,
+        pools_config: Pubkey,
+        nonce: u64,
+        change_type: u8,
+        pool: Option<Pubkey>,
+        new_value: u64,
+        proposer: Pubkey,
+        timestamp: u64,
+    ) -> Result<()> {
+        validate_parameter_change(change_type, pool.is_some(), new_value)?;
+
+        self.pools_config = pools_config;
+        self.nonce = nonce;
+        self.change_type = change_type;
+        self.pool = pool.unwrap_or_default();
+        self.new_value = new_value;
+        self.proposer = proposer;
+        self.proposed_at = timestamp;
+        self.execute_after = timestamp
+            .checked_add(MIN_GOVERNANCE_DELAY_SECONDS)
+            .ok_or(ErrorCode::InvalidTimestamp)?;
+        Ok(())
+    }
+
+    pub fn is_pool_change(&self) -> bool {
+        is_pool_change_type(self.change_type)
+    }
+
+    pub fn check_executable(&self, timestamp: u64) -> Result<()> {
+        if timestamp < self.execute_after {
+            return Err(ErrorCode::ParameterChangeDelayNotElapsed.into());
+        }
+        Ok(())
+    }
+}
+
+fn is_pool_change_type(change_type: u8) -> bool {
+    change_type == PARAMETER_CHANGE_FEE_RATE || change_type == PARAMETER_CHANGE_PROTOCOL_FEE_RATE
+}
+
+/// Pack protocol fee rate bounds into the new_value of a parameter change.
+pub fn pack_protocol_fee_rate_bounds(
+    min_protocol_fee_rate: u16,
+    max_protocol_fee_rate: u16,
+) -> u64 {
+    (min_protocol_fee_rate as u64) | ((max_protocol_fee_rate as u64) << 16)
+}
+
+pub fn unpack_protocol_fee_rate_bounds(new_value: u64) -> (u16, u16) {
+    (new_value as u16, (new_value >> 16) as u16)
+}
+
+pub fn validate_parameter_change(change_type: u8, has_pool: bool, new_value: u64) -> Result<()> {
+    if change_type == PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS {
+        if has_pool || new_value >> 32 != 0 {
+            return Err(ErrorCode::InvalidProposalType.into());
+        }
+        let (min_protocol_fee_rate, max_protocol_fee_rate) =
+            unpack_protocol_fee_rate_bounds(new_value);
+        return validate_protocol_fee_rate_bounds(min_protocol_fee_rate, max_protocol_fee_rate);
+    }
+
+    let max_value = match change_type {
+        PARAMETER_CHANGE_FEE_RATE => MAX_FEE_RATE,
+        PARAMETER_CHANGE_PROTOCOL_FEE_RATE | PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE => {
+            MAX_PROTOCOL_FEE_RATE
+        }
+        _ => return Err(ErrorCode::InvalidProposalType.into()),
+    };
+    if has_pool != is_pool_change_type(change_type) {
+        return Err(ErrorCode::InvalidProposalType.into());
+    }
+    if new_value > max_value as u64 {
+        return Err(match change_type {
+            PARAMETER_CHANGE_FEE_RATE => ErrorCode::FeeRateMaxExceeded,
+            _ => ErrorCode::ProtocolFeeRateMaxExceeded,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod protocol_parameter_change_tests {
+    use super::*;
+
+    fn parameter_change(
+        change_type: u8,
+        pool: Option<Pubkey>,
+        new_value: u64,
+    ) -> Result<ProtocolParameterChange> {
+        let mut parameter_change = ProtocolParameterChange::default();
+        parameter_change.initialize(
+            Pubkey::new_unique(),
+            0,
+            change_type,
+            pool,
+            new_value,
+            Pubkey::new_unique(),
+            1_000,
+        )?;
+        Ok(parameter_change)
+    }
+
+    #[test]
+    fn test_execute_after_delay() {
+        let parameter_change =
+            parameter_change(PARAMETER_CHANGE_FEE_RATE, Some(Pubkey::new_unique()), 3000).unwrap();
+        assert_eq!(parameter_change.proposed_at, 1_000);
+        assert_eq!(
+            parameter_change.execute_after,
+            1_000 + MIN_GOVERNANCE_DELAY_SECONDS
+        );
+
+        let result = parameter_change.check_executable(1_000 + MIN_GOVERNANCE_DELAY_SECONDS - 1);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::ParameterChangeDelayNotElapsed.into()
+        );
+        assert!(parameter_change
+            .check_executable(1_000 + MIN_GOVERNANCE_DELAY_SECONDS)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_pool_target_matches_change_type() {
+        assert!(parameter_change(PARAMETER_CHANGE_PROTOCOL_FEE_RATE, None, 300).is_err());
+        assert!(parameter_change(
+            PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE,
+            Some(Pubkey::new_unique()),
+            300
+        )
+        .is_err());
+
+        let parameter_change =
+            parameter_change(PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE, None, 300).unwrap();
+        assert!(!parameter_change.is_pool_change());
+        assert_eq!(parameter_change.pool, Pubkey::default());
+    }
+
+    #[test]
+    fn test_rejects_invalid_values() {
+        let result = parameter_change(4, None, 0);
+        assert_eq!(result.err().unwrap(), ErrorCode::InvalidProposalType.into());
+
+        let result = parameter_change(
+            PARAMETER_CHANGE_FEE_RATE,
+            Some(Pubkey::new_unique()),
+            MAX_FEE_RATE as u64 + 1,
+        );
+        assert_eq!(result.err().unwrap(), ErrorCode::FeeRateMaxExceeded.into());
+
+        let result = parameter_change(
+            PARAMETER_CHANGE_DEFAULT_PROTOCOL_FEE_RATE,
+            None,
+            MAX_PROTOCOL_FEE_RATE as u64 + 1,
+        );
+        assert_eq!(
+            result.err().unwrap(),
+            ErrorCode::ProtocolFeeRateMaxExceeded.into()
+        );
+    }
+
+    #[test]
+    fn test_protocol_fee_rate_bounds_change() {
+        let new_value = pack_protocol_fee_rate_bounds(100, 500);
+        assert_eq!(unpack_protocol_fee_rate_bounds(new_value), (100, 500));
+
+        let bounds_change =
+            parameter_change(PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS, None, new_value).unwrap();
+        assert_eq!(
+            bounds_change.execute_after,
+            1_000 + MIN_GOVERNANCE_DELAY_SECONDS
+        );
+
+        let result = parameter_change(
+            PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS,
+            None,
+            pack_protocol_fee_rate_bounds(500, 100),
+        );
+        assert_eq!(
+            result.err().unwrap(),
+            ErrorCode::InvalidProtocolFeeRateBounds.into()
+        );
+        let result = parameter_change(
+            PARAMETER_CHANGE_PROTOCOL_FEE_RATE_BOUNDS,
+            Some(Pubkey::new_unique()),
+            new_value,
+        );
+        assert_eq!(result.err().unwrap(), ErrorCode::InvalidProposalType.into());
+    }
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+// Maximum number of points of a reward emissions schedule
+pub const MAX_REWARD_EMISSIONS_SCHEDULE_POINTS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardEmissionsSchedulePoint {
+    pub start_timestamp: u64,           // 8
+    pub emissions_per_second_x64: u128, // 16
+}
+
+impl RewardEmissionsSchedulePoint {
+    pub const LEN: usize = 8 + 16;
+}
+
+/// Emission rates of a pool reward over time, stored in the pool next to the reward.
+///
+/// The rate of a point applies from its start timestamp until the start of the next point.
+/// Nothing is emitted before the first point. After the last point the reward keeps emitting at
+/// the rate of the last point if `hold_final_rate` is set, otherwise the last point ends the
+/// schedule and emissions stop. A schedule without points is not set, and the reward emits at
+/// the emission rate of its reward info.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct RewardEmissionsSchedule {
+    pub hold_final_rate: bool, // 1
+    pub num_points: u8,        // 1
+    // 24 * MAX_REWARD_EMISSIONS_SCHEDULE_POINTS
+    pub points: [RewardEmissionsSchedulePoint; MAX_REWARD_EMISSIONS_SCHEDULE_POINTS],
+}
+
+impl RewardEmissionsSchedule {
+    pub const LEN: usize =
+        1 + 1 + RewardEmissionsSchedulePoint::LEN * MAX_REWARD_EMISSIONS_SCHEDULE_POINTS;
+
+    pub fn update_points(
+        &mut self,
+        points: &[RewardEmissionsSchedulePoint],
+        hold_final_rate: bool,
+    ) -> Result<()> {
+        if points.is_empty() || points.len() > MAX_REWARD_EMISSIONS_SCHEDULE_POINTS {
+            return Err(ErrorCode::InvalidRewardEmissionsSchedule.into());
+        }
+        if points
+            .windows(2)
+            .any(|pair| pair[0].start_timestamp >= pair[1].start_timestamp)
+        {
+            return Err(ErrorCode::InvalidRewardEmissionsSchedule.into());
+        }
+
+        self.hold_final_rate = hold_final_rate;
+        self.num_points = points.len() as u8;
+        self.points =
+            [RewardEmissionsSchedulePoint::default(); MAX_REWARD_EMISSIONS_SCHEDULE_POINTS];
+        self.points[..points.len()].copy_from_slice(points);
+        Ok(())
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.num_points > 0
+    }
+
+    fn active_points(&self) -> &[RewardEmissionsSchedulePoint] {
+        &self.points[..self.num_points as usize]
+    }
+
+    /// Returns the emission rate active at the given timestamp.
+    pub fn emissions_per_second_at(&self, timestamp: u64) -> u128 {
+        let points = self.active_points();
+        match points
+            .iter()
+            .rposition(|point| point.start_timestamp <= timestamp)
+        {
+            None => 0,
+            Some(index) if index == points.len() - 1 && !self.hold_final_rate => 0,
+            Some(index) => points[index].emissions_per_second_x64,
+        }
+    }
+
+    /// Returns the start timestamp of the first point after the given timestamp, at which the
+    /// emission rate changes next.
+    pub fn next_rate_change_after(&self, timestamp: u64) -> Option<u64> {
+        self.active_points()
+            .iter()
+            .map(|point| point.start_timestamp)
+            .find(|start_timestamp| *start_timestamp > timestamp)
+    }
+}
+
+#[cfg(test)]
+mod reward_emissions_schedule_tests {
+    use super::*;
+
+    fn point(start_timestamp: u64, emissions_per_second_x64: u128) -> RewardEmissionsSchedulePoint {
+        RewardEmissionsSchedulePoint {
+            start_timestamp,
+            emissions_per_second_x64,
+        }
+    }
+
+    fn schedule(
+        points: &[RewardEmissionsSchedulePoint],
+        hold_final_rate: bool,
+    ) -> RewardEmissionsSchedule {
+        let mut schedule = RewardEmissionsSchedule::default();
+        schedule.update_points(points, hold_final_rate).unwrap();
+        schedule
+    }
+
+    #[test]
+    fn test_rate_selected_per_interval() {
+        let schedule = schedule(&[point(100, 30), point(200, 20), point(300, 10)], true);
+
+        // No emissions before the first point
+        assert_eq!(schedule.emissions_per_second_at(0), 0);
+        assert_eq!(schedule.emissions_per_second_at(99), 0);
+        assert_eq!(schedule.emissions_per_second_at(100), 30);
+        assert_eq!(schedule.emissions_per_second_at(199), 30);
+        assert_eq!(schedule.emissions_per_second_at(200), 20);
+        assert_eq!(schedule.emissions_per_second_at(300), 10);
+        assert_eq!(schedule.emissions_per_second_at(u64::MAX), 10);
+    }
+
+    #[test]
+    fn test_last_point_ends_schedule_without_hold() {
+        let schedule = schedule(&[point(100, 30), point(200, 20)], false);
+        assert_eq!(schedule.emissions_per_second_at(199), 30);
+        assert_eq!(schedule.emissions_per_second_at(200), 0);
+        assert_eq!(schedule.emissions_per_second_at(1_000), 0);
+    }
+
+    #[test]
+    fn test_next_rate_change() {
+        let schedule = schedule(&[point(100, 30), point(200, 20)], true);
+        assert_eq!(schedule.next_rate_change_after(0), Some(100));
+        assert_eq!(schedule.next_rate_change_after(100), Some(200));
+        assert_eq!(schedule.next_rate_change_after(200), None);
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        let mut schedule = RewardEmissionsSchedule::default();
+        for points in [
+            vec![],
+            vec![point(100, 1), point(100, 2)],
+            vec![point(200, 1), point(100, 2)],
+            (0..9).map(|i| point(i, 1)).collect(),
+        ] {
+            assert_eq!(
+                schedule.update_points(&points, true).unwrap_err(),
+                ErrorCode::InvalidRewardEmissionsSchedule.into()
+            );
+        }
+    }
+}
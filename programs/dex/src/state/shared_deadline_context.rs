@@ -0,0 +1,97 @@
+use crate::errors::ErrorCode;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct SharedDeadlineContext {
+    pub authority: Pubkey, // 32
+    pub nonce: u64,        // 8
+    pub deadline: u64,     // 8
+    pub remaining_ops: u8, // 1
+}
+
+impl SharedDeadlineContext {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+
+    pub fn initialize(
+        &mut self,
+        authority: Pubkey,
+        nonce: u64,
+        deadline: u64,
+        remaining_ops: u8,
+        timestamp: u64,
+    ) -> Result<()> {
+        if deadline < timestamp {
+            return Err(ErrorCode::DeadlineExceeded.into());
+        }
+        if remaining_ops == 0 {
+            return Err(ErrorCode::DeadlineContextExhausted.into());
+        }
+
+        self.authority = authority;
+        self.nonce = nonce;
+        self.deadline = deadline;
+        self.remaining_ops = remaining_ops;
+        Ok(())
+    }
+
+    /// Check the shared deadline for one operation of the batch and count the operation.
+    pub fn consume(&mut self, timestamp: u64) -> Result<()> {
+        if timestamp > self.deadline {
+            return Err(ErrorCode::DeadlineExceeded.into());
+        }
+        if self.remaining_ops == 0 {
+            return Err(ErrorCode::DeadlineContextExhausted.into());
+        }
+        self.remaining_ops -= 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod shared_deadline_context_tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_rejects_past_deadline() {
+        let mut context = SharedDeadlineContext::default();
+        let result = context.initialize(Pubkey::new_unique(), 0, 99, 2, 100);
+        assert_eq!(result.unwrap_err(), ErrorCode::DeadlineExceeded.into());
+
+        let result = context.initialize(Pubkey::new_unique(), 0, 100, 0, 100);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::DeadlineContextExhausted.into()
+        );
+    }
+
+    #[test]
+    fn test_consume_counts_operations() {
+        let mut context = SharedDeadlineContext::default();
+        context
+            .initialize(Pubkey::new_unique(), 0, 100, 2, 50)
+            .unwrap();
+
+        context.consume(60).unwrap();
+        context.consume(100).unwrap();
+        assert_eq!(context.remaining_ops, 0);
+
+        let result = context.consume(100);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::DeadlineContextExhausted.into()
+        );
+    }
+
+    #[test]
+    fn test_consume_after_deadline() {
+        let mut context = SharedDeadlineContext::default();
+        context
+            .initialize(Pubkey::new_unique(), 0, 100, 2, 50)
+            .unwrap();
+
+        let result = context.consume(101);
+        assert_eq!(result.unwrap_err(), ErrorCode::DeadlineExceeded.into());
+        assert_eq!(context.remaining_ops, 2);
+    }
+}
@@ -1,18 +1,37 @@
 use crate::errors::ErrorCode;
+use crate::math::tick_bitmap_search;
 use crate::state::NUM_REWARDS;
 use anchor_lang::prelude::*;
 
 use super::Pool;
 
 // Max & min tick index based on sqrt(1.0001) & max.min price of 2^64
-pub const MAX_TICK_INDEX: i32 = 443636;
-pub const MIN_TICK_INDEX: i32 = -443636;
+pub use dex_math::{MAX_TICK_INDEX, MIN_TICK_INDEX};
 
 // We have two consts because most of our code uses it as a i32. However,
 // for us to use it in tick array declarations, anchor requires it to be a usize.
 pub const TICK_ARRAY_SIZE: i32 = 88;
 pub const TICK_ARRAY_SIZE_USIZE: usize = 88;
 
+/// Validate a tick index provided to the program for a pool with the given tick spacing.
+///
+/// # Parameters
+/// - `tick_index` - A i32 integer representing the tick index
+/// - `tick_spacing` - A u16 integer of the tick spacing for the pool
+///
+/// # Errors
+/// - `TickIndexOutOfBounds`: The tick index is not within [MIN_TICK_INDEX, MAX_TICK_INDEX]
+/// - `TickNotAlignedToSpacing`: The tick index is not a multiple of the tick spacing
+pub fn validate_tick_index(tick_index: i32, tick_spacing: u16) -> Result<()> {
+    if tick_index < MIN_TICK_INDEX || tick_index > MAX_TICK_INDEX {
+        return Err(ErrorCode::TickIndexOutOfBounds.into());
+    }
+    if tick_index % tick_spacing as i32 != 0 {
+        return Err(ErrorCode::TickNotAlignedToSpacing.into());
+    }
+    Ok(())
+}
+
 #[zero_copy(unsafe)]
 #[repr(packed)]
 #[derive(Default, Debug, PartialEq)]
@@ -38,13 +57,19 @@ impl Tick {
     ///
     /// # Parameters
     /// - `update` - An update object to update the values in this tick
-    pub fn update(&mut self, update: &TickUpdate) {
+    ///
+    /// # Returns
+    /// - `true`: The tick flipped from uninitialized to initialized or back
+    /// - `false`: The initialized state of the tick is unchanged
+    pub fn update(&mut self, update: &TickUpdate) -> bool {
+        let flipped = self.initialized != update.initialized;
         self.initialized = update.initialized;
         self.liquidity_net = update.liquidity_net;
         self.liquidity_gross = update.liquidity_gross;
         self.fee_growth_outside_a = update.fee_growth_outside_a;
         self.fee_growth_outside_b = update.fee_growth_outside_b;
         self.reward_growths_outside = update.reward_growths_outside;
+        flipped
     }
 
     /// Check that the tick index is within the supported range of this contract
@@ -212,6 +237,36 @@ impl TickArray {
         Ok(None)
     }
 
+    /// Bitmap of the initialized ticks of this array, bit i is set if the tick at offset i is
+    /// initialized.
+    pub fn initialized_bitmap(&self) -> u128 {
+        let mut bitmap = 0;
+        for offset in 0..TICK_ARRAY_SIZE_USIZE {
+            if self.ticks[offset].initialized {
+                bitmap |= 1 << offset;
+            }
+        }
+        bitmap
+    }
+
+    /// Same as `get_next_init_tick_index`, but finds the next initialized tick with bit operations
+    /// on the `initialized_bitmap` of this array instead of visiting the ticks one by one.
+    pub fn get_next_init_tick_index_in_bitmap(
+        &self,
+        initialized_bitmap: u128,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Option<i32>> {
+        if !self.in_search_range(tick_index, tick_spacing, !a_to_b) {
+            return Err(ErrorCode::InvalidTickArraySequence.into());
+        }
+
+        let curr_offset = self.tick_offset(tick_index, tick_spacing)? as i32;
+        Ok(tick_bitmap_search(initialized_bitmap, curr_offset, a_to_b)
+            .map(|offset| (offset * tick_spacing as i32) + self.start_tick_index))
+    }
+
     /// Initialize the TickArray object
     ///
     /// # Parameters
@@ -259,6 +314,9 @@ impl TickArray {
     /// - `tick_spacing` - A u8 integer of the tick spacing for this pool
     /// - `update` - A reference to a TickUpdate object to update the Tick object at the given index
     ///
+    /// # Returns
+    /// - `bool`: Whether the Tick object flipped between initialized and uninitialized
+    ///
     /// # Errors
     /// - `TickNotFound`: - The provided tick-index is not an initializable tick index in this pool w/ this tick-spacing.
     pub fn update_tick(
@@ -266,7 +324,7 @@ impl TickArray {
         tick_index: i32,
         tick_spacing: u16,
         update: &TickUpdate,
-    ) -> Result<()> {
+    ) -> Result<bool> {
         if !self.check_in_array_bounds(tick_index, tick_spacing)
             || !Tick::check_is_usable_tick(tick_index, tick_spacing)
         {
@@ -276,8 +334,7 @@ impl TickArray {
         if offset < 0 {
             return Err(ErrorCode::TickNotFound.into());
         }
-        self.ticks.get_mut(offset as usize).unwrap().update(update);
-        Ok(())
+        Ok(self.ticks.get_mut(offset as usize).unwrap().update(update))
     }
 
     /// Checks that this array holds the next tick index for the current tick index, given the pool's tick spacing & search direction.
@@ -618,3 +675,120 @@ mod array_update_tests {
         assert_eq!(*result, expected);
     }
 }
+
+#[cfg(test)]
+mod initialized_bitmap_tests {
+    use super::*;
+
+    const TS_8: u16 = 8;
+
+    fn tick_array(start_tick_index: i32, initialized_offsets: &[usize]) -> TickArray {
+        let mut tick_array = TickArray::default();
+        tick_array.start_tick_index = start_tick_index;
+        for offset in initialized_offsets {
+            tick_array.ticks[*offset].initialized = true;
+        }
+        tick_array
+    }
+
+    #[test]
+    fn test_initialized_bitmap() {
+        assert_eq!(tick_array(0, &[]).initialized_bitmap(), 0);
+        assert_eq!(
+            tick_array(0, &[0, 3, 87]).initialized_bitmap(),
+            1 | 1 << 3 | 1 << 87
+        );
+    }
+
+    #[test]
+    fn test_bitmap_search_matches_scan() {
+        for initialized_offsets in [vec![], vec![0], vec![87], vec![0, 40, 87], vec![5, 6, 7]] {
+            let tick_array = tick_array(-704, &initialized_offsets);
+            let bitmap = tick_array.initialized_bitmap();
+            for a_to_b in [true, false] {
+                for tick_index in -720..16 {
+                    assert_eq!(
+                        tick_array
+                            .get_next_init_tick_index_in_bitmap(bitmap, tick_index, TS_8, a_to_b)
+                            .ok(),
+                        tick_array
+                            .get_next_init_tick_index(tick_index, TS_8, a_to_b)
+                            .ok()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitmap_search_does_not_visit_ticks() {
+        // The ticks of the array are empty, so any tick found comes from the bitmap alone
+        let tick_array = tick_array(0, &[]);
+        let bitmap = 1 | 1 << 87;
+
+        assert_eq!(
+            tick_array
+                .get_next_init_tick_index_in_bitmap(bitmap, 0, TS_8, false)
+                .unwrap(),
+            Some(696)
+        );
+        assert_eq!(
+            tick_array
+                .get_next_init_tick_index_in_bitmap(bitmap, 688, TS_8, true)
+                .unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            tick_array.get_next_init_tick_index(0, TS_8, false).unwrap(),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod validate_tick_index_tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_ticks() {
+        assert!(validate_tick_index(0, 64).is_ok());
+        assert!(validate_tick_index(128, 64).is_ok());
+        assert!(validate_tick_index(-128, 64).is_ok());
+        assert!(validate_tick_index(MAX_TICK_INDEX, 1).is_ok());
+        assert!(validate_tick_index(MIN_TICK_INDEX, 1).is_ok());
+    }
+
+    #[test]
+    fn test_misaligned_ticks() {
+        assert_eq!(
+            validate_tick_index(1, 64).unwrap_err(),
+            ErrorCode::TickNotAlignedToSpacing.into()
+        );
+        assert_eq!(
+            validate_tick_index(-65, 64).unwrap_err(),
+            ErrorCode::TickNotAlignedToSpacing.into()
+        );
+        // MAX_TICK_INDEX is not a multiple of 64
+        assert_eq!(
+            validate_tick_index(MAX_TICK_INDEX, 64).unwrap_err(),
+            ErrorCode::TickNotAlignedToSpacing.into()
+        );
+    }
+
+    #[test]
+    fn test_out_of_bounds_ticks() {
+        assert_eq!(
+            validate_tick_index(MAX_TICK_INDEX + 1, 1).unwrap_err(),
+            ErrorCode::TickIndexOutOfBounds.into()
+        );
+        assert_eq!(
+            validate_tick_index(MIN_TICK_INDEX - 1, 1).unwrap_err(),
+            ErrorCode::TickIndexOutOfBounds.into()
+        );
+        // Bounds are checked before the alignment
+        assert_eq!(
+            validate_tick_index(MAX_TICK_INDEX + 4, 4).unwrap_err(),
+            ErrorCode::TickIndexOutOfBounds.into()
+        );
+    }
+}
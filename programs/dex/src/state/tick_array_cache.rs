@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+
+use super::{Pool, TickArray};
+
+// Maximum number of tick arrays warmed by a single instruction
+pub const MAX_WARM_TICK_ARRAYS: usize = 8;
+
+/// Compact view of the initialized ticks of a tick array.
+///
+/// `next_initialized_above` is the lowest and `next_initialized_below` the highest initialized
+/// tick of the array, which are the first ticks a swap entering the array from below and from
+/// above would cross. Both are only meaningful if `initialized_count` is not zero.
+/// `initialized_bitmap` resolves the searches that start between the two.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TickArraySummary {
+    pub start_tick_index: i32,       // 4
+    pub initialized_count: u8,       // 1
+    pub next_initialized_above: i32, // 4
+    pub next_initialized_below: i32, // 4
+    pub initialized_bitmap: u128,    // 16
+}
+
+impl TickArraySummary {
+    pub const LEN: usize = 4 + 1 + 4 + 4 + 16;
+
+    pub fn new(tick_array: &TickArray, tick_spacing: u16) -> Self {
+        let mut summary = TickArraySummary {
+            start_tick_index: tick_array.start_tick_index,
+            initialized_bitmap: tick_array.initialized_bitmap(),
+            ..Default::default()
+        };
+
+        for (offset, tick) in tick_array.ticks.iter().enumerate() {
+            if !tick.initialized {
+                continue;
+            }
+            let tick_index = tick_array.start_tick_index + offset as i32 * tick_spacing as i32;
+            if summary.initialized_count == 0 {
+                summary.next_initialized_above = tick_index;
+            }
+            summary.next_initialized_below = tick_index;
+            summary.initialized_count += 1;
+        }
+
+        summary
+    }
+
+    /// Get the next initialized tick index in the array without scanning its ticks.
+    ///
+    /// Follows the search semantics of `TickArray::get_next_init_tick_index`, the search index
+    /// must already be in the search range of the array.
+    ///
+    /// # Returns
+    /// - `Some(Some(i32))`: The next initialized tick index in the array
+    /// - `Some(None)`: The array has no initialized tick in the search direction
+    /// - `None`: The search starts between the min and max ticks, use `initialized_bitmap`
+    pub fn get_next_init_tick_index(&self, tick_index: i32, a_to_b: bool) -> Option<Option<i32>> {
+        if self.initialized_count == 0 {
+            return Some(None);
+        }
+
+        // a_to_b searches include the current tick, b_to_a searches exclude it
+        if a_to_b {
+            if tick_index < self.next_initialized_above {
+                return Some(None);
+            }
+            if tick_index >= self.next_initialized_below {
+                return Some(Some(self.next_initialized_below));
+            }
+        } else {
+            if tick_index >= self.next_initialized_below {
+                return Some(None);
+            }
+            if tick_index < self.next_initialized_above {
+                return Some(Some(self.next_initialized_above));
+            }
+        }
+        None
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct TickArrayCache {
+    pub tick_array: Pubkey,        // 32
+    pub tick_flip_count: u64,      // 8
+    pub valid_until_slot: u64,     // 8
+    pub summary: TickArraySummary, // 29
+}
+
+impl TickArrayCache {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + TickArraySummary::LEN;
+
+    pub fn update(
+        &mut self,
+        tick_array: Pubkey,
+        pool: &Pool,
+        summary: TickArraySummary,
+        slot: u64,
+    ) {
+        self.tick_array = tick_array;
+        self.tick_flip_count = pool.tick_flip_count;
+        // Entries expire after one slot to avoid stale reads
+        self.valid_until_slot = slot + 1;
+        self.summary = summary;
+    }
+
+    /// The summary can be used as long as the entry did not expire and no tick of the pool was
+    /// initialized or uninitialized since the tick array was warmed.
+    pub fn get_valid_summary(&self, pool: &Pool, slot: u64) -> Option<TickArraySummary> {
+        if slot > self.valid_until_slot || pool.tick_flip_count != self.tick_flip_count {
+            return None;
+        }
+        Some(self.summary)
+    }
+}
+
+#[cfg(test)]
+mod tick_array_cache_tests {
+    use super::*;
+    use crate::state::Tick;
+
+    const TS_8: u16 = 8;
+
+    fn tick_array(start_tick_index: i32, initialized_offsets: &[usize]) -> TickArray {
+        let mut tick_array = TickArray::default();
+        tick_array.start_tick_index = start_tick_index;
+        for offset in initialized_offsets {
+            tick_array.ticks[*offset] = Tick {
+                initialized: true,
+                ..Default::default()
+            };
+        }
+        tick_array
+    }
+
+    #[test]
+    fn test_summary_of_tick_array() {
+        let summary = TickArraySummary::new(&tick_array(704, &[3, 10, 40]), TS_8);
+        assert_eq!(
+            summary,
+            TickArraySummary {
+                start_tick_index: 704,
+                initialized_count: 3,
+                next_initialized_above: 728,
+                next_initialized_below: 1024,
+                initialized_bitmap: 1 << 3 | 1 << 10 | 1 << 40,
+            }
+        );
+
+        let summary = TickArraySummary::new(&tick_array(-704, &[]), TS_8);
+        assert_eq!(summary.initialized_count, 0);
+    }
+
+    #[test]
+    fn test_summary_next_init_tick_index() {
+        let summary = TickArraySummary::new(&tick_array(0, &[10, 20, 30]), TS_8);
+
+        assert_eq!(summary.get_next_init_tick_index(79, true), Some(None));
+        assert_eq!(summary.get_next_init_tick_index(240, true), Some(Some(240)));
+        assert_eq!(summary.get_next_init_tick_index(200, true), None);
+
+        assert_eq!(summary.get_next_init_tick_index(240, false), Some(None));
+        assert_eq!(summary.get_next_init_tick_index(-8, false), Some(Some(80)));
+        assert_eq!(summary.get_next_init_tick_index(80, false), None);
+
+        let summary = TickArraySummary::new(&tick_array(0, &[]), TS_8);
+        assert_eq!(summary.get_next_init_tick_index(80, true), Some(None));
+        assert_eq!(summary.get_next_init_tick_index(80, false), Some(None));
+    }
+
+    #[test]
+    fn test_cache_expires_after_one_slot() {
+        let pool = Pool::default();
+        let summary = TickArraySummary::new(&tick_array(0, &[10]), TS_8);
+        let mut cache = TickArrayCache::default();
+        cache.update(Pubkey::new_unique(), &pool, summary, 100);
+
+        assert_eq!(cache.get_valid_summary(&pool, 100), Some(summary));
+        assert_eq!(cache.get_valid_summary(&pool, 101), Some(summary));
+        assert_eq!(cache.get_valid_summary(&pool, 102), None);
+    }
+
+    #[test]
+    fn test_cache_invalidated_by_tick_flip() {
+        let mut pool = Pool::default();
+        let summary = TickArraySummary::new(&tick_array(0, &[]), TS_8);
+        let mut cache = TickArrayCache::default();
+        cache.update(Pubkey::new_unique(), &pool, summary, 100);
+
+        pool.tick_flip_count += 1;
+        assert_eq!(cache.get_valid_summary(&pool, 100), None);
+    }
+}
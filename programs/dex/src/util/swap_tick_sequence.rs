@@ -1,10 +1,35 @@
 use crate::errors::ErrorCode;
 use crate::state::*;
 use anchor_lang::prelude::*;
-use std::cell::RefMut;
+use std::cell::{Ref, RefMut};
+use std::ops::Deref;
+
+// Maximum number of tick-arrays a swap can traverse, including supplemental tick-arrays
+pub const MAX_SWAP_TICK_ARRAYS: usize = 5;
+
+enum TickArrayRef<'info> {
+    Mutable(RefMut<'info, TickArray>),
+    // Used by quotes, which must not write to the tick-array accounts
+    ReadOnly(Ref<'info, TickArray>),
+}
+
+impl<'info> Deref for TickArrayRef<'info> {
+    type Target = TickArray;
+
+    fn deref(&self) -> &TickArray {
+        match self {
+            TickArrayRef::Mutable(array) => array,
+            TickArrayRef::ReadOnly(array) => array,
+        }
+    }
+}
 
 pub struct SwapTickSequence<'info> {
-    arrays: Vec<RefMut<'info, TickArray>>,
+    arrays: Vec<TickArrayRef<'info>>,
+    // Cached summaries of the arrays, used to find initialized ticks without scanning
+    summaries: Vec<Option<TickArraySummary>>,
+    // Number of initialized ticks a swap may cross before it stops early, 0 for no limit
+    max_tick_crossings: u8,
 }
 
 impl<'info> SwapTickSequence<'info> {
@@ -14,14 +39,112 @@ impl<'info> SwapTickSequence<'info> {
         ta2: Option<RefMut<'info, TickArray>>,
     ) -> Self {
         let mut vec = Vec::with_capacity(3);
-        vec.push(ta0);
+        vec.push(TickArrayRef::Mutable(ta0));
         if ta1.is_some() {
-            vec.push(ta1.unwrap());
+            vec.push(TickArrayRef::Mutable(ta1.unwrap()));
         }
         if ta2.is_some() {
-            vec.push(ta2.unwrap());
+            vec.push(TickArrayRef::Mutable(ta2.unwrap()));
+        }
+        Self {
+            arrays: vec,
+            summaries: vec![],
+            max_tick_crossings: 0,
+        }
+    }
+
+    /// Create a sequence from the tick-array accounts of a swap. tick_array_1 and tick_array_2 are
+    /// loaded with `load_optional_tick_array`.
+    pub fn from_accounts(
+        ta0: &'info AccountLoader<'_, TickArray>,
+        ta1: &'info AccountLoader<'_, TickArray>,
+        ta2: &'info AccountLoader<'_, TickArray>,
+    ) -> Result<Self> {
+        Ok(Self::new(
+            ta0.load_mut()?,
+            load_optional_tick_array(ta1, &[ta0.key()])?,
+            load_optional_tick_array(ta2, &[ta0.key(), ta1.key()])?,
+        ))
+    }
+
+    /// Create a sequence over read-only tick-arrays for quoting a swap.
+    ///
+    /// Tick updates applied to this sequence are discarded. A swap never revisits a tick it has
+    /// crossed, so discarding the updates does not change the calculated amounts.
+    pub fn new_read_only(
+        ta0: Ref<'info, TickArray>,
+        ta1: Option<Ref<'info, TickArray>>,
+        ta2: Option<Ref<'info, TickArray>>,
+    ) -> Self {
+        let mut vec = Vec::with_capacity(3);
+        vec.push(TickArrayRef::ReadOnly(ta0));
+        if let Some(ta1) = ta1 {
+            vec.push(TickArrayRef::ReadOnly(ta1));
+        }
+        if let Some(ta2) = ta2 {
+            vec.push(TickArrayRef::ReadOnly(ta2));
+        }
+        Self {
+            arrays: vec,
+            summaries: vec![],
+            max_tick_crossings: 0,
         }
-        Self { arrays: vec }
+    }
+
+    /// Append supplemental tick-arrays to a sequence of three tick-arrays, so that a swap can
+    /// traverse a wider range. Each supplemental array must directly follow the previous array of
+    /// the sequence in the swap direction.
+    ///
+    /// # Errors
+    /// - `InvalidTickArraySequence` - The sequence does not have three tick-arrays, it would exceed
+    ///                                MAX_SWAP_TICK_ARRAYS, or a supplemental array is out of order.
+    pub fn with_supplemental_arrays(
+        mut self,
+        arrays: Vec<RefMut<'info, TickArray>>,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<Self> {
+        if arrays.is_empty() {
+            return Ok(self);
+        }
+        if self.arrays.len() != 3 || self.arrays.len() + arrays.len() > MAX_SWAP_TICK_ARRAYS {
+            return Err(ErrorCode::InvalidTickArraySequence.into());
+        }
+
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        for array in arrays {
+            let prev_start_tick_index = self.arrays[self.arrays.len() - 1].start_tick_index;
+            let expected_start_tick_index = if a_to_b {
+                prev_start_tick_index - ticks_in_array
+            } else {
+                prev_start_tick_index + ticks_in_array
+            };
+            if array.start_tick_index != expected_start_tick_index {
+                return Err(ErrorCode::InvalidTickArraySequence.into());
+            }
+            self.arrays.push(TickArrayRef::Mutable(array));
+        }
+        Ok(self)
+    }
+
+    /// Use cached tick-array summaries as a fast path when searching for initialized ticks.
+    ///
+    /// `summaries[i]` describes the i-th array of the sequence. Summaries must be up to date,
+    /// a summary whose start tick does not match its array is ignored.
+    pub fn with_summaries(mut self, summaries: Vec<Option<TickArraySummary>>) -> Self {
+        self.summaries = summaries;
+        self
+    }
+
+    /// Stop a swap over this sequence after it crossed `max_tick_crossings` initialized ticks.
+    /// The swap then returns the amounts filled so far. 0 means no limit.
+    pub fn with_max_tick_crossings(mut self, max_tick_crossings: u8) -> Self {
+        self.max_tick_crossings = max_tick_crossings;
+        self
+    }
+
+    pub fn max_tick_crossings(&self) -> u8 {
+        self.max_tick_crossings
     }
 
     /// Get the Tick object at the given tick-index & tick-spacing
@@ -68,10 +191,15 @@ impl<'info> SwapTickSequence<'info> {
     ) -> Result<()> {
         let array = self.arrays.get_mut(array_index);
         match array {
-            Some(array) => {
+            Some(TickArrayRef::Mutable(array)) => {
                 array.update_tick(tick_index, tick_spacing, update)?;
                 Ok(())
             }
+            Some(TickArrayRef::ReadOnly(array)) => {
+                // Validate the tick as a mutable sequence would, then discard the update
+                array.get_tick(tick_index, tick_spacing)?;
+                Ok(())
+            }
             _ => Err(ErrorCode::TickArrayIndexOutofBounds.into()),
         }
     }
@@ -122,8 +250,23 @@ impl<'info> SwapTickSequence<'info> {
                 None => return Err(ErrorCode::TickArraySequenceInvalidIndex.into()),
             };
 
-            let next_index =
-                next_array.get_next_init_tick_index(search_index, tick_spacing, a_to_b)?;
+            let next_index = match self.summaries.get(array_index) {
+                Some(Some(summary)) if summary.start_tick_index == next_array.start_tick_index => {
+                    if !next_array.in_search_range(search_index, tick_spacing, !a_to_b) {
+                        return Err(ErrorCode::InvalidTickArraySequence.into());
+                    }
+                    match summary.get_next_init_tick_index(search_index, a_to_b) {
+                        Some(next_index) => next_index,
+                        None => next_array.get_next_init_tick_index_in_bitmap(
+                            summary.initialized_bitmap,
+                            search_index,
+                            tick_spacing,
+                            a_to_b,
+                        )?,
+                    }
+                }
+                _ => next_array.get_next_init_tick_index(search_index, tick_spacing, a_to_b)?,
+            };
 
             match next_index {
                 Some(next_index) => {
@@ -160,6 +303,527 @@ impl<'info> SwapTickSequence<'info> {
             }
         }
     }
+
+    /// Flatten the initialized ticks of the sequence into a `PackedSwapTickSequence` for the
+    /// given swap direction.
+    ///
+    /// # Errors
+    /// - `InvalidTickArraySequence` - An array does not directly follow the previous array of the
+    ///                                sequence in the swap direction.
+    pub fn pack_for_direction(
+        &self,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Result<PackedSwapTickSequence> {
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        let mut ticks = Vec::new();
+
+        for (array_index, array) in self.arrays.iter().enumerate() {
+            let start_tick_index = array.start_tick_index;
+            if array_index > 0 {
+                let prev_start_tick_index = self.arrays[array_index - 1].start_tick_index;
+                let expected_start_tick_index = if a_to_b {
+                    prev_start_tick_index - ticks_in_array
+                } else {
+                    prev_start_tick_index + ticks_in_array
+                };
+                if start_tick_index != expected_start_tick_index {
+                    return Err(ErrorCode::InvalidTickArraySequence.into());
+                }
+            }
+
+            let mut array_ticks = Vec::new();
+            for offset in 0..TICK_ARRAY_SIZE_USIZE {
+                let tick = array.ticks[offset];
+                if tick.initialized {
+                    let tick_index = start_tick_index + offset as i32 * tick_spacing as i32;
+                    array_ticks.push((tick_index, tick.liquidity_net));
+                }
+            }
+            if a_to_b {
+                array_ticks.reverse();
+            }
+            ticks.extend(array_ticks);
+        }
+
+        let first_array = &self.arrays[0];
+        let last_array = &self.arrays[self.arrays.len() - 1];
+        let end_tick_index = if a_to_b {
+            if last_array.is_min_tick_array() {
+                MIN_TICK_INDEX
+            } else {
+                last_array.start_tick_index
+            }
+        } else if last_array.is_max_tick_array(tick_spacing) {
+            MAX_TICK_INDEX
+        } else {
+            last_array.start_tick_index + ticks_in_array - 1
+        };
+
+        Ok(PackedSwapTickSequence {
+            ticks,
+            a_to_b,
+            tick_spacing,
+            start_tick_index: first_array.start_tick_index,
+            end_tick_index,
+            cursor: 0,
+        })
+    }
+}
+
+/// The initialized ticks of a tick-array sequence, flattened into `(tick_index, liquidity_net)`
+/// pairs in swap order.
+///
+/// Looking up the next initialized tick only reads the packed pairs instead of the sparse tick
+/// slots of the arrays. Crossed ticks are not updated, so the sequence can only be used to quote
+/// swaps.
+pub struct PackedSwapTickSequence {
+    ticks: Vec<(i32, i128)>,
+    a_to_b: bool,
+    tick_spacing: u16,
+    // Start tick index of the first array of the sequence
+    start_tick_index: i32,
+    // The last tick a swap over the sequence can move to
+    end_tick_index: i32,
+    // Index of the next pair a swap can reach, pairs before it have been passed
+    cursor: usize,
+}
+
+impl PackedSwapTickSequence {
+    pub fn a_to_b(&self) -> bool {
+        self.a_to_b
+    }
+
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    /// Check that a swap starting at `tick_index` begins in the first array of the sequence, using
+    /// the same shifted search range for b to a swaps as `SwapTickSequence`.
+    ///
+    /// # Errors
+    /// - `InvalidTickArraySequence` - The tick index is outside the search range of the first array.
+    pub fn verify_start_tick_index(&self, tick_index: i32) -> Result<()> {
+        let shift = if self.a_to_b {
+            0
+        } else {
+            self.tick_spacing as i32
+        };
+        let lower = self.start_tick_index - shift;
+        let upper = self.start_tick_index + TICK_ARRAY_SIZE * self.tick_spacing as i32 - shift;
+        if tick_index < lower || tick_index >= upper {
+            return Err(ErrorCode::InvalidTickArraySequence.into());
+        }
+        Ok(())
+    }
+
+    /// Get the next tick a swap moves to from `tick_index`. Like `SwapTickSequence`, the search is
+    /// inclusive of `tick_index` for a to b swaps.
+    ///
+    /// # Returns
+    /// - `(i32, Option<i128>)`: The next tick index and its liquidity_net, or the end of the
+    ///                          sequence without liquidity_net if no initialized tick is left
+    /// - `TickArraySequenceInvalidIndex` - The swap moved past the end of the sequence.
+    pub fn next_tick(&mut self, tick_index: i32) -> Result<(i32, Option<i128>)> {
+        while let Some(&(next_tick_index, liquidity_net)) = self.ticks.get(self.cursor) {
+            let reachable = if self.a_to_b {
+                next_tick_index <= tick_index
+            } else {
+                next_tick_index > tick_index
+            };
+            if reachable {
+                return Ok((next_tick_index, Some(liquidity_net)));
+            }
+            self.cursor += 1;
+        }
+
+        let end_reachable = if self.a_to_b {
+            tick_index >= self.end_tick_index
+        } else {
+            tick_index < self.end_tick_index
+        };
+        if end_reachable {
+            Ok((self.end_tick_index, None))
+        } else {
+            Err(ErrorCode::TickArraySequenceInvalidIndex.into())
+        }
+    }
+}
+
+/// Returns the start tick indexes of the three tick-arrays a swap from the current tick of the
+/// pool traverses, in swap order.
+///
+/// For b to a swaps the first array is the array containing `tick_current_index + tick_spacing`,
+/// matching the shifted search range of `SwapTickSequence`. Arrays past the min or max tick-array
+/// repeat the last valid array, which the swap ignores as a duplicate account.
+pub fn swap_tick_array_start_indexes(
+    tick_current_index: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+) -> [i32; 3] {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let shift = if a_to_b { 0 } else { tick_spacing as i32 };
+    let first_start_tick_index =
+        (tick_current_index + shift).div_euclid(ticks_in_array) * ticks_in_array;
+
+    let mut start_tick_indexes = [first_start_tick_index; 3];
+    for i in 1..3 {
+        let next_start_tick_index = if a_to_b {
+            start_tick_indexes[i - 1] - ticks_in_array
+        } else {
+            start_tick_indexes[i - 1] + ticks_in_array
+        };
+        start_tick_indexes[i] =
+            if Tick::check_is_valid_start_tick(next_start_tick_index, tick_spacing) {
+                next_start_tick_index
+            } else {
+                start_tick_indexes[i - 1]
+            };
+    }
+    start_tick_indexes
+}
+
+/// Returns the start tick indexes of the three tick-arrays around the current tick of a pool: the
+/// array containing the current tick and its neighbours on each side, in ascending order.
+///
+/// Next to the min or max tick-array the window shifts inwards, so that all three arrays are valid
+/// and a swap in either direction can start from the current tick.
+pub fn surrounding_tick_array_start_indexes(
+    tick_current_index: i32,
+    tick_spacing: u16,
+) -> [i32; 3] {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let current_start_tick_index = tick_current_index.div_euclid(ticks_in_array) * ticks_in_array;
+
+    let mut first_start_tick_index = current_start_tick_index - ticks_in_array;
+    if !Tick::check_is_valid_start_tick(first_start_tick_index, tick_spacing) {
+        first_start_tick_index = current_start_tick_index;
+    }
+    if !Tick::check_is_valid_start_tick(first_start_tick_index + 2 * ticks_in_array, tick_spacing) {
+        first_start_tick_index -= ticks_in_array;
+    }
+    [
+        first_start_tick_index,
+        first_start_tick_index + ticks_in_array,
+        first_start_tick_index + 2 * ticks_in_array,
+    ]
+}
+
+/// Derive the addresses of the three tick-arrays to pass to a swap in the given direction, so
+/// that clients do not have to compute the tick-array sequence themselves.
+pub fn derive_swap_tick_arrays(pool: &Pool, a_to_b: bool) -> Result<[Pubkey; 3]> {
+    let pool_key = Pubkey::create_program_address(&pool.seeds(), &crate::ID)
+        .map_err(|_| ErrorCode::InvalidTickArraySequence)?;
+    let start_tick_indexes =
+        swap_tick_array_start_indexes(pool.tick_current_index, pool.tick_spacing, a_to_b);
+
+    Ok(start_tick_indexes.map(|start_tick_index| derive_tick_array(&pool_key, start_tick_index)))
+}
+
+/// Derive the address of the tick-array of a pool with the given start tick index.
+pub fn derive_tick_array(pool_key: &Pubkey, start_tick_index: i32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"tick_array",
+            pool_key.as_ref(),
+            start_tick_index.to_string().as_bytes(),
+        ],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Returns the start tick index of the tick-array a swap in the given direction traverses after
+/// the tick-array starting at `start_tick_index`.
+///
+/// # Errors
+/// - `InvalidStartTick`: `start_tick_index` is not a valid start tick index for the tick spacing.
+/// - `TickArraySequenceInvalidIndex`: The tick-array is the min or max tick-array, the swap cannot
+///   continue past it.
+pub fn next_tick_array_start_index(
+    start_tick_index: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+) -> Result<i32> {
+    if !Tick::check_is_valid_start_tick(start_tick_index, tick_spacing) {
+        return Err(ErrorCode::InvalidStartTick.into());
+    }
+
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let next_start_tick_index = if a_to_b {
+        start_tick_index - ticks_in_array
+    } else {
+        start_tick_index + ticks_in_array
+    };
+    if !Tick::check_is_valid_start_tick(next_start_tick_index, tick_spacing) {
+        return Err(ErrorCode::TickArraySequenceInvalidIndex.into());
+    }
+    Ok(next_start_tick_index)
+}
+
+/// Reject a tick-array account that is passed again after a different tick-array.
+///
+/// Repeating the previous account is allowed, since clients pad the sequence at the min or max
+/// tick-array that way (see `swap_tick_array_start_indexes`). Any other reuse means that one
+/// account would stand for two positions in the sequence. The error data holds the repeated key.
+pub fn verify_tick_array_accounts(tick_arrays: &[Pubkey]) -> Result<()> {
+    for (i, key) in tick_arrays.iter().enumerate().skip(1) {
+        if tick_arrays[i - 1] != *key && tick_arrays[..i - 1].contains(key) {
+            return Err(error!(ErrorCode::DuplicateTickArrayAccount).with_pubkeys((*key, *key)));
+        }
+    }
+    Ok(())
+}
+
+/// Verify the tick-array accounts of both legs of a two-hop swap. The legs trade on different
+/// pools, so no tick-array may be shared between them.
+pub fn verify_two_hop_tick_array_accounts(
+    tick_arrays_one: &[Pubkey],
+    tick_arrays_two: &[Pubkey],
+) -> Result<()> {
+    verify_tick_array_accounts(tick_arrays_one)?;
+    verify_tick_array_accounts(tick_arrays_two)?;
+    if let Some(key) = tick_arrays_one
+        .iter()
+        .find(|key| tick_arrays_two.contains(key))
+    {
+        return Err(error!(ErrorCode::DuplicateTickArrayAccount).with_pubkeys((*key, *key)));
+    }
+    Ok(())
+}
+
+/// Load tick_array_1 or tick_array_2 of a swap.
+///
+/// A tick-array passed again after a previous array of the swap only pads the sequence and is left
+/// out of it. Any other account must load, so that a corrupt or wrong account fails the swap with
+/// `TickArrayLoadFailed` instead of silently shortening the sequence.
+pub fn load_optional_tick_array<'a, 'info>(
+    tick_array: &'a AccountLoader<'info, TickArray>,
+    previous_tick_arrays: &[Pubkey],
+) -> Result<Option<RefMut<'a, TickArray>>> {
+    if previous_tick_arrays.contains(&tick_array.key()) {
+        return Ok(None);
+    }
+    match tick_array.load_mut() {
+        Ok(tick_array) => Ok(Some(tick_array)),
+        Err(_) => Err(error!(ErrorCode::TickArrayLoadFailed)
+            .with_pubkeys((tick_array.key(), tick_array.key()))),
+    }
+}
+
+#[cfg(test)]
+mod verify_tick_array_accounts_tests {
+    use super::*;
+    use anchor_lang::error::{ComparedValues, Error};
+
+    fn assert_duplicate(result: Result<()>, duplicate: Pubkey) {
+        let error = result.unwrap_err();
+        assert_eq!(error, ErrorCode::DuplicateTickArrayAccount.into());
+        match error {
+            Error::AnchorError(error) => assert!(matches!(
+                error.compared_values,
+                Some(ComparedValues::Pubkeys((left, right))) if left == duplicate && right == duplicate
+            )),
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_distinct_and_padded_sequences() {
+        let (ta0, ta1, ta2) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        assert!(verify_tick_array_accounts(&[ta0, ta1, ta2]).is_ok());
+        assert!(verify_tick_array_accounts(&[ta0, ta1, ta1]).is_ok());
+        assert!(verify_tick_array_accounts(&[ta0, ta0, ta0]).is_ok());
+    }
+
+    #[test]
+    fn test_reused_tick_array() {
+        let (ta0, ta1) = (Pubkey::new_unique(), Pubkey::new_unique());
+        assert_duplicate(verify_tick_array_accounts(&[ta0, ta1, ta0]), ta0);
+    }
+
+    #[test]
+    fn test_tick_array_shared_between_hops() {
+        let one = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let two = [Pubkey::new_unique(), one[2], Pubkey::new_unique()];
+        assert!(verify_two_hop_tick_array_accounts(&one, &[two[0], two[2], two[2]]).is_ok());
+        assert_duplicate(verify_two_hop_tick_array_accounts(&one, &two), one[2]);
+
+        let two = [Pubkey::new_unique(), two[0], two[0]];
+        assert_duplicate(
+            verify_two_hop_tick_array_accounts(&[one[0], one[1], one[0]], &two),
+            one[0],
+        );
+    }
+}
+
+#[cfg(test)]
+mod derive_swap_tick_arrays_tests {
+    use super::*;
+
+    #[test]
+    fn test_a_to_b_start_indexes() {
+        assert_eq!(
+            swap_tick_array_start_indexes(100, 64, true),
+            [0, -5632, -11264]
+        );
+        assert_eq!(
+            swap_tick_array_start_indexes(-1, 64, true),
+            [-5632, -11264, -16896]
+        );
+        // The current tick at the start of an array is searched within that array
+        assert_eq!(
+            swap_tick_array_start_indexes(5632, 64, true),
+            [5632, 0, -5632]
+        );
+    }
+
+    #[test]
+    fn test_b_to_a_start_indexes() {
+        assert_eq!(
+            swap_tick_array_start_indexes(100, 64, false),
+            [0, 5632, 11264]
+        );
+        // One tick spacing below the next array, the swap starts in the next array
+        assert_eq!(
+            swap_tick_array_start_indexes(5568, 64, false),
+            [5632, 11264, 16896]
+        );
+        assert_eq!(
+            swap_tick_array_start_indexes(-65, 64, false),
+            [-5632, 0, 5632]
+        );
+    }
+
+    #[test]
+    fn test_start_indexes_at_tick_bounds() {
+        let min_array_start = -444928;
+        assert_eq!(
+            swap_tick_array_start_indexes(MIN_TICK_INDEX + 6000, 64, true),
+            [min_array_start + 5632, min_array_start, min_array_start]
+        );
+
+        let max_array_start = 439296;
+        assert_eq!(
+            swap_tick_array_start_indexes(MAX_TICK_INDEX - 6000, 64, false),
+            [max_array_start - 5632, max_array_start, max_array_start]
+        );
+    }
+
+    #[test]
+    fn test_surrounding_start_indexes() {
+        assert_eq!(
+            surrounding_tick_array_start_indexes(100, 64),
+            [-5632, 0, 5632]
+        );
+        assert_eq!(
+            surrounding_tick_array_start_indexes(-1, 64),
+            [-11264, -5632, 0]
+        );
+        assert_eq!(
+            surrounding_tick_array_start_indexes(5632, 8),
+            [4928, 5632, 6336]
+        );
+    }
+
+    #[test]
+    fn test_surrounding_start_indexes_at_tick_bounds() {
+        let min_array_start = -444928;
+        assert_eq!(
+            surrounding_tick_array_start_indexes(MIN_TICK_INDEX, 64),
+            [
+                min_array_start,
+                min_array_start + 5632,
+                min_array_start + 11264
+            ]
+        );
+
+        let max_array_start = 439296;
+        assert_eq!(
+            surrounding_tick_array_start_indexes(MAX_TICK_INDEX, 64),
+            [
+                max_array_start - 11264,
+                max_array_start - 5632,
+                max_array_start
+            ]
+        );
+    }
+
+    #[test]
+    fn test_derive_swap_tick_arrays() {
+        let mut pool = Pool::default();
+        pool.tick_spacing = 64;
+        pool.tick_current_index = 100;
+        let (pool_key, bump) = Pubkey::find_program_address(
+            &[
+                b"pool",
+                pool.pools_config.as_ref(),
+                pool.token_mint_a.as_ref(),
+                pool.token_mint_b.as_ref(),
+                pool.tick_spacing_seed.as_ref(),
+            ],
+            &crate::ID,
+        );
+        pool.pool_bump = [bump];
+
+        let tick_arrays = derive_swap_tick_arrays(&pool, true).unwrap();
+        let expected_first =
+            Pubkey::find_program_address(&[b"tick_array", pool_key.as_ref(), b"0"], &crate::ID).0;
+        assert_eq!(tick_arrays[0], expected_first);
+        assert_ne!(tick_arrays[1], tick_arrays[2]);
+
+        let tick_arrays_b_to_a = derive_swap_tick_arrays(&pool, false).unwrap();
+        assert_eq!(tick_arrays_b_to_a[0], expected_first);
+        assert_ne!(tick_arrays_b_to_a[1], tick_arrays[1]);
+    }
+}
+
+#[cfg(test)]
+mod next_tick_array_start_index_tests {
+    use super::*;
+
+    #[test]
+    fn test_next_start_index_in_swap_direction() {
+        assert_eq!(next_tick_array_start_index(0, 64, true).unwrap(), -5632);
+        assert_eq!(next_tick_array_start_index(0, 64, false).unwrap(), 5632);
+        assert_eq!(next_tick_array_start_index(-704, 8, true).unwrap(), -1408);
+    }
+
+    #[test]
+    fn test_invalid_start_index() {
+        assert_eq!(
+            next_tick_array_start_index(100, 64, true).unwrap_err(),
+            ErrorCode::InvalidStartTick.into()
+        );
+    }
+
+    #[test]
+    fn test_no_array_past_tick_bounds() {
+        assert_eq!(
+            next_tick_array_start_index(-444928, 64, true).unwrap_err(),
+            ErrorCode::TickArraySequenceInvalidIndex.into()
+        );
+        assert_eq!(
+            next_tick_array_start_index(439296, 64, false).unwrap_err(),
+            ErrorCode::TickArraySequenceInvalidIndex.into()
+        );
+        assert_eq!(
+            next_tick_array_start_index(439296, 64, true).unwrap(),
+            433664
+        );
+    }
 }
 
 #[cfg(test)]
@@ -231,6 +895,36 @@ mod swap_tick_sequence_tests {
             }
         }
 
+        #[test]
+        fn modify_tick_read_only_discards_update() {
+            let ta0 = build_tick_array(11264, vec![50]);
+            let ta1 = build_tick_array(0, vec![25, 71]);
+            let mut swap_tick_sequence =
+                SwapTickSequence::new_read_only(ta0.borrow(), Some(ta1.borrow()), None);
+
+            let tick_index = 11264 + 50 * TS_128 as i32;
+            let update_result = swap_tick_sequence.update_tick(
+                0,
+                tick_index,
+                TS_128,
+                &TickUpdate {
+                    initialized: false,
+                    liquidity_net: 1500,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(update_result.is_ok(), true);
+
+            let tick = swap_tick_sequence.get_tick(0, tick_index, TS_128).unwrap();
+            assert_eq!(tick.initialized, true);
+            let liq_net = tick.liquidity_net;
+            assert_ne!(liq_net, 1500);
+
+            let update_result =
+                swap_tick_sequence.update_tick(0, 11264 + 1, TS_128, &TickUpdate::default());
+            assert_eq!(update_result.unwrap_err(), ErrorCode::TickNotFound.into());
+        }
+
         #[test]
         fn modify_tick_uninitializable_tick() {
             let ta0 = build_tick_array(9216, vec![50]);
@@ -496,6 +1190,227 @@ mod swap_tick_sequence_tests {
         }
     }
 
+    mod supplemental_arrays {
+        use super::*;
+
+        #[test]
+        fn supplemental_arrays_extend_sequence() {
+            let ta0 = build_tick_array(0, vec![]);
+            let ta1 = build_tick_array(-704, vec![]);
+            let ta2 = build_tick_array(-1408, vec![]);
+            let ta3 = build_tick_array(-2112, vec![]);
+            let ta4 = build_tick_array(-2816, vec![10]);
+            let swap_tick_sequence = SwapTickSequence::new(
+                ta0.borrow_mut(),
+                Some(ta1.borrow_mut()),
+                Some(ta2.borrow_mut()),
+            )
+            .with_supplemental_arrays(vec![ta3.borrow_mut(), ta4.borrow_mut()], TS_8, true)
+            .unwrap();
+
+            let (array_index, tick_index) = swap_tick_sequence
+                .get_next_initialized_tick_index(-1408, TS_8, true, 2)
+                .unwrap();
+            assert_eq!(array_index, 4);
+            assert_eq!(tick_index, -2816 + 10 * TS_8 as i32);
+        }
+
+        #[test]
+        fn supplemental_arrays_out_of_order() {
+            let ta0 = build_tick_array(0, vec![]);
+            let ta1 = build_tick_array(704, vec![]);
+            let ta2 = build_tick_array(1408, vec![]);
+            let ta3 = build_tick_array(2816, vec![]);
+            let result = SwapTickSequence::new(
+                ta0.borrow_mut(),
+                Some(ta1.borrow_mut()),
+                Some(ta2.borrow_mut()),
+            )
+            .with_supplemental_arrays(vec![ta3.borrow_mut()], TS_8, false);
+            assert_eq!(
+                result.err().unwrap(),
+                ErrorCode::InvalidTickArraySequence.into()
+            );
+        }
+
+        #[test]
+        fn supplemental_arrays_exceed_max() {
+            let ta0 = build_tick_array(0, vec![]);
+            let ta1 = build_tick_array(704, vec![]);
+            let ta2 = build_tick_array(1408, vec![]);
+            let ta3 = build_tick_array(2112, vec![]);
+            let ta4 = build_tick_array(2816, vec![]);
+            let ta5 = build_tick_array(3520, vec![]);
+            let result = SwapTickSequence::new(
+                ta0.borrow_mut(),
+                Some(ta1.borrow_mut()),
+                Some(ta2.borrow_mut()),
+            )
+            .with_supplemental_arrays(
+                vec![ta3.borrow_mut(), ta4.borrow_mut(), ta5.borrow_mut()],
+                TS_8,
+                false,
+            );
+            assert_eq!(
+                result.err().unwrap(),
+                ErrorCode::InvalidTickArraySequence.into()
+            );
+        }
+
+        #[test]
+        fn supplemental_arrays_require_three_arrays() {
+            let ta0 = build_tick_array(0, vec![]);
+            let ta1 = build_tick_array(704, vec![]);
+            let result = SwapTickSequence::new(ta0.borrow_mut(), None, None)
+                .with_supplemental_arrays(vec![ta1.borrow_mut()], TS_8, false);
+            assert_eq!(
+                result.err().unwrap(),
+                ErrorCode::InvalidTickArraySequence.into()
+            );
+        }
+    }
+
+    mod cached_summaries {
+        use super::*;
+
+        #[test]
+        fn cached_search_matches_scan() {
+            let ta0 = build_tick_array(0, vec![10, 50]);
+            let ta1 = build_tick_array(704, vec![]);
+            let ta2 = build_tick_array(1408, vec![2]);
+            let summaries = vec![
+                Some(TickArraySummary::new(&ta0.borrow(), TS_8)),
+                Some(TickArraySummary::new(&ta1.borrow(), TS_8)),
+                Some(TickArraySummary::new(&ta2.borrow(), TS_8)),
+            ];
+            let swap_tick_sequence = SwapTickSequence::new_read_only(
+                ta0.borrow(),
+                Some(ta1.borrow()),
+                Some(ta2.borrow()),
+            );
+            let cached_swap_tick_sequence = SwapTickSequence::new_read_only(
+                ta0.borrow(),
+                Some(ta1.borrow()),
+                Some(ta2.borrow()),
+            )
+            .with_summaries(summaries);
+
+            for tick_index in [-8, 0, 79, 80, 200, 400, 703, 1000, 1420] {
+                let start_array_index = if tick_index < 696 {
+                    0
+                } else if tick_index < 1400 {
+                    1
+                } else {
+                    2
+                };
+                assert_eq!(
+                    cached_swap_tick_sequence
+                        .get_next_initialized_tick_index(tick_index, TS_8, false, start_array_index)
+                        .unwrap(),
+                    swap_tick_sequence
+                        .get_next_initialized_tick_index(tick_index, TS_8, false, start_array_index)
+                        .unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn cached_search_skips_empty_array() {
+            let ta0 = build_tick_array(1408, vec![]);
+            let ta1 = build_tick_array(704, vec![]);
+            let ta2 = build_tick_array(0, vec![30, 60]);
+            let summaries = vec![
+                Some(TickArraySummary::new(&ta0.borrow(), TS_8)),
+                Some(TickArraySummary::new(&ta1.borrow(), TS_8)),
+                Some(TickArraySummary::new(&ta2.borrow(), TS_8)),
+            ];
+            let swap_tick_sequence = SwapTickSequence::new(
+                ta0.borrow_mut(),
+                Some(ta1.borrow_mut()),
+                Some(ta2.borrow_mut()),
+            )
+            .with_summaries(summaries);
+
+            let (array_index, index) = swap_tick_sequence
+                .get_next_initialized_tick_index(1500, TS_8, true, 0)
+                .unwrap();
+            assert_eq!(array_index, 2);
+            assert_eq!(index, 480);
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidTickArraySequence")]
+        fn cached_search_checks_search_range() {
+            let ta0 = build_tick_array(0, vec![]);
+            let summaries = vec![Some(TickArraySummary::new(&ta0.borrow(), TS_8))];
+            let swap_tick_sequence =
+                SwapTickSequence::new(ta0.borrow_mut(), None, None).with_summaries(summaries);
+
+            swap_tick_sequence
+                .get_next_initialized_tick_index(704, TS_8, true, 0)
+                .unwrap();
+        }
+    }
+
+    mod initialized_bitmaps {
+        use super::*;
+
+        #[test]
+        fn cached_bitmap_search_matches_scan() {
+            let ta0 = build_tick_array(0, vec![10, 50]);
+            let ta1 = build_tick_array(704, vec![]);
+            let ta2 = build_tick_array(1408, vec![0, 40, LAST_TICK_OFFSET]);
+            let summaries = vec![
+                Some(TickArraySummary::new(&ta0.borrow(), TS_8)),
+                Some(TickArraySummary::new(&ta1.borrow(), TS_8)),
+                Some(TickArraySummary::new(&ta2.borrow(), TS_8)),
+            ];
+            let swap_tick_sequence = SwapTickSequence::new_read_only(
+                ta0.borrow(),
+                Some(ta1.borrow()),
+                Some(ta2.borrow()),
+            );
+            let cached_swap_tick_sequence = SwapTickSequence::new_read_only(
+                ta0.borrow(),
+                Some(ta1.borrow()),
+                Some(ta2.borrow()),
+            )
+            .with_summaries(summaries);
+
+            // Searches between the first and last initialized tick of an array need the bitmap
+            for tick_index in [-8, 0, 79, 80, 400, 703, 1000, 1408, 1500, 1728, 2095] {
+                let array_index = swap_tick_sequence
+                    .arrays
+                    .iter()
+                    .position(|array| array.in_search_range(tick_index, TS_8, true))
+                    .unwrap();
+                assert_eq!(
+                    cached_swap_tick_sequence
+                        .get_next_initialized_tick_index(tick_index, TS_8, false, array_index)
+                        .unwrap(),
+                    swap_tick_sequence
+                        .get_next_initialized_tick_index(tick_index, TS_8, false, array_index)
+                        .unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn cached_bitmap_search_skips_empty_ticks() {
+            let ta0 = build_tick_array(0, vec![0, 40, LAST_TICK_OFFSET]);
+            let summaries = vec![Some(TickArraySummary::new(&ta0.borrow(), TS_8))];
+            // Tick 40 is cleared after the array is warmed, the cached bitmap still has it
+            ta0.borrow_mut().ticks[40].initialized = false;
+            let swap_tick_sequence =
+                SwapTickSequence::new(ta0.borrow_mut(), None, None).with_summaries(summaries);
+
+            let (_, index) = swap_tick_sequence
+                .get_next_initialized_tick_index(8, TS_8, false, 0)
+                .unwrap();
+            assert_eq!(index, 320);
+        }
+    }
+
     mod tick_bound {
         use super::*;
 
@@ -734,3 +1649,56 @@ mod swap_tick_sequence_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod load_optional_tick_array_tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    // Loads a tick-array account passed after tick_array_0, and optionally also after itself
+    fn load(discriminator: [u8; 8], repeated: bool) -> Result<bool> {
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0;
+        let mut data = vec![0u8; TickArray::LEN];
+        data[..8].copy_from_slice(&discriminator);
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        let tick_array =
+            AccountLoader::<TickArray>::try_from_unchecked(&crate::ID, &account_info).unwrap();
+
+        let mut previous_tick_arrays = vec![Pubkey::new_unique()];
+        if repeated {
+            previous_tick_arrays.push(key);
+        }
+        load_optional_tick_array(&tick_array, &previous_tick_arrays)
+            .map(|tick_array| tick_array.is_some())
+    }
+
+    #[test]
+    fn test_valid_tick_array_loads() {
+        assert!(load(TickArray::discriminator(), false).unwrap());
+    }
+
+    #[test]
+    fn test_repeated_tick_array_is_omitted() {
+        assert!(!load(TickArray::discriminator(), true).unwrap());
+        assert!(!load([0xff; 8], true).unwrap());
+    }
+
+    #[test]
+    fn test_malformed_tick_array_fails() {
+        assert_eq!(
+            load([0xff; 8], false).unwrap_err(),
+            ErrorCode::TickArrayLoadFailed.into()
+        );
+    }
+}
@@ -1,7 +1,12 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
 
-use crate::{manager::swap_manager::PostSwapUpdate, state::Pool};
+use crate::{
+    errors::ErrorCode,
+    events::SwapCountMilestoneEvent,
+    manager::swap_manager::PostSwapUpdate,
+    state::{Pool, PoolsConfig},
+};
 
 use super::{transfer_from_owner_to_vault, transfer_from_vault_to_owner};
 
@@ -17,13 +22,14 @@ pub fn update_and_swap_pool<'info>(
     is_token_fee_in_a: bool,
     reward_last_updated_timestamp: u64,
 ) -> Result<()> {
-    pool.update_after_swap(
-        swap_update.next_liquidity,
-        swap_update.next_tick_index,
-        swap_update.next_sqrt_price,
-        swap_update.next_fee_growth_global,
-        swap_update.next_reward_infos,
-        swap_update.next_protocol_fee,
+    // The vault amounts are updated with the amounts computed by the swap, which is only correct
+    // if the full amounts arrive in the vaults
+    verify_no_transfer_fee(token_vault_a)?;
+    verify_no_transfer_fee(token_vault_b)?;
+
+    update_pool_after_swap(
+        pool,
+        swap_update,
         is_token_fee_in_a,
         reward_last_updated_timestamp,
     );
@@ -44,6 +50,87 @@ pub fn update_and_swap_pool<'info>(
     Ok(())
 }
 
+// Applies a simulated swap to the pool state and records its volume and count
+pub fn update_pool_after_swap(
+    pool: &mut Account<Pool>,
+    swap_update: &PostSwapUpdate,
+    is_token_fee_in_a: bool,
+    reward_last_updated_timestamp: u64,
+) {
+    pool.update_after_swap(
+        swap_update.next_liquidity,
+        swap_update.next_tick_index,
+        swap_update.next_sqrt_price,
+        swap_update.next_fee_growth_global,
+        swap_update.next_reward_infos,
+        swap_update.next_protocol_fee,
+        is_token_fee_in_a,
+        reward_last_updated_timestamp,
+    );
+    pool.record_swap_volume(swap_update.amount_a, swap_update.amount_b);
+    if let Some(milestone) = pool.record_swap_count() {
+        emit!(SwapCountMilestoneEvent {
+            pool: pool.key(),
+            count: pool.total_swap_count,
+            milestone,
+        });
+    }
+}
+
+// Only the legacy token program is supported here, and its mints cannot have a transfer fee.
+// Token-2022 mints must use the v2 instructions, which account for transfer fees.
+fn verify_no_transfer_fee(token_vault: &Account<TokenAccount>) -> Result<()> {
+    verify_legacy_token_program_owner(token_vault.to_account_info().owner)
+}
+
+fn verify_legacy_token_program_owner(owner: &Pubkey) -> Result<()> {
+    if *owner != Token::id() {
+        return Err(ErrorCode::TransferFeeNotSupportedInLegacySwap.into());
+    }
+    Ok(())
+}
+
+/// Returns the part of a swap's protocol fee that is streamed to the protocol treasury instead of
+/// accumulating in the pool.
+///
+/// The fee is streamed when streaming is enabled in the pools config and the fee exceeds
+/// `min_stream_amount`. The treasury of the pools config extension and a treasury token account
+/// must then be supplied, so that a swap cannot keep the fee in the pool by leaving them out.
+/// Otherwise 0 is returned and the whole fee accumulates in `protocol_fee_owed_a/b` as before.
+///
+/// # Parameters
+/// - `protocol_treasury` - The protocol treasury of the pools config extension, if supplied
+/// - `treasury_token_account` - The owner and mint of the treasury token account, if supplied
+/// - `fee_mint` - The mint the protocol fee is taken in (the input mint of the swap)
+///
+/// # Errors
+/// - `MissingProtocolTreasuryAccount` - Streaming applies and the pools config extension or the
+///   treasury token account is not supplied.
+/// - `InvalidProtocolTreasuryAccount` - The treasury token account is not owned by the
+///   protocol treasury or does not hold `fee_mint`.
+pub fn get_streamed_protocol_fee(
+    pools_config: &PoolsConfig,
+    protocol_treasury: Option<Pubkey>,
+    treasury_token_account: Option<(Pubkey, Pubkey)>,
+    fee_mint: Pubkey,
+    protocol_fee: u64,
+) -> Result<u64> {
+    if !pools_config.should_stream_protocol_fee(protocol_fee) {
+        return Ok(0);
+    }
+
+    let (protocol_treasury, (treasury_owner, treasury_mint)) =
+        match (protocol_treasury, treasury_token_account) {
+            (Some(protocol_treasury), Some(treasury)) => (protocol_treasury, treasury),
+            _ => return Err(ErrorCode::MissingProtocolTreasuryAccount.into()),
+        };
+    if treasury_owner != protocol_treasury || treasury_mint != fee_mint {
+        return Err(ErrorCode::InvalidProtocolTreasuryAccount.into());
+    }
+
+    Ok(protocol_fee)
+}
+
 fn perform_swap<'info>(
     pool: &Account<'info, Pool>,
     token_authority: &Signer<'info>,
@@ -102,3 +189,98 @@ fn perform_swap<'info>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod legacy_swap_tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_swap_accepts_token_program() {
+        assert!(verify_legacy_token_program_owner(&Token::id()).is_ok());
+    }
+
+    #[test]
+    fn test_legacy_swap_rejects_token_2022() {
+        // Transfer fees are a Token-2022 extension
+        assert_eq!(
+            verify_legacy_token_program_owner(&anchor_spl::token_2022::ID).unwrap_err(),
+            ErrorCode::TransferFeeNotSupportedInLegacySwap.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod streamed_protocol_fee_tests {
+    use super::*;
+
+    fn streaming_config() -> PoolsConfig {
+        let mut config = PoolsConfig::default();
+        config.enable_protocol_fee_streaming(100);
+        config
+    }
+
+    #[test]
+    fn test_fee_not_streamed_when_streaming_does_not_apply() {
+        let fee_mint = Pubkey::new_unique();
+        assert_eq!(
+            get_streamed_protocol_fee(&PoolsConfig::default(), None, None, fee_mint, 1_000)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            get_streamed_protocol_fee(&streaming_config(), None, None, fee_mint, 100).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_fee_streamed_to_treasury() {
+        let treasury = Pubkey::new_unique();
+        let fee_mint = Pubkey::new_unique();
+        assert_eq!(
+            get_streamed_protocol_fee(
+                &streaming_config(),
+                Some(treasury),
+                Some((treasury, fee_mint)),
+                fee_mint,
+                1_000
+            )
+            .unwrap(),
+            1_000
+        );
+        assert_eq!(
+            get_streamed_protocol_fee(
+                &streaming_config(),
+                Some(treasury),
+                Some((Pubkey::new_unique(), fee_mint)),
+                fee_mint,
+                1_000
+            )
+            .unwrap_err(),
+            ErrorCode::InvalidProtocolTreasuryAccount.into()
+        );
+    }
+
+    #[test]
+    fn test_missing_treasury_accounts_rejected_when_streaming() {
+        let treasury = Pubkey::new_unique();
+        let fee_mint = Pubkey::new_unique();
+        for (protocol_treasury, treasury_token_account) in [
+            (None, Some((treasury, fee_mint))),
+            (Some(treasury), None),
+            (None, None),
+        ] {
+            assert_eq!(
+                get_streamed_protocol_fee(
+                    &streaming_config(),
+                    protocol_treasury,
+                    treasury_token_account,
+                    fee_mint,
+                    1_000
+                )
+                .unwrap_err(),
+                ErrorCode::MissingProtocolTreasuryAccount.into()
+            );
+        }
+    }
+}
@@ -1,7 +1,11 @@
-use crate::state::{Pool, PositionBundle};
+use crate::state::{
+    Pool, PositionBundle, PositionFractionVault, ProposalVote, ProtocolLiquidityManager,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::metadata::{self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3};
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Burn, MintTo, TokenInterface, TransferChecked};
 use solana_program::program::invoke_signed;
 use spl_token::instruction::{burn_checked, close_account, mint_to, set_authority, AuthorityType};
 
@@ -51,6 +55,27 @@ pub fn transfer_from_vault_to_owner<'info>(
     )
 }
 
+pub fn transfer_from_protocol_treasury_to_vault<'info>(
+    manager: &Account<'info, ProtocolLiquidityManager>,
+    treasury_token_account: &Account<'info, TokenAccount>,
+    token_vault: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: treasury_token_account.to_account_info(),
+                to: token_vault.to_account_info(),
+                authority: manager.to_account_info(),
+            },
+            &[&manager.seeds()],
+        ),
+        amount,
+    )
+}
+
 pub fn burn_and_close_user_position_token<'info>(
     token_authority: &Signer<'info>,
     receiver: &UncheckedAccount<'info>,
@@ -363,3 +388,213 @@ pub fn burn_and_close_position_bundle_token<'info>(
         token_program,
     )
 }
+
+pub fn transfer_position_token_from_fraction_vault_and_close<'info>(
+    vault: &Account<'info, PositionFractionVault>,
+    receiver: &UncheckedAccount<'info>,
+    vault_position_token_account: &Account<'info, TokenAccount>,
+    position_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault_position_token_account.to_account_info(),
+                to: position_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            &[&vault.seeds()],
+        ),
+        1,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault_position_token_account.to_account_info(),
+            destination: receiver.to_account_info(),
+            authority: vault.to_account_info(),
+        },
+        &[&vault.seeds()],
+    ))
+}
+
+pub fn transfer_position_token_from_vote_and_close<'info>(
+    vote: &Account<'info, ProposalVote>,
+    receiver: &UncheckedAccount<'info>,
+    vote_position_token_account: &Account<'info, TokenAccount>,
+    position_token_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vote_position_token_account.to_account_info(),
+                to: position_token_account.to_account_info(),
+                authority: vote.to_account_info(),
+            },
+            &[&vote.seeds()],
+        ),
+        1,
+    )?;
+
+    token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vote_position_token_account.to_account_info(),
+            destination: receiver.to_account_info(),
+            authority: vote.to_account_info(),
+        },
+        &[&vote.seeds()],
+    ))
+}
+
+pub fn mint_position_fractions<'info>(
+    vault: &Account<'info, PositionFractionVault>,
+    fraction_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    fraction_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    fraction_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            fraction_token_program.to_account_info(),
+            MintTo {
+                mint: fraction_mint.to_account_info(),
+                to: fraction_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            &[&vault.seeds()],
+        ),
+        amount,
+    )
+}
+
+pub fn burn_position_fractions<'info>(
+    fraction_authority: &Signer<'info>,
+    fraction_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    fraction_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    fraction_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::burn(
+        CpiContext::new(
+            fraction_token_program.to_account_info(),
+            Burn {
+                mint: fraction_mint.to_account_info(),
+                from: fraction_token_account.to_account_info(),
+                authority: fraction_authority.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+pub fn transfer_position_fractions_to_escrow<'info>(
+    fraction_authority: &Signer<'info>,
+    fraction_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    fraction_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    fraction_escrow: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    fraction_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::transfer_checked(
+        CpiContext::new(
+            fraction_token_program.to_account_info(),
+            TransferChecked {
+                from: fraction_token_account.to_account_info(),
+                mint: fraction_mint.to_account_info(),
+                to: fraction_escrow.to_account_info(),
+                authority: fraction_authority.to_account_info(),
+            },
+        ),
+        amount,
+        fraction_mint.decimals,
+    )
+}
+
+pub fn transfer_position_fractions_from_escrow<'info>(
+    vault: &Account<'info, PositionFractionVault>,
+    fraction_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    fraction_escrow: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    fraction_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    fraction_token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            fraction_token_program.to_account_info(),
+            TransferChecked {
+                from: fraction_escrow.to_account_info(),
+                mint: fraction_mint.to_account_info(),
+                to: fraction_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            &[&vault.seeds()],
+        ),
+        amount,
+        fraction_mint.decimals,
+    )
+}
+
+pub fn close_position_fraction_escrow<'info>(
+    vault: &Account<'info, PositionFractionVault>,
+    receiver: &UncheckedAccount<'info>,
+    fraction_escrow: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    fraction_token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        fraction_token_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: fraction_escrow.to_account_info(),
+            destination: receiver.to_account_info(),
+            authority: vault.to_account_info(),
+        },
+        &[&vault.seeds()],
+    ))
+}
+
+// Wraps lamports of the owner into a WSOL token account of the owner
+pub fn wrap_sol<'info>(
+    owner: &Signer<'info>,
+    token_owner_account: &Account<'info, TokenAccount>,
+    system_program: &Program<'info, System>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: owner.to_account_info(),
+                to: token_owner_account.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    token::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        token::SyncNative {
+            account: token_owner_account.to_account_info(),
+        },
+    ))
+}
+
+// Closes a WSOL token account of the owner, returning the wrapped SOL and the rent to the owner
+pub fn unwrap_sol<'info>(
+    owner: &Signer<'info>,
+    token_owner_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    token::close_account(CpiContext::new(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: token_owner_account.to_account_info(),
+            destination: owner.to_account_info(),
+            authority: owner.to_account_info(),
+        },
+    ))
+}
@@ -1,12 +1,13 @@
 use anchor_lang::{
     prelude::{AccountInfo, Pubkey, Signer, *},
-    ToAccountInfo,
+    Discriminator, ToAccountInfo,
 };
 use anchor_spl::token::TokenAccount;
 use solana_program::program_option::COption;
 use std::convert::TryFrom;
 
 use crate::errors::ErrorCode;
+use crate::state::{PositionHistory, PositionHistoryEntry, SharedDeadlineContext, TickArray};
 
 pub fn verify_position_bundle_authority<'info>(
     position_bundle_token_account: &TokenAccount,
@@ -44,6 +45,398 @@ fn validate_owner(expected_owner: &Pubkey, owner_account_info: &AccountInfo) ->
     Ok(())
 }
 
+/// Load the tick-array holding a boundary tick of a position.
+///
+/// A tick-array has to be created with `initialize_tick_array` before positions can use its ticks.
+/// A missing or zeroed account fails with `TickArrayNotInitialized` instead of a generic account
+/// error, the error data holds the key of the tick-array and the pool.
+pub fn load_position_tick_array<'info>(
+    tick_array: &AccountInfo<'info>,
+    pool: &Pubkey,
+) -> Result<AccountLoader<'info, TickArray>> {
+    let initialized = tick_array.owner == &crate::ID
+        && tick_array.data_len() == TickArray::LEN
+        && tick_array.try_borrow_data()?[..8] == TickArray::discriminator();
+    if !initialized {
+        return Err(
+            error!(ErrorCode::TickArrayNotInitialized).with_pubkeys((tick_array.key(), *pool))
+        );
+    }
+
+    // AccountLoader borrows its AccountInfo for 'info, which outlives the borrow of the accounts
+    // struct the tick-array comes from. The clone shares the account data, and heap memory is
+    // only released at the end of the instruction anyway.
+    let tick_array: &'info AccountInfo<'info> = Box::leak(Box::new(tick_array.clone()));
+    let tick_array = AccountLoader::<TickArray>::try_from(tick_array)?;
+    if tick_array.load()?.pool != *pool {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+    Ok(tick_array)
+}
+
 pub fn to_timestamp_u64(t: i64) -> Result<u64> {
+    // The clock sysvar timestamp is signed; a negative value must not wrap into a huge elapsed time
+    if t < 0 {
+        return Err(ErrorCode::InvalidTimestamp.into());
+    }
     u64::try_from(t).or(Err(ErrorCode::InvalidTimestampConversion.into()))
 }
+
+// Operations that are part of a batch check the shared deadline instead of a per-instruction one
+pub fn consume_deadline_context(
+    deadline_context: &mut Option<Box<Account<SharedDeadlineContext>>>,
+    timestamp: u64,
+) -> Result<()> {
+    if let Some(deadline_context) = deadline_context {
+        deadline_context.consume(timestamp)?;
+    }
+    Ok(())
+}
+
+// Instructions which could not take the deadline context as an account receive it in the
+// remaining accounts. It is checked like the account would be, and persisted after being consumed.
+pub fn consume_remaining_deadline_context<'info>(
+    deadline_context: Option<&'info [AccountInfo<'info>]>,
+    authority: &Pubkey,
+    timestamp: u64,
+) -> Result<()> {
+    let deadline_context = match deadline_context {
+        Some([deadline_context]) => deadline_context,
+        Some(_) => return Err(ErrorCode::RemainingAccountsInvalidSlice.into()),
+        None => return Ok(()),
+    };
+    if !deadline_context.is_writable {
+        return Err(anchor_lang::error::ErrorCode::ConstraintMut.into());
+    }
+    let mut deadline_context = Account::<SharedDeadlineContext>::try_from(deadline_context)?;
+    if deadline_context.authority != *authority {
+        return Err(anchor_lang::error::ErrorCode::ConstraintRaw.into());
+    }
+    deadline_context.consume(timestamp)?;
+    deadline_context.exit(&crate::ID)
+}
+
+// Positions only keep a history if its account was initialized and passed
+pub fn record_position_history(
+    position_history: &mut Option<Box<Account<PositionHistory>>>,
+    entry: PositionHistoryEntry,
+) {
+    if let Some(position_history) = position_history {
+        position_history.record(entry);
+    }
+}
+
+// Instructions which could not take the position history as an account receive it in the
+// remaining accounts. It is checked like the account would be, and has to be persisted with
+// exit_remaining_position_history after recording.
+pub fn load_remaining_position_history<'info>(
+    position_history: Option<&'info [AccountInfo<'info>]>,
+    position: &Pubkey,
+) -> Result<Option<Box<Account<'info, PositionHistory>>>> {
+    let position_history = match position_history {
+        Some([position_history]) => position_history,
+        Some(_) => return Err(ErrorCode::RemainingAccountsInvalidSlice.into()),
+        None => return Ok(None),
+    };
+    if !position_history.is_writable {
+        return Err(anchor_lang::error::ErrorCode::ConstraintMut.into());
+    }
+    let position_history = Account::<PositionHistory>::try_from(position_history)?;
+    if position_history.position != *position {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+    Ok(Some(Box::new(position_history)))
+}
+
+pub fn exit_remaining_position_history(
+    position_history: &Option<Box<Account<PositionHistory>>>,
+) -> Result<()> {
+    match position_history {
+        Some(position_history) => position_history.exit(&crate::ID),
+        None => Ok(()),
+    }
+}
+
+/// Check that a vault of a pool being created holds no tokens. The accounting of a new pool starts
+/// with zero reserves, so tokens seeded into a vault before the pool exists would skew it. A vault
+/// which was not created yet holds no tokens.
+pub fn verify_vault_empty(vault: &AccountInfo) -> Result<()> {
+    if vault.data_is_empty() {
+        return Ok(());
+    }
+    // Token and Token-2022 accounts start with the mint and the owner, followed by the amount
+    let data = vault.try_borrow_data()?;
+    match data.get(64..72) {
+        Some(amount) if amount.iter().all(|byte| *byte == 0) => Ok(()),
+        _ => Err(ErrorCode::VaultNotEmpty.into()),
+    }
+}
+
+#[cfg(test)]
+mod to_timestamp_u64_tests {
+    use super::*;
+
+    #[test]
+    fn test_non_negative_timestamp() {
+        assert_eq!(to_timestamp_u64(0).unwrap(), 0);
+        assert_eq!(to_timestamp_u64(1_700_000_000).unwrap(), 1_700_000_000);
+        assert_eq!(to_timestamp_u64(i64::MAX).unwrap(), i64::MAX as u64);
+    }
+
+    #[test]
+    fn test_negative_timestamp() {
+        assert_eq!(
+            to_timestamp_u64(-1).unwrap_err(),
+            ErrorCode::InvalidTimestamp.into()
+        );
+        assert_eq!(
+            to_timestamp_u64(i64::MIN).unwrap_err(),
+            ErrorCode::InvalidTimestamp.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod load_position_tick_array_tests {
+    use super::*;
+
+    fn load(owner: Pubkey, discriminator: [u8; 8], pool: &Pubkey) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0u8; TickArray::LEN];
+        data[..8].copy_from_slice(&discriminator);
+        let account_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        load_position_tick_array(&account_info, pool).map(|_| ())
+    }
+
+    #[test]
+    fn test_uninitialized_tick_array() {
+        let pool = Pubkey::default();
+        // Not created yet, the account is still owned by the system program
+        let result = load(anchor_lang::system_program::ID, [0; 8], &pool);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickArrayNotInitialized.into()
+        );
+
+        let result = load(crate::ID, [0; 8], &pool);
+        assert_eq!(
+            result.unwrap_err(),
+            ErrorCode::TickArrayNotInitialized.into()
+        );
+    }
+
+    #[test]
+    fn test_initialized_tick_array() {
+        // The pool of the zeroed tick-array data is the default pubkey
+        assert!(load(crate::ID, TickArray::discriminator(), &Pubkey::default()).is_ok());
+
+        let result = load(crate::ID, TickArray::discriminator(), &Pubkey::new_unique());
+        assert_eq!(
+            result.unwrap_err(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod verify_vault_empty_tests {
+    use super::*;
+
+    fn verify(data: &mut [u8]) -> Result<()> {
+        let key = Pubkey::new_unique();
+        let owner = anchor_spl::token::ID;
+        let mut lamports = 0;
+        let account_info =
+            AccountInfo::new(&key, false, true, &mut lamports, data, &owner, false, 0);
+        verify_vault_empty(&account_info)
+    }
+
+    #[test]
+    fn test_vault_not_created() {
+        assert!(verify(&mut []).is_ok());
+    }
+
+    #[test]
+    fn test_empty_vault() {
+        assert!(verify(&mut [0u8; TokenAccount::LEN]).is_ok());
+    }
+
+    #[test]
+    fn test_pre_funded_vault() {
+        let mut data = [0u8; TokenAccount::LEN];
+        data[64..72].copy_from_slice(&1_000u64.to_le_bytes());
+        assert_eq!(
+            verify(&mut data).unwrap_err(),
+            ErrorCode::VaultNotEmpty.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod consume_remaining_deadline_context_tests {
+    use super::*;
+
+    fn deadline_context_info(authority: Pubkey, is_writable: bool) -> AccountInfo<'static> {
+        let mut context = SharedDeadlineContext::default();
+        context.initialize(authority, 0, 100, 2, 50).unwrap();
+        let mut data = Vec::with_capacity(SharedDeadlineContext::LEN);
+        context.try_serialize(&mut data).unwrap();
+        AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            is_writable,
+            Box::leak(Box::new(1_000_000)),
+            Box::leak(data.into_boxed_slice()),
+            &crate::ID,
+            false,
+            0,
+        )
+    }
+
+    fn consume(accounts: Vec<AccountInfo<'static>>, authority: &Pubkey) -> Result<()> {
+        let accounts: &'static [AccountInfo<'static>] = Box::leak(accounts.into_boxed_slice());
+        consume_remaining_deadline_context(Some(accounts), authority, 60)
+    }
+
+    #[test]
+    fn test_consume_persists_remaining_ops() {
+        let authority = Pubkey::new_unique();
+        let info = deadline_context_info(authority, true);
+        consume(vec![info.clone()], &authority).unwrap();
+
+        let context =
+            SharedDeadlineContext::try_deserialize(&mut &info.try_borrow_data().unwrap()[..])
+                .unwrap();
+        assert_eq!(context.remaining_ops, 1);
+    }
+
+    #[test]
+    fn test_no_deadline_context() {
+        assert!(consume_remaining_deadline_context(None, &Pubkey::new_unique(), 60).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_deadline_context_rejected() {
+        let authority = Pubkey::new_unique();
+        assert_eq!(
+            consume(vec![deadline_context_info(authority, false)], &authority).unwrap_err(),
+            anchor_lang::error::ErrorCode::ConstraintMut.into()
+        );
+        assert_eq!(
+            consume(
+                vec![deadline_context_info(Pubkey::new_unique(), true)],
+                &authority
+            )
+            .unwrap_err(),
+            anchor_lang::error::ErrorCode::ConstraintRaw.into()
+        );
+        assert_eq!(
+            consume(
+                vec![
+                    deadline_context_info(authority, true),
+                    deadline_context_info(authority, true)
+                ],
+                &authority
+            )
+            .unwrap_err(),
+            ErrorCode::RemainingAccountsInvalidSlice.into()
+        );
+    }
+}
+
+#[cfg(test)]
+mod load_remaining_position_history_tests {
+    use super::*;
+    use crate::state::POSITION_HISTORY_COLLECT_FEES;
+
+    fn position_history_info(position: Pubkey, is_writable: bool) -> AccountInfo<'static> {
+        let mut position_history = PositionHistory::default();
+        position_history.initialize(position);
+        let mut data = Vec::with_capacity(PositionHistory::LEN);
+        position_history.try_serialize(&mut data).unwrap();
+        AccountInfo::new(
+            Box::leak(Box::new(Pubkey::new_unique())),
+            false,
+            is_writable,
+            Box::leak(Box::new(1_000_000)),
+            Box::leak(data.into_boxed_slice()),
+            &crate::ID,
+            false,
+            0,
+        )
+    }
+
+    fn load(
+        accounts: Vec<AccountInfo<'static>>,
+        position: &Pubkey,
+    ) -> Result<Option<Box<Account<'static, PositionHistory>>>> {
+        let accounts: &'static [AccountInfo<'static>] = Box::leak(accounts.into_boxed_slice());
+        load_remaining_position_history(Some(accounts), position)
+    }
+
+    #[test]
+    fn test_record_persists_entry() {
+        let position = Pubkey::new_unique();
+        let info = position_history_info(position, true);
+        let mut position_history = load(vec![info.clone()], &position).unwrap();
+        record_position_history(
+            &mut position_history,
+            PositionHistoryEntry::new(POSITION_HISTORY_COLLECT_FEES, 10, 0, 100, 200),
+        );
+        exit_remaining_position_history(&position_history).unwrap();
+
+        let position_history =
+            PositionHistory::try_deserialize(&mut &info.try_borrow_data().unwrap()[..]).unwrap();
+        assert_eq!(position_history.recorded_entries().len(), 1);
+    }
+
+    #[test]
+    fn test_no_position_history() {
+        assert!(load_remaining_position_history(None, &Pubkey::new_unique())
+            .unwrap()
+            .is_none());
+        assert!(exit_remaining_position_history(&None).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_position_history_rejected() {
+        let position = Pubkey::new_unique();
+        assert_eq!(
+            load(vec![position_history_info(position, false)], &position)
+                .err()
+                .unwrap(),
+            anchor_lang::error::ErrorCode::ConstraintMut.into()
+        );
+        assert_eq!(
+            load(
+                vec![position_history_info(Pubkey::new_unique(), true)],
+                &position
+            )
+            .err()
+            .unwrap(),
+            anchor_lang::error::ErrorCode::ConstraintHasOne.into()
+        );
+        assert_eq!(
+            load(
+                vec![
+                    position_history_info(position, true),
+                    position_history_info(position, true)
+                ],
+                &position
+            )
+            .err()
+            .unwrap(),
+            ErrorCode::RemainingAccountsInvalidSlice.into()
+        );
+    }
+}
@@ -10,8 +10,13 @@ pub enum AccountsType {
     TransferHookIntermediate,
     TransferHookOutput,
     //TickArray,
-    //TickArrayOne,
-    //TickArrayTwo,
+    TickArrayOne,
+    TickArrayTwo,
+    TickArrayThree,
+    TransferHookSecondIntermediate,
+    SupplementalTickArrays,
+    DeadlineContext,
+    PositionHistory,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -26,24 +31,31 @@ pub struct RemainingAccountsInfo {
 }
 
 #[derive(Default)]
-pub struct ParsedRemainingAccounts<'info> {
+pub struct ParsedRemainingAccounts<'c, 'info> {
     pub transfer_hook_a: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_b: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_reward: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_input: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_intermediate: Option<Vec<AccountInfo<'info>>>,
     pub transfer_hook_output: Option<Vec<AccountInfo<'info>>>,
+    pub transfer_hook_second_intermediate: Option<Vec<AccountInfo<'info>>>,
     //pub tick_array: Option<Vec<AccountInfo<'info>>>,
-    //pub tick_array_one: Option<Vec<AccountInfo<'info>>>,
-    //pub tick_array_two: Option<Vec<AccountInfo<'info>>>,
+    // Tick arrays are borrowed rather than cloned so that they can back an AccountLoader
+    pub tick_array_one: Option<&'c [AccountInfo<'info>]>,
+    pub tick_array_two: Option<&'c [AccountInfo<'info>]>,
+    pub tick_array_three: Option<&'c [AccountInfo<'info>]>,
+    pub supplemental_tick_arrays: Option<&'c [AccountInfo<'info>]>,
+    pub deadline_context: Option<&'c [AccountInfo<'info>]>,
+    pub position_history: Option<&'c [AccountInfo<'info>]>,
 }
 
-pub fn parse_remaining_accounts<'info>(
-    remaining_accounts: &[AccountInfo<'info>],
+pub fn parse_remaining_accounts<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
     remaining_accounts_info: &Option<RemainingAccountsInfo>,
     valid_accounts_type_list: &[AccountsType],
-) -> Result<ParsedRemainingAccounts<'info>> {
+) -> Result<ParsedRemainingAccounts<'c, 'info>> {
     let mut remaining_accounts_iter = remaining_accounts.iter();
+    let mut remaining_accounts_offset: usize = 0;
     let mut parsed_remaining_accounts = ParsedRemainingAccounts::default();
 
     if remaining_accounts_info.is_none() {
@@ -67,6 +79,9 @@ pub fn parse_remaining_accounts<'info>(
                     return Err(ErrorCode::RemainingAccountsInsufficient.into());
                 }
             }
+            let accounts_slice = &remaining_accounts
+                [remaining_accounts_offset..remaining_accounts_offset + slice.length as usize];
+            remaining_accounts_offset += slice.length as usize;
 
             match slice.accounts_type {
                 AccountsType::TransferHookA => {
@@ -107,17 +122,52 @@ pub fn parse_remaining_accounts<'info>(
                         return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
                     }
                     parsed_remaining_accounts.transfer_hook_output = Some(accounts);
-                } /*
-                  AccountsType::TickArray => {
-                    parsed_remaining_accounts.tick_array = Some(accounts);
-                  }
-                  AccountsType::TickArrayOne => {
-                    parsed_remaining_accounts.tick_array_one = Some(accounts);
-                  }
-                  AccountsType::TickArrayTwo => {
-                    parsed_remaining_accounts.tick_array_two = Some(accounts);
-                  }
-                  */
+                }
+                AccountsType::TransferHookSecondIntermediate => {
+                    if parsed_remaining_accounts
+                        .transfer_hook_second_intermediate
+                        .is_some()
+                    {
+                        return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                    }
+                    parsed_remaining_accounts.transfer_hook_second_intermediate = Some(accounts);
+                }
+                AccountsType::TickArrayOne => {
+                    if parsed_remaining_accounts.tick_array_one.is_some() {
+                        return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                    }
+                    parsed_remaining_accounts.tick_array_one = Some(accounts_slice);
+                }
+                AccountsType::TickArrayTwo => {
+                    if parsed_remaining_accounts.tick_array_two.is_some() {
+                        return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                    }
+                    parsed_remaining_accounts.tick_array_two = Some(accounts_slice);
+                }
+                AccountsType::TickArrayThree => {
+                    if parsed_remaining_accounts.tick_array_three.is_some() {
+                        return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                    }
+                    parsed_remaining_accounts.tick_array_three = Some(accounts_slice);
+                }
+                AccountsType::SupplementalTickArrays => {
+                    if parsed_remaining_accounts.supplemental_tick_arrays.is_some() {
+                        return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                    }
+                    parsed_remaining_accounts.supplemental_tick_arrays = Some(accounts_slice);
+                }
+                AccountsType::DeadlineContext => {
+                    if parsed_remaining_accounts.deadline_context.is_some() {
+                        return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                    }
+                    parsed_remaining_accounts.deadline_context = Some(accounts_slice);
+                }
+                AccountsType::PositionHistory => {
+                    if parsed_remaining_accounts.position_history.is_some() {
+                        return Err(ErrorCode::RemainingAccountsDuplicatedAccountsType.into());
+                    }
+                    parsed_remaining_accounts.position_history = Some(accounts_slice);
+                }
             }
         }
 
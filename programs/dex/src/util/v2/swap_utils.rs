@@ -2,7 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_spl::memo::Memo;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{manager::swap_manager::PostSwapUpdate, state::Pool};
+use crate::{
+    constants::transfer_memo,
+    events::{ProtocolFeeStreamedEvent, ReferralFeePaidEvent},
+    manager::swap_manager::PostSwapUpdate,
+    state::Pool,
+    util::update_pool_after_swap,
+};
 
 use super::{transfer_from_owner_to_vault_v2, transfer_from_vault_to_owner_v2};
 
@@ -25,13 +31,9 @@ pub fn update_and_swap_pool_v2<'info>(
     reward_last_updated_timestamp: u64,
     memo: &[u8],
 ) -> Result<()> {
-    pool.update_after_swap(
-        swap_update.next_liquidity,
-        swap_update.next_tick_index,
-        swap_update.next_sqrt_price,
-        swap_update.next_fee_growth_global,
-        swap_update.next_reward_infos,
-        swap_update.next_protocol_fee,
+    update_pool_after_swap(
+        pool,
+        &swap_update,
         is_token_fee_in_a,
         reward_last_updated_timestamp,
     );
@@ -57,6 +59,72 @@ pub fn update_and_swap_pool_v2<'info>(
     )
 }
 
+pub fn stream_protocol_fee_v2<'info>(
+    pool: &Account<'info, Pool>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    treasury_token_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+    amount: u64,
+) -> Result<()> {
+    transfer_from_vault_to_owner_v2(
+        pool,
+        token_mint,
+        token_vault,
+        treasury_token_account,
+        token_program,
+        memo_program,
+        transfer_hook_accounts,
+        amount,
+        transfer_memo::TRANSFER_MEMO_STREAM_PROTOCOL_FEE.as_bytes(),
+    )?;
+
+    emit!(ProtocolFeeStreamedEvent {
+        pool: pool.key(),
+        mint: token_mint.key(),
+        treasury_token_account: treasury_token_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+pub fn pay_referral_fee_v2<'info>(
+    pool: &Account<'info, Pool>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    referral_fee_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    memo_program: &Program<'info, Memo>,
+    transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+    referral_fee_rate: u16,
+    amount: u64,
+) -> Result<()> {
+    transfer_from_vault_to_owner_v2(
+        pool,
+        token_mint,
+        token_vault,
+        referral_fee_account,
+        token_program,
+        memo_program,
+        transfer_hook_accounts,
+        amount,
+        transfer_memo::TRANSFER_MEMO_REFERRAL_FEE.as_bytes(),
+    )?;
+
+    emit!(ReferralFeePaidEvent {
+        pool: pool.key(),
+        referral_fee_account: referral_fee_account.key(),
+        mint: token_mint.key(),
+        referral_fee_rate,
+        amount,
+    });
+
+    Ok(())
+}
+
 fn perform_swap_v2<'info>(
     pool: &Account<'info, Pool>,
     token_authority: &Signer<'info>,
@@ -183,24 +251,16 @@ pub fn update_and_two_hop_swap_pool_v2<'info>(
     reward_last_updated_timestamp: u64,
     memo: &[u8],
 ) -> Result<()> {
-    pool_one.update_after_swap(
-        swap_update_one.next_liquidity,
-        swap_update_one.next_tick_index,
-        swap_update_one.next_sqrt_price,
-        swap_update_one.next_fee_growth_global,
-        swap_update_one.next_reward_infos,
-        swap_update_one.next_protocol_fee,
+    update_pool_after_swap(
+        pool_one,
+        &swap_update_one,
         is_token_fee_in_one_a,
         reward_last_updated_timestamp,
     );
 
-    pool_two.update_after_swap(
-        swap_update_two.next_liquidity,
-        swap_update_two.next_tick_index,
-        swap_update_two.next_sqrt_price,
-        swap_update_two.next_fee_growth_global,
-        swap_update_two.next_reward_infos,
-        swap_update_two.next_protocol_fee,
+    update_pool_after_swap(
+        pool_two,
+        &swap_update_two,
         is_token_fee_in_two_a,
         reward_last_updated_timestamp,
     );
@@ -66,9 +66,7 @@ pub fn transfer_from_owner_to_vault_v2<'info>(
 
     // TransferHook extension
     if let Some(hook_program_id) = get_transfer_hook_program_id(token_mint)? {
-        if transfer_hook_accounts.is_none() {
-            return Err(ErrorCode::NoExtraAccountsForTransferHook.into());
-        }
+        check_transfer_hook_accounts(Some(hook_program_id), transfer_hook_accounts.is_some())?;
         let transfer_hook_accounts = transfer_hook_accounts
             .clone()
             .ok_or(ErrorCode::MissingOrInvalidDelegate)?;
@@ -146,9 +144,7 @@ pub fn transfer_from_vault_to_owner_v2<'info>(
 
     // TransferHook extension
     if let Some(hook_program_id) = get_transfer_hook_program_id(token_mint)? {
-        if transfer_hook_accounts.is_none() {
-            return Err(ErrorCode::NoExtraAccountsForTransferHook.into());
-        }
+        check_transfer_hook_accounts(Some(hook_program_id), transfer_hook_accounts.is_some())?;
 
         let transfer_hook_accounts = transfer_hook_accounts
             .clone()
@@ -171,6 +167,29 @@ pub fn transfer_from_vault_to_owner_v2<'info>(
     Ok(())
 }
 
+/// Verify that the extra accounts of the transfer hook of the mint were provided, as transfers
+/// of the token would require them. Transfer hooks cannot change the transferred amount, so a
+/// quote only needs to resolve the accounts to fail exactly where the transfer would.
+pub fn verify_transfer_hook_accounts<'info>(
+    token_mint: &InterfaceAccount<'info, Mint>,
+    transfer_hook_accounts: &Option<Vec<AccountInfo<'info>>>,
+) -> Result<()> {
+    check_transfer_hook_accounts(
+        get_transfer_hook_program_id(token_mint)?,
+        transfer_hook_accounts.is_some(),
+    )
+}
+
+fn check_transfer_hook_accounts(
+    hook_program_id: Option<Pubkey>,
+    transfer_hook_accounts_provided: bool,
+) -> Result<()> {
+    if hook_program_id.is_some() && !transfer_hook_accounts_provided {
+        return Err(ErrorCode::NoExtraAccountsForTransferHook.into());
+    }
+    Ok(())
+}
+
 fn get_transfer_hook_program_id<'info>(
     token_mint: &InterfaceAccount<'info, Mint>,
 ) -> Result<Option<Pubkey>> {
@@ -428,3 +447,46 @@ pub fn get_epoch_transfer_fee<'info>(
 
     Ok(None)
 }
+
+/// Get the transfer fees withheld in a Token-2022 token account. Withheld fees are not part of the
+/// amount of the account, only the withdraw authority of the mint can harvest them.
+pub fn get_withheld_transfer_fee<'info>(
+    token_account: &InterfaceAccount<'info, TokenAccount>,
+) -> Result<u64> {
+    let token_account_info = token_account.to_account_info();
+    if *token_account_info.owner == Token::id() {
+        return Ok(0);
+    }
+
+    let token_account_data = token_account_info.try_borrow_data()?;
+    let token_account_unpacked =
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account_data)?;
+    if let Ok(transfer_fee_amount) =
+        token_account_unpacked.get_extension::<extension::transfer_fee::TransferFeeAmount>()
+    {
+        return Ok(transfer_fee_amount.withheld_amount.into());
+    }
+
+    Ok(0)
+}
+
+#[cfg(test)]
+mod transfer_hook_accounts_tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_hook_accounts_required_for_hook_mint() {
+        let hook_program_id = Some(Pubkey::new_unique());
+        assert_eq!(
+            check_transfer_hook_accounts(hook_program_id, false).unwrap_err(),
+            ErrorCode::NoExtraAccountsForTransferHook.into()
+        );
+        assert!(check_transfer_hook_accounts(hook_program_id, true).is_ok());
+    }
+
+    #[test]
+    fn test_transfer_hook_accounts_optional_without_hook() {
+        assert!(check_transfer_hook_accounts(None, false).is_ok());
+        assert!(check_transfer_hook_accounts(None, true).is_ok());
+    }
+}